@@ -0,0 +1,39 @@
+//! 单个页面的提取耗时：爬取时每个页面都会经过 `extract_html` 或 `extract_json`，
+//! 链接和正文的处理是主要的 CPU 开销。输入为 `benches/fixtures` 中的文档页。
+
+use adoc::extract::{clean_text, extract_html, extract_json, extract_links, extract_search_results};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use std::hint::black_box;
+
+const URL: &str = "https://developer.apple.com/documentation/swiftui/view";
+const VIEW_HTML: &str = include_str!("fixtures/view.html");
+const VIEW_JSON: &str = include_str!("fixtures/view.json");
+const SEARCH_HTML: &str = include_str!("fixtures/search.html");
+
+fn extraction(c: &mut Criterion) {
+    let mut group = c.benchmark_group("extract");
+    group.throughput(Throughput::Bytes(VIEW_HTML.len() as u64));
+    group.bench_function("html", |b| b.iter(|| extract_html(black_box(URL), black_box(VIEW_HTML)).unwrap()));
+    group.bench_function("links", |b| b.iter(|| extract_links(black_box(URL), black_box(VIEW_HTML)).unwrap()));
+    group.throughput(Throughput::Bytes(VIEW_JSON.len() as u64));
+    group.bench_function("json", |b| b.iter(|| extract_json(black_box(URL), black_box(VIEW_JSON)).unwrap()));
+    group.throughput(Throughput::Bytes(SEARCH_HTML.len() as u64));
+    group.bench_function("search", |b| {
+        b.iter(|| extract_search_results(black_box(URL), black_box(SEARCH_HTML)).unwrap())
+    });
+    group.finish();
+}
+
+fn cleaning(c: &mut Criterion) {
+    // 未清理的正文：与 scraper 收集到的文本一样带有缩进、多余空格和空行
+    let text: String = (0..400)
+        .map(|i| format!("  Paragraph {}   describing   a   modifier.  \n\n    modifier{}\n", i, i))
+        .collect();
+    let mut group = c.benchmark_group("clean_text");
+    group.throughput(Throughput::Bytes(text.len() as u64));
+    group.bench_function("paragraphs", |b| b.iter(|| clean_text(black_box(&text))));
+    group.finish();
+}
+
+criterion_group!(benches, extraction, cleaning);
+criterion_main!(benches);
@@ -0,0 +1,62 @@
+//! 保存结果的耗时：大量页面序列化为各种输出格式和分块 JSONL。
+//! 页面由 `benches/fixtures/view.html` 提取后复制而成，写入内存以排除磁盘的影响。
+
+use adoc::chunk::{chunk_pages, to_jsonl};
+use adoc::extract::{extract_html, DocPage};
+use adoc::output::{write_pages, OutputFormat};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use std::hint::black_box;
+
+const PAGES: usize = 2_000;
+
+fn sample_pages() -> Vec<DocPage> {
+    let page = extract_html(
+        "https://developer.apple.com/documentation/swiftui/view",
+        include_str!("fixtures/view.html"),
+    )
+    .unwrap();
+    (0..PAGES)
+        .map(|i| DocPage {
+            title: format!("{} {}", page.title, i),
+            url: format!("{}{}", page.url, i),
+            ..page.clone()
+        })
+        .collect()
+}
+
+fn serialization(c: &mut Criterion) {
+    let pages = sample_pages();
+    let mut group = c.benchmark_group("output");
+    group.sample_size(20);
+    group.throughput(Throughput::Elements(PAGES as u64));
+    for format in [
+        OutputFormat::Json,
+        OutputFormat::PrettyJson,
+        OutputFormat::Txt,
+        OutputFormat::Markdown,
+        OutputFormat::EsBulk,
+    ] {
+        group.bench_function(format.to_string(), |b| {
+            b.iter_batched_ref(
+                Vec::new,
+                |out| write_pages(black_box(pages.as_slice()), format, out).unwrap(),
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn chunks(c: &mut Criterion) {
+    let pages = sample_pages();
+    let chunks = chunk_pages(&pages, 1000, 100).unwrap();
+    let mut group = c.benchmark_group("chunk");
+    group.sample_size(20);
+    group.throughput(Throughput::Elements(PAGES as u64));
+    group.bench_function("split", |b| b.iter(|| chunk_pages(black_box(&pages), 1000, 100).unwrap()));
+    group.bench_function("jsonl", |b| b.iter(|| to_jsonl(black_box(&chunks)).unwrap()));
+    group.finish();
+}
+
+criterion_group!(benches, serialization, chunks);
+criterion_main!(benches);
@@ -0,0 +1,11 @@
+fn main() {
+    // self-update 按目标三元组选择 release 中的文件
+    println!("cargo:rustc-env=ADOC_TARGET={}", std::env::var("TARGET").unwrap());
+    #[cfg(feature = "grpc")]
+    {
+        // 使用随依赖分发的 protoc，构建时无需系统安装
+        std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path().unwrap());
+        tonic_build::compile_protos("proto/adoc.proto").unwrap();
+    }
+    println!("cargo:rerun-if-changed=proto/adoc.proto");
+}
@@ -0,0 +1,26 @@
+#ifndef ADOC_H
+#define ADOC_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/*
+ * 爬取 url（或按关键字搜索），返回 DocPage 数组的 JSON 字符串。
+ * options_json 可以为 NULL，例如 {"recursive": true, "concurrency": 4,
+ * "max_retries": 3, "timeout_secs": 30}。
+ * 失败时返回 NULL，可通过 adoc_last_error() 获取错误信息。
+ */
+char *adoc_crawl(const char *url, const char *options_json);
+
+/* 当前线程最近一次失败的错误信息，没有错误时返回 NULL */
+char *adoc_last_error(void);
+
+/* 释放由本库返回的字符串 */
+void adoc_string_free(char *s);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* ADOC_H */
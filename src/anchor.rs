@@ -0,0 +1,165 @@
+//! 正文中小节标题的锚点。
+//!
+//! 文档页面的小节（`Overview`、`Discussion`、`Parameters` 等）在原站点上有锚点，`…/view#discussion`
+//! 这样的链接可以直接跳到小节。提取时保留原页面的锚点（DocC render JSON 中标题块的 `anchor`、HTML 中标题的 `id`），
+//! 没有时按标题生成与 GitHub 相同的 slug，保存在 [`DocPage::anchors`](crate::extract::DocPage::anchors) 中。
+//! Markdown 输出、git 快照和本地浏览服务把这些行渲染为带锚点的标题，指向本次结果中页面小节的链接也改写到对应锚点。
+
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+use crate::extract::clean_text;
+use crate::profile::SiteProfile;
+
+/// 正文中的一个小节标题
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Anchor {
+    /// 页面内唯一的锚点，不含 `#`
+    pub id: String,
+    /// 小节标题，与正文中的一行相同
+    pub title: String,
+}
+
+/// 与 GitHub 相同的标题 slug：转为小写，空白替换为 `-`，去掉字母、数字、`-` 和 `_` 以外的字符，
+/// 例如 `Return Value` → `return-value`
+pub fn slugify(text: &str) -> String {
+    text.trim()
+        .chars()
+        .flat_map(char::to_lowercase)
+        .filter_map(|c| match c {
+            c if c.is_whitespace() => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// DocC render JSON 正文中的小节标题，依次取标题块的 `anchor`，没有时按标题生成
+pub fn from_render_json(data: &Value) -> Vec<Anchor> {
+    let mut anchors = Vec::new();
+    for section in array(data.get("primaryContentSections")) {
+        if section.get("kind").and_then(Value::as_str) == Some("content") {
+            for block in array(section.get("content")) {
+                json_headings(block, &mut anchors);
+            }
+        }
+    }
+    anchors
+}
+
+fn json_headings(block: &Value, anchors: &mut Vec<Anchor>) {
+    match block.get("type").and_then(Value::as_str) {
+        Some("heading") => {
+            let title = block.get("text").and_then(Value::as_str).map(clean_text).unwrap_or_default();
+            let id = block.get("anchor").and_then(Value::as_str).map(str::to_string).unwrap_or_else(|| slugify(&title));
+            anchors.push(Anchor { id, title });
+        }
+        Some("aside") => {
+            for child in array(block.get("content")) {
+                json_headings(child, anchors);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// HTML 页面正文（`profile` 的内容选择器）中的 `<h2>` 到 `<h6>`，依次取标题的 `id`，没有时按标题生成
+pub fn from_html(profile: &SiteProfile, html: &str) -> Vec<Anchor> {
+    const MARKERS: [&str; 5] = ["<h2", "<h3", "<h4", "<h5", "<h6"];
+    if !MARKERS.iter().any(|marker| html.contains(marker)) {
+        return Vec::new();
+    }
+    static HEADING: OnceLock<Selector> = OnceLock::new();
+    let heading = HEADING.get_or_init(|| Selector::parse("h2, h3, h4, h5, h6").unwrap());
+    let document = Html::parse_document(html);
+    let Some(content) = document.select(&profile.selectors.content).next() else {
+        return Vec::new();
+    };
+    content
+        .select(heading)
+        .map(|el| {
+            let title = clean_text(&el.text().collect::<String>());
+            let id = el.value().attr("id").map(str::to_string).unwrap_or_else(|| slugify(&title));
+            Anchor { id, title }
+        })
+        .collect()
+}
+
+/// 只保留在正文中单独成行的标题，锚点为空或重复时按 GitHub 的方式改为 `slug-1`、`slug-2`
+pub fn for_content(content: &str, candidates: Vec<Anchor>) -> Vec<Anchor> {
+    let lines: HashSet<&str> = content.lines().map(str::trim).collect();
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut anchors = Vec::new();
+    for mut anchor in candidates {
+        if anchor.title.is_empty() || !lines.contains(anchor.title.as_str()) {
+            continue;
+        }
+        if anchor.id.is_empty() {
+            anchor.id = slugify(&anchor.title);
+        }
+        if seen.contains(&anchor.id) {
+            let base = anchor.id.clone();
+            anchor.id = (1..).map(|n| format!("{}-{}", base, n)).find(|id| !seen.contains(id)).unwrap_or(base);
+        }
+        seen.insert(anchor.id.clone());
+        anchors.push(anchor);
+    }
+    anchors
+}
+
+/// 正文的每一行及其对应的小节标题；标题按顺序匹配，原页面中有但正文中缺少的标题会被跳过
+pub fn match_headings<'a>(content: &'a str, anchors: &'a [Anchor]) -> Vec<(&'a str, Option<&'a Anchor>)> {
+    let mut next = 0;
+    content
+        .lines()
+        .map(|line| {
+            let found = anchors[next..].iter().position(|anchor| anchor.title == line.trim());
+            let anchor = found.map(|offset| {
+                next += offset + 1;
+                &anchors[next - 1]
+            });
+            (line, anchor)
+        })
+        .collect()
+}
+
+/// 把正文写成 Markdown：小节标题写成 `level` 级标题，锚点为 `id_prefix` 加上小节的锚点，其余行按空行分段
+pub fn markdown(content: &str, anchors: &[Anchor], level: usize, id_prefix: &str) -> String {
+    let mut out = String::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let flush = |paragraph: &mut Vec<&str>, out: &mut String| {
+        let text = paragraph.join("\n");
+        if !text.trim().is_empty() {
+            out.push_str(text.trim());
+            out.push_str("\n\n");
+        }
+        paragraph.clear();
+    };
+    for (line, anchor) in match_headings(content, anchors) {
+        match anchor {
+            Some(anchor) => {
+                flush(&mut paragraph, &mut out);
+                out.push_str(&format!("{} <a id=\"{}{}\">{}</a>\n\n", "#".repeat(level), id_prefix, anchor.id, anchor.title));
+            }
+            None if line.trim().is_empty() => flush(&mut paragraph, &mut out),
+            None => paragraph.push(line),
+        }
+    }
+    flush(&mut paragraph, &mut out);
+    out
+}
+
+/// 链接中指向 `anchors` 里某个小节的片段，返回该小节的锚点（片段不区分大小写）
+pub fn link_target<'a>(link: &str, anchors: &'a [Anchor]) -> Option<&'a str> {
+    let (_, fragment) = link.split_once('#')?;
+    let fragment = urlencoding::decode(fragment).map(|f| f.into_owned()).unwrap_or_else(|_| fragment.to_string());
+    anchors.iter().find(|anchor| anchor.id.eq_ignore_ascii_case(&fragment)).map(|anchor| anchor.id.as_str())
+}
+
+fn array(value: Option<&Value>) -> &[Value] {
+    value.and_then(Value::as_array).map(Vec::as_slice).unwrap_or_default()
+}
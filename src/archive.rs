@@ -0,0 +1,110 @@
+//! 文档归档（`developer.apple.com/library/archive`）页面的提取。
+//!
+//! 归档中是 UIKit 时代的编程指南和技术说明，页面结构与现在的文档完全不同：正文在 `#contents` 中，
+//! 示例代码是每行一个单元格的表格，页首页尾有书籍目录和上一页 / 下一页导航。这里按文档顺序提取标题、段落、
+//! 列表和代码，标题写成 `## 标题`，列表项写成 `- 内容`，代码逐行保留；书籍目录和导航不计入正文，
+//! 但其中的链接作为相关链接，递归爬取时沿目录和上一页 / 下一页爬取同一指南的其他章节。
+
+use anyhow::Result;
+use scraper::{ElementRef, Html, Selector};
+use std::sync::OnceLock;
+use url::Url;
+
+use crate::extract::{clean_text, links_with_sections, DocPage, LinkCollector, LinkFilter};
+use crate::profile::SiteProfile;
+
+/// 归档页面的路径前缀
+const ARCHIVE_PATH: &str = "/library/archive/";
+
+/// 是否为文档归档中的页面
+pub fn is_archive_url(url: &str) -> bool {
+    url.contains("developer.apple.com") && Url::parse(url).is_ok_and(|url| url.path().starts_with(ARCHIVE_PATH))
+}
+
+/// 从归档页面的 HTML 中提取 `DocPage`
+pub fn extract_archive_html(profile: &SiteProfile, url: &str, html: &str, links: &LinkFilter) -> Result<DocPage> {
+    static CONTENTS: OnceLock<Selector> = OnceLock::new();
+    static TITLE: OnceLock<Selector> = OnceLock::new();
+    static LINK: OnceLock<Selector> = OnceLock::new();
+    let contents = CONTENTS.get_or_init(|| Selector::parse("#contents, article, #content, body").unwrap());
+    let title_selector = TITLE.get_or_init(|| Selector::parse("#pageTitle, h1, title").unwrap());
+    let link_selector = LINK.get_or_init(|| Selector::parse("a[href]").unwrap());
+    let document = Html::parse_document(html);
+    let base_url = Url::parse(url)?;
+
+    let title = document
+        .select(title_selector)
+        .map(|el| clean_text(&el.text().collect::<String>()))
+        .find(|title| !title.is_empty())
+        .unwrap_or_default();
+
+    let mut lines = Vec::new();
+    if let Some(root) = document.select(contents).next() {
+        walk(root, &title, &mut lines);
+    }
+
+    // 页面内的锚点（`#//apple_ref/...`）指向同一个页面，去掉后再去重
+    let mut related_links = LinkCollector::new(profile, url, links)?;
+    for (el, section) in links_with_sections(&document, link_selector) {
+        let Some(mut link) = el.value().attr("href").and_then(|href| base_url.join(href).ok()) else {
+            continue;
+        };
+        link.set_fragment(None);
+        if link.as_str() != url {
+            related_links.push(link, &el.text().collect::<String>(), section.as_deref());
+        }
+    }
+
+    Ok(DocPage {
+        title,
+        content: lines.join("\n"),
+        url: url.to_string(),
+        related_links: related_links.finish(),
+        ..Default::default()
+    })
+}
+
+/// 按文档顺序遍历元素，收集标题、段落、列表项和代码；与页面标题相同的标题不重复写入
+fn walk(element: ElementRef, title: &str, lines: &mut Vec<String>) {
+    // 段落在 HTML 中常常折行，合并为一行
+    let text = |el: ElementRef| clean_text(&el.text().collect::<String>()).replace('\n', " ");
+    for child in element.children().filter_map(ElementRef::wrap) {
+        let value = child.value();
+        // 书籍目录、上一页 / 下一页导航、反馈表单和版权信息
+        let navigation = value.id().is_some_and(|id| {
+            id.starts_with("toc") || id.starts_with("pageNavigationLinks") || id == "feedback" || id == "footer"
+        }) || value.classes().any(|class| matches!(class, "pageNavigationLinks" | "copyright"));
+        if navigation {
+            continue;
+        }
+        match value.name() {
+            name @ ("h1" | "h2" | "h3" | "h4" | "h5") => {
+                let heading = text(child);
+                if !heading.is_empty() && heading != title {
+                    let level = name[1..].parse::<usize>().unwrap_or(2).max(2);
+                    lines.push(format!("{} {}", "#".repeat(level), heading));
+                }
+            }
+            "p" | "dt" | "dd" => {
+                let paragraph = text(child);
+                if !paragraph.is_empty() {
+                    lines.push(paragraph);
+                }
+            }
+            "li" => {
+                let item = text(child);
+                if !item.is_empty() {
+                    lines.push(format!("- {}", item));
+                }
+            }
+            // 示例代码每行一个 <pre>，保留缩进
+            "pre" => {
+                let code: String = child.text().collect();
+                let code = code.lines().map(str::trim_end).filter(|line| !line.trim().is_empty());
+                lines.extend(code.map(str::to_string));
+            }
+            "script" | "style" | "noscript" | "nav" | "header" | "footer" | "form" => {}
+            _ => walk(child, title, lines),
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! 输出前对页面排序和分组（`--sort`、`--group-by`）。
+//!
+//! 默认按爬取完成的顺序输出。`--sort` 按标题、URL 或文档层级排序；`--group-by` 先按框架、符号类型或上级页面
+//! 把页面分组，组按名称排列，无法归入任何组的页面排在最后，组内再按 `--sort` 排列（未设置时保持原顺序）。
+//! 所有输出格式都按排列后的顺序写出，Markdown 输出的目录和正文还为每组加上标题。
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::extract::DocPage;
+
+/// 页面的排序方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum SortKey {
+    /// 按标题（不区分大小写），标题相同时按 URL
+    Title,
+    Url,
+    /// 按文档层级，上级页面排在其下的页面之前
+    Hierarchy,
+}
+
+impl SortKey {
+    fn key(self, page: &DocPage) -> Vec<String> {
+        match self {
+            SortKey::Title => vec![page.title.to_lowercase(), page.url.clone()],
+            SortKey::Url => vec![page.url.clone()],
+            SortKey::Hierarchy => page.hierarchy(),
+        }
+    }
+}
+
+/// 页面的分组方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupBy {
+    /// 所属框架（层级的第一段）
+    Framework,
+    /// 符号类型（按声明识别）；文章等没有声明的页面不归入任何组
+    Kind,
+    /// 上级页面的层级，例如 `swiftui / view`；框架首页与框架下的页面同组
+    Hierarchy,
+}
+
+impl GroupBy {
+    /// 页面所在组的名称，无法归入任何组时为 `None`
+    pub fn group(self, page: &DocPage) -> Option<String> {
+        match self {
+            GroupBy::Framework => page.framework(),
+            GroupBy::Kind => page.symbol_kind().map(str::to_string),
+            GroupBy::Hierarchy => {
+                let hierarchy = page.hierarchy();
+                let parent = match hierarchy.len() {
+                    0 => return None,
+                    1 => 1,
+                    len => len - 1,
+                };
+                Some(hierarchy[..parent].join(" / "))
+            }
+        }
+    }
+}
+
+/// 按 `group_by` 分组、组内按 `sort` 排列页面；两者都未设置时保持原顺序
+pub fn arrange(pages: &mut [DocPage], sort: Option<SortKey>, group_by: Option<GroupBy>) {
+    if sort.is_none() && group_by.is_none() {
+        return;
+    }
+    // 稳定排序：键相同的页面保持爬取顺序
+    pages.sort_by_cached_key(|page| {
+        let group = group_by.map(|group_by| match group_by.group(page) {
+            Some(name) => (false, name),
+            None => (true, String::new()),
+        });
+        (group, sort.map(|sort| sort.key(page)))
+    });
+}
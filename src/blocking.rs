@@ -0,0 +1,42 @@
+//! 同步（阻塞）版本的爬虫 API。
+//!
+//! 与 `reqwest::blocking` 的做法一致：内部持有一个独立的 tokio 运行时，
+//! 调用方无需自己创建或管理异步运行时，适合嵌入同步的构建工具或脚本中。
+//!
+//! 注意：不要在异步上下文（例如 `#[tokio::main]` 内部）中调用这里的方法，
+//! 否则会因为在运行时内部阻塞而 panic。
+
+use anyhow::Result;
+use tokio::runtime::Runtime;
+
+use crate::crawler::{self, CrawlReport, CrawlerConfig, DocPage};
+
+/// 阻塞式爬虫，接口与 [`crate::crawler::Crawler`] 保持一致
+pub struct Crawler {
+    inner: crawler::Crawler,
+    runtime: Runtime,
+}
+
+impl Crawler {
+    pub fn new(config: CrawlerConfig) -> Self {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("Failed to create tokio runtime");
+
+        Self {
+            inner: crawler::Crawler::new(config),
+            runtime,
+        }
+    }
+
+    /// 爬取指定 URL，阻塞直到完成
+    pub fn crawl_url(&mut self, url: &str, recursive: bool) -> Result<(Vec<DocPage>, CrawlReport)> {
+        self.runtime.block_on(self.inner.crawl_url(url, recursive))
+    }
+
+    /// 按关键字搜索并爬取，阻塞直到完成
+    pub fn search_and_crawl(&mut self, keyword: &str, recursive: bool) -> Result<(Vec<DocPage>, CrawlReport)> {
+        self.runtime.block_on(self.inner.search_and_crawl(keyword, recursive))
+    }
+}
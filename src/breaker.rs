@@ -0,0 +1,127 @@
+//! 连续失败时的熔断。
+//!
+//! 网络中断或 IP 被封禁时，之后的请求几乎都会失败，继续爬取只是在消耗重试和超时。
+//! 连续失败的请求数达到阈值时熔断：设置了冷却时间则暂停发起新请求，冷却结束后放行请求试探，
+//! 试探成功即恢复，仍然失败则再次熔断；没有设置冷却时间或熔断次数达到上限时放弃剩余的链接，
+//! 只输出已获取的结果。熔断前已发起的请求的结果不再计入。
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use crate::tr;
+
+/// 暂停后恢复爬取的最多次数，之后的熔断直接放弃剩余的链接
+const MAX_TRIPS: u32 = 5;
+
+#[derive(Default)]
+struct State {
+    consecutive: u32,
+    /// 冷却结束、等待试探结果
+    half_open: bool,
+    open_until: Option<Instant>,
+    last_trip: Option<Instant>,
+    trips: u32,
+    aborted: bool,
+}
+
+pub struct CircuitBreaker {
+    threshold: u32,
+    cooldown: Option<Duration>,
+    state: Mutex<State>,
+}
+
+impl CircuitBreaker {
+    /// 连续 `threshold` 个请求失败时熔断；`cooldown` 为暂停时间，`None` 时熔断即放弃剩余的链接
+    pub fn new(threshold: u32, cooldown: Option<Duration>) -> Self {
+        Self {
+            threshold: threshold.max(1),
+            cooldown,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// 是否已放弃剩余的链接
+    pub fn aborted(&self) -> bool {
+        self.state.lock().unwrap().aborted
+    }
+
+    /// 熔断期间等待冷却结束；已放弃时返回 `false`
+    pub async fn ready(&self) -> bool {
+        loop {
+            let until = {
+                let state = self.state.lock().unwrap();
+                if state.aborted {
+                    return false;
+                }
+                match state.open_until {
+                    Some(until) if until > Instant::now() => until,
+                    _ => return true,
+                }
+            };
+            tokio::time::sleep_until(until.into()).await;
+        }
+    }
+
+    /// 记录一次成功的请求（服务器正常响应，包括 404 等），`started` 为该请求的发起时间
+    pub fn record_success(&self, started: Instant) {
+        let mut state = self.state.lock().unwrap();
+        if state.last_trip.is_some_and(|tripped| started < tripped) {
+            return;
+        }
+        if state.half_open {
+            info!("{}", tr!("请求恢复正常，继续爬取", "Requests are succeeding again, resuming the crawl"));
+        }
+        state.consecutive = 0;
+        state.half_open = false;
+    }
+
+    /// 记录一次失败的请求，`reason` 为失败原因，用于熔断时的诊断信息
+    pub fn record_failure(&self, started: Instant, reason: &str) {
+        let mut state = self.state.lock().unwrap();
+        if state.aborted || state.last_trip.is_some_and(|tripped| started < tripped) {
+            return;
+        }
+        state.consecutive += 1;
+        if !state.half_open && state.consecutive < self.threshold {
+            return;
+        }
+
+        let failures = state.consecutive;
+        state.consecutive = 0;
+        state.trips += 1;
+        state.last_trip = Some(Instant::now());
+        let diagnosis = tr!(
+            "连续 {} 个请求失败（最近一次: {}），可能是网络中断或当前 IP 被限制访问",
+            "{} consecutive requests failed (last: {}); the network may be down or this IP may be blocked",
+            failures,
+            reason
+        );
+        match self.cooldown {
+            Some(cooldown) if state.trips <= MAX_TRIPS => {
+                state.half_open = true;
+                state.open_until = Some(Instant::now() + cooldown);
+                warn!(
+                    "{}",
+                    tr!(
+                        "{}，暂停 {}s 后重试",
+                        "{}; pausing for {}s before retrying",
+                        diagnosis,
+                        cooldown.as_secs()
+                    )
+                );
+            }
+            _ => {
+                state.aborted = true;
+                warn!(
+                    "{}",
+                    tr!(
+                        "{}，放弃剩余的链接并输出已获取的结果",
+                        "{}; giving up on the remaining links and writing the results so far",
+                        diagnosis
+                    )
+                );
+            }
+        }
+    }
+}
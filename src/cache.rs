@@ -0,0 +1,230 @@
+//! 响应缓存与离线模式（`--cache-dir`、`--offline`）。
+//!
+//! 设置 `--cache-dir` 时，爬取中下载的每个页面、搜索结果页和 JSON 数据都按 URL 保存到缓存目录，
+//! 失效页面（404 / 410）只记录状态码。加上 `--offline` 后不再发起任何请求，所有内容都从缓存读取，
+//! 缓存中没有的 URL 按 [`CacheMiss`] 失败，这样在没有网络的环境中也能基于上一次的缓存重新爬取、
+//! 换一种格式或范围输出。
+//!
+//! 缓存文件为 `<目录>/<主机>/<URL 的 64 位 FNV-1a 哈希>.json`，内容是 [`CachedResponse`]，
+//! 读取时校验其中的 URL，哈希冲突按未命中处理。每次联网爬取结束后，本次写入的文件连同 SHA-256 和大小
+//! 合并到目录中的 [`Manifest`]（`manifest.json`，页面还记录提取出的标题），
+//! `adoc verify --mirror` 据此检查缓存是否完整，见 [`crate::verify`]。
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::hash::{fnv1a, sha256_hex, FNV_OFFSET};
+use crate::tr;
+
+/// 缓存目录中清单文件的文件名
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// 缓存中的一个响应
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedResponse {
+    pub url: String,
+    /// HTTP 状态码，失效页面为 404 或 410，正文为空
+    pub status: u16,
+    /// 响应的 `Content-Type`，用于按原来的方式提取
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// 解码后的正文
+    #[serde(default)]
+    pub body: String,
+    /// 下载时间（Unix 时间戳，秒）
+    pub fetched_at: u64,
+}
+
+/// 离线模式下缓存中没有该 URL
+#[derive(Debug)]
+pub struct CacheMiss {
+    pub url: String,
+}
+
+impl std::fmt::Display for CacheMiss {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            tr!(
+                "离线模式下缓存中没有该页面: {}（需要先联网用 --cache-dir 爬取一次）",
+                "offline mode: {} is not in the cache (crawl it once online with --cache-dir first)",
+                self.url
+            )
+        )
+    }
+}
+
+impl std::error::Error for CacheMiss {}
+
+/// 缓存清单中的一个文件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub url: String,
+    /// 相对缓存目录的路径，以 `/` 分隔
+    pub file: String,
+    /// 缓存文件的 SHA-256
+    pub sha256: String,
+    /// 缓存文件的字节数
+    pub bytes: u64,
+    /// 响应的 HTTP 状态码
+    pub status: u16,
+    /// 从响应中提取的页面标题，搜索结果页、JSON 数据和失效页面没有
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+/// 缓存清单，按 URL 排序
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// 条目数，与 `entries` 的长度不同时说明清单被截断或改动过
+    pub count: usize,
+    pub entries: Vec<ManifestEntry>,
+}
+
+impl Manifest {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| tr!("无法读取缓存清单: {}", "cannot read cache manifest: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| tr!("无法解析缓存清单: {}", "cannot parse cache manifest: {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_vec_pretty(self)?)
+            .with_context(|| tr!("无法写入缓存清单: {}", "cannot write cache manifest: {}", path.display()))
+    }
+}
+
+/// 保存在目录中的响应缓存
+#[derive(Debug)]
+pub struct ResponseCache {
+    dir: PathBuf,
+    offline: bool,
+    /// 本次写入的文件，由 [`ResponseCache::save_manifest`] 合并到清单
+    written: Mutex<BTreeMap<String, ManifestEntry>>,
+}
+
+impl ResponseCache {
+    /// 打开缓存目录，不存在时创建；`offline` 为 true 时目录必须已存在
+    pub fn open(dir: &Path, offline: bool) -> Result<Self> {
+        if offline {
+            if !dir.is_dir() {
+                bail!(tr!(
+                    "离线模式需要已有的缓存目录: {}",
+                    "offline mode needs an existing cache directory: {}",
+                    dir.display()
+                ));
+            }
+        } else {
+            std::fs::create_dir_all(dir)
+                .with_context(|| tr!("无法创建缓存目录: {}", "cannot create cache directory: {}", dir.display()))?;
+        }
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            offline,
+            written: Mutex::new(BTreeMap::new()),
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// 清单文件的路径
+    pub fn manifest_path(&self) -> PathBuf {
+        self.dir.join(MANIFEST_FILE)
+    }
+
+    /// 是否只从缓存读取，不发起请求
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// URL 对应的缓存文件
+    pub fn path(&self, url: &str) -> PathBuf {
+        let host = url::Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_else(|| "_".to_string());
+        self.dir.join(host).join(format!("{:016x}.json", fnv1a(url.as_bytes(), FNV_OFFSET)))
+    }
+
+    /// 读取缓存的响应，没有或无法读取时返回 `None`
+    pub fn get(&self, url: &str) -> Option<CachedResponse> {
+        let content = std::fs::read_to_string(self.path(url)).ok()?;
+        serde_json::from_str::<CachedResponse>(&content).ok().filter(|cached| cached.url == url)
+    }
+
+    /// 读取缓存的响应，没有时返回 [`CacheMiss`]
+    pub fn require(&self, url: &str) -> Result<CachedResponse> {
+        self.get(url).ok_or_else(|| CacheMiss { url: url.to_string() }.into())
+    }
+
+    /// 保存一个响应，覆盖已有的缓存
+    pub fn put(&self, url: &str, status: u16, content_type: Option<&str>, body: &str) -> Result<()> {
+        let cached = CachedResponse {
+            url: url.to_string(),
+            status,
+            content_type: content_type.map(str::to_string),
+            body: body.to_string(),
+            fetched_at: SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs()),
+        };
+        let path = self.path(url);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        // 先写临时文件再改名，中途退出不会留下不完整的缓存
+        let partial = path.with_extension("json.partial");
+        let content = serde_json::to_vec(&cached)?;
+        std::fs::write(&partial, &content)
+            .with_context(|| tr!("无法写入缓存: {}", "cannot write cache: {}", path.display()))?;
+        std::fs::rename(&partial, &path)?;
+
+        let entry = ManifestEntry {
+            url: url.to_string(),
+            file: manifest_file(&self.dir, &path),
+            sha256: sha256_hex(&content),
+            bytes: content.len() as u64,
+            status,
+            title: None,
+        };
+        self.written.lock().unwrap().insert(url.to_string(), entry);
+        Ok(())
+    }
+
+    /// 记录本次写入的 `url` 提取出的页面标题，写入清单
+    pub fn set_title(&self, url: &str, title: &str) {
+        if let Some(entry) = self.written.lock().unwrap().get_mut(url) {
+            entry.title = Some(title.to_string());
+        }
+    }
+
+    /// 把本次写入的文件合并到清单，返回清单中的条目数；已有的清单无法读取时重新生成
+    pub fn save_manifest(&self) -> Result<usize> {
+        let path = self.manifest_path();
+        let existing = if path.exists() { Manifest::load(&path).ok() } else { None };
+        let mut entries: BTreeMap<String, ManifestEntry> = existing
+            .into_iter()
+            .flat_map(|manifest| manifest.entries)
+            .map(|entry| (entry.url.clone(), entry))
+            .collect();
+        entries.extend(self.written.lock().unwrap().iter().map(|(url, entry)| (url.clone(), entry.clone())));
+        let manifest = Manifest {
+            count: entries.len(),
+            entries: entries.into_values().collect(),
+        };
+        manifest.save(&path)?;
+        Ok(manifest.count)
+    }
+}
+
+/// 清单中记录的文件路径：相对缓存目录，以 `/` 分隔
+pub(crate) fn manifest_file(dir: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(dir).unwrap_or(path);
+    relative.components().map(|part| part.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/")
+}
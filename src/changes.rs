@@ -0,0 +1,296 @@
+//! 发行说明（release notes）和 API 变化页面中的符号变化。
+//!
+//! 发行说明（`/documentation/ios-ipados-release-notes/ios-17-release-notes` 等）按框架分节，每节下有
+//! `New Features`、`Deprecations`、`Resolved Issues` 等小节；这里把各小节中引用的符号整理为一个 SDK 的
+//! [`SdkChanges`]（新增、修改、弃用），保存在 [`DocPage::changes`] 中，正文不变。
+//! API 变化数据（以 DocC 标识符为键、值带 `change` 字段的 JSON）提取为单独的页面，正文按变化类型列出符号，
+//! 相关链接为这些符号的文档页面。与 `adoc diff` 配合，可以跟踪每个 SDK 版本新增、修改和弃用的接口。
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use serde_json::{Map, Value};
+use url::Url;
+
+use crate::extract::{clean_text, DocPage, LinkCollector, LinkFilter};
+use crate::profile::SiteProfile;
+
+/// DocC 标识符对应的文档站点
+const DOCUMENTATION_HOST: &str = "https://developer.apple.com";
+
+/// 符号的变化类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Added,
+    Modified,
+    Deprecated,
+}
+
+impl ChangeKind {
+    fn parse(text: &str) -> Option<Self> {
+        match text {
+            "added" => Some(Self::Added),
+            "modified" => Some(Self::Modified),
+            "deprecated" => Some(Self::Deprecated),
+            _ => None,
+        }
+    }
+
+    /// 发行说明中小节标题对应的变化类型，`Known Issues` 等与接口无关的小节返回 `None`
+    fn from_heading(heading: &str) -> Option<Self> {
+        let heading = heading.to_ascii_lowercase();
+        if heading.contains("known issue") {
+            None
+        } else if heading.contains("new feature") || heading.starts_with("new ") || heading.contains("addition") {
+            Some(Self::Added)
+        } else if heading.contains("deprecat") || heading.contains("removed") || heading.contains("removal") {
+            Some(Self::Deprecated)
+        } else if heading.contains("resolved") || heading.contains("change") || heading.contains("update") {
+            Some(Self::Modified)
+        } else {
+            None
+        }
+    }
+
+    fn heading(self) -> &'static str {
+        match self {
+            Self::Added => "Added",
+            Self::Modified => "Modified",
+            Self::Deprecated => "Deprecated",
+        }
+    }
+}
+
+/// 发生变化的符号
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ChangedSymbol {
+    pub name: String,
+    /// 符号的文档页面
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// 所属框架，发行说明中为框架小节的标题，API 变化数据中取自文档路径
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub framework: Option<String>,
+}
+
+/// 一个 SDK 版本中的符号变化
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SdkChanges {
+    /// SDK 版本，例如 `iOS 17`
+    pub sdk: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub added: Vec<ChangedSymbol>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modified: Vec<ChangedSymbol>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deprecated: Vec<ChangedSymbol>,
+}
+
+impl SdkChanges {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.modified.is_empty() && self.deprecated.is_empty()
+    }
+
+    pub fn symbols(&self, kind: ChangeKind) -> &[ChangedSymbol] {
+        match kind {
+            ChangeKind::Added => &self.added,
+            ChangeKind::Modified => &self.modified,
+            ChangeKind::Deprecated => &self.deprecated,
+        }
+    }
+
+    /// 加入一个符号，同一类型中已有同一地址（没有地址时同名）的符号时忽略
+    fn push(&mut self, kind: ChangeKind, symbol: ChangedSymbol) {
+        let list = match kind {
+            ChangeKind::Added => &mut self.added,
+            ChangeKind::Modified => &mut self.modified,
+            ChangeKind::Deprecated => &mut self.deprecated,
+        };
+        let duplicate = list.iter().any(|existing| match (&existing.url, &symbol.url) {
+            (Some(a), Some(b)) => a == b,
+            _ => existing.name == symbol.name,
+        });
+        if !duplicate {
+            list.push(symbol);
+        }
+    }
+
+    /// 按变化类型列出符号的正文行：`## Added`，然后每个符号一行 `- 名称 (地址)`
+    pub fn summary_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for kind in [ChangeKind::Added, ChangeKind::Modified, ChangeKind::Deprecated] {
+            let symbols = self.symbols(kind);
+            if symbols.is_empty() {
+                continue;
+            }
+            lines.push(format!("## {}", kind.heading()));
+            for symbol in symbols {
+                match &symbol.url {
+                    Some(url) => lines.push(format!("- {} ({})", symbol.name, url)),
+                    None => lines.push(format!("- {}", symbol.name)),
+                }
+            }
+        }
+        lines
+    }
+}
+
+/// 是否为发行说明页面
+pub fn is_release_notes_url(url: &str) -> bool {
+    url.contains("developer.apple.com")
+        && Url::parse(url).is_ok_and(|url| {
+            url.path().starts_with("/documentation/") && url.path().to_ascii_lowercase().contains("release-notes")
+        })
+}
+
+/// 从发行说明的 DocC render JSON 中整理符号变化，SDK 取自标题（`iOS 17 Release Notes` → `iOS 17`），
+/// 没有引用任何符号时返回 `None`
+pub fn from_release_notes(url: &str, data: &Value) -> Option<SdkChanges> {
+    let base_url = Url::parse(url).ok()?;
+    let references = data.get("references").unwrap_or(&Value::Null);
+    let title = data.pointer("/metadata/title").and_then(Value::as_str).map(clean_text).unwrap_or_default();
+    let sdk = title
+        .strip_suffix(" Release Notes")
+        .or_else(|| title.strip_suffix(" release notes"))
+        .unwrap_or(&title)
+        .to_string();
+    let mut changes = SdkChanges { sdk, ..Default::default() };
+
+    let mut framework: Option<String> = None;
+    let mut kind: Option<ChangeKind> = None;
+    for section in array(data.get("primaryContentSections")) {
+        for block in array(section.get("content")) {
+            if block.get("type").and_then(Value::as_str) == Some("heading") {
+                let heading = block.get("text").and_then(Value::as_str).map(clean_text).unwrap_or_default();
+                let level = block.get("level").and_then(Value::as_u64).unwrap_or(2);
+                // 小节标题决定变化类型，其他标题（通常是框架名）开始新的一节
+                match ChangeKind::from_heading(&heading) {
+                    Some(heading_kind) => kind = Some(heading_kind),
+                    None if heading.to_ascii_lowercase().contains("issue") => kind = None,
+                    None if level <= 3 => {
+                        framework = Some(heading).filter(|heading| !heading.is_empty());
+                        kind = None;
+                    }
+                    None => {}
+                }
+                continue;
+            }
+            let Some(kind) = kind else {
+                continue;
+            };
+            let mut identifiers = Vec::new();
+            referenced(block, &mut identifiers);
+            for reference in identifiers.into_iter().filter_map(|id| references.get(id)) {
+                if reference.get("kind").and_then(Value::as_str) != Some("symbol") {
+                    continue;
+                }
+                let Some(name) = reference.get("title").and_then(Value::as_str).map(clean_text) else {
+                    continue;
+                };
+                let url = reference
+                    .get("url")
+                    .and_then(Value::as_str)
+                    .and_then(|href| base_url.join(href).ok())
+                    .map(String::from);
+                changes.push(kind, ChangedSymbol { name, url, framework: framework.clone() });
+            }
+        }
+    }
+    (!changes.is_empty()).then_some(changes)
+}
+
+/// 块中按顺序出现的引用标识符
+fn referenced<'a>(value: &'a Value, identifiers: &mut Vec<&'a str>) {
+    match value {
+        Value::Object(object) => {
+            if object.get("type").and_then(Value::as_str) == Some("reference") {
+                if let Some(id) = object.get("identifier").and_then(Value::as_str) {
+                    identifiers.push(id);
+                }
+            }
+            for child in object.values() {
+                referenced(child, identifiers);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                referenced(item, identifiers);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// API 变化数据中以标识符为键的变化表，可以直接是顶层对象，也可以在 `changes` 字段中
+fn change_map(data: &Value) -> Option<&Map<String, Value>> {
+    let map = data.get("changes").unwrap_or(data).as_object()?;
+    let is_change = |value: &Value| value.get("change").and_then(Value::as_str).and_then(ChangeKind::parse).is_some();
+    (!map.is_empty() && map.values().all(is_change)).then_some(map)
+}
+
+/// 是否为 API 变化数据
+pub fn is_api_diff(data: &Value) -> bool {
+    change_map(data).is_some()
+}
+
+/// 从 API 变化数据中提取 `DocPage`：正文按变化类型列出符号，`changes` 为该 SDK 的变化，
+/// 相关链接为各符号的文档页面。SDK 取自数据中的 `sdk` 或 `version` 字段，没有时取自文件名
+pub fn extract_api_diff(profile: &SiteProfile, url: &str, data: &Value, links: &LinkFilter) -> Result<DocPage> {
+    let page_url = Url::parse(url)?;
+    let sdk = ["sdk", "version"]
+        .iter()
+        .find_map(|key| data.get(key).and_then(Value::as_str))
+        .map(str::to_string)
+        .or_else(|| {
+            let name = page_url.path_segments()?.rev().find(|segment| !segment.is_empty())?;
+            Some(name.trim_end_matches(".json").to_string())
+        })
+        .unwrap_or_default();
+    let mut changes = SdkChanges { sdk, ..Default::default() };
+
+    let base_url = Url::parse(DOCUMENTATION_HOST)?;
+    let mut entries: Vec<(&String, &Value)> = change_map(data).into_iter().flatten().collect();
+    entries.sort_by_key(|(identifier, _)| identifier.as_str());
+    for (identifier, entry) in entries {
+        let Some(kind) = entry.get("change").and_then(Value::as_str).and_then(ChangeKind::parse) else {
+            continue;
+        };
+        // doc://com.apple.documentation/documentation/swiftui/list → /documentation/swiftui/list
+        let path = identifier.strip_prefix("doc://").and_then(|rest| rest.find('/').map(|at| &rest[at..]));
+        let url = path.and_then(|path| base_url.join(path).ok());
+        let framework = url.as_ref().and_then(|url| {
+            let mut segments = url.path_segments()?;
+            (segments.next()? == "documentation").then(|| segments.next()).flatten().map(str::to_string)
+        });
+        let name = entry
+            .get("title")
+            .and_then(Value::as_str)
+            .map(clean_text)
+            .or_else(|| path.and_then(|path| path.rsplit('/').next()).map(str::to_string))
+            .unwrap_or_else(|| identifier.clone());
+        changes.push(kind, ChangedSymbol { name, url: url.map(String::from), framework });
+    }
+
+    let mut related_links = LinkCollector::new(profile, url, links)?;
+    let groups = [("Added", &changes.added), ("Modified", &changes.modified), ("Deprecated", &changes.deprecated)];
+    for (section, symbols) in groups {
+        for symbol in symbols {
+            if let Some(link) = symbol.url.as_deref().and_then(|link| Url::parse(link).ok()) {
+                related_links.push(link, &symbol.name, Some(section));
+            }
+        }
+    }
+    Ok(DocPage {
+        title: format!("API Changes: {}", changes.sdk),
+        content: changes.summary_lines().join("\n"),
+        url: url.to_string(),
+        related_links: related_links.finish(),
+        changes: vec![changes],
+        ..Default::default()
+    })
+}
+
+fn array(value: Option<&Value>) -> &[Value] {
+    value.and_then(Value::as_array).map(Vec::as_slice).unwrap_or_default()
+}
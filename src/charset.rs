@@ -0,0 +1,71 @@
+//! 响应正文的编码识别与解码。
+//!
+//! 按以下顺序确定编码：
+//!
+//! 1. BOM；
+//! 2. 正文是合法的 UTF-8 时按 UTF-8 解码：其他编码的非 ASCII 文本几乎不可能恰好是合法的 UTF-8，
+//!    这样声明错误（例如 UTF-8 页面声明为 ISO-8859-1）的页面也能正确解码；
+//! 3. `Content-Type` 中的 charset，其次是前 1024 字节中 `<meta>` 声明的 charset，
+//!    只有能无损解码时才采用；
+//! 4. 以上都不满足时由 chardetng 根据字节分布猜测。
+//!
+//! 与提取逻辑一样不依赖 tokio 或 reqwest，可以编译到 wasm32。
+
+use chardetng::EncodingDetector;
+use encoding_rs::Encoding;
+use regex::bytes::Regex;
+use std::sync::OnceLock;
+
+/// `<meta>` 预扫描的字节数，与浏览器相同
+const PRESCAN_BYTES: usize = 1024;
+
+/// 把响应正文解码为字符串，`content_type` 为 `Content-Type` 响应头
+pub fn decode(bytes: Vec<u8>, content_type: Option<&str>) -> String {
+    if let Some((encoding, bom_length)) = Encoding::for_bom(&bytes) {
+        return encoding.decode_without_bom_handling(&bytes[bom_length..]).0.into_owned();
+    }
+    let bytes = match String::from_utf8(bytes) {
+        Ok(text) => return text,
+        Err(e) => e.into_bytes(),
+    };
+
+    let declared = content_type.and_then(header_charset).into_iter().chain(meta_charset(&bytes));
+    for label in declared {
+        let Some(encoding) = Encoding::for_label(label.as_bytes()) else {
+            continue;
+        };
+        if let Some(text) = encoding.decode_without_bom_handling_and_without_replacement(&bytes) {
+            return text.into_owned();
+        }
+    }
+
+    detect(&bytes).decode_without_bom_handling(&bytes).0.into_owned()
+}
+
+/// 根据字节分布猜测编码
+pub fn detect(bytes: &[u8]) -> &'static Encoding {
+    let mut detector = EncodingDetector::new();
+    detector.feed(bytes, true);
+    detector.guess(None, true)
+}
+
+/// `Content-Type` 中的 charset 参数，例如 `text/html; charset="Shift_JIS"`
+fn header_charset(content_type: &str) -> Option<String> {
+    content_type.split(';').skip(1).find_map(|parameter| {
+        let (name, value) = parameter.split_once('=')?;
+        name.trim()
+            .eq_ignore_ascii_case("charset")
+            .then(|| value.trim().trim_matches(['"', '\'']).to_string())
+    })
+}
+
+/// 前 1024 字节中 `<meta charset>` 或 `<meta http-equiv="Content-Type" content="...; charset=...">` 声明的编码
+fn meta_charset(bytes: &[u8]) -> Option<String> {
+    static META: OnceLock<Regex> = OnceLock::new();
+    let meta = META.get_or_init(|| {
+        Regex::new(r#"(?i-u)<meta[^>]*?charset\s*=\s*["']?\s*([a-z0-9_:.\-]+)"#).expect("valid meta regex")
+    });
+    let head = &bytes[..bytes.len().min(PRESCAN_BYTES)];
+    let label = meta.captures(head)?.get(1)?.as_bytes();
+    Some(String::from_utf8_lossy(label).into_owned())
+}
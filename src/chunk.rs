@@ -0,0 +1,124 @@
+//! 面向 RAG 的内容分块导出。
+//!
+//! token 数以空白分隔的词近似计算，分块保留原文中的换行。
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::extract::DocPage;
+use crate::tr;
+
+/// 一个内容分块及其来源信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Chunk {
+    pub url: String,
+    pub title: String,
+    pub hierarchy: Vec<String>,
+    /// 分块在页面中的序号，从 0 开始
+    pub position: usize,
+    /// 该页面的分块总数
+    pub total: usize,
+    pub token_count: usize,
+    pub content: String,
+    /// 内容的嵌入向量，只有设置 `--embed` 时才有
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>,
+}
+
+/// 返回每个词在文本中的字节区间
+fn word_spans(text: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices() {
+        match (c.is_whitespace(), start) {
+            (true, Some(s)) => {
+                spans.push((s, i));
+                start = None;
+            }
+            (false, None) => start = Some(i),
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        spans.push((s, text.len()));
+    }
+    spans
+}
+
+/// 检查分块参数是否有效
+pub fn check_options(size: usize, overlap: usize) -> Result<()> {
+    if size == 0 {
+        bail!(tr!("分块大小必须大于 0", "chunk size must be greater than 0"));
+    }
+    if overlap >= size {
+        bail!(tr!(
+            "分块重叠 ({}) 必须小于分块大小 ({})",
+            "chunk overlap ({}) must be less than chunk size ({})",
+            overlap,
+            size
+        ));
+    }
+    Ok(())
+}
+
+/// 将单个页面按 `size` 个 token 分块，相邻分块重叠 `overlap` 个 token
+pub fn chunk_page(page: &DocPage, size: usize, overlap: usize) -> Result<Vec<Chunk>> {
+    check_options(size, overlap)?;
+
+    let spans = word_spans(&page.content);
+    let step = size - overlap;
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    while start < spans.len() {
+        let end = (start + size).min(spans.len());
+        ranges.push((start, end));
+        if end == spans.len() {
+            break;
+        }
+        start += step;
+    }
+
+    let hierarchy = page.hierarchy();
+    let total = ranges.len();
+    Ok(ranges
+        .into_iter()
+        .enumerate()
+        .map(|(position, (start, end))| Chunk {
+            url: page.url.clone(),
+            title: page.title.clone(),
+            hierarchy: hierarchy.clone(),
+            position,
+            total,
+            token_count: end - start,
+            content: page.content[spans[start].0..spans[end - 1].1].to_string(),
+            embedding: None,
+        })
+        .collect())
+}
+
+/// 对所有页面分块
+pub fn chunk_pages(pages: &[DocPage], size: usize, overlap: usize) -> Result<Vec<Chunk>> {
+    let mut chunks = Vec::new();
+    for page in pages {
+        chunks.extend(chunk_page(page, size, overlap)?);
+    }
+    Ok(chunks)
+}
+
+/// 序列化为 JSONL，每行一个分块
+pub fn to_jsonl(chunks: &[Chunk]) -> Result<String> {
+    #[cfg(not(target_arch = "wasm32"))]
+    let lines = {
+        use rayon::prelude::*;
+        chunks.par_iter().map(serde_json::to_string).collect::<Result<Vec<_>, _>>()?
+    };
+    #[cfg(target_arch = "wasm32")]
+    let lines = chunks.iter().map(serde_json::to_string).collect::<Result<Vec<_>, _>>()?;
+
+    let mut content = String::with_capacity(lines.iter().map(|line| line.len() + 1).sum());
+    for line in lines {
+        content.push_str(&line);
+        content.push('\n');
+    }
+    Ok(content)
+}
@@ -0,0 +1,128 @@
+//! 自适应并发控制（AIMD）。
+//!
+//! 请求顺利时每完成一轮（与当前并发数相同的成功请求数）把并发数加一；
+//! 遇到超时、429、503 等过载信号时减半。过载后只对减半之后发起的请求重新计数，
+//! 同一批超时不会让并发数连续减半。
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::{Semaphore, SemaphorePermit};
+use tracing::debug;
+
+use crate::tr;
+
+/// 延迟超过历史最低延迟的倍数时，不再视为"顺利"，也不再提高并发数
+const LATENCY_TOLERANCE: u32 = 3;
+
+struct State {
+    limit: usize,
+    /// 减少并发数时仍被占用、需要在归还时回收的许可数
+    debt: usize,
+    successes: usize,
+    best_latency: Option<Duration>,
+    last_decrease: Option<Instant>,
+}
+
+/// 并发数在 1 和 `max` 之间自动调整的信号量
+pub struct AdaptiveLimit {
+    semaphore: Semaphore,
+    state: Mutex<State>,
+    max: usize,
+}
+
+/// 并发许可，释放时归还给 [`AdaptiveLimit`]
+pub struct AdaptivePermit<'a> {
+    limit: &'a AdaptiveLimit,
+    permit: Option<SemaphorePermit<'a>>,
+}
+
+impl Drop for AdaptivePermit<'_> {
+    fn drop(&mut self) {
+        let Some(permit) = self.permit.take() else {
+            return;
+        };
+        let mut state = self.limit.state.lock().unwrap();
+        if state.debt > 0 {
+            state.debt -= 1;
+            permit.forget();
+        }
+    }
+}
+
+impl AdaptiveLimit {
+    /// 以 `initial` 为初始并发数，最多提高到 `max`
+    pub fn new(initial: usize, max: usize) -> Self {
+        let max = max.max(1);
+        let initial = initial.clamp(1, max);
+        Self {
+            semaphore: Semaphore::new(initial),
+            state: Mutex::new(State {
+                limit: initial,
+                debt: 0,
+                successes: 0,
+                best_latency: None,
+                last_decrease: None,
+            }),
+            max,
+        }
+    }
+
+    /// 当前并发数
+    pub fn current(&self) -> usize {
+        self.state.lock().unwrap().limit
+    }
+
+    /// 并发数上限
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    pub async fn acquire(&self) -> AdaptivePermit<'_> {
+        let permit = self.semaphore.acquire().await.expect("semaphore is never closed");
+        AdaptivePermit {
+            limit: self,
+            permit: Some(permit),
+        }
+    }
+
+    /// 记录一次顺利完成的请求
+    pub fn record_success(&self, latency: Duration) {
+        let mut state = self.state.lock().unwrap();
+        let best = state.best_latency.map_or(latency, |best| best.min(latency));
+        state.best_latency = Some(best);
+        if latency > best * LATENCY_TOLERANCE {
+            return;
+        }
+        state.successes += 1;
+        if state.successes < state.limit || state.limit >= self.max {
+            return;
+        }
+        state.successes = 0;
+        state.limit += 1;
+        if state.debt > 0 {
+            state.debt -= 1;
+        } else {
+            self.semaphore.add_permits(1);
+        }
+        debug!("{}", tr!("提高并发数到 {}", "Raising concurrency to {}", state.limit));
+    }
+
+    /// 记录一次过载信号（超时、429、503），`started` 为该请求的发起时间
+    pub fn record_overload(&self, started: Instant) {
+        let mut state = self.state.lock().unwrap();
+        if state.last_decrease.is_some_and(|decreased| started < decreased) {
+            return;
+        }
+        state.successes = 0;
+        let target = (state.limit / 2).max(1);
+        let reduce = state.limit - target;
+        if reduce == 0 {
+            return;
+        }
+        state.limit = target;
+        state.last_decrease = Some(Instant::now());
+        let forgotten = self.semaphore.forget_permits(reduce);
+        state.debt += reduce - forgotten;
+        debug!("{}", tr!("检测到过载，降低并发数到 {}", "Overload detected, lowering concurrency to {}", target));
+    }
+}
@@ -0,0 +1,358 @@
+//! `adoc.toml` 配置文件。
+//!
+//! 键名与命令行参数一致（去掉前缀 `--`），命令行中显式给出的参数优先于配置文件：
+//!
+//! ```toml
+//! input = "SwiftUI"
+//! recursive = true
+//! concurrency = 10
+//! output = "swiftui.json"
+//! format = "pretty"
+//! max-retries = 5
+//! timeout = 60
+//! max-duration = "10m"
+//!
+//! # 通过 --profile uikit 选择，未出现的键沿用上面的顶层配置
+//! [profile.uikit]
+//! input = "UIKit"
+//! output = "uikit.json"
+//! ```
+
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use serde::{Deserialize, Deserializer};
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::arrange::{GroupBy, SortKey};
+use crate::crawler::HttpVersion;
+use crate::dns::{parse_dns, Dns};
+use crate::i18n::Lang;
+use crate::output::OutputFormat;
+use crate::profile::DocSource;
+use crate::raw::RawEncoding;
+use crate::progress::ProgressFormat;
+use crate::sink::SinkKind;
+use crate::tokens::TokenEncoding;
+use crate::tr;
+use crate::visited::{parse_count, VisitedKind};
+
+/// 未指定 `--config` 时在当前目录查找的配置文件
+pub const DEFAULT_CONFIG_FILE: &str = "adoc.toml";
+
+/// 日志输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// 便于阅读的文本
+    Text,
+    /// 每条日志一个 JSON 对象，便于日志系统解析
+    Json,
+}
+
+/// 配置文件内容，未出现的键为 `None`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct FileConfig {
+    pub input: Option<String>,
+    #[serde(default, deserialize_with = "value_enum")]
+    pub source: Option<DocSource>,
+    pub retry_failed: Option<PathBuf>,
+    pub first: Option<bool>,
+    pub top: Option<usize>,
+    pub recursive: Option<bool>,
+    pub dry_run: Option<bool>,
+    pub stream: Option<bool>,
+    pub tombstones: Option<bool>,
+    #[serde(default, deserialize_with = "positive")]
+    pub concurrency: Option<usize>,
+    pub adaptive_concurrency: Option<bool>,
+    #[serde(default, deserialize_with = "positive")]
+    pub max_concurrency: Option<usize>,
+    pub output: Option<PathBuf>,
+    #[serde(default, deserialize_with = "value_enum")]
+    pub format: Option<OutputFormat>,
+    pub report: Option<PathBuf>,
+    pub merge_into: Option<PathBuf>,
+    pub delta_output: Option<PathBuf>,
+    pub git_repo: Option<PathBuf>,
+    pub failures: Option<PathBuf>,
+    pub download_media: Option<PathBuf>,
+    pub chunk_size: Option<usize>,
+    pub chunk_overlap: Option<usize>,
+    pub embed: Option<String>,
+    pub embed_model: Option<String>,
+    pub embed_key: Option<String>,
+    pub embed_batch_size: Option<usize>,
+    pub summarize: Option<String>,
+    pub summarize_workers: Option<usize>,
+    pub translate: Option<String>,
+    pub translator: Option<String>,
+    #[serde(default, deserialize_with = "value_enum")]
+    pub token_stats: Option<TokenEncoding>,
+    pub excerpt_only: Option<bool>,
+    pub max_content_chars: Option<usize>,
+    pub with_http_meta: Option<bool>,
+    #[serde(default, deserialize_with = "value_enum")]
+    pub include_raw_html: Option<RawEncoding>,
+    pub glossary: Option<PathBuf>,
+    #[serde(default, deserialize_with = "value_enum")]
+    pub sort: Option<SortKey>,
+    #[serde(default, deserialize_with = "value_enum")]
+    pub group_by: Option<GroupBy>,
+    pub tags: Option<bool>,
+    #[serde(default, deserialize_with = "value_enum")]
+    pub sink: Option<SinkKind>,
+    pub sink_url: Option<String>,
+    pub sink_key: Option<String>,
+    pub sink_index: Option<String>,
+    pub sink_primary_key: Option<String>,
+    pub sink_searchable: Option<Vec<String>>,
+    pub sink_batch_size: Option<usize>,
+    pub database_id: Option<String>,
+    pub space: Option<String>,
+    pub parent_page: Option<String>,
+    pub brokers: Option<String>,
+    pub topic: Option<String>,
+    pub max_retries: Option<u32>,
+    pub timeout: Option<u64>,
+    #[serde(default, deserialize_with = "duration")]
+    pub max_duration: Option<Duration>,
+    pub breaker_threshold: Option<u32>,
+    #[serde(default, deserialize_with = "duration")]
+    pub breaker_cooldown: Option<Duration>,
+    pub pool_max_idle_per_host: Option<usize>,
+    #[serde(default, deserialize_with = "duration")]
+    pub pool_idle_timeout: Option<Duration>,
+    #[serde(default, deserialize_with = "duration")]
+    pub tcp_keepalive: Option<Duration>,
+    #[serde(default, deserialize_with = "value_enum")]
+    pub http_version: Option<HttpVersion>,
+    pub http2_adaptive_window: Option<bool>,
+    #[serde(default, deserialize_with = "size")]
+    pub max_body_size: Option<usize>,
+    pub cache_dir: Option<PathBuf>,
+    pub offline: Option<bool>,
+    pub ca_cert: Option<PathBuf>,
+    pub insecure: Option<bool>,
+    pub ipv4_only: Option<bool>,
+    pub ipv6_only: Option<bool>,
+    #[serde(default, deserialize_with = "dns")]
+    pub dns: Option<Dns>,
+    pub site_profile: Option<PathBuf>,
+    pub allow_domain: Option<Vec<String>>,
+    pub allow_external: Option<bool>,
+    pub sample_readme: Option<bool>,
+    pub search_pages: Option<usize>,
+    #[serde(default, deserialize_with = "value_enum")]
+    pub visited: Option<VisitedKind>,
+    #[serde(default, deserialize_with = "count")]
+    pub expected_urls: Option<usize>,
+    pub redis_url: Option<String>,
+    pub redis_key: Option<String>,
+    #[serde(default, deserialize_with = "size")]
+    pub memory_budget: Option<usize>,
+    #[serde(default, deserialize_with = "value_enum")]
+    pub progress: Option<ProgressFormat>,
+    pub progress_file: Option<PathBuf>,
+    pub dashboard: Option<bool>,
+    pub metrics_addr: Option<SocketAddr>,
+    pub log_level: Option<String>,
+    #[serde(default, deserialize_with = "value_enum")]
+    pub log_format: Option<LogFormat>,
+    #[serde(default, deserialize_with = "value_enum")]
+    pub lang: Option<Lang>,
+    /// 命名配置 `[profile.<name>]`
+    #[serde(default)]
+    pub profile: BTreeMap<String, FileConfig>,
+}
+
+/// 解析时长，例如 `90s`、`10m`、`1h30m`，不带单位时按秒计算
+pub fn parse_duration(value: &str) -> Result<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Ok(Duration::from_secs(secs));
+    }
+    let mut total = 0;
+    let mut number = String::new();
+    for c in value.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let unit = match c {
+            's' => 1,
+            'm' => 60,
+            'h' => 3600,
+            _ => bail!(tr!("无效的时长: {}", "invalid duration: {}", value)),
+        };
+        let Ok(count) = number.parse::<u64>() else {
+            bail!(tr!("无效的时长: {}", "invalid duration: {}", value));
+        };
+        total += count * unit;
+        number.clear();
+    }
+    if !number.is_empty() || value.is_empty() {
+        bail!(tr!("无效的时长: {}", "invalid duration: {}", value));
+    }
+    Ok(Duration::from_secs(total))
+}
+
+/// 解析字节数，支持 `K`、`M`、`G` 后缀（按 1024 进位，可带 `B`，不区分大小写），例如 `512M`、`2GB`
+pub fn parse_size(value: &str) -> Result<usize> {
+    let value = value.trim();
+    let upper = value.to_ascii_uppercase();
+    let number = upper.strip_suffix('B').unwrap_or(&upper);
+    let (number, multiplier) = match number.char_indices().last() {
+        Some((index, 'K')) => (&number[..index], 1 << 10),
+        Some((index, 'M')) => (&number[..index], 1 << 20),
+        Some((index, 'G')) => (&number[..index], 1 << 30),
+        _ => (number, 1),
+    };
+    number
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .and_then(|number| number.checked_mul(multiplier))
+        .with_context(|| tr!("无效的大小: {}", "invalid size: {}", value))
+}
+
+fn duration<'de, D>(deserializer: D) -> std::result::Result<Option<Duration>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|value| parse_duration(&value).map_err(serde::de::Error::custom))
+        .transpose()
+}
+
+/// 域名解析方式，例如 `"doh:https://1.1.1.1/dns-query"`
+fn dns<'de, D>(deserializer: D) -> std::result::Result<Option<Dns>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|value| parse_dns(&value).map_err(serde::de::Error::custom))
+        .transpose()
+}
+
+/// 数量既可以写成整数，也可以写成带后缀的字符串，例如 `"5M"`
+fn count<'de, D>(deserializer: D) -> std::result::Result<Option<usize>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    number_or_text(deserializer, parse_count)
+}
+
+/// 字节数，例如 `"512M"`、`"2G"`
+fn size<'de, D>(deserializer: D) -> std::result::Result<Option<usize>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    number_or_text(deserializer, parse_size)
+}
+
+fn number_or_text<'de, D>(
+    deserializer: D,
+    parse: fn(&str) -> Result<usize>,
+) -> std::result::Result<Option<usize>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Value {
+        Number(usize),
+        Text(String),
+    }
+    match Option::<Value>::deserialize(deserializer)? {
+        Some(Value::Number(number)) => Ok(Some(number)),
+        Some(Value::Text(text)) => parse(&text).map(Some).map_err(serde::de::Error::custom),
+        None => Ok(None),
+    }
+}
+
+/// 必须大于 0 的数量，例如 `concurrency = 10`
+fn positive<'de, D>(deserializer: D) -> std::result::Result<Option<usize>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<usize>::deserialize(deserializer)? {
+        Some(0) => Err(serde::de::Error::custom(tr!("必须大于 0", "must be greater than 0"))),
+        value => Ok(value),
+    }
+}
+
+/// 按命令行取值的写法解析枚举，例如 `format = "es-bulk"`
+fn value_enum<'de, D, T>(deserializer: D) -> std::result::Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: ValueEnum,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|value| T::from_str(&value, true).map_err(serde::de::Error::custom))
+        .transpose()
+}
+
+impl FileConfig {
+    pub fn parse(content: &str) -> Result<Self> {
+        Ok(toml::from_str(content)?)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| tr!("无法读取配置文件: {}", "cannot read config file: {}", path.display()))?;
+        Self::parse(&content).with_context(|| tr!("配置文件格式错误: {}", "invalid config file: {}", path.display()))
+    }
+
+    /// 以顶层配置为基础，用指定 profile 中出现的键覆盖
+    pub fn with_profile(mut self, name: &str) -> Result<Self> {
+        let Some(profile) = self.profile.remove(name) else {
+            let names: Vec<&str> = self.profile.keys().map(String::as_str).collect();
+            bail!(tr!(
+                "配置文件中没有 profile: {}（可用: {}）",
+                "no profile {} in the config file (available: {})",
+                name,
+                names.join(", ")
+            ));
+        };
+        if !profile.profile.is_empty() {
+            bail!(tr!("profile {} 中不能再定义 profile", "profile {} cannot define nested profiles", name));
+        }
+
+        macro_rules! overlay {
+            ($($field:ident),*) => {$(
+                if profile.$field.is_some() {
+                    self.$field = profile.$field;
+                }
+            )*};
+        }
+        overlay!(
+            input, source, retry_failed, first, top, recursive, dry_run, stream, tombstones, concurrency,
+            adaptive_concurrency, max_concurrency, output, format, report, merge_into, delta_output, git_repo,
+            failures, download_media, chunk_size, chunk_overlap, embed, embed_model, embed_key,
+            embed_batch_size, summarize, summarize_workers, translate, translator, token_stats, excerpt_only,
+            max_content_chars, with_http_meta, include_raw_html, glossary, sort, group_by, tags, sink,
+            sink_url, sink_key, sink_index, sink_primary_key, sink_searchable, sink_batch_size, database_id,
+            space, parent_page, brokers, topic, max_retries, timeout, max_duration, breaker_threshold,
+            breaker_cooldown, pool_max_idle_per_host, pool_idle_timeout, tcp_keepalive, http_version,
+            http2_adaptive_window, max_body_size, cache_dir, offline, ca_cert, insecure, ipv4_only, ipv6_only,
+            dns, site_profile, allow_domain, allow_external, sample_readme, search_pages, visited,
+            expected_urls, redis_url, redis_key, memory_budget, progress, progress_file, dashboard,
+            metrics_addr, log_level, log_format, lang
+        );
+        self.profile.clear();
+        Ok(self)
+    }
+
+    /// 读取 `--config` 指定的文件；未指定时读取当前目录的 `adoc.toml`，不存在则返回空配置
+    pub fn discover(path: Option<&Path>) -> Result<Self> {
+        match path {
+            Some(path) => Self::load(path),
+            None if Path::new(DEFAULT_CONFIG_FILE).exists() => Self::load(Path::new(DEFAULT_CONFIG_FILE)),
+            None => Ok(Self::default()),
+        }
+    }
+}
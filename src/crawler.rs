@@ -1,93 +1,1097 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
 use backoff::ExponentialBackoff;
 use futures::stream::{self, StreamExt};
 use reqwest::Client;
-use scraper::{Html, Selector};
-use serde::Serialize;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::collections::HashSet;
 use std::sync::Arc;
-use tokio::sync::Mutex;
-use url::Url;
-use tracing::{info, warn, debug, error, instrument};
-use indicatif::{ProgressBar, ProgressStyle};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::sync::mpsc;
+use tracing::{info, warn, debug, instrument};
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
 
-#[derive(Debug, Serialize)]
-pub struct DocPage {
-    pub title: String,
-    pub content: String,
-    pub url: String,
-    pub related_links: Vec<String>,
-}
+pub use crate::extract::DocPage;
+use crate::breaker::CircuitBreaker;
+use crate::cache::{CacheMiss, ResponseCache};
+use crate::charset;
+use crate::concurrency::AdaptiveLimit;
+use crate::dns::Dns;
+use crate::extract::{
+    extract_html_for, extract_json_for, extract_links_for, extract_markdown_for, extract_search_results_for,
+    ContentLimit, HttpMeta, LinkFilter, SearchResult,
+};
+use crate::find::suggest_frameworks;
+use crate::metrics::Metrics;
+use crate::profile::SiteProfile;
+use crate::raw::{RawEncoding, RawHtml};
+use crate::progress::{ProgressEventKind, ProgressEvents};
+pub use crate::report::CrawlReport;
+use crate::report::{FailedPage, FailureKind};
+use crate::tr;
+use crate::sink::PageSink;
+use crate::visited::SharedVisited;
 
 #[derive(Debug, Clone)]
 pub struct CrawlerConfig {
+    /// 请求失败后的最大重试次数
     pub max_retries: u32,
+    /// 同时进行的请求数，0 按 1 处理
     pub concurrency: usize,
+    /// 单个请求的超时时间，同时也是重试的总耗时上限，与 `max_retries` 先到者为准
     pub timeout: std::time::Duration,
+    /// 每个主机保留的最大空闲连接数，`None` 时使用 reqwest 的默认值（不限制）
+    pub pool_max_idle_per_host: Option<usize>,
+    /// 空闲连接保留多久后关闭，`None` 时使用 reqwest 的默认值（90 秒）
+    pub pool_idle_timeout: Option<Duration>,
+    /// TCP keepalive 间隔，`None` 时不设置
+    pub tcp_keepalive: Option<Duration>,
+    /// 启用 HTTP/2 自适应流控窗口
+    pub http2_adaptive_window: bool,
+    /// 响应体的最大字节数，超过时中止下载，`None` 时不限制
+    pub max_body_size: Option<usize>,
+    /// 哪些链接作为相关链接继续爬取，默认只爬取文档站点本身
+    pub link_filter: LinkFilter,
+    /// 文档站点的链接范围、提取方式和搜索地址，默认为 Apple 开发者文档
+    pub profile: Arc<SiteProfile>,
+    /// 关键字搜索时最多读取的结果页数
+    pub search_pages: usize,
+    /// 额外信任的根证书，例如重新签发流量的企业代理的 CA，见 [`load_ca_certs`]
+    pub ca_certs: Vec<reqwest::Certificate>,
+    /// 不校验服务器证书，仅用于无法提供 CA 证书的调试场景
+    pub insecure: bool,
+    /// 只通过 IPv4 或 IPv6 连接，默认两者都可以
+    pub ip_family: IpFamily,
+    /// 域名解析方式，默认使用系统解析器
+    pub dns: Dns,
+    /// 使用的 HTTP 版本，默认按 TLS 协商结果
+    pub http_version: HttpVersion,
+    /// 示例代码页面下载项目压缩包，读取其中的 README，见 [`crate::sample`]
+    pub sample_readme: bool,
+    /// 在每个页面的 [`DocPage::http`] 中记录 HTTP 响应信息
+    pub http_meta: bool,
+    /// 提取后对正文的裁剪
+    pub content_limit: ContentLimit,
+    /// 在每个页面的 [`DocPage::raw_html`] 中按指定方式保存下载到的正文，见 [`crate::raw`]
+    pub raw_html: Option<RawEncoding>,
+    /// 响应缓存：保存下载的内容，离线模式下只从中读取，见 [`crate::cache`]
+    pub cache: Option<Arc<ResponseCache>>,
+}
+
+/// 连接使用的 IP 协议版本
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IpFamily {
+    #[default]
+    Any,
+    V4,
+    V6,
+}
+
+impl IpFamily {
+    /// 绑定的本地地址：只绑定一种协议的未指定地址时，连接时只使用解析结果中该协议的地址
+    fn local_address(self) -> Option<std::net::IpAddr> {
+        match self {
+            IpFamily::Any => None,
+            IpFamily::V4 => Some(std::net::Ipv4Addr::UNSPECIFIED.into()),
+            IpFamily::V6 => Some(std::net::Ipv6Addr::UNSPECIFIED.into()),
+        }
+    }
+}
+
+/// 使用的 HTTP 版本
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum HttpVersion {
+    /// 通过 TLS 的 ALPN 协商，服务器支持时使用 HTTP/2
+    #[default]
+    Auto,
+    /// 只使用 HTTP/1.1，例如网络中的中间设备会破坏 HTTP/2 流时
+    #[value(name = "1.1")]
+    Http1,
+    /// 直接使用 HTTP/2，不支持 HTTP/2 的服务器会连接失败
+    #[value(name = "2")]
+    Http2,
+}
+
+impl Default for CrawlerConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            concurrency: 5,
+            timeout: Duration::from_secs(30),
+            pool_max_idle_per_host: None,
+            pool_idle_timeout: None,
+            tcp_keepalive: None,
+            http2_adaptive_window: false,
+            max_body_size: None,
+            link_filter: LinkFilter::default(),
+            profile: Arc::new(SiteProfile::default()),
+            search_pages: 1,
+            ca_certs: Vec::new(),
+            insecure: false,
+            ip_family: IpFamily::Any,
+            dns: Dns::System,
+            http_version: HttpVersion::Auto,
+            sample_readme: false,
+            http_meta: false,
+            content_limit: ContentLimit::default(),
+            raw_html: None,
+            cache: None,
+        }
+    }
+}
+
+/// 单个页面请求的统计数据
+struct FetchStats {
+    bytes: u64,
+    latency: Duration,
+    retries: u32,
+    /// HTML 是空外壳，页面改从 JSON 数据接口提取
+    json_fallback: bool,
+    /// 页面本身的响应，没有收到响应时为 `None`
+    response: Option<ResponseInfo>,
+}
+
+/// 页面响应的状态码、内容类型、正文字节数和收到的时间
+struct ResponseInfo {
+    status: u16,
+    content_type: Option<String>,
+    length: u64,
+    received: SystemTime,
+    /// 响应正文，只在需要保存原始正文时保留
+    body: Option<String>,
+}
+
+impl ResponseInfo {
+    fn http_meta(self, latency: Duration) -> HttpMeta {
+        let fetched_at = time::OffsetDateTime::from(self.received)
+            .format(&time::format_description::well_known::Rfc3339)
+            .unwrap_or_default();
+        HttpMeta {
+            status_code: self.status,
+            content_length: self.length,
+            content_type: self.content_type,
+            fetched_at,
+            fetch_duration_ms: latency.as_millis() as u64,
+        }
+    }
+}
+
+/// 单个页面请求的重试策略：重试次数和总耗时都有上限，先到者为准
+#[derive(Debug, Clone, Copy)]
+struct RetryPolicy {
+    max_retries: u32,
+    max_elapsed: Duration,
+}
+
+impl RetryPolicy {
+    fn new(config: &CrawlerConfig) -> Self {
+        Self {
+            max_retries: config.max_retries,
+            max_elapsed: config.timeout,
+        }
+    }
+
+    fn backoff(&self) -> ExponentialBackoff {
+        ExponentialBackoff {
+            max_elapsed_time: Some(self.max_elapsed),
+            ..Default::default()
+        }
+    }
+
+    /// 第 `attempt` 次请求（从 1 开始）失败，重试次数用完后不再重试
+    fn failed(&self, url: &str, error: reqwest::Error, attempt: u32) -> backoff::Error<reqwest::Error> {
+        if attempt > self.max_retries {
+            warn!(
+                url,
+                attempt,
+                "{}",
+                tr!(
+                    "请求失败（第 {} 次尝试），已达到重试上限: {}, 错误: {}",
+                    "Request failed (attempt {}), no retries left: {}, error: {}",
+                    attempt,
+                    url,
+                    error
+                )
+            );
+            return backoff::Error::permanent(error);
+        }
+        warn!(
+            url,
+            attempt,
+            "{}",
+            tr!(
+                "请求失败（第 {} 次尝试），准备重试: {}, 错误: {}",
+                "Request failed (attempt {}), retrying: {}, error: {}",
+                attempt,
+                url,
+                error
+            )
+        );
+        backoff::Error::transient(error)
+    }
+}
+
+/// 单个页面请求用到的配置，起始页面和并发爬取的页面相同
+#[derive(Debug, Clone)]
+struct FetchOptions {
+    retry: RetryPolicy,
+    max_body_size: Option<usize>,
+    links: Arc<LinkFilter>,
+    profile: Arc<SiteProfile>,
+    sample_readme: bool,
+    http_meta: bool,
+    content_limit: ContentLimit,
+    raw_html: Option<RawEncoding>,
+    cache: Option<Arc<ResponseCache>>,
+}
+
+impl FetchOptions {
+    fn new(config: &CrawlerConfig) -> Self {
+        Self {
+            retry: RetryPolicy::new(config),
+            max_body_size: config.max_body_size,
+            links: Arc::new(config.link_filter.clone()),
+            profile: config.profile.clone(),
+            sample_readme: config.sample_readme,
+            http_meta: config.http_meta,
+            content_limit: config.content_limit,
+            raw_html: config.raw_html,
+            cache: config.cache.clone(),
+        }
+    }
+}
+
+/// 并发爬取中单个链接的处理结果
+enum PageOutcome {
+    Fetched(Box<DocPage>, FetchStats),
+    Skipped(String),
+    /// 内容类型不是 HTML 或 JSON（PDF、压缩包、图片、视频等），没有下载正文
+    Ignored { url: String, reason: String },
+    /// 页面已失效（404 / 410），没有提取内容
+    Gone { url: String, status: u16 },
+    /// 已超出时间预算，没有发起请求
+    Unscheduled(String),
+    /// 连续失败熔断后放弃，没有发起请求
+    Abandoned(String),
+    Failed { failure: FailedPage, retries: u32 },
+}
+
+/// 响应体超过了 [`CrawlerConfig::max_body_size`]
+#[derive(Debug)]
+pub struct BodyTooLarge {
+    pub limit: usize,
+}
+
+impl std::fmt::Display for BodyTooLarge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", tr!("响应体超过 {} 字节的上限", "response body exceeds the {} byte limit", self.limit))
+    }
+}
+
+impl std::error::Error for BodyTooLarge {}
+
+/// 关键字搜索没有任何文档结果（通常是拼写错误）
+#[derive(Debug)]
+pub struct NoSearchResults {
+    pub keyword: String,
+    /// 拼写相近的框架名
+    pub suggestions: Vec<&'static str>,
+}
+
+impl NoSearchResults {
+    pub fn new(keyword: &str) -> Self {
+        Self {
+            keyword: keyword.to_string(),
+            suggestions: suggest_frameworks(keyword, 3),
+        }
+    }
+}
+
+impl std::fmt::Display for NoSearchResults {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", tr!("没有找到与 \"{}\" 相关的文档", "No documentation found for \"{}\"", self.keyword))?;
+        if !self.suggestions.is_empty() {
+            let separator = tr!("、", ", ");
+            write!(f, "{}", tr!("，是否要找: {}？", ". Did you mean: {}?", self.suggestions.join(&separator)))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for NoSearchResults {}
+
+/// 页面已失效（404 Not Found / 410 Gone），不算作失败，在报告中单独计数
+#[derive(Debug)]
+pub struct PageGone {
+    pub status: u16,
+}
+
+impl std::fmt::Display for PageGone {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", tr!("页面已失效（HTTP {}）", "page is gone (HTTP {})", self.status))
+    }
+}
+
+impl std::error::Error for PageGone {}
+
+/// 离线模式下缓存的响应是 404/410 以外的非 2xx 状态码，按失败处理
+#[derive(Debug)]
+pub struct CachedStatus {
+    pub status: u16,
+}
+
+impl std::fmt::Display for CachedStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", tr!("缓存的响应为 HTTP {}", "cached response is HTTP {}", self.status))
+    }
+}
+
+impl std::error::Error for CachedStatus {}
+
+/// 检查缓存的状态码：404/410 为 [`PageGone`]，其他非 2xx 为 [`CachedStatus`]
+fn check_cached_status(status: u16) -> Result<()> {
+    match status {
+        404 | 410 => Err(PageGone { status }.into()),
+        200..=299 => Ok(()),
+        _ => Err(CachedStatus { status }.into()),
+    }
+}
+
+/// 响应的 `Content-Type` 既不是 HTML 也不是 JSON
+#[derive(Debug)]
+pub struct UnsupportedContentType {
+    pub content_type: String,
+}
+
+impl std::fmt::Display for UnsupportedContentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", tr!("不支持的内容类型: {}", "unsupported content type: {}", self.content_type))
+    }
+}
+
+impl std::error::Error for UnsupportedContentType {}
+
+/// 可以提取的页面内容
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ContentKind {
+    Html,
+    /// DocC render JSON
+    Json,
+    /// Markdown 原文，例如 Swift Evolution 提案
+    Markdown,
+}
+
+/// 根据 `Content-Type` 判断内容类型，没有该响应头时按 HTML 处理；
+/// 以 `text/plain` 返回的 `.md` 文件（例如 GitHub 的原始文件）按 Markdown 处理
+fn content_kind(response: &reqwest::Response) -> Result<ContentKind> {
+    content_kind_of(content_type(response).as_deref(), response.url().path())
+}
+
+/// 响应的 `Content-Type`
+fn content_type(response: &reqwest::Response) -> Option<String> {
+    let value = response.headers().get(reqwest::header::CONTENT_TYPE)?;
+    Some(String::from_utf8_lossy(value.as_bytes()).into_owned())
+}
+
+/// 按 `Content-Type` 和 URL 路径判断内容类型，缓存中的响应也按这里的规则提取
+fn content_kind_of(content_type: Option<&str>, path: &str) -> Result<ContentKind> {
+    let Some(content_type) = content_type else {
+        return Ok(ContentKind::Html);
+    };
+    let essence = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+    match essence.as_str() {
+        "text/html" | "application/xhtml+xml" => Ok(ContentKind::Html),
+        "application/json" => Ok(ContentKind::Json),
+        essence if essence.ends_with("+json") => Ok(ContentKind::Json),
+        "text/markdown" | "text/x-markdown" => Ok(ContentKind::Markdown),
+        "text/plain" if path.ends_with(".md") => Ok(ContentKind::Markdown),
+        _ => Err(UnsupportedContentType {
+            content_type: essence,
+        }
+        .into()),
+    }
+}
+
+/// 按内容类型和站点配置提取页面
+fn extract_page(kind: ContentKind, url: &str, body: &str, options: &FetchOptions) -> Result<DocPage> {
+    match kind {
+        ContentKind::Html => extract_html_for(&options.profile, url, body, &options.links),
+        ContentKind::Json => extract_json_for(&options.profile, url, body, &options.links),
+        ContentKind::Markdown => extract_markdown_for(&options.profile, url, body, &options.links),
+    }
+}
+
+/// 读取响应体并按 [`charset::decode`] 解码，非 2xx 状态码返回错误；
+/// 超过 `limit` 字节时立即中止下载并返回 [`BodyTooLarge`]
+async fn read_body(response: reqwest::Response, limit: Option<usize>) -> Result<String> {
+    let mut response = response.error_for_status()?;
+    let length = response.content_length();
+    // 服务器声明的长度已超出时不必开始下载
+    if let Some(limit) = limit.filter(|&limit| length.is_some_and(|length| length > limit as u64)) {
+        return Err(BodyTooLarge { limit }.into());
+    }
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if let Some(limit) = limit.filter(|&limit| body.len() + chunk.len() > limit) {
+            return Err(BodyTooLarge { limit }.into());
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(charset::decode(body, content_type.as_deref()))
+}
+
+/// 页面是空外壳且有对应的 JSON 数据时，改用 JSON 提取的页面，返回页面、下载的总字节数和是否回退；
+/// JSON 数据同样没有正文或请求失败时保留原页面
+async fn fill_empty_shell(client: &Client, page: DocPage, bytes: u64, options: &FetchOptions) -> (DocPage, u64, bool) {
+    if !page.is_empty_shell() {
+        return (page, bytes, false);
+    }
+    let Some(data_url) = options.profile.json_data_url(&page.url) else {
+        return (page, bytes, false);
+    };
+    debug!(
+        "{}",
+        tr!("页面没有正文，改从 JSON 数据提取: {}", "Page has no content, trying JSON data: {}", data_url)
+    );
+    let fallback = async {
+        let body = get_body(client, &data_url, options.max_body_size, options.cache.as_deref()).await?;
+        let fallback = extract_json_for(&options.profile, &page.url, &body, &options.links)?;
+        Ok::<_, anyhow::Error>((fallback, body.len() as u64))
+    };
+    match fallback.await {
+        Ok((fallback, extra)) if !fallback.is_empty_shell() => (fallback, bytes + extra, true),
+        Ok(_) => {
+            warn!(
+                "{}",
+                tr!(
+                    "页面及其 JSON 数据都没有正文: {}",
+                    "Neither the page nor its JSON data has content: {}",
+                    page.url
+                )
+            );
+            (page, bytes, false)
+        }
+        Err(e) => {
+            warn!(
+                "{}",
+                tr!(
+                    "页面没有正文，JSON 数据也无法获取: {}, 错误: {}",
+                    "Page has no content and its JSON data failed: {}, error: {}",
+                    page.url,
+                    e
+                )
+            );
+            (page, bytes, false)
+        }
+    }
+}
+
+/// 下载一个地址的正文，非 2xx 状态码返回错误；设置了缓存时同时保存，离线模式下只从缓存读取
+async fn get_body(client: &Client, url: &str, limit: Option<usize>, cache: Option<&ResponseCache>) -> Result<String> {
+    if let Some(cache) = cache.filter(|cache| cache.is_offline()) {
+        let cached = cache.require(url)?;
+        check_cached_status(cached.status)?;
+        return Ok(cached.body);
+    }
+    let response = client.get(url).send().await?;
+    let content_type = content_type(&response);
+    let body = read_body(response, limit).await?;
+    if let Some(cache) = cache {
+        save_to_cache(cache, url, 200, content_type.as_deref(), &body);
+    }
+    Ok(body)
+}
+
+/// 保存到缓存，失败只记录警告，不影响爬取
+fn save_to_cache(cache: &ResponseCache, url: &str, status: u16, content_type: Option<&str>, body: &str) {
+    if let Err(e) = cache.put(url, status, content_type, body) {
+        warn!("{}", tr!("无法缓存: {}, 错误: {:#}", "Cannot cache: {}, error: {:#}", url, e));
+    }
+}
+
+/// 离线模式下从缓存读取页面，按缓存的内容类型用 `extract` 处理正文；没有下载，字节数为 0。
+/// `keep_body` 时在统计数据的响应信息中保留正文
+fn fetch_cached<T>(
+    cache: &ResponseCache,
+    url: &str,
+    keep_body: bool,
+    extract: impl Fn(ContentKind, &str, &str) -> Result<T>,
+) -> (Result<T>, FetchStats) {
+    let start = Instant::now();
+    let mut response = None;
+    let result = cache.require(url).and_then(|cached| {
+        check_cached_status(cached.status)?;
+        let path = url::Url::parse(url).map(|url| url.path().to_string()).unwrap_or_default();
+        let kind = content_kind_of(cached.content_type.as_deref(), &path)?;
+        let page = extract(kind, url, &cached.body);
+        response = Some(ResponseInfo {
+            status: cached.status,
+            length: cached.body.len() as u64,
+            received: SystemTime::UNIX_EPOCH + Duration::from_secs(cached.fetched_at),
+            content_type: cached.content_type,
+            body: keep_body.then_some(cached.body),
+        });
+        page
+    });
+    debug!("{}", tr!("从缓存读取页面: {}", "Read page from cache: {}", url));
+    let stats = FetchStats {
+        bytes: 0,
+        latency: start.elapsed(),
+        retries: 0,
+        json_fallback: false,
+        response,
+    };
+    (result, stats)
+}
+
+/// 带重试地下载页面，并按内容类型用 `extract` 处理正文；
+/// 返回的统计数据在失败时也记录了重试次数。离线模式下改从缓存读取，见 [`fetch_cached`]
+async fn fetch_with<T>(
+    client: &Client,
+    url: &str,
+    options: &FetchOptions,
+    extract: impl Fn(ContentKind, &str, &str) -> Result<T>,
+) -> (Result<T>, FetchStats) {
+    if let Some(cache) = options.cache.as_ref().filter(|cache| cache.is_offline()) {
+        return fetch_cached(cache, url, options.raw_html.is_some(), extract);
+    }
+    let retry = options.retry;
+    let start = Instant::now();
+    let attempts = AtomicU32::new(0);
+    debug!("{}", tr!("开始请求页面: {}", "Requesting page: {}", url));
+
+    let response = backoff::future::retry(retry.backoff(), || async {
+        let attempt = attempts.fetch_add(1, Ordering::Relaxed) + 1;
+        let request_start = Instant::now();
+        match client.get(url).send().await {
+            Ok(resp) => {
+                let elapsed = request_start.elapsed();
+                debug!(
+                    url,
+                    elapsed = elapsed.as_secs_f64(),
+                    "{}",
+                    tr!(
+                        "请求成功: {}, 状态码: {}, 耗时: {:.2}s",
+                        "Request succeeded: {}, status: {}, took {:.2}s",
+                        url,
+                        resp.status(),
+                        elapsed.as_secs_f64()
+                    )
+                );
+                Ok(resp)
+            }
+            Err(e) => Err(retry.failed(url, e, attempt)),
+        }
+    });
+    let mut bytes = 0;
+    let mut info = None;
+    let result = async {
+        // 先检查状态码和内容类型，失效页面和不支持的内容不下载正文
+        let response = response.await?;
+        let status = response.status();
+        if matches!(status, reqwest::StatusCode::NOT_FOUND | reqwest::StatusCode::GONE) {
+            if let Some(cache) = &options.cache {
+                save_to_cache(cache, url, status.as_u16(), None, "");
+            }
+            return Err(PageGone { status: status.as_u16() }.into());
+        }
+        let response = response.error_for_status()?;
+        let kind = content_kind(&response)?;
+        let content_type = content_type(&response);
+        let body = read_body(response, options.max_body_size).await?;
+        bytes = body.len() as u64;
+        if let Some(cache) = &options.cache {
+            save_to_cache(cache, url, status.as_u16(), content_type.as_deref(), &body);
+        }
+        info = Some(ResponseInfo {
+            status: status.as_u16(),
+            content_type,
+            length: bytes,
+            received: SystemTime::now(),
+            body: None,
+        });
+        let page = extract(kind, url, &body);
+        if let Some(info) = info.as_mut().filter(|_| options.raw_html.is_some()) {
+            info.body = Some(body);
+        }
+        page
+    }
+    .await;
+
+    let elapsed = start.elapsed();
+    if result.is_ok() {
+        debug!(
+            url,
+            elapsed = elapsed.as_secs_f64(),
+            "{}",
+            tr!(
+                "页面处理完成: {}, 总耗时: {:.2}s",
+                "Page done: {}, took {:.2}s in total",
+                url,
+                elapsed.as_secs_f64()
+            )
+        );
+    }
+    let stats = FetchStats {
+        bytes,
+        latency: elapsed,
+        retries: attempts.load(Ordering::Relaxed).saturating_sub(1),
+        json_fallback: false,
+        response: info,
+    };
+    (result, stats)
+}
+
+/// 下载并提取一个文档页面，HTML 是空外壳时改从 JSON 数据提取；起始页面和并发爬取的页面都经过这里
+async fn fetch_and_extract(client: &Client, url: &str, options: &FetchOptions) -> (Result<DocPage>, FetchStats) {
+    let (page, mut stats) =
+        fetch_with(client, url, options, |kind, url, body| extract_page(kind, url, body, options)).await;
+    let page = match page {
+        Ok(page) => page,
+        Err(e) => return (Err(e), stats),
+    };
+    let (mut page, bytes, json_fallback) = fill_empty_shell(client, page, stats.bytes, options).await;
+    stats.bytes = bytes;
+    stats.json_fallback = json_fallback;
+    if let Some(cache) = &options.cache {
+        cache.set_title(url, &page.title);
+    }
+    if let Some(encoding) = options.raw_html {
+        let body = stats.response.as_mut().and_then(|response| response.body.take());
+        page.raw_html = body.and_then(|body| match RawHtml::encode(&body, encoding) {
+            Ok(raw) => Some(raw),
+            Err(e) => {
+                warn!("{}", tr!("无法保存原始正文: {}, 错误: {:#}", "Cannot keep raw body: {}, error: {:#}", url, e));
+                None
+            }
+        });
+    }
+    if options.http_meta {
+        page.http = stats.response.take().map(|response| response.http_meta(stats.latency));
+    }
+    options.content_limit.apply(&mut page);
+    // 示例项目的压缩包不缓存，离线模式下不下载
+    let offline = options.cache.as_ref().is_some_and(|cache| cache.is_offline());
+    if options.sample_readme && !offline {
+        stats.bytes += fill_sample_readme(client, &mut page, options).await;
+    }
+    (Ok(page), stats)
+}
+
+/// 示例代码页面下载项目压缩包，把其中的 README 保存到 `sample.readme`，返回下载的字节数；
+/// 下载或解压失败只记录警告，页面照常输出
+async fn fill_sample_readme(client: &Client, page: &mut DocPage, options: &FetchOptions) -> u64 {
+    let Some(sample) = page.sample.as_mut().filter(|sample| sample.readme.is_none()) else {
+        return 0;
+    };
+    let Some(download_url) = sample.download_url.clone() else {
+        return 0;
+    };
+    debug!("{}", tr!("下载示例代码项目: {}", "Downloading sample project: {}", download_url));
+    let readme = async {
+        let mut response = client.get(&download_url).send().await?.error_for_status()?;
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            if let Some(limit) = options.max_body_size.filter(|&limit| bytes.len() + chunk.len() > limit) {
+                return Err(BodyTooLarge { limit }.into());
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        Ok::<_, anyhow::Error>((crate::sample::readme_from_zip(&bytes)?, bytes.len() as u64))
+    };
+    match readme.await {
+        Ok((readme, bytes)) => {
+            sample.readme = readme;
+            bytes
+        }
+        Err(e) => {
+            warn!(
+                "{}",
+                tr!(
+                    "示例代码项目的 README 读取失败: {}, 错误: {:#}",
+                    "Failed to read the sample project README: {}, error: {:#}",
+                    download_url,
+                    e
+                )
+            );
+            0
+        }
+    }
+}
+
+/// 读取 CA 证书文件：PEM（可以包含多个证书）或单个 DER 编码的证书
+pub fn load_ca_certs(path: &std::path::Path) -> Result<Vec<reqwest::Certificate>> {
+    let bytes = std::fs::read(path)
+        .with_context(|| tr!("无法读取 CA 证书: {}", "cannot read CA certificate: {}", path.display()))?;
+    let certs = if bytes.windows(10).any(|window| window == b"-----BEGIN") {
+        reqwest::Certificate::from_pem_bundle(&bytes)?
+    } else {
+        vec![reqwest::Certificate::from_der(&bytes)?]
+    };
+    if certs.is_empty() {
+        anyhow::bail!(tr!("CA 证书文件中没有证书: {}", "no certificates in CA file: {}", path.display()));
+    }
+    Ok(certs)
+}
+
+/// 根据错误类型归类失败原因，HTTP 状态码错误同时记录状态码
+pub fn failed_page(url: &str, error: &anyhow::Error) -> FailedPage {
+    let http = error.chain().find_map(|cause| cause.downcast_ref::<reqwest::Error>());
+    let too_large = error.chain().any(|cause| cause.is::<BodyTooLarge>());
+    let gone = error.chain().find_map(|cause| cause.downcast_ref::<PageGone>());
+    let cached = error.chain().find_map(|cause| cause.downcast_ref::<CachedStatus>());
+    let cache_miss = error.chain().any(|cause| cause.is::<CacheMiss>());
+    let (kind, status) = match http {
+        _ if too_large => (FailureKind::TooLarge, None),
+        _ if cache_miss => (FailureKind::Offline, None),
+        _ if gone.is_some() => (FailureKind::Status, gone.map(|gone| gone.status)),
+        _ if cached.is_some() => (FailureKind::Status, cached.map(|cached| cached.status)),
+        Some(e) if e.is_timeout() => (FailureKind::Timeout, None),
+        Some(e) if e.is_connect() => (FailureKind::Connect, None),
+        Some(e) if e.status().is_some() => (FailureKind::Status, e.status().map(|status| status.as_u16())),
+        Some(_) => (FailureKind::Other, None),
+        None => (FailureKind::Extract, None),
+    };
+    FailedPage {
+        url: url.to_string(),
+        reason: error.to_string(),
+        kind,
+        status,
+    }
+}
+
+/// 多个进程协作爬取时共享的已访问集合，例如保存在 Redis 中的 `RedisVisited`。
+/// 本进程的已访问集合之外再认领一次，其他进程已认领的链接跳过
+#[async_trait]
+pub trait DistributedVisited: Send + Sync {
+    /// 认领 URL，已被（任何进程）认领时返回 false
+    async fn claim(&self, url: &str) -> Result<bool>;
+
+    /// 爬取失败时释放认领，之后的重试或其他进程可以再次爬取
+    async fn release(&self, url: &str) -> Result<()>;
+}
+
+/// 递归爬取链接时检查的已访问集合：先查本进程的集合，再向其他进程共享的集合认领
+#[derive(Clone)]
+struct LinkVisited {
+    local: Arc<SharedVisited>,
+    distributed: Option<Arc<dyn DistributedVisited>>,
+}
+
+impl LinkVisited {
+    /// 链接还没有被本进程或其他进程访问时返回 true
+    async fn claim(&self, url: &str) -> bool {
+        if !self.local.insert(url) {
+            return false;
+        }
+        let Some(distributed) = &self.distributed else {
+            return true;
+        };
+        match distributed.claim(url).await {
+            Ok(claimed) => {
+                if !claimed {
+                    debug!("{}", tr!("其他进程已认领: {}", "Claimed by another process: {}", url));
+                }
+                claimed
+            }
+            // 共享集合不可用时照常爬取，最坏情况是与其他进程重复请求
+            Err(e) => {
+                warn!("{}", tr!("无法认领 URL: {}, 错误: {:#}", "Cannot claim URL: {}, error: {:#}", url, e));
+                true
+            }
+        }
+    }
+
+    /// 爬取失败时释放共享集合中的认领
+    async fn release(&self, url: &str) {
+        let Some(distributed) = &self.distributed else {
+            return;
+        };
+        if let Err(e) = distributed.release(url).await {
+            warn!("{}", tr!("无法释放认领: {}, 错误: {:#}", "Cannot release claim: {}, error: {:#}", url, e));
+        }
+    }
 }
 
 pub struct Crawler {
     client: Client,
     config: CrawlerConfig,
-    visited_urls: Arc<Mutex<HashSet<String>>>,
+    visited_urls: Arc<SharedVisited>,
+    distributed: Option<Arc<dyn DistributedVisited>>,
+    metrics: Option<Arc<Metrics>>,
+    sinks: Vec<Box<dyn PageSink>>,
+    events: Option<Arc<ProgressEvents>>,
+    deadline: Option<Instant>,
+    adaptive: Option<Arc<AdaptiveLimit>>,
+    breaker: Option<Arc<CircuitBreaker>>,
+    retain_pages: bool,
+    tombstones: bool,
 }
 
 impl Crawler {
-    fn clean_text(text: &str) -> String {
-        text.lines()  // 按行分割
-            .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))  // 清理每行的空白
-            .filter(|line| !line.is_empty())  // 移除空行
-            .collect::<Vec<_>>()  // 收集到 Vec
-            .join("\n")  // 用换行符��新连接
-            .trim()  // 去除首尾空白
-            .to_string()
-    }
-
-    pub fn new(config: CrawlerConfig) -> Self {
-        let client = Client::builder()
+    pub fn new(mut config: CrawlerConfig) -> Self {
+        // 并发数为 0 时通道无法创建、请求也无法调度，库、阻塞接口和 C ABI 的调用方都可能传入
+        config.concurrency = config.concurrency.max(1);
+        let mut builder = Client::builder()
             .timeout(config.timeout)
-            .build()
-            .expect("Failed to create HTTP client");
+            .tcp_keepalive(config.tcp_keepalive)
+            .http2_adaptive_window(config.http2_adaptive_window)
+            .local_address(config.ip_family.local_address());
+        if let Some(max) = config.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        if let Some(timeout) = config.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        for cert in &config.ca_certs {
+            builder = builder.add_root_certificate(cert.clone());
+        }
+        if config.insecure {
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+        match config.http_version {
+            HttpVersion::Auto => {}
+            HttpVersion::Http1 => builder = builder.http1_only(),
+            HttpVersion::Http2 => builder = builder.http2_prior_knowledge(),
+        }
+        if let Some(resolver) = crate::dns::resolver(&config.dns) {
+            builder = builder.dns_resolver(resolver);
+        }
+        let client = builder.build().expect("Failed to create HTTP client");
 
         Self {
             client,
             config,
-            visited_urls: Arc::new(Mutex::new(HashSet::new())),
+            visited_urls: Arc::new(SharedVisited::default()),
+            distributed: None,
+            metrics: None,
+            sinks: Vec::new(),
+            events: None,
+            deadline: None,
+            adaptive: None,
+            breaker: None,
+            retain_pages: true,
+            tombstones: false,
+        }
+    }
+
+    /// 替换已访问 URL 集合，例如大规模爬取时改用布隆过滤器。应在开始爬取前调用
+    pub fn set_visited(&mut self, visited: SharedVisited) {
+        self.visited_urls = Arc::new(visited);
+    }
+
+    /// 与其他进程共享已访问集合，递归爬取时各进程分担起始页面的链接。
+    /// 起始页面本身不认领，每个进程都会爬取，从而发现同样的链接
+    pub fn set_distributed_visited(&mut self, visited: Arc<dyn DistributedVisited>) {
+        self.distributed = Some(visited);
+    }
+
+    /// 记录请求、重试、失败和待爬取链接数等运行指标，见 [`Metrics`]
+    pub fn set_metrics(&mut self, metrics: Arc<Metrics>) {
+        self.metrics = Some(metrics);
+    }
+
+    /// 是否在 `crawl_url` 的返回值中保留页面，默认保留。
+    /// 大规模爬取时关闭，页面只发送给推送目标，内存占用不随页面数增长
+    pub fn set_retain_pages(&mut self, retain: bool) {
+        self.retain_pages = retain;
+    }
+
+    /// 是否为失效页面（404 / 410）输出只有 `url` 和 `status` 的占位页面，默认不输出
+    pub fn set_tombstones(&mut self, enabled: bool) {
+        self.tombstones = enabled;
+    }
+
+    /// 设置截止时间，之后不再发起新的请求，已开始的请求照常完成
+    pub fn set_deadline(&mut self, deadline: Instant) {
+        self.deadline = Some(deadline);
+    }
+
+    /// 递归爬取时按 AIMD 自动调整并发数：以 `concurrency` 为初始值，最多提高到 `max`
+    pub fn set_adaptive_concurrency(&mut self, max: usize) {
+        self.adaptive = Some(Arc::new(AdaptiveLimit::new(self.config.concurrency, max)));
+    }
+
+    /// 连续 `threshold` 个请求失败时熔断，见 [`CircuitBreaker`]
+    pub fn set_circuit_breaker(&mut self, threshold: u32, cooldown: Option<Duration>) {
+        self.breaker = Some(Arc::new(CircuitBreaker::new(threshold, cooldown)));
+    }
+
+    /// 把请求结果反馈给熔断器：超时、连接失败、403、429 和 5xx 视为失败，服务器正常响应的视为成功
+    fn observe_failures(breaker: &CircuitBreaker, outcome: &PageOutcome, started: Instant) {
+        match outcome {
+            PageOutcome::Fetched(..) | PageOutcome::Gone { .. } | PageOutcome::Ignored { .. } => {
+                breaker.record_success(started)
+            }
+            PageOutcome::Failed { failure, .. }
+                if matches!(failure.kind, FailureKind::Timeout | FailureKind::Connect | FailureKind::Other)
+                    || matches!(failure.status, Some(403 | 429 | 500..=599)) =>
+            {
+                breaker.record_failure(started, &failure.reason)
+            }
+            _ => {}
+        }
+    }
+
+    /// 把请求结果反馈给自适应并发控制：超时、429、503 以及经过重试才成功的请求视为过载
+    fn observe(limit: &AdaptiveLimit, outcome: &PageOutcome, started: Instant) {
+        match outcome {
+            PageOutcome::Fetched(_, stats) if stats.retries == 0 => limit.record_success(stats.latency),
+            PageOutcome::Fetched(..) => limit.record_overload(started),
+            PageOutcome::Failed { failure, .. }
+                if failure.kind == FailureKind::Timeout || matches!(failure.status, Some(429 | 503)) =>
+            {
+                limit.record_overload(started)
+            }
+            _ => {}
+        }
+    }
+
+    /// 记录一个成功获取的页面，改从 JSON 提取时在进度事件中注明
+    fn record_fetched(&self, report: &mut CrawlReport, url: &str, stats: &FetchStats) {
+        let source = stats.json_fallback.then_some("json");
+        self.progress_event(ProgressEventKind::Done, url, source);
+        report.record_fetched(stats.bytes, stats.latency, stats.retries);
+        if let Some(metrics) = &self.metrics {
+            metrics.record_fetched(stats.bytes, stats.latency, stats.retries);
+        }
+        if stats.json_fallback {
+            report.record_json_fallback(url);
+        }
+    }
+
+    /// 记录一个失效的页面，需要输出占位页面时返回该页面
+    fn record_gone(&self, report: &mut CrawlReport, url: &str, status: u16) -> Option<DocPage> {
+        info!(url, "{}", tr!("页面已失效: {} (HTTP {})", "Page is gone: {} (HTTP {})", url, status));
+        self.progress_event(ProgressEventKind::Skipped, url, Some(&status.to_string()));
+        report.record_gone();
+        if let Some(metrics) = &self.metrics {
+            metrics.record_gone();
+        }
+        self.tombstones.then(|| DocPage {
+            url: url.to_string(),
+            status: Some(status),
+            ..Default::default()
+        })
+    }
+
+    fn deadline_passed(deadline: Option<Instant>) -> bool {
+        deadline.is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// 输出机器可读的进度事件，同时隐藏终端进度条
+    pub fn set_progress_events(&mut self, events: ProgressEvents) {
+        self.events = Some(Arc::new(events));
+    }
+
+    fn progress_event(&self, event: ProgressEventKind, url: &str, reason: Option<&str>) {
+        if let Some(events) = &self.events {
+            events.emit(event, url, reason);
+        }
+    }
+
+    /// 注册一个推送目标，爬取到的每个页面都会发送给它
+    pub fn add_sink(&mut self, sink: Box<dyn PageSink>) {
+        self.sinks.push(sink);
+    }
+
+    /// 将页面发送给所有推送目标，失败只记录警告，不中断爬取
+    async fn emit(&self, page: &DocPage) {
+        for sink in &self.sinks {
+            if let Err(e) = sink.send(page).await {
+                warn!(
+                    "{}",
+                    tr!("推送页面失败: {}, 错误: {:#}", "Failed to push page: {}, error: {:#}", page.url, e)
+                );
+            }
         }
     }
 
     #[instrument(skip(self))]
-    pub async fn crawl_url(&mut self, url: &str, recursive: bool) -> Result<Vec<DocPage>> {
+    pub async fn crawl_url(&mut self, url: &str, recursive: bool) -> Result<(Vec<DocPage>, CrawlReport)> {
         let mut pages = Vec::new();
+        let mut report = CrawlReport::default();
         
-        // 创建主进度条
-        let spinner = ProgressBar::new_spinner();
+        // 创建主进度条，递归爬取时每个工作任务在其下方单独占一行
+        let multi = MultiProgress::new();
+        if self.events.is_some() {
+            multi.set_draw_target(ProgressDrawTarget::hidden());
+        }
+        let spinner = multi.add(ProgressBar::new_spinner());
         spinner.set_style(
             ProgressStyle::default_spinner()
                 .template("{spinner:.green} [{elapsed_precise}] {msg}")
                 .unwrap()
         );
-        spinner.set_message(format!("爬取页面: {}", url));
+        spinner.set_message(tr!("爬取页面: {}", "Crawling: {}", url));
+
+        if Self::deadline_passed(self.deadline) {
+            report.record_truncated();
+            return Ok((pages, report));
+        }
         
-        {
-            let mut visited = self.visited_urls.lock().await;
-            if visited.contains(url) {
-                debug!("跳过已访问的 URL: {}", url);
-                return Ok(pages);
-            }
-            visited.insert(url.to_string());
-            debug!("添加 URL 到已访问列表: {}", url);
+        if !self.visited_urls.insert(url) {
+            debug!("{}", tr!("跳过已访问的 URL: {}", "Skipping visited URL: {}", url));
+            report.record_skipped();
+            return Ok((pages, report));
         }
+        debug!("{}", tr!("添加 URL 到已访问列表: {}", "Marked URL as visited: {}", url));
         
         // 获取第一个页面
-        let page = self.fetch_page(url).await?;
-        pages.push(page);
+        self.progress_event(ProgressEventKind::Queued, url, None);
+        self.progress_event(ProgressEventKind::Fetching, url, None);
+        let (page, stats) = fetch_and_extract(&self.client, url, &FetchOptions::new(&self.config)).await;
+        let mut page = match page {
+            Ok(page) => page,
+            Err(e) => {
+                let Some(gone) = e.downcast_ref::<PageGone>() else {
+                    self.progress_event(ProgressEventKind::Failed, url, Some(&e.to_string()));
+                    return Err(e);
+                };
+                // 起始页面已失效，没有可以继续爬取的链接
+                if let Some(tombstone) = self.record_gone(&mut report, url, gone.status) {
+                    self.emit(&tombstone).await;
+                    if self.retain_pages {
+                        pages.push(tombstone);
+                    }
+                }
+                for sink in &self.sinks {
+                    sink.flush().await?;
+                }
+                spinner.finish_and_clear();
+                return Ok((pages, report));
+            }
+        };
+        self.record_fetched(&mut report, url, &stats);
+        self.emit(&page).await;
+        // 不保留页面时直接取走链接，不必复制
+        let links = match (recursive, self.retain_pages) {
+            (false, _) => Vec::new(),
+            (true, true) => page.link_urls().map(str::to_string).collect(),
+            (true, false) => std::mem::take(&mut page.related_links).into_iter().map(|link| link.url).collect(),
+        };
+        if self.retain_pages {
+            pages.push(page);
+        }
 
         if recursive {
-            let links: Vec<String> = pages[0].related_links.clone();
-            info!("发现 {} 个相关链接，开始并发爬取", links.len());
+            info!(
+                "{}",
+                tr!("发现 {} 个相关链接，开始并发爬取", "Found {} related links, crawling concurrently", links.len())
+            );
+            for link in &links {
+                self.progress_event(ProgressEventKind::Queued, link, None);
+            }
+            if let Some(metrics) = &self.metrics {
+                metrics.enqueue(links.len());
+            }
             
-            let progress = ProgressBar::new(links.len() as u64);
+            let progress = multi.add(ProgressBar::new(links.len() as u64));
             progress.set_style(
                 ProgressStyle::default_bar()
                     .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta}) {msg}")
@@ -96,287 +1100,319 @@ impl Crawler {
             );
             
             let client = self.client.clone();
-            let visited_urls = self.visited_urls.clone();
-            let config = self.config.clone();
+            let visited = LinkVisited {
+                local: self.visited_urls.clone(),
+                distributed: self.distributed.clone(),
+            };
+            let options = FetchOptions::new(&self.config);
             
             // 创建一个独立的函数来处理单个页面
             async fn fetch_single_page(
                 link: String,
                 client: Client,
-                visited_urls: Arc<Mutex<HashSet<String>>>,
-                config: CrawlerConfig,
+                visited: LinkVisited,
+                options: FetchOptions,
+                multi: MultiProgress,
                 progress: ProgressBar,
-            ) -> Result<Vec<DocPage>> {
-                let mut visited = visited_urls.lock().await;
-                if visited.contains(&link) {
+                events: Option<Arc<ProgressEvents>>,
+            ) -> PageOutcome {
+                if !visited.claim(&link).await {
                     progress.inc(1);
-                    progress.set_message(format!("跳过: {}", link));
-                    return Ok(vec![]);
+                    return PageOutcome::Skipped(link);
                 }
-                visited.insert(link.clone());
-                progress.set_message(format!("爬取: {}", link));
-                drop(visited);
-
-                let mut backoff = ExponentialBackoff::default();
-                backoff.max_elapsed_time = Some(config.timeout);
-                
-                let start = std::time::Instant::now();
-                debug!("开始请求页面: {}", link);
-                
-                let response = backoff::future::retry(backoff, || async {
-                    let request_start = std::time::Instant::now();
-                    match client.get(&link).send().await {
-                        Ok(resp) => {
-                            let elapsed = request_start.elapsed();
-                            debug!(
-                                "请求成功: {}, 状态码: {}, 耗时: {:.2}s",
-                                link,
-                                resp.status(),
-                                elapsed.as_secs_f64()
-                            );
-                            Ok(resp)
-                        }
-                        Err(e) => {
-                            warn!("请求失败，准备重试: {}, 错误: {}", link, e);
-                            Err(e.into())
+                if let Some(events) = &events {
+                    events.emit(ProgressEventKind::Fetching, &link, None);
+                }
+
+                // 当前工作任务的状态行：正在请求的 URL 和已耗时间
+                let worker = multi.add(ProgressBar::new_spinner());
+                worker.set_style(
+                    ProgressStyle::default_spinner()
+                        .template("  {spinner:.blue} [{elapsed:>3}] {msg}")
+                        .unwrap()
+                );
+                worker.set_message(link.clone());
+                worker.enable_steady_tick(Duration::from_millis(120));
+
+                let (page, stats) = fetch_and_extract(&client, &link, &options).await;
+                worker.finish_and_clear();
+                multi.remove(&worker);
+                progress.inc(1);
+
+                match page {
+                    Ok(page) => PageOutcome::Fetched(Box::new(page), stats),
+                    Err(e) if e.is::<PageGone>() => PageOutcome::Gone {
+                        status: e.downcast_ref::<PageGone>().map_or(404, |gone| gone.status),
+                        url: link,
+                    },
+                    Err(e) if e.is::<UnsupportedContentType>() => {
+                        info!(url = %link, "{}", tr!("跳过页面: {}, {}", "Skipping page: {}, {}", link, e));
+                        PageOutcome::Ignored {
+                            url: link,
+                            reason: e.to_string(),
                         }
                     }
-                }).await;
-
-                match response {
-                    Ok(response) => {
-                        let page = Crawler::parse_page(&link, response).await?;
-                        progress.inc(1);
-                        progress.set_message(format!("成功: {}", link));
-                        Ok(vec![page])
-                    }
                     Err(e) => {
-                        progress.inc(1);
-                        progress.set_message(format!("失败: {}", link));
-                        warn!("爬取相关页面失败: {}, 错误: {}", link, e);
-                        Ok(vec![])
+                        warn!(
+                            url = %link,
+                            "{}",
+                            tr!("爬取相关页面失败: {}, 错误: {}", "Failed to crawl related page: {}, error: {}", link, e)
+                        );
+                        visited.release(&link).await;
+                        PageOutcome::Failed {
+                            failure: failed_page(&link, &e),
+                            retries: stats.retries,
+                        }
                     }
                 }
             }
 
-            let results: Vec<_> = stream::iter(links)
+            let this = &*self;
+            // 自适应并发时按最大并发数分配，同时进行的请求由许可数控制
+            let width = this.adaptive.as_ref().map_or(this.config.concurrency, |limit| limit.max());
+            let mut outcomes = stream::iter(links)
                 .map(|link| {
                     let client = client.clone();
-                    let visited_urls = visited_urls.clone();
+                    let visited = visited.clone();
+                    let multi = multi.clone();
                     let progress = progress.clone();
-                    let config = config.clone();
+                    let events = this.events.clone();
+                    let deadline = this.deadline;
+                    let adaptive = this.adaptive.clone();
+                    let breaker = this.breaker.clone();
+                    let options = options.clone();
                     async move {
-                        fetch_single_page(link, client, visited_urls, config, progress).await
+                        // 自适应并发时由许可数限制同时进行的请求，buffer_unordered 只提供上限
+                        let _permit = match &adaptive {
+                            Some(limit) => Some(limit.acquire().await),
+                            None => None,
+                        };
+                        // 链接在轮到执行时才检查截止时间，已开始的请求不受影响
+                        if Crawler::deadline_passed(deadline) {
+                            progress.inc(1);
+                            return PageOutcome::Unscheduled(link);
+                        }
+                        if let Some(breaker) = &breaker {
+                            if !breaker.ready().await {
+                                progress.inc(1);
+                                return PageOutcome::Abandoned(link);
+                            }
+                        }
+                        let started = Instant::now();
+                        let outcome =
+                            fetch_single_page(link, client, visited, options, multi, progress, events).await;
+                        if let Some(limit) = &adaptive {
+                            Crawler::observe(limit, &outcome, started);
+                        }
+                        if let Some(breaker) = &breaker {
+                            Crawler::observe_failures(breaker, &outcome, started);
+                        }
+                        outcome
                     }
                 })
-                .buffer_unordered(config.concurrency)
-                .collect()
-                .await;
-
-            let mut success_count = 0;
-            for result in results {
-                if let Ok(mut sub_pages) = result {
-                    success_count += sub_pages.len();
-                    pages.append(&mut sub_pages);
+                .buffer_unordered(width);
+
+            // 抓取与推送之间用有界通道连接：推送目标处理不过来时暂停调度新的请求，
+            // 内存中最多只有与并发数相同个待推送的页面
+            let (sender, mut receiver) = mpsc::channel::<DocPage>(width);
+            let report = &mut report;
+            let fetch = async move {
+                let mut success_count = 0;
+                while let Some(outcome) = outcomes.next().await {
+                    if let Some(metrics) = &this.metrics {
+                        metrics.dequeue();
+                        match &outcome {
+                            PageOutcome::Skipped(_) | PageOutcome::Ignored { .. } => metrics.record_skipped(),
+                            PageOutcome::Failed { failure, retries } => {
+                                metrics.record_failed(&failure.kind.to_string(), *retries)
+                            }
+                            _ => {}
+                        }
+                    }
+                    match outcome {
+                        PageOutcome::Fetched(page, stats) => {
+                            success_count += 1;
+                            this.record_fetched(report, &page.url, &stats);
+                            if sender.send(*page).await.is_err() {
+                                break;
+                            }
+                        }
+                        PageOutcome::Skipped(url) => {
+                            this.progress_event(ProgressEventKind::Skipped, &url, None);
+                            report.record_skipped();
+                        }
+                        PageOutcome::Ignored { url, reason } => {
+                            this.progress_event(ProgressEventKind::Skipped, &url, Some(&reason));
+                            report.record_skipped();
+                        }
+                        PageOutcome::Gone { url, status } => {
+                            let Some(tombstone) = this.record_gone(report, &url, status) else {
+                                continue;
+                            };
+                            if sender.send(tombstone).await.is_err() {
+                                break;
+                            }
+                        }
+                        PageOutcome::Abandoned(url) => {
+                            this.progress_event(ProgressEventKind::Skipped, &url, Some("circuit-breaker"));
+                            report.record_circuit_broken();
+                        }
+                        PageOutcome::Unscheduled(url) => {
+                            this.progress_event(ProgressEventKind::Skipped, &url, Some("max-duration"));
+                            report.record_truncated();
+                        }
+                        PageOutcome::Failed { failure, retries } => {
+                            this.progress_event(ProgressEventKind::Failed, &failure.url, Some(&failure.reason));
+                            report.record_failed(failure, retries);
+                        }
+                    }
+                }
+                success_count
+            };
+            let deliver = async {
+                let mut retained = Vec::new();
+                while let Some(page) = receiver.recv().await {
+                    this.emit(&page).await;
+                    if this.retain_pages {
+                        retained.push(page);
+                    }
                 }
+                retained
+            };
+            let (success_count, retained) = tokio::join!(fetch, deliver);
+            pages.extend(retained);
+            if let Some(limit) = &self.adaptive {
+                info!("{}", tr!("自适应并发: 当前并发数 {}", "Adaptive concurrency: currently {}", limit.current()));
             }
-            
-            progress.finish_with_message(format!("完成！成功爬取 {} 个页面", success_count));
-        }
-
-        spinner.finish_with_message(format!("完成！共获取 {} 个页面", pages.len()));
-        Ok(pages)
-    }
 
-    // 添加一个辅助方法来解析页面
-    async fn parse_page(url: &str, response: reqwest::Response) -> Result<DocPage> {
-        let html = response.text().await?;
-        let document = Html::parse_document(&html);
-        
-        let title_selector = Selector::parse("h1").unwrap();
-        let content_selector = Selector::parse("article").unwrap();
-        let links_selector = Selector::parse("a[href]").unwrap();
-
-        let title = document
-            .select(&title_selector)
-            .next()
-            .map(|el| Self::clean_text(&el.text().collect::<String>()))
-            .unwrap_or_default();
-
-        let content = document
-            .select(&content_selector)
-            .next()
-            .map(|el| Self::clean_text(&el.text().collect::<String>()))
-            .unwrap_or_default();
-
-        let base_url = Url::parse(url)?;
-        let related_links: Vec<String> = document
-            .select(&links_selector)
-            .filter_map(|el| {
-                el.value().attr("href").and_then(|href| {
-                    base_url.join(href).ok().map(|url| url.to_string())
-                })
-            })
-            .filter(|url| url.contains("developer.apple.com"))
-            .collect();
+            progress.finish_with_message(tr!("完成！成功爬取 {} 个页面", "Done! Crawled {} pages", success_count));
+        }
 
-        Ok(DocPage {
-            title,
-            content,
-            url: url.to_string(),
-            related_links,
-        })
-    }
+        for sink in &self.sinks {
+            sink.flush().await?;
+        }
 
-    pub async fn search_and_crawl(&mut self, keyword: &str, recursive: bool) -> Result<Vec<DocPage>> {
-        let search_url = format!(
-            "https://developer.apple.com/search/index.php?q={}",
-            urlencoding::encode(keyword)
-        );
-        self.crawl_url(&search_url, recursive).await
+        spinner.finish_with_message(tr!("完成！共获取 {} 个页面", "Done! Fetched {} pages", report.pages_fetched));
+        Ok((pages, report))
     }
 
-    async fn fetch_page(&mut self, url: &str) -> Result<DocPage> {
-        let mut backoff = ExponentialBackoff::default();
-        backoff.max_elapsed_time = Some(self.config.timeout);
-        
-        let start = std::time::Instant::now();
-        debug!("开始求页面: {}", url);
-        
-        let response = backoff::future::retry(backoff, || async {
-            let request_start = std::time::Instant::now();
-            match self.client.get(url).send().await {
-                Ok(resp) => {
-                    let elapsed = request_start.elapsed();
-                    debug!(
-                        "请求成功: {}, 状态码: {}, 耗时: {:.2}s",
-                        url,
-                        resp.status(),
-                        elapsed.as_secs_f64()
-                    );
-                    Ok(resp)
+    /// 搜索关键字并爬取汇总去重后的各个结果，搜索页本身不作为文档；
+    /// 单个结果失败时记录在报告中并继续，没有任何文档结果时返回 [`NoSearchResults`]
+    pub async fn search_and_crawl(&mut self, keyword: &str, recursive: bool) -> Result<(Vec<DocPage>, CrawlReport)> {
+        let results = self.search(keyword).await?;
+        if results.is_empty() {
+            return Err(NoSearchResults::new(keyword).into());
+        }
+        let mut pages = Vec::new();
+        let mut report = CrawlReport::default();
+        for result in results {
+            match self.crawl_url(&result.url, recursive).await {
+                Ok((seed_pages, seed_report)) => {
+                    pages.extend(seed_pages);
+                    report.merge(seed_report);
                 }
+                // 单个结果失败不影响其余结果，记录到失败列表中
                 Err(e) => {
-                    warn!("请求失败，准备重试: {}, 错误: {}", url, e);
-                    Err(e.into())
+                    warn!(
+                        "{}",
+                        tr!("搜索结果爬取失败: {}, 错误: {:#}", "Search result failed: {}, error: {:#}", result.url, e)
+                    );
+                    report.record_failed(failed_page(&result.url, &e), 0);
                 }
             }
-        }).await?;
-
-        let html = response.text().await?;
-        let document = Html::parse_document(&html);
-        
-        let title_selector = Selector::parse("h1").unwrap();
-        let content_selector = Selector::parse("article").unwrap();
-        let links_selector = Selector::parse("a[href]").unwrap();
-
-        let title = document
-            .select(&title_selector)
-            .next()
-            .map(|el| Self::clean_text(&el.text().collect::<String>()))
-            .unwrap_or_default();
+        }
+        Ok((pages, report))
+    }
 
-        let content = document
-            .select(&content_selector)
-            .next()
-            .map(|el| Self::clean_text(&el.text().collect::<String>()))
-            .unwrap_or_default();
+    /// 关键字对应的 Apple 开发者文档搜索页
+    pub fn search_url(keyword: &str) -> String {
+        Self::search_page_url(keyword, 1)
+    }
 
-        let base_url = Url::parse(url)?;
-        let related_links: Vec<String> = document
-            .select(&links_selector)
-            .filter_map(|el| {
-                el.value().attr("href").and_then(|href| {
-                    base_url.join(href).ok().map(|url| url.to_string())
-                })
-            })
-            .filter(|url| url.contains("developer.apple.com"))
-            .collect();
+    /// Apple 开发者文档搜索结果的第 `page` 页（从 1 开始），其他站点见 [`SiteProfile::search_page_url`]
+    pub fn search_page_url(keyword: &str, page: usize) -> String {
+        SiteProfile::apple().search_page_url(keyword, page).unwrap_or_default()
+    }
 
-        let elapsed = start.elapsed();
-        info!(
-            "页面处理完成: {}, 总耗时: {:.2}s",
-            url,
-            elapsed.as_secs_f64()
-        );
-        
-        Ok(DocPage {
-            title,
-            content,
-            url: url.to_string(),
-            related_links,
+    /// 按站点配置生成搜索结果的第 `page` 页，站点不支持关键字搜索时报错
+    fn site_search_url(&self, keyword: &str, page: usize) -> Result<String> {
+        let profile = &self.config.profile;
+        profile.search_page_url(keyword, page).ok_or_else(|| {
+            anyhow::anyhow!(tr!(
+                "站点配置 {} 没有设置 search_url，不支持关键字搜索，请直接输入 URL",
+                "site profile {} has no search_url and cannot search keywords, pass a URL instead",
+                profile.name
+            ))
         })
     }
 
-    async fn fetch_page_with_client(&self, url: &str, client: &Client) -> Result<DocPage> {
-        let mut backoff = ExponentialBackoff::default();
-        backoff.max_elapsed_time = Some(self.config.timeout);
-        
-        let start = std::time::Instant::now();
-        debug!("开始请求页面: {}", url);
-        
-        let response = backoff::future::retry(backoff, || async {
-            let request_start = std::time::Instant::now();
-            match client.get(url).send().await {
-                Ok(resp) => {
-                    let elapsed = request_start.elapsed();
-                    debug!(
-                        "请求成功: {}, 状态码: {}, 耗时: {:.2}s",
-                        url,
-                        resp.status(),
-                        elapsed.as_secs_f64()
+    /// 搜索关键字，返回文档结果
+    ///
+    /// 依次读取至多 `search_pages` 页结果并按 URL 去重，某一页没有新结果时停止；
+    /// 第一页之后的请求失败时保留已获取的结果。
+    pub async fn search(&self, keyword: &str) -> Result<Vec<SearchResult>> {
+        let mut results: Vec<SearchResult> = Vec::new();
+        let mut seen = HashSet::new();
+        for page in 1..=self.config.search_pages.max(1) {
+            let url = self.site_search_url(keyword, page)?;
+            let hits = match self.search_page(&url).await {
+                Ok(hits) => hits,
+                Err(e) if page > 1 => {
+                    warn!(
+                        "{}",
+                        tr!(
+                            "读取第 {} 页搜索结果失败，使用已获取的 {} 个结果: {}",
+                            "Failed to read search results page {}, keeping the {} results so far: {}",
+                            page,
+                            results.len(),
+                            e
+                        )
                     );
-                    Ok(resp)
-                }
-                Err(e) => {
-                    warn!("请求失败，准备重试: {}, 错误: {}", url, e);
-                    Err(e.into())
+                    break;
                 }
+                Err(e) => return Err(e),
+            };
+            let before = results.len();
+            results.extend(hits.into_iter().filter(|hit| seen.insert(hit.url.clone())));
+            if results.len() == before {
+                break;
             }
-        }).await?;
-
-        let html = response.text().await?;
-        let document = Html::parse_document(&html);
-        
-        let title_selector = Selector::parse("h1").unwrap();
-        let content_selector = Selector::parse("article").unwrap();
-        let links_selector = Selector::parse("a[href]").unwrap();
-
-        let title = document
-            .select(&title_selector)
-            .next()
-            .map(|el| Self::clean_text(&el.text().collect::<String>()))
-            .unwrap_or_default();
+            debug!("{}", tr!("第 {} 页搜索结果: {} 个", "Search results page {}: {} hits", page, results.len() - before));
+        }
+        Ok(results)
+    }
 
-        let content = document
-            .select(&content_selector)
-            .next()
-            .map(|el| Self::clean_text(&el.text().collect::<String>()))
-            .unwrap_or_default();
+    async fn search_page(&self, url: &str) -> Result<Vec<SearchResult>> {
+        let html = get_body(&self.client, url, self.config.max_body_size, self.config.cache.as_deref()).await?;
+        extract_search_results_for(&self.config.profile, url, &html)
+    }
 
-        let base_url = Url::parse(url)?;
-        let related_links: Vec<String> = document
-            .select(&links_selector)
-            .filter_map(|el| {
-                el.value().attr("href").and_then(|href| {
-                    base_url.join(href).ok().map(|url| url.to_string())
-                })
+    /// 列出一次爬取将会请求的 URL，只下载起始页面用于发现链接
+    pub async fn plan_url(&mut self, url: &str, recursive: bool) -> Result<Vec<String>> {
+        let mut urls = vec![url.to_string()];
+        if recursive {
+            // 只需要链接，不提取正文
+            let options = FetchOptions::new(&self.config);
+            let (links, _) = fetch_with(&self.client, url, &options, |kind, url, body| match kind {
+                ContentKind::Html => extract_links_for(&options.profile, url, body, &options.links),
+                ContentKind::Json => Ok(extract_json_for(&options.profile, url, body, &options.links)?
+                    .link_urls()
+                    .map(str::to_string)
+                    .collect()),
+                ContentKind::Markdown => Ok(extract_markdown_for(&options.profile, url, body, &options.links)?
+                    .link_urls()
+                    .map(str::to_string)
+                    .collect()),
             })
-            .filter(|url| url.contains("developer.apple.com"))
-            .collect();
+            .await;
+            for link in links? {
+                if !urls.contains(&link) {
+                    urls.push(link);
+                }
+            }
+        }
 
-        let elapsed = start.elapsed();
-        info!(
-            "页面处理完成: {}, 总耗时: {:.2}s",
-            url,
-            elapsed.as_secs_f64()
-        );
-        
-        Ok(DocPage {
-            title,
-            content,
-            url: url.to_string(),
-            related_links,
-        })
+        urls.retain(|url| !self.visited_urls.contains(url));
+        Ok(urls)
     }
 } 
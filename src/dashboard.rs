@@ -0,0 +1,273 @@
+//! 爬取时的终端监控面板（`--dashboard`）。
+//!
+//! 面板代替进度条占用整个终端（stderr 的备用屏幕），每 250 毫秒刷新一次：总体进度、排队中的链接数、
+//! 每个正在进行的请求及其已用时间、每秒完成页面数的走势、按结果分类的计数和最近的失败。
+//! 面板由 [`ProgressEvents`] 的回调更新，与 `--progress json` 的事件相同；显示期间日志暂存在内存中，
+//! 面板关闭后再输出，避免覆盖面板。
+
+use anyhow::Result;
+use ratatui::backend::CrosstermBackend;
+use ratatui::crossterm::{cursor, execute, terminal};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Gauge, List, ListItem, Paragraph, Sparkline};
+use ratatui::{Frame, Terminal};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::progress::{hold_logs, release_logs, ProgressEventKind, ProgressEvents};
+use crate::tr;
+
+/// 刷新间隔
+const REFRESH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// 最多保留的失败记录数
+const RECENT_FAILURES: usize = 100;
+
+/// 计算当前速度时取最近几秒的平均值
+const RATE_WINDOW_SECS: usize = 10;
+
+/// 面板显示的爬取状态，按进度事件更新
+#[derive(Debug, Clone)]
+pub struct DashboardState {
+    started: Instant,
+    total: usize,
+    completed: usize,
+    fetched: usize,
+    skipped: usize,
+    gone: usize,
+    failed: usize,
+    /// 正在请求的 URL 及开始时间，按开始时间排列
+    active: Vec<(String, Instant)>,
+    /// 爬取开始后每一秒内完成的页面数
+    per_second: Vec<u64>,
+    /// 最近的失败（URL、原因），最新的在最后
+    failures: VecDeque<(String, String)>,
+}
+
+impl DashboardState {
+    pub fn new(started: Instant) -> Self {
+        Self {
+            started,
+            total: 0,
+            completed: 0,
+            fetched: 0,
+            skipped: 0,
+            gone: 0,
+            failed: 0,
+            active: Vec::new(),
+            per_second: Vec::new(),
+            failures: VecDeque::new(),
+        }
+    }
+
+    /// 记录一个进度事件
+    pub fn record(&mut self, event: ProgressEventKind, url: &str, reason: Option<&str>, now: Instant) {
+        match event {
+            ProgressEventKind::Queued => {
+                self.total += 1;
+                return;
+            }
+            ProgressEventKind::Fetching => {
+                self.active.push((url.to_string(), now));
+                return;
+            }
+            ProgressEventKind::Done => {
+                self.fetched += 1;
+                let second = now.saturating_duration_since(self.started).as_secs() as usize;
+                if self.per_second.len() <= second {
+                    self.per_second.resize(second + 1, 0);
+                }
+                self.per_second[second] += 1;
+            }
+            // 失效页面以状态码作为跳过原因
+            ProgressEventKind::Skipped if reason.is_some_and(|reason| reason.parse::<u16>().is_ok()) => self.gone += 1,
+            ProgressEventKind::Skipped => self.skipped += 1,
+            ProgressEventKind::Failed => {
+                self.failed += 1;
+                if self.failures.len() == RECENT_FAILURES {
+                    self.failures.pop_front();
+                }
+                self.failures.push_back((url.to_string(), reason.unwrap_or_default().to_string()));
+            }
+        }
+        self.completed += 1;
+        if let Some(index) = self.active.iter().position(|(active, _)| active == url) {
+            self.active.remove(index);
+        }
+    }
+
+    /// 已发现但还没有开始请求的链接数
+    pub fn queued(&self) -> usize {
+        self.total.saturating_sub(self.completed + self.active.len())
+    }
+
+    /// 正在请求的 URL
+    pub fn active(&self) -> impl Iterator<Item = &str> {
+        self.active.iter().map(|(url, _)| url.as_str())
+    }
+
+    /// 最近 `RATE_WINDOW_SECS` 秒内平均每秒完成的页面数
+    pub fn pages_per_sec(&self, now: Instant) -> f64 {
+        let rates = self.rates(now, RATE_WINDOW_SECS);
+        rates.iter().sum::<u64>() as f64 / rates.len().max(1) as f64
+    }
+
+    /// 截至 `now` 的最近 `count` 秒每秒完成的页面数，不含正在进行的这一秒
+    fn rates(&self, now: Instant, count: usize) -> Vec<u64> {
+        let elapsed = now.saturating_duration_since(self.started).as_secs() as usize;
+        let start = elapsed.saturating_sub(count);
+        (start..elapsed).map(|second| self.per_second.get(second).copied().unwrap_or(0)).collect()
+    }
+
+    /// 绘制面板
+    pub fn render(&self, frame: &mut Frame, now: Instant) {
+        let [progress, middle, active, failures] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Length(6),
+            Constraint::Min(3),
+            Constraint::Length(8),
+        ])
+        .areas(frame.area());
+        let [counters, rate] = Layout::horizontal([Constraint::Length(24), Constraint::Min(10)]).areas(middle);
+
+        let elapsed = now.saturating_duration_since(self.started).as_secs();
+        let label = tr!(
+            "{}/{}  排队 {}  进行中 {}  已用 {:02}:{:02}:{:02}",
+            "{}/{}  queued {}  active {}  elapsed {:02}:{:02}:{:02}",
+            self.completed,
+            self.total,
+            self.queued(),
+            self.active.len(),
+            elapsed / 3600,
+            elapsed / 60 % 60,
+            elapsed % 60
+        );
+        let ratio = if self.total == 0 { 0.0 } else { (self.completed as f64 / self.total as f64).min(1.0) };
+        frame.render_widget(
+            Gauge::default()
+                .block(Block::default().borders(Borders::ALL).title(" adoc "))
+                .gauge_style(Style::default().fg(Color::Cyan))
+                .ratio(ratio)
+                .label(label),
+            progress,
+        );
+
+        let lines = vec![
+            Line::from(format!("{}  {}", tr!("成功", "Fetched"), self.fetched)),
+            Line::from(format!("{}  {}", tr!("跳过", "Skipped"), self.skipped)),
+            Line::from(format!("{}  {}", tr!("失效", "Gone"), self.gone)),
+            Line::styled(
+                format!("{}  {}", tr!("失败", "Failed"), self.failed),
+                Style::default().fg(if self.failed > 0 { Color::Red } else { Color::Reset }),
+            ),
+        ];
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(tr!(" 计数 ", " Counts "))),
+            counters,
+        );
+
+        let title = tr!(" 页面/秒: {:.1} ", " Pages/sec: {:.1} ", self.pages_per_sec(now));
+        frame.render_widget(
+            Sparkline::default()
+                .block(Block::default().borders(Borders::ALL).title(title))
+                .style(Style::default().fg(Color::Green))
+                .data(self.rates(now, rate.width.saturating_sub(2) as usize)),
+            rate,
+        );
+
+        let items: Vec<ListItem> = self
+            .active
+            .iter()
+            .map(|(url, started)| {
+                ListItem::new(format!("[{:>4}s] {}", now.saturating_duration_since(*started).as_secs(), url))
+            })
+            .collect();
+        let title = tr!(" 进行中 ({}) ", " Active ({}) ", self.active.len());
+        frame.render_widget(List::new(items).block(Block::default().borders(Borders::ALL).title(title)), active);
+
+        let items: Vec<ListItem> = self
+            .failures
+            .iter()
+            .rev()
+            .map(|(url, reason)| ListItem::new(format!("{}  {}", url, reason)).style(Style::default().fg(Color::Red)))
+            .collect();
+        let title = tr!(" 最近的失败 ", " Recent failures ");
+        frame.render_widget(List::new(items).block(Block::default().borders(Borders::ALL).title(title)), failures);
+    }
+}
+
+/// 面板是否正在占用终端
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// 正在显示的监控面板，drop 时恢复终端并输出暂存的日志
+pub struct Dashboard {
+    state: Arc<Mutex<DashboardState>>,
+    stop: Option<Sender<()>>,
+    drawer: Option<JoinHandle<()>>,
+}
+
+impl Dashboard {
+    /// 切换到备用屏幕并开始刷新面板
+    pub fn start() -> Result<Self> {
+        let mut terminal = Terminal::new(CrosstermBackend::new(std::io::stderr()))?;
+        execute!(std::io::stderr(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        ACTIVE.store(true, Ordering::Release);
+        hold_logs();
+        // 按 Ctrl-C 退出时也要先恢复终端
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            runtime.spawn(async {
+                if tokio::signal::ctrl_c().await.is_ok() {
+                    restore();
+                    std::process::exit(130);
+                }
+            });
+        }
+
+        let state = Arc::new(Mutex::new(DashboardState::new(Instant::now())));
+        let (stop, stopped) = mpsc::channel::<()>();
+        let drawn = state.clone();
+        let drawer = std::thread::spawn(move || loop {
+            let _ = terminal.draw(|frame| drawn.lock().unwrap().render(frame, Instant::now()));
+            if stopped.recv_timeout(REFRESH_INTERVAL) != Err(RecvTimeoutError::Timeout) {
+                break;
+            }
+        });
+        Ok(Self {
+            state,
+            stop: Some(stop),
+            drawer: Some(drawer),
+        })
+    }
+
+    /// 更新面板的进度事件，交给 [`Crawler::set_progress_events`](crate::crawler::Crawler::set_progress_events)
+    pub fn events(&self) -> ProgressEvents {
+        let state = self.state.clone();
+        ProgressEvents::callback(Box::new(move |event, url, reason| {
+            state.lock().unwrap().record(event, url, reason, Instant::now());
+        }))
+    }
+}
+
+impl Drop for Dashboard {
+    fn drop(&mut self) {
+        self.stop.take();
+        if let Some(drawer) = self.drawer.take() {
+            let _ = drawer.join();
+        }
+        restore();
+    }
+}
+
+/// 离开备用屏幕，输出暂存的日志
+fn restore() {
+    if ACTIVE.swap(false, Ordering::AcqRel) {
+        let _ = execute!(std::io::stderr(), cursor::Show, terminal::LeaveAlternateScreen);
+        release_logs();
+    }
+}
@@ -0,0 +1,90 @@
+//! 增量爬取的变更导出（`--delta-output`）。
+//!
+//! 与 `--merge-into` 一起使用：合并前把本次爬取的页面与已有结果文件（基线）按 URL 对比，
+//! 只把新增的页面和内容哈希变化的页面写入单独的文件，供只需要增量的下游（例如搜索索引）读取：
+//!
+//! ```json
+//! {"schema_version": 1, "pages": [{"change": "modified", "content_hash": "9f86d0…", "title": "View", ...}]}
+//! ```
+//!
+//! 内容哈希为标题和正文的 SHA-256，与 [`crate::merge`] 判断冲突的依据相同。
+//! 文件格式与 JSON 输出的信封相同，也可以直接用 [`crate::output::load_results`] 读取。
+
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::extract::DocPage;
+use crate::hash::sha256_hex;
+use crate::merge::canonical_url;
+use crate::schema::SCHEMA_VERSION;
+
+/// 页面相对于基线的变化
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    /// 基线中没有的页面
+    Added,
+    /// 内容哈希与基线不同的页面
+    Modified,
+}
+
+/// 变更文件中的一个页面
+#[derive(Debug, Clone, Serialize)]
+pub struct DeltaPage<'a> {
+    pub change: ChangeKind,
+    pub content_hash: String,
+    #[serde(flatten)]
+    pub page: &'a DocPage,
+}
+
+#[derive(Serialize)]
+struct Delta<'a> {
+    schema_version: u32,
+    pages: &'a [DeltaPage<'a>],
+}
+
+/// 页面的内容哈希：标题和正文的 SHA-256
+pub fn content_hash(page: &DocPage) -> String {
+    let mut data = Vec::with_capacity(page.title.len() + page.content.len() + 1);
+    data.extend_from_slice(page.title.as_bytes());
+    data.push(0);
+    data.extend_from_slice(page.content.as_bytes());
+    sha256_hex(&data)
+}
+
+/// 按爬取顺序列出 `pages` 中相对于 `baseline` 新增或内容变化的页面，URL 按 [`canonical_url`] 比较
+pub fn delta_pages<'a>(baseline: &[DocPage], pages: &'a [DocPage]) -> Vec<DeltaPage<'a>> {
+    let baseline: HashMap<&str, String> =
+        baseline.iter().map(|page| (canonical_url(&page.url), content_hash(page))).collect();
+    pages
+        .iter()
+        .filter_map(|page| {
+            let content_hash = content_hash(page);
+            let change = match baseline.get(canonical_url(&page.url)) {
+                None => ChangeKind::Added,
+                Some(hash) if *hash != content_hash => ChangeKind::Modified,
+                Some(_) => return None,
+            };
+            Some(DeltaPage { change, content_hash, page })
+        })
+        .collect()
+}
+
+/// 把变更写入 `path`，`pretty` 时缩进
+pub fn save_delta(path: &Path, pages: &[DeltaPage], pretty: bool) -> Result<()> {
+    let delta = Delta {
+        schema_version: SCHEMA_VERSION,
+        pages,
+    };
+    let mut out = BufWriter::new(std::fs::File::create(path)?);
+    if pretty {
+        serde_json::to_writer_pretty(&mut out, &delta)?;
+    } else {
+        serde_json::to_writer(&mut out, &delta)?;
+    }
+    out.flush()?;
+    Ok(())
+}
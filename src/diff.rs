@@ -0,0 +1,220 @@
+//! 比较两次爬取结果：新增、删除和变更的页面。
+
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+use similar::TextDiff;
+use std::collections::BTreeMap;
+
+use crate::extract::DocPage;
+use crate::merge::canonical_url;
+use crate::tr;
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+#[value(rename_all = "lowercase")]
+pub enum DiffFormat {
+    Text,
+    Markdown,
+    Json,
+}
+
+/// 新增或删除的页面
+#[derive(Debug, Clone, Serialize)]
+pub struct PageRef {
+    pub url: String,
+    pub title: String,
+}
+
+/// 变更前后的值
+#[derive(Debug, Clone, Serialize)]
+pub struct Change<T> {
+    pub old: T,
+    pub new: T,
+}
+
+/// 内容发生变化的页面
+#[derive(Debug, Clone, Serialize)]
+pub struct PageChange {
+    pub url: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title_change: Option<Change<String>>,
+    /// 正文的 unified diff，正文未变化时为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_diff: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub availability: Option<Change<Vec<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<Change<bool>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CrawlDiff {
+    pub added: Vec<PageRef>,
+    pub removed: Vec<PageRef>,
+    pub changed: Vec<PageChange>,
+}
+
+fn page_ref(page: &DocPage) -> PageRef {
+    PageRef {
+        url: page.url.clone(),
+        title: page.title.clone(),
+    }
+}
+
+fn change<T: PartialEq>(old: T, new: T) -> Option<Change<T>> {
+    (old != new).then_some(Change { old, new })
+}
+
+fn compare(old: &DocPage, new: &DocPage) -> Option<PageChange> {
+    let content_diff = (old.content != new.content).then(|| {
+        TextDiff::from_lines(&old.content, &new.content)
+            .unified_diff()
+            .context_radius(2)
+            .header("old", "new")
+            .to_string()
+    });
+    let page_change = PageChange {
+        url: new.url.clone(),
+        title: new.title.clone(),
+        title_change: change(old.title.clone(), new.title.clone()),
+        content_diff,
+        availability: change(old.availability(), new.availability()),
+        deprecated: change(old.is_deprecated(), new.is_deprecated()),
+    };
+
+    let changed = page_change.title_change.is_some() || page_change.content_diff.is_some();
+    changed.then_some(page_change)
+}
+
+/// 按 URL 对比两次爬取结果，结果按 URL 排序
+pub fn diff_pages(old: &[DocPage], new: &[DocPage]) -> CrawlDiff {
+    let old: BTreeMap<&str, &DocPage> = old.iter().map(|page| (canonical_url(&page.url), page)).collect();
+    let new: BTreeMap<&str, &DocPage> = new.iter().map(|page| (canonical_url(&page.url), page)).collect();
+
+    let mut diff = CrawlDiff::default();
+    for (url, page) in &new {
+        match old.get(url) {
+            None => diff.added.push(page_ref(page)),
+            Some(old_page) => diff.changed.extend(compare(old_page, page)),
+        }
+    }
+    for (url, page) in &old {
+        if !new.contains_key(url) {
+            diff.removed.push(page_ref(page));
+        }
+    }
+    diff
+}
+
+impl CrawlDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    pub fn render(&self, format: DiffFormat) -> Result<String> {
+        Ok(match format {
+            DiffFormat::Text => self.to_text(),
+            DiffFormat::Markdown => self.to_markdown(),
+            DiffFormat::Json => serde_json::to_string_pretty(self)?,
+        })
+    }
+
+    /// 一行变更摘要
+    pub fn summary(&self) -> String {
+        tr!(
+            "新增 {} 个页面，删除 {} 个页面，变更 {} 个页面",
+            "{} pages added, {} removed, {} changed",
+            self.added.len(),
+            self.removed.len(),
+            self.changed.len()
+        )
+    }
+
+    fn to_text(&self) -> String {
+        let mut content = format!("{}\n", self.summary());
+        for page in &self.added {
+            content.push_str(&format!("\n+ {} ({})", page.title, page.url));
+        }
+        for page in &self.removed {
+            content.push_str(&format!("\n- {} ({})", page.title, page.url));
+        }
+        for page in &self.changed {
+            content.push_str(&format!("\n~ {} ({})\n", page.title, page.url));
+            if let Some(title) = &page.title_change {
+                content.push_str(&tr!("  标题: {} -> {}\n", "  title: {} -> {}\n", title.old, title.new));
+            }
+            if let Some(deprecated) = &page.deprecated {
+                content.push_str(&tr!(
+                    "  弃用: {} -> {}\n",
+                    "  deprecated: {} -> {}\n",
+                    deprecated.old,
+                    deprecated.new
+                ));
+            }
+            if let Some(availability) = &page.availability {
+                content.push_str(&tr!(
+                    "  可用性: {} -> {}\n",
+                    "  availability: {} -> {}\n",
+                    availability.old.join(", "),
+                    availability.new.join(", ")
+                ));
+            }
+            if let Some(diff) = &page.content_diff {
+                content.push_str(diff);
+            }
+        }
+        content.push('\n');
+        content
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut content = tr!("# 文档变更\n\n", "# Documentation changes\n\n");
+        content.push_str(&format!("{}\n\n", self.summary()));
+
+        if !self.added.is_empty() {
+            content.push_str(&tr!("## 新增页面\n\n", "## Added pages\n\n"));
+            for page in &self.added {
+                content.push_str(&format!("- [{}]({})\n", page.title, page.url));
+            }
+            content.push('\n');
+        }
+        if !self.removed.is_empty() {
+            content.push_str(&tr!("## 删除页面\n\n", "## Removed pages\n\n"));
+            for page in &self.removed {
+                content.push_str(&format!("- [{}]({})\n", page.title, page.url));
+            }
+            content.push('\n');
+        }
+        if !self.changed.is_empty() {
+            content.push_str(&tr!("## 变更页面\n\n", "## Changed pages\n\n"));
+            for page in &self.changed {
+                content.push_str(&format!("### [{}]({})\n\n", page.title, page.url));
+                if let Some(title) = &page.title_change {
+                    content.push_str(&tr!("- 标题: {} → {}\n", "- Title: {} → {}\n", title.old, title.new));
+                }
+                if let Some(deprecated) = &page.deprecated {
+                    content.push_str(&tr!(
+                        "- 弃用: {} → {}\n",
+                        "- Deprecated: {} → {}\n",
+                        deprecated.old,
+                        deprecated.new
+                    ));
+                }
+                if let Some(availability) = &page.availability {
+                    content.push_str(&tr!(
+                        "- 可用性: {} → {}\n",
+                        "- Availability: {} → {}\n",
+                        availability.old.join(", "),
+                        availability.new.join(", ")
+                    ));
+                }
+                if let Some(diff) = &page.content_diff {
+                    content.push_str(&format!("\n```diff\n{}```\n", diff));
+                }
+                content.push('\n');
+            }
+        }
+        content
+    }
+}
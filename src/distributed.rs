@@ -0,0 +1,55 @@
+//! 多个 adoc 进程（可以在不同机器上）协作完成一次大规模爬取时共享的状态。
+//!
+//! 已访问集合保存在 Redis 的一个集合（set）中，认领 URL 即 `SADD`，返回 1 表示之前没有进程认领过。
+//! 集合在最后一次认领的一天后过期；重新完整爬取前应换一个 `--redis-key` 或删除该键。
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+
+use crate::crawler::DistributedVisited;
+use crate::tr;
+
+/// 集合的过期时间（秒），每次认领后重新计算
+const VISITED_TTL_SECS: i64 = 24 * 60 * 60;
+
+/// 保存在 Redis 中的已访问集合
+pub struct RedisVisited {
+    connection: ConnectionManager,
+    key: String,
+}
+
+impl RedisVisited {
+    /// 连接 Redis，例如 `redis://localhost:6379/0`。连接断开后自动重连
+    pub async fn connect(url: &str, key: &str) -> Result<Self> {
+        let client =
+            redis::Client::open(url).with_context(|| tr!("无效的 Redis 地址: {}", "invalid Redis URL: {}", url))?;
+        let connection = ConnectionManager::new(client)
+            .await
+            .with_context(|| tr!("无法连接 Redis: {}", "cannot connect to Redis: {}", url))?;
+        Ok(Self {
+            connection,
+            key: key.to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl DistributedVisited for RedisVisited {
+    async fn claim(&self, url: &str) -> Result<bool> {
+        let mut connection = self.connection.clone();
+        let (added,): (i64,) = redis::pipe()
+            .sadd(&self.key, url)
+            .expire(&self.key, VISITED_TTL_SECS)
+            .ignore()
+            .query_async(&mut connection)
+            .await?;
+        Ok(added == 1)
+    }
+
+    async fn release(&self, url: &str) -> Result<()> {
+        let mut connection = self.connection.clone();
+        redis::cmd("SREM").arg(&self.key).arg(url).query_async::<()>(&mut connection).await?;
+        Ok(())
+    }
+}
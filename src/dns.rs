@@ -0,0 +1,116 @@
+//! 自定义 DNS 解析。
+//!
+//! 系统解析器屏蔽或污染 developer.apple.com 时，可以改为通过 DNS-over-HTTPS（DoH）解析：
+//! `--dns doh:https://1.1.1.1/dns-query`。DoH 服务器本身写成域名时仍由系统解析器解析，
+//! 只在第一次解析时查询一次。
+
+use anyhow::{bail, Context, Result};
+use hickory_resolver::config::{LookupIpStrategy, NameServerConfigGroup, ResolverConfig, ResolverOpts};
+use hickory_resolver::TokioAsyncResolver;
+use hyper::client::connect::dns::Name;
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+
+use crate::tr;
+
+/// 域名解析方式
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Dns {
+    /// 系统解析器
+    #[default]
+    System,
+    /// 通过 DoH 服务器解析，`host` 可以是 IP 地址或域名
+    Doh { host: String, port: u16 },
+}
+
+/// 解析 `--dns` 的值：`system`，或 `doh:` 加 DoH 服务器的地址，例如 `doh:https://dns.google/dns-query`
+pub fn parse_dns(value: &str) -> Result<Dns> {
+    let value = value.trim();
+    if value.eq_ignore_ascii_case("system") {
+        return Ok(Dns::System);
+    }
+    let Some(server) = value.strip_prefix("doh:") else {
+        bail!(tr!(
+            "无效的 DNS 设置: {}，应为 system 或 doh:https://…",
+            "invalid DNS setting: {}, expected system or doh:https://…",
+            value
+        ));
+    };
+    let url = url::Url::parse(server).with_context(|| tr!("无效的 DoH 地址: {}", "invalid DoH URL: {}", server))?;
+    // hickory 固定请求 /dns-query，其他路径无法使用
+    if url.scheme() != "https" || !matches!(url.path(), "" | "/" | "/dns-query") || url.query().is_some() {
+        bail!(tr!(
+            "不支持的 DoH 地址: {}，应为 https://<服务器>/dns-query",
+            "unsupported DoH URL: {}, expected https://<server>/dns-query",
+            server
+        ));
+    }
+    let host = match url.host() {
+        Some(url::Host::Domain(domain)) => domain.to_string(),
+        Some(url::Host::Ipv4(ip)) => ip.to_string(),
+        Some(url::Host::Ipv6(ip)) => ip.to_string(),
+        None => bail!(tr!("无效的 DoH 地址: {}", "invalid DoH URL: {}", server)),
+    };
+    Ok(Dns::Doh {
+        host,
+        port: url.port().unwrap_or(443),
+    })
+}
+
+/// 交给 reqwest 的解析器，`Dns::System` 时返回 `None`，沿用 reqwest 默认的系统解析
+pub(crate) fn resolver(dns: &Dns) -> Option<Arc<DohResolver>> {
+    match dns {
+        Dns::System => None,
+        Dns::Doh { host, port } => Some(Arc::new(DohResolver {
+            host: host.clone(),
+            port: *port,
+            resolver: Arc::new(OnceCell::new()),
+        })),
+    }
+}
+
+/// 通过 DoH 解析域名，hickory 的解析器在第一次解析时才创建（需要 tokio 运行时）
+#[derive(Clone)]
+pub(crate) struct DohResolver {
+    host: String,
+    port: u16,
+    resolver: Arc<OnceCell<TokioAsyncResolver>>,
+}
+
+impl DohResolver {
+    async fn resolver(&self) -> Result<&TokioAsyncResolver> {
+        self.resolver
+            .get_or_try_init(|| async {
+                let ips: Vec<IpAddr> = match self.host.parse::<IpAddr>() {
+                    Ok(ip) => vec![ip],
+                    Err(_) => tokio::net::lookup_host((self.host.as_str(), self.port))
+                        .await
+                        .with_context(|| {
+                            tr!("无法解析 DoH 服务器: {}", "cannot resolve DoH server: {}", self.host)
+                        })?
+                        .map(|addr| addr.ip())
+                        .collect(),
+                };
+                let servers = NameServerConfigGroup::from_ips_https(&ips, self.port, self.host.clone(), true);
+                let mut options = ResolverOpts::default();
+                // 同时查询 A 和 AAAA 记录，由 `--ipv4-only` / `--ipv6-only` 决定实际使用哪一种
+                options.ip_strategy = LookupIpStrategy::Ipv4AndIpv6;
+                Ok(TokioAsyncResolver::tokio(ResolverConfig::from_parts(None, vec![], servers), options))
+            })
+            .await
+    }
+}
+
+impl Resolve for DohResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let this = self.clone();
+        Box::pin(async move {
+            let resolver = this.resolver().await?;
+            let lookup = resolver.lookup_ip(name.as_str()).await?;
+            let addrs: Vec<SocketAddr> = lookup.iter().map(|ip| SocketAddr::new(ip, 0)).collect();
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
@@ -0,0 +1,99 @@
+//! 导入 DocC 归档（`.doccarchive`）。
+//!
+//! 归档的 `data/` 目录下是每个页面的渲染 JSON，与 Apple 开发者网站 JSON 数据接口的格式相同，
+//! 因此直接用 [`extract_json_with`] 转换为 [`DocPage`]，之后可以与爬取的 Apple 文档一起输出、
+//! 合并或建立索引。
+//!
+//! 页面 URL 为 `base_url` 加上页面在归档中的路径，例如 `data/documentation/myframework/foo.json`
+//! 对应 `<base_url>/documentation/myframework/foo`，与归档部署为静态网站后的地址一致。
+
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+use url::Url;
+
+use crate::extract::{extract_json_with, DocPage, LinkFilter, RelatedLink};
+use crate::tr;
+
+/// `docc preview` 默认的本地预览地址
+pub const DEFAULT_BASE_URL: &str = "http://localhost:8080";
+
+/// 读取 DocC 归档中的所有文档和教程页面
+pub fn import_docc(archive: &Path, base_url: &str) -> Result<Vec<DocPage>> {
+    let data = archive.join("data");
+    if !data.is_dir() {
+        bail!(tr!(
+            "不是 DocC 归档（缺少 data 目录）: {}",
+            "not a DocC archive (no data directory): {}",
+            archive.display()
+        ));
+    }
+    let mut base = Url::parse(base_url).with_context(|| tr!("无效的 URL: {}", "invalid URL: {}", base_url))?;
+    // 以 `/` 结尾，页面路径接在 `base_url` 的路径之后，而不是替换它
+    if !base.path().ends_with('/') {
+        base.set_path(&format!("{}/", base.path()));
+    }
+    // 归档内部的链接与 Apple 文档的链接都保留为相关链接
+    let links = LinkFilter {
+        allowed_domains: base.host_str().map(|host| host.to_string()).into_iter().collect(),
+        allow_external: false,
+    };
+
+    let mut files = Vec::new();
+    for section in ["documentation", "tutorials"] {
+        collect_json(&data.join(section), &mut files)?;
+    }
+    // 按去掉扩展名的路径排序，上级页面排在其下的页面之前
+    files.sort_by_key(|file| file.with_extension(""));
+
+    let mut pages = Vec::new();
+    for file in files {
+        let relative = file.strip_prefix(&data)?.with_extension("");
+        let path: Vec<String> = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        let url = base
+            .join(&path.join("/"))
+            .with_context(|| tr!("无效的 URL: {}", "invalid URL: {}", relative.display()))?;
+        let json = std::fs::read_to_string(&file)
+            .with_context(|| tr!("无法读取文件: {}", "cannot read file: {}", file.display()))?;
+        let mut page = extract_json_with(url.as_str(), &json, &links)
+            .with_context(|| tr!("无法解析页面: {}", "cannot parse page: {}", file.display()))?;
+        add_base_path(&mut page.related_links, &base);
+        pages.push(page);
+    }
+    Ok(pages)
+}
+
+/// 渲染 JSON 中归档内部的链接是 `/documentation/...` 形式的绝对路径，`base_url` 带路径时补上
+fn add_base_path(links: &mut [RelatedLink], base: &Url) {
+    let prefix = base.path().trim_end_matches('/');
+    if prefix.is_empty() {
+        return;
+    }
+    for link in links {
+        let Ok(mut url) = Url::parse(&link.url) else {
+            continue;
+        };
+        if url.origin() == base.origin() && !url.path().starts_with(&format!("{}/", prefix)) {
+            url.set_path(&format!("{}{}", prefix, url.path()));
+            link.url = url.into();
+        }
+    }
+}
+
+/// 递归收集 `dir` 下的 JSON 文件，目录不存在时不做任何事
+fn collect_json(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Ok(());
+    };
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_json(&path, files)?;
+        } else if path.extension().is_some_and(|extension| extension == "json") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
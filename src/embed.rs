@@ -0,0 +1,112 @@
+//! 为 RAG 分块生成嵌入向量。
+//!
+//! 调用 OpenAI 兼容的 `/v1/embeddings` 接口，OpenAI 以及 Ollama、LM Studio、vLLM 等本地服务都可以使用。
+//! 分块按批发送，向量写回 [`Chunk::embedding`]，随分块一起输出到 JSONL 或 SQLite。
+
+use anyhow::{bail, Context, Result};
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+use tracing::debug;
+
+use crate::chunk::Chunk;
+use crate::tr;
+
+#[derive(Debug, Clone)]
+pub struct EmbeddingConfig {
+    /// 嵌入接口地址，例如 https://api.openai.com/v1/embeddings 或 http://localhost:11434/v1/embeddings
+    pub url: String,
+    pub model: String,
+    pub api_key: Option<String>,
+    /// 每次请求包含的分块数
+    pub batch_size: usize,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<Embedding>,
+}
+
+#[derive(Deserialize)]
+struct Embedding {
+    embedding: Vec<f32>,
+    #[serde(default)]
+    index: Option<usize>,
+}
+
+pub struct EmbeddingClient {
+    client: Client,
+    config: EmbeddingConfig,
+}
+
+impl EmbeddingClient {
+    pub fn new(config: EmbeddingConfig) -> Self {
+        Self {
+            client: Client::new(),
+            config,
+        }
+    }
+
+    /// 为一组文本生成向量，顺序与输入相同
+    pub async fn embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        let mut request = self
+            .client
+            .post(&self.config.url)
+            .json(&json!({ "model": self.config.model, "input": texts }));
+        if let Some(key) = &self.config.api_key {
+            request = request.bearer_auth(key);
+        }
+        let response = request
+            .send()
+            .await
+            .with_context(|| tr!("无法连接嵌入接口: {}", "cannot reach embedding endpoint: {}", self.config.url))?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            bail!(tr!(
+                "嵌入接口返回 {}: {}",
+                "embedding endpoint returned {}: {}",
+                status,
+                body.trim()
+            ));
+        }
+        let mut data = response
+            .json::<EmbeddingResponse>()
+            .await
+            .context(tr!("无法解析嵌入接口的响应", "cannot parse the embedding response"))?
+            .data;
+        if data.len() != texts.len() {
+            bail!(tr!(
+                "嵌入接口返回了 {} 个向量，应为 {} 个",
+                "embedding endpoint returned {} vectors, expected {}",
+                data.len(),
+                texts.len()
+            ));
+        }
+        // 接口不保证按输入顺序返回，有 index 时按 index 排序
+        data.sort_by_key(|embedding| embedding.index);
+        Ok(data.into_iter().map(|embedding| embedding.embedding).collect())
+    }
+
+    /// 为所有分块生成向量
+    pub async fn embed_chunks(&self, chunks: &mut [Chunk]) -> Result<()> {
+        let total = chunks.len();
+        for (batch, chunks) in chunks.chunks_mut(self.config.batch_size.max(1)).enumerate() {
+            let texts: Vec<&str> = chunks.iter().map(|chunk| chunk.content.as_str()).collect();
+            let vectors = self.embed(&texts).await?;
+            for (chunk, vector) in chunks.iter_mut().zip(vectors) {
+                chunk.embedding = Some(vector);
+            }
+            debug!(
+                "{}",
+                tr!(
+                    "已生成 {}/{} 个分块的向量",
+                    "Embedded {}/{} chunks",
+                    (batch * self.config.batch_size.max(1) + chunks.len()),
+                    total
+                )
+            );
+        }
+        Ok(())
+    }
+}
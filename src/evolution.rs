@@ -0,0 +1,160 @@
+//! Swift Evolution 提案（`--source swift-evolution`）。
+//!
+//! 从提案索引 [`INDEX_URL`] 开始：索引页面列出所有提案的编号、标题和状态，相关链接为各提案的 Markdown 原文，
+//! 递归爬取时逐个下载并提取为 `DocPage`。提案开头的元数据列表（`* Status: ...`、`* Review: ...`）
+//! 整理为 `Status: Implemented (Swift 5.9)` 形式的行放在正文开头，评审和实现的链接以 `标签 (地址)` 保留。
+
+use anyhow::Result;
+use serde_json::Value;
+use url::Url;
+
+use crate::extract::{
+    clean_text, markdown_inline, markdown_link_regex, markdown_links, markdown_links_by_section, DocPage, LinkCollector,
+    LinkFilter,
+};
+use crate::profile::SiteProfile;
+
+/// 提案索引（JSON）
+pub const INDEX_URL: &str = "https://download.swift.org/swift-evolution/v1/evolution.json";
+
+/// 索引中的提案文件名相对于该地址
+pub const PROPOSALS_URL: &str = "https://raw.githubusercontent.com/swiftlang/swift-evolution/main/proposals/";
+
+/// 是否为提案索引：顶层有 `proposals` 数组，其中的提案有 `SE-` 编号
+pub fn is_index(data: &Value) -> bool {
+    data.get("proposals")
+        .and_then(Value::as_array)
+        .and_then(|proposals| proposals.first())
+        .and_then(|proposal| proposal.get("id"))
+        .and_then(Value::as_str)
+        .is_some_and(|id| id.starts_with("SE-"))
+}
+
+/// 是否为提案的 Markdown 原文：开头的元数据列表中有 `* Proposal: [SE-xxxx]`
+pub fn is_proposal(markdown: &str) -> bool {
+    markdown.lines().take(20).any(|line| line.trim_start().starts_with("* Proposal: [SE-"))
+}
+
+/// 从提案索引中提取 `DocPage`，每个提案一行，相关链接为各提案的原文
+pub fn extract_index(profile: &SiteProfile, url: &str, data: &Value, links: &LinkFilter) -> Result<DocPage> {
+    let base_url = Url::parse(PROPOSALS_URL)?;
+    let mut lines = Vec::new();
+    let mut related_links = LinkCollector::new(profile, url, links)?;
+    for proposal in data.get("proposals").and_then(Value::as_array).into_iter().flatten() {
+        let text = |key: &str| proposal.get(key).and_then(Value::as_str).map(clean_text).unwrap_or_default();
+        let mut line = format!("{}: {}", text("id"), text("title"));
+        if let Some(status) = proposal.get("status").map(index_status).filter(|status| !status.is_empty()) {
+            line.push_str(&format!(" [{}]", status));
+        }
+        lines.push(line);
+        let Some(link) = proposal.get("link").and_then(Value::as_str).and_then(|link| base_url.join(link).ok())
+        else {
+            continue;
+        };
+        related_links.push(link, &text("title"), None);
+    }
+    Ok(DocPage {
+        title: "Swift Evolution".to_string(),
+        content: lines.join("\n"),
+        url: url.to_string(),
+        related_links: related_links.finish(),
+        ..Default::default()
+    })
+}
+
+/// 索引中的状态，例如 `{"state": "implemented", "version": "5.9"}` → `Implemented (Swift 5.9)`，
+/// 评审中的提案附上评审日期
+fn index_status(status: &Value) -> String {
+    let state = status.get("state").and_then(Value::as_str).unwrap_or_default();
+    // camelCase 的状态名转为句子，例如 activeReview → Active review
+    let mut words = String::new();
+    for (i, c) in state.chars().enumerate() {
+        match c {
+            c if i == 0 => words.extend(c.to_uppercase()),
+            c if c.is_uppercase() => {
+                words.push(' ');
+                words.extend(c.to_lowercase());
+            }
+            c => words.push(c),
+        }
+    }
+    // 日期为 ISO 8601 格式，只保留年月日
+    let date = |key: &str| {
+        status.get(key).and_then(Value::as_str).map(|date| date.chars().take(10).collect::<String>())
+    };
+    let version = status.get("version").and_then(Value::as_str).filter(|version| !version.is_empty());
+    match (version, date("start"), date("end")) {
+        (Some(version), _, _) => format!("{} (Swift {})", words, version),
+        (None, Some(start), Some(end)) => format!("{} ({} – {})", words, start, end),
+        _ => words,
+    }
+}
+
+/// 从提案的 Markdown 原文中提取 `DocPage`，标题为 `SE-xxxx: 提案标题`
+pub fn extract_proposal(profile: &SiteProfile, url: &str, markdown: &str, links: &LinkFilter) -> Result<DocPage> {
+    let base_url = Url::parse(url)?;
+    let mut heading = String::new();
+    let mut id = String::new();
+    let mut metadata = Vec::new();
+    let mut body = Vec::new();
+    let mut in_header = true;
+    for line in markdown.lines() {
+        if heading.is_empty() {
+            if let Some(title) = line.strip_prefix("# ") {
+                heading = clean_text(&markdown_inline(title, false));
+                continue;
+            }
+        }
+        // 元数据列表在标题之后、第一个分节标题之前
+        if in_header {
+            if let Some((key, value)) = line.trim_start().strip_prefix("* ").and_then(|item| item.split_once(": ")) {
+                if key == "Proposal" {
+                    id = clean_text(&markdown_inline(value, false));
+                }
+                metadata.push(format!("{}: {}", key.trim(), metadata_value(key.trim(), value)));
+                continue;
+            }
+            if line.starts_with("## ") {
+                in_header = false;
+            }
+        }
+        body.push(markdown_inline(line, false));
+    }
+
+    let mut related_links = LinkCollector::new(profile, url, links)?;
+    for (section, label, href) in markdown_links_by_section(markdown) {
+        let Ok(link) = base_url.join(href) else {
+            continue;
+        };
+        related_links.push(link, &markdown_inline(label, false), section.as_deref());
+    }
+
+    let title = match (id.is_empty(), heading.is_empty()) {
+        (false, false) => format!("{}: {}", id, heading),
+        (true, _) => heading,
+        (false, true) => id,
+    };
+    metadata.extend(body);
+    Ok(DocPage {
+        title,
+        content: clean_text(&metadata.join("\n")),
+        url: url.to_string(),
+        related_links: related_links.finish(),
+        ..Default::default()
+    })
+}
+
+/// 元数据的值：作者和评审经理只保留姓名，其余保留链接地址，例如
+/// `([pitch](https://…)) ([review](https://…))` → `pitch (https://…), review (https://…)`
+fn metadata_value(key: &str, value: &str) -> String {
+    if key.starts_with("Author") || key.starts_with("Review Manager") {
+        return clean_text(&markdown_inline(value, false));
+    }
+    let links = markdown_links(value);
+    let rest = markdown_link_regex().replace_all(value, "");
+    // 只有链接时逐个列出，否则按原文保留链接地址
+    if !links.is_empty() && rest.chars().all(|c| c.is_whitespace() || matches!(c, '(' | ')' | ',')) {
+        return links.iter().map(|(label, href)| format!("{} ({})", label, href)).collect::<Vec<_>>().join(", ");
+    }
+    clean_text(&markdown_inline(value, true))
+}
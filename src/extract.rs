@@ -0,0 +1,964 @@
+//! HTML / JSON → `DocPage` 的提取逻辑。
+//!
+//! 本模块只依赖 `scraper`（及其底层的 `html5ever`）、`serde_json` 和 `url`，不依赖 tokio 或 reqwest，
+//! 可以在关闭默认的 `crawler` 特性后编译到 `wasm32-unknown-unknown`，
+//! 例如在浏览器扩展中对用户正在浏览的页面运行同样的提取逻辑：
+//!
+//! ```text
+//! cargo build --lib --no-default-features --target wasm32-unknown-unknown
+//! ```
+
+use anyhow::Result;
+use html5ever::tendril::StrTendril;
+use html5ever::tokenizer::states::RawKind;
+use html5ever::tokenizer::{
+    BufferQueue, Tag, TagKind, Token, TokenSink, TokenSinkResult, Tokenizer, TokenizerOpts,
+};
+use regex::Regex;
+use schemars::JsonSchema;
+use scraper::{ElementRef, Html, Selector};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use url::Url;
+
+use crate::anchor::{self, Anchor};
+use crate::archive::{extract_archive_html, is_archive_url};
+use crate::changes::{self, SdkChanges};
+use crate::evolution;
+use crate::hash::{fnv1a, FNV_OFFSET};
+use crate::hig::{extract_hig_html, extract_hig_json, is_hig_url};
+use crate::media::{self, Media};
+use crate::profile::{JsonMapping, SiteProfile};
+use crate::raw::RawHtml;
+use crate::rest::RestApi;
+use crate::sample::SampleCode;
+use crate::tokens::PageSize;
+use crate::tutorial::{self, TutorialStep};
+use crate::wwdc::{extract_video_html, is_video_url};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct DocPage {
+    pub title: String,
+    pub content: String,
+    pub url: String,
+    pub related_links: Vec<RelatedLink>,
+    /// 页面已失效（404 / 410）时的 HTTP 状态码，这样的页面只是占位记录，没有标题和正文
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    /// REST API 参考页面的接口或对象类型定义，见 [`crate::rest`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rest: Option<Box<RestApi>>,
+    /// 教程页面按顺序排列的步骤，见 [`crate::tutorial`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub steps: Vec<TutorialStep>,
+    /// 页面中嵌入或链接的视频、音频，见 [`crate::media`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub media: Vec<Media>,
+    /// 发行说明和 API 变化页面中各 SDK 新增、修改、弃用的符号，见 [`crate::changes`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<SdkChanges>,
+    /// 示例代码页面的项目信息，见 [`crate::sample`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sample: Option<Box<SampleCode>>,
+    /// 大模型生成的摘要，只在设置 `--summarize` 时生成，见 [`crate::summarize`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    /// 框架、平台、提到的符号和关键词标签，只在设置 `--tags` 时生成，见 [`crate::tags`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// 翻译后的正文，只在设置 `--translate` 时生成，见 [`crate::translate`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_translated: Option<String>,
+    /// 正文的词数、字符数和 token 数，只在设置 `--token-stats` 时统计，见 [`crate::tokens`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<PageSize>,
+    /// 正文中小节标题的锚点，见 [`crate::anchor`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub anchors: Vec<Anchor>,
+    /// 页面的 HTTP 响应信息，只在设置 `--with-http-meta` 时记录
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http: Option<HttpMeta>,
+    /// 下载到的原始正文，只在设置 `--include-raw-html` 时保存，见 [`crate::raw`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_html: Option<RawHtml>,
+}
+
+/// 页面中的一个相关链接：地址、链接文字、类型和所在小节，便于下游按原页面的结构重建导航
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(from = "RelatedLinkRepr")]
+pub struct RelatedLink {
+    pub url: String,
+    /// 链接文字，图片链接等没有文字时为空
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub text: String,
+    pub rel: LinkRel,
+    /// 链接所在小节的标题（HTML 和 Markdown 为之前最近的二级或三级标题，JSON 数据为 Topics、See Also 等分组）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
+}
+
+/// 相关链接的类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkRel {
+    /// 与页面同一域名
+    Internal,
+    /// 其他域名
+    External,
+    /// 指向页面本身的锚点
+    Anchor,
+}
+
+impl RelatedLink {
+    /// `page` 中指向 `link` 的链接，按两者的地址判断类型
+    pub fn new(page: &Url, link: Url, text: &str, section: Option<&str>) -> Self {
+        let rel = if link.fragment().is_some() && link.as_str().split('#').next() == page.as_str().split('#').next() {
+            LinkRel::Anchor
+        } else if link.host_str() == page.host_str() {
+            LinkRel::Internal
+        } else {
+            LinkRel::External
+        };
+        Self {
+            url: link.into(),
+            text: clean_text(text),
+            rel,
+            section: section.map(clean_text).filter(|section| !section.is_empty()),
+        }
+    }
+}
+
+/// 旧版本的结果文件中相关链接只有地址，读取为没有文字和小节的站内链接
+impl From<String> for RelatedLink {
+    fn from(url: String) -> Self {
+        Self {
+            url,
+            text: String::new(),
+            rel: LinkRel::Internal,
+            section: None,
+        }
+    }
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+enum RelatedLinkRepr {
+    Url(String),
+    Link {
+        url: String,
+        #[serde(default)]
+        text: String,
+        rel: LinkRel,
+        #[serde(default)]
+        section: Option<String>,
+    },
+}
+
+impl From<RelatedLinkRepr> for RelatedLink {
+    fn from(repr: RelatedLinkRepr) -> Self {
+        match repr {
+            RelatedLinkRepr::Url(url) => url.into(),
+            RelatedLinkRepr::Link { url, text, rel, section } => Self { url, text, rel, section },
+        }
+    }
+}
+
+/// 按出现顺序收集 `filter` 放行的相关链接，同一地址只保留第一次出现
+pub(crate) struct LinkCollector<'a> {
+    profile: &'a SiteProfile,
+    filter: &'a LinkFilter,
+    page: Url,
+    links: Vec<RelatedLink>,
+}
+
+impl<'a> LinkCollector<'a> {
+    pub(crate) fn new(profile: &'a SiteProfile, url: &str, filter: &'a LinkFilter) -> Result<Self> {
+        Ok(Self {
+            profile,
+            filter,
+            page: Url::parse(url)?,
+            links: Vec::new(),
+        })
+    }
+
+    pub(crate) fn push(&mut self, link: Url, text: &str, section: Option<&str>) {
+        let known = self.links.iter().any(|known| known.url == link.as_str());
+        if !known && self.filter.allows_on(self.profile, link.as_str()) {
+            self.links.push(RelatedLink::new(&self.page, link, text, section));
+        }
+    }
+
+    pub(crate) fn finish(self) -> Vec<RelatedLink> {
+        self.links
+    }
+}
+
+/// 按文档顺序列出 `selector` 匹配的链接元素，以及各自所在小节的标题（之前最近的 `<h2>` 或 `<h3>`）
+pub(crate) fn links_with_sections<'a>(
+    document: &'a Html,
+    selector: &Selector,
+) -> Vec<(ElementRef<'a>, Option<String>)> {
+    let mut section = None;
+    let mut links = Vec::new();
+    for element in document.root_element().descendants().filter_map(ElementRef::wrap) {
+        if matches!(element.value().name(), "h2" | "h3") {
+            section = Some(clean_text(&element.text().collect::<String>())).filter(|title| !title.is_empty());
+        } else if selector.matches(&element) {
+            links.push((element, section.clone()));
+        }
+    }
+    links
+}
+
+/// DocC render JSON 中各引用所在的分组（Topics、Relationships、See Also 下的小节标题），键为引用的标识符
+pub(crate) fn reference_sections(data: &Value) -> HashMap<&str, &str> {
+    let mut sections = HashMap::new();
+    for group in ["topicSections", "relationshipsSections", "seeAlsoSections"] {
+        for section in array(data.get(group)) {
+            let Some(title) = section.get("title").and_then(Value::as_str) else {
+                continue;
+            };
+            for identifier in array(section.get("identifiers")).iter().filter_map(Value::as_str) {
+                sections.entry(identifier).or_insert(title);
+            }
+        }
+    }
+    sections
+}
+
+/// 页面的 HTTP 响应信息，用于按状态或大小筛选页面、核对每个页面的抓取时间
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct HttpMeta {
+    pub status_code: u16,
+    /// 响应正文的字节数（解码为 UTF-8 后）
+    pub content_length: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// 收到响应的时间（RFC 3339，UTC）；从缓存读取的页面为当初下载的时间
+    pub fetched_at: String,
+    /// 请求耗时，包括重试
+    pub fetch_duration_ms: u64,
+}
+
+impl DocPage {
+    /// 相关链接的地址
+    pub fn link_urls(&self) -> impl Iterator<Item = &str> {
+        self.related_links.iter().map(|link| link.url.as_str())
+    }
+
+    /// 根据 URL 路径推导文档层级，例如
+    /// `/documentation/swiftui/view/frame(width:height:)` → `["swiftui", "view", "frame(width:height:)"]`
+    pub fn hierarchy(&self) -> Vec<String> {
+        let Ok(url) = Url::parse(&self.url) else {
+            return Vec::new();
+        };
+        let segments: Vec<String> = url
+            .path_segments()
+            .map(|segments| {
+                segments
+                    .filter(|segment| !segment.is_empty())
+                    .map(|segment| {
+                        urlencoding::decode(segment)
+                            .map(|s| s.into_owned())
+                            .unwrap_or_else(|_| segment.to_string())
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        match segments.iter().position(|segment| segment == "documentation") {
+            Some(index) => segments[index + 1..].to_vec(),
+            None => segments,
+        }
+    }
+
+    /// 根据 URL 生成的稳定 ID（FNV-1a 64 位哈希的十六进制），可用作外部存储的主键
+    pub fn id(&self) -> String {
+        format!("{:016x}", fnv1a(self.url.as_bytes(), FNV_OFFSET))
+    }
+
+    /// 文档所属的框架（层级的第一段）
+    pub fn framework(&self) -> Option<String> {
+        self.hierarchy().into_iter().next()
+    }
+
+    /// 从正文中识别平台可用性标记，例如 `iOS 17.0+`、`macOS 14.0+`
+    pub fn availability(&self) -> Vec<String> {
+        static AVAILABILITY: OnceLock<Regex> = OnceLock::new();
+        let regex = AVAILABILITY.get_or_init(|| {
+            Regex::new(r"\b(iOS|iPadOS|Mac Catalyst|macOS|tvOS|visionOS|watchOS|Xcode) \d+(\.\d+)*\+").unwrap()
+        });
+        let mut platforms: Vec<String> = Vec::new();
+        for found in regex.find_iter(&self.content) {
+            if !platforms.iter().any(|platform| platform == found.as_str()) {
+                platforms.push(found.as_str().to_string());
+            }
+        }
+        platforms
+    }
+
+    /// 从正文的声明中识别符号类型，例如 `struct`、`protocol`、`func`、`init`；
+    /// 框架首页为 `framework`，文章等没有声明的页面返回 `None`
+    pub fn symbol_kind(&self) -> Option<&'static str> {
+        const KINDS: &[&str] = &[
+            "actor", "associatedtype", "case", "class", "enum", "extension", "func", "let", "macro", "operator",
+            "protocol", "struct", "typealias", "var",
+        ];
+        const MODIFIERS: &[&str] = &[
+            "convenience", "dynamic", "final", "indirect", "infix", "mutating", "nonisolated", "open", "optional",
+            "override", "postfix", "prefix", "public", "required", "static", "unowned", "weak",
+        ];
+        if self.hierarchy().len() == 1 {
+            return Some("framework");
+        }
+        // 声明在正文开头，只看前几行，避免把示例代码中的关键字当作声明
+        for line in self.content.lines().take(8) {
+            let Some(word) = line
+                .split_whitespace()
+                .find(|word| !word.starts_with('@') && !MODIFIERS.contains(word))
+            else {
+                continue;
+            };
+            if let Some(kind) = KINDS.iter().find(|kind| **kind == word) {
+                return Some(kind);
+            }
+            if word.starts_with("init(") || word.starts_with("init?(") || word.starts_with("init!(") {
+                return Some("init");
+            }
+            if word.starts_with("subscript(") {
+                return Some("subscript");
+            }
+        }
+        None
+    }
+
+    /// 页面是否标记为已弃用（Apple 文档在标题附近显示 `Deprecated` 标签）
+    pub fn is_deprecated(&self) -> bool {
+        self.content.lines().any(|line| line.trim() == "Deprecated")
+    }
+
+    /// 页面是否标记为 Beta
+    pub fn is_beta(&self) -> bool {
+        self.content.lines().any(|line| line.trim() == "Beta")
+    }
+
+    /// 正文中的第一句简介：跳过声明、平台可用性、`Deprecated` / `Beta` 标记和与标题相同的行
+    pub fn abstract_line(&self) -> &str {
+        const DECLARATION_WORDS: &[&str] = &[
+            "actor", "associatedtype", "case", "class", "convenience", "enum", "extension", "final", "func", "init",
+            "let", "macro", "mutating", "nonisolated", "operator", "optional", "override", "protocol", "public",
+            "required", "static", "struct", "subscript", "typealias", "var",
+        ];
+        let availability = self.availability();
+        self.content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && *line != self.title && *line != "Deprecated" && *line != "Beta")
+            .filter(|line| !availability.iter().any(|platform| platform == line))
+            .find(|line| {
+                let first = line.split(|c: char| c.is_whitespace() || c == '(').next().unwrap_or_default();
+                !first.starts_with('@') && !DECLARATION_WORDS.contains(&first)
+            })
+            .unwrap_or_default()
+    }
+
+    /// 正文为空或几乎为空：通常是内容由 JavaScript 渲染、HTML 中只有外壳的单页应用页面
+    pub fn is_empty_shell(&self) -> bool {
+        self.content.trim().chars().count() < SHELL_CONTENT_CHARS
+    }
+}
+
+/// 正文少于该字符数时视为空的页面外壳
+const SHELL_CONTENT_CHARS: usize = 40;
+
+/// 提取后对正文的裁剪（`--excerpt-only`、`--max-content-chars`），只需要页面清单时不必保存整篇正文
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ContentLimit {
+    /// 正文只保留第一句简介，见 [`DocPage::abstract_line`]
+    pub excerpt_only: bool,
+    /// 正文的最大字符数，超出时截断并以 `…` 结尾
+    pub max_chars: Option<usize>,
+}
+
+impl ContentLimit {
+    /// 裁剪页面正文，并去掉不再出现在正文中的小节锚点
+    pub fn apply(&self, page: &mut DocPage) {
+        let mut changed = false;
+        if self.excerpt_only {
+            page.content = page.abstract_line().to_string();
+            changed = true;
+        }
+        if let Some(max_chars) = self.max_chars.filter(|&max| page.content.chars().count() > max) {
+            page.content = match max_chars {
+                0 => String::new(),
+                _ => format!("{}…", page.content.chars().take(max_chars - 1).collect::<String>().trim_end()),
+            };
+            changed = true;
+        }
+        if changed {
+            page.anchors = anchor::for_content(&page.content, std::mem::take(&mut page.anchors));
+        }
+    }
+}
+
+/// 文档页对应的 DocC JSON 数据地址，不是 `/documentation/`、`/design/` 或 `/tutorials/` 下的页面时返回 `None`，
+/// 例如
+/// `https://developer.apple.com/documentation/swiftui/view` →
+/// `https://developer.apple.com/tutorials/data/documentation/swiftui/view.json`
+pub fn json_data_url(url: &str) -> Option<String> {
+    SiteProfile::apple().json_data_url(url)
+}
+
+/// 清理文本中的多余空白和空行
+///
+/// 每个页面的正文都会经过这里，直接写入一个预先分配的 `String`，不为每行创建中间的 `Vec`。
+pub fn clean_text(text: &str) -> String {
+    let mut cleaned = String::with_capacity(text.len());
+    for line in text.lines() {
+        let mut words = line.split_whitespace();
+        // 移除空行
+        let Some(first) = words.next() else {
+            continue;
+        };
+        if !cleaned.is_empty() {
+            cleaned.push('\n');
+        }
+        cleaned.push_str(first);
+        for word in words {
+            cleaned.push(' ');
+            cleaned.push_str(word);
+        }
+    }
+    cleaned
+}
+
+/// 搜索结果页中的一条文档结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchResult {
+    pub title: String,
+    pub url: String,
+    pub description: String,
+}
+
+/// 从 Apple 开发者文档搜索页的 HTML 中提取文档结果，按页面顺序去重
+///
+/// 优先读取 `li.search-result` 条目；页面结构不同时退回到所有指向文档页面的链接。
+pub fn extract_search_results(url: &str, html: &str) -> Result<Vec<SearchResult>> {
+    extract_search_results_for(SiteProfile::apple(), url, html)
+}
+
+/// 与 [`extract_search_results`] 相同，按 `profile` 的选择器读取搜索结果
+pub fn extract_search_results_for(profile: &SiteProfile, url: &str, html: &str) -> Result<Vec<SearchResult>> {
+    let document = Html::parse_document(html);
+    let base_url = Url::parse(url)?;
+
+    let selectors = &profile.selectors;
+
+    let text = |el: scraper::ElementRef| clean_text(&el.text().collect::<String>());
+    let resolve = |el: scraper::ElementRef| {
+        el.value()
+            .attr("href")
+            .and_then(|href| base_url.join(href).ok())
+            .map(String::from)
+            .filter(|url| {
+                profile.is_site_link(url)
+                    && profile.search_result_path.as_ref().is_none_or(|path| url.contains(path.as_str()))
+            })
+    };
+
+    let mut candidates = Vec::new();
+    for item in document.select(&selectors.search_item) {
+        let Some(link) = item.select(&selectors.link).find_map(|el| resolve(el).map(|url| (el, url))) else {
+            continue;
+        };
+        let title = item.select(&selectors.search_title).next().map(text).unwrap_or_else(|| text(link.0));
+        let description = item.select(&selectors.search_description).next().map(text).unwrap_or_default();
+        candidates.push(SearchResult { title, url: link.1, description });
+    }
+    if candidates.is_empty() {
+        for el in document.select(&selectors.link) {
+            if let Some(url) = resolve(el) {
+                candidates.push(SearchResult { title: text(el), url, description: String::new() });
+            }
+        }
+    }
+
+    let mut results: Vec<SearchResult> = Vec::new();
+    for result in candidates {
+        if !result.title.is_empty() && !results.iter().any(|existing| existing.url == result.url) {
+            results.push(result);
+        }
+    }
+    Ok(results)
+}
+
+/// 哪些链接保留为相关链接（递归爬取时也只会请求这些链接）
+///
+/// 默认只保留文档站点（[`SiteProfile::domains`]，默认为 Apple 开发者文档）的链接；
+/// `allowed_domains` 中的域名（含子域名）额外放行，
+/// 写成 `github.com/apple` 时还要求路径以 `/apple` 开头；`allow_external` 放行所有 http(s) 链接。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LinkFilter {
+    pub allowed_domains: Vec<String>,
+    pub allow_external: bool,
+}
+
+impl LinkFilter {
+    pub fn allows(&self, url: &str) -> bool {
+        self.allows_on(SiteProfile::apple(), url)
+    }
+
+    /// 与 [`LinkFilter::allows`] 相同，文档站点为 `profile`
+    pub fn allows_on(&self, profile: &SiteProfile, url: &str) -> bool {
+        if profile.is_site_link(url) {
+            return true;
+        }
+        if !self.allow_external && self.allowed_domains.is_empty() {
+            return false;
+        }
+        let Ok(url) = Url::parse(url) else {
+            return false;
+        };
+        if !matches!(url.scheme(), "http" | "https") {
+            return false;
+        }
+        let Some(host) = url.host_str() else {
+            return false;
+        };
+        self.allow_external || self.allowed_domains.iter().any(|allowed| domain_matches(allowed, host, url.path()))
+    }
+}
+
+/// `allowed` 为 `swift.org` 或 `github.com/apple` 形式，域名不区分大小写（`host` 已由 `Url` 转为小写）
+fn domain_matches(allowed: &str, host: &str, path: &str) -> bool {
+    let allowed = allowed.trim().trim_start_matches('.');
+    let (domain, prefix) = match allowed.split_once('/') {
+        Some((domain, prefix)) => (domain, Some(prefix.trim_end_matches('/'))),
+        None => (allowed, None),
+    };
+    let domain = domain.to_ascii_lowercase();
+    let host_matches = host == domain || host.strip_suffix(&domain).is_some_and(|sub| sub.ends_with('.'));
+    let path_matches = prefix.is_none_or(|prefix| {
+        path.trim_start_matches('/')
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+    });
+    host_matches && path_matches
+}
+
+/// 从文档页面的 HTML 中提取 `DocPage`
+pub fn extract_html(url: &str, html: &str) -> Result<DocPage> {
+    extract_html_with(url, html, &LinkFilter::default())
+}
+
+/// 与 [`extract_html`] 相同，按 `links` 过滤相关链接
+pub fn extract_html_with(url: &str, html: &str, links: &LinkFilter) -> Result<DocPage> {
+    extract_html_for(SiteProfile::apple(), url, html, links)
+}
+
+/// 与 [`extract_html_with`] 相同，按 `profile` 的选择器提取
+pub fn extract_html_for(profile: &SiteProfile, url: &str, html: &str, links: &LinkFilter) -> Result<DocPage> {
+    let mut page = extract_html_page(profile, url, html, links)?;
+    page.media = media::from_html(url, html);
+    page.anchors = anchor::for_content(&page.content, anchor::from_html(profile, html));
+    Ok(page)
+}
+
+fn extract_html_page(profile: &SiteProfile, url: &str, html: &str, links: &LinkFilter) -> Result<DocPage> {
+    if is_hig_url(url) {
+        return extract_hig_html(profile, url, html, links);
+    }
+    if is_video_url(url) {
+        return extract_video_html(profile, url, html, links);
+    }
+    if is_archive_url(url) {
+        return extract_archive_html(profile, url, html, links);
+    }
+    let document = Html::parse_document(html);
+    let selectors = &profile.selectors;
+
+    let title = document
+        .select(&selectors.title)
+        .next()
+        .map(|el| clean_text(&el.text().collect::<String>()))
+        .unwrap_or_default();
+
+    let content = document
+        .select(&selectors.content)
+        .next()
+        .map(|el| clean_text(&el.text().collect::<String>()))
+        .unwrap_or_default();
+
+    // 与 `extract_links` 一致，同一地址出现多次时都保留
+    let base_url = Url::parse(url)?;
+    let related_links: Vec<RelatedLink> = links_with_sections(&document, &selectors.link)
+        .into_iter()
+        .filter_map(|(el, section)| {
+            let link = el.value().attr("href").and_then(|href| base_url.join(href).ok())?;
+            links.allows_on(profile, link.as_str()).then(|| {
+                RelatedLink::new(&base_url, link, &el.text().collect::<String>(), section.as_deref())
+            })
+        })
+        .collect();
+
+    Ok(DocPage {
+        title,
+        content,
+        url: url.to_string(),
+        related_links,
+        ..Default::default()
+    })
+}
+
+/// 只提取页面中的文档链接，结果与 [`extract_html`] 的 `related_links` 的地址相同
+///
+/// 用分词器顺序扫描标签，不构建 DOM 树，适合只需要发现链接的场景（例如 `--dry-run`）。
+pub fn extract_links(url: &str, html: &str) -> Result<Vec<String>> {
+    extract_links_with(url, html, &LinkFilter::default())
+}
+
+/// 与 [`extract_links`] 相同，按 `links` 过滤
+pub fn extract_links_with(url: &str, html: &str, links: &LinkFilter) -> Result<Vec<String>> {
+    extract_links_for(SiteProfile::apple(), url, html, links)
+}
+
+/// 与 [`extract_links_with`] 相同，文档站点为 `profile`
+pub fn extract_links_for(profile: &SiteProfile, url: &str, html: &str, links: &LinkFilter) -> Result<Vec<String>> {
+    // 视频列表只链接到其中的视频页面，归档页面的链接去掉了页内锚点
+    if is_video_url(url) {
+        return Ok(extract_video_html(profile, url, html, links)?.link_urls().map(str::to_string).collect());
+    }
+    if is_archive_url(url) {
+        return Ok(extract_archive_html(profile, url, html, links)?.link_urls().map(str::to_string).collect());
+    }
+    let base_url = Url::parse(url)?;
+    let mut tokenizer = Tokenizer::new(LinkSink::default(), TokenizerOpts::default());
+    let mut input = BufferQueue::new();
+    input.push_back(StrTendril::from_slice(html));
+    let _ = tokenizer.feed(&mut input);
+    tokenizer.end();
+
+    Ok(tokenizer
+        .sink
+        .hrefs
+        .iter()
+        .filter_map(|href| base_url.join(href).ok().map(String::from))
+        .filter(|url| links.allows_on(profile, url))
+        .collect())
+}
+
+/// 收集 `<a href>` 的分词器输出
+#[derive(Default)]
+struct LinkSink {
+    hrefs: Vec<String>,
+}
+
+impl TokenSink for LinkSink {
+    type Handle = ();
+
+    fn process_token(&mut self, token: Token, _line_number: u64) -> TokenSinkResult<()> {
+        let Token::TagToken(Tag { kind: TagKind::StartTag, name, attrs, .. }) = token else {
+            return TokenSinkResult::Continue;
+        };
+        // 没有树构建器时需要自行切换到原始文本状态，否则脚本中的 `<a` 会被当作标签
+        match &*name {
+            "a" => {
+                if let Some(href) = attrs.iter().find(|attr| &*attr.name.local == "href") {
+                    self.hrefs.push(href.value.to_string());
+                }
+                TokenSinkResult::Continue
+            }
+            "script" => TokenSinkResult::RawData(RawKind::ScriptData),
+            "style" | "xmp" | "iframe" | "noembed" | "noframes" => TokenSinkResult::RawData(RawKind::Rawtext),
+            "title" | "textarea" => TokenSinkResult::RawData(RawKind::Rcdata),
+            _ => TokenSinkResult::Continue,
+        }
+    }
+}
+
+/// 从 Apple 文档的 JSON 数据（DocC render JSON）中提取 `DocPage`
+///
+/// `url` 为该文档对应的页面地址，用于解析引用中的相对链接。
+pub fn extract_json(url: &str, json: &str) -> Result<DocPage> {
+    extract_json_with(url, json, &LinkFilter::default())
+}
+
+/// 与 [`extract_json`] 相同，按 `links` 过滤相关链接
+pub fn extract_json_with(url: &str, json: &str, links: &LinkFilter) -> Result<DocPage> {
+    extract_json_for(SiteProfile::apple(), url, json, links)
+}
+
+/// 与 [`extract_json_with`] 相同，`profile` 设置了 JSON 字段映射时按映射提取，否则按 DocC render JSON 提取
+pub fn extract_json_for(profile: &SiteProfile, url: &str, json: &str, links: &LinkFilter) -> Result<DocPage> {
+    let data: Value = serde_json::from_str(json)?;
+    let mut page = extract_json_page(profile, url, &data, links)?;
+    page.media = media::from_json(url, &data);
+    page.anchors = anchor::for_content(&page.content, anchor::from_render_json(&data));
+    if changes::is_release_notes_url(url) {
+        page.changes.extend(changes::from_release_notes(url, &data));
+    }
+    Ok(page)
+}
+
+fn extract_json_page(profile: &SiteProfile, url: &str, data: &Value, links: &LinkFilter) -> Result<DocPage> {
+    if let Some(mapping) = &profile.json {
+        return extract_mapped_json(profile, mapping, url, data, links);
+    }
+    if is_hig_url(url) {
+        return extract_hig_json(profile, url, data, links);
+    }
+    if evolution::is_index(data) {
+        return evolution::extract_index(profile, url, data, links);
+    }
+    if tutorial::is_tutorial(data) {
+        return tutorial::extract_tutorial_json(profile, url, data, links);
+    }
+    if changes::is_api_diff(data) {
+        return changes::extract_api_diff(profile, url, data, links);
+    }
+    let references = data.get("references").unwrap_or(&Value::Null);
+
+    let title = data
+        .pointer("/metadata/title")
+        .and_then(Value::as_str)
+        .map(clean_text)
+        .unwrap_or_default();
+
+    let mut blocks = Vec::new();
+    if let Some(abstract_) = data.get("abstract") {
+        blocks.push(inline_text(abstract_, references));
+    }
+    for section in array(data.get("primaryContentSections")) {
+        match section.get("kind").and_then(Value::as_str) {
+            Some("declarations") => {
+                for declaration in array(section.get("declarations")) {
+                    let tokens: String = array(declaration.get("tokens"))
+                        .iter()
+                        .filter_map(|token| token.get("text").and_then(Value::as_str))
+                        .collect();
+                    blocks.push(tokens);
+                }
+            }
+            Some("content") => {
+                for block in array(section.get("content")) {
+                    block_text(block, references, &mut blocks);
+                }
+            }
+            _ => {}
+        }
+    }
+    let rest = RestApi::from_render_json(data).map(Box::new);
+    let sample = SampleCode::from_render_json(url, data).map(Box::new);
+    // 接口定义和示例代码的项目信息写在摘要之后、正文之前
+    let at = blocks.len().min(usize::from(data.get("abstract").is_some()));
+    if let Some(rest) = &rest {
+        blocks.splice(at..at, rest.summary_lines());
+    }
+    if let Some(sample) = &sample {
+        blocks.splice(at..at, sample.summary_lines());
+    }
+    let content = blocks
+        .iter()
+        .map(|block| clean_text(block))
+        .filter(|block| !block.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let base_url = Url::parse(url)?;
+    let sections = reference_sections(data);
+    let mut related_links = LinkCollector::new(profile, url, links)?;
+    if let Some(references) = references.as_object() {
+        for (identifier, reference) in references {
+            let Some(href) = reference.get("url").and_then(Value::as_str) else {
+                continue;
+            };
+            if let Ok(link) = base_url.join(href) {
+                let title = reference.get("title").and_then(Value::as_str).unwrap_or_default();
+                related_links.push(link, title, sections.get(identifier.as_str()).copied());
+            }
+        }
+    }
+
+    Ok(DocPage {
+        title,
+        content,
+        url: url.to_string(),
+        related_links: related_links.finish(),
+        rest,
+        sample,
+        ..Default::default()
+    })
+}
+
+/// 按 [`JsonMapping`] 中的 JSON Pointer 读取标题、正文和链接
+fn extract_mapped_json(
+    profile: &SiteProfile,
+    mapping: &JsonMapping,
+    url: &str,
+    data: &Value,
+    links: &LinkFilter,
+) -> Result<DocPage> {
+    let text = |value: Option<&Value>| match value {
+        Some(Value::String(text)) => clean_text(text),
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .map(clean_text)
+            .filter(|item| !item.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    };
+    let base_url = Url::parse(url)?;
+    let mut related_links = LinkCollector::new(profile, url, links)?;
+    let hrefs = mapping.links.as_deref().and_then(|pointer| data.pointer(pointer));
+    for href in array(hrefs).iter().filter_map(Value::as_str) {
+        if let Ok(link) = base_url.join(href) {
+            related_links.push(link, "", None);
+        }
+    }
+    Ok(DocPage {
+        title: text(data.pointer(&mapping.title)),
+        content: text(data.pointer(&mapping.content)),
+        url: url.to_string(),
+        related_links: related_links.finish(),
+        ..Default::default()
+    })
+}
+
+/// 从 Markdown 原文中提取 `DocPage`：标题为第一个一级标题，正文去掉链接和强调标记，相关链接为文中的链接。
+/// Swift Evolution 提案按 [`evolution::extract_proposal`] 提取
+pub fn extract_markdown_for(profile: &SiteProfile, url: &str, markdown: &str, links: &LinkFilter) -> Result<DocPage> {
+    if evolution::is_proposal(markdown) {
+        return evolution::extract_proposal(profile, url, markdown, links);
+    }
+    let base_url = Url::parse(url)?;
+    let title = markdown
+        .lines()
+        .find_map(|line| line.strip_prefix("# "))
+        .map(|title| clean_text(&markdown_inline(title, false)))
+        .unwrap_or_default();
+    let mut related_links = LinkCollector::new(profile, url, links)?;
+    for (section, label, href) in markdown_links_by_section(markdown) {
+        if let Ok(link) = base_url.join(href) {
+            related_links.push(link, &markdown_inline(label, false), section.as_deref());
+        }
+    }
+    Ok(DocPage {
+        title,
+        content: clean_text(&markdown_inline(markdown, false)),
+        url: url.to_string(),
+        related_links: related_links.finish(),
+        ..Default::default()
+    })
+}
+
+/// Markdown 的链接和图片：`[标签](地址)`、`![替代文字](地址)`
+pub(crate) fn markdown_link_regex() -> &'static Regex {
+    static LINK: OnceLock<Regex> = OnceLock::new();
+    LINK.get_or_init(|| Regex::new(r"!?\[([^\]]*)\]\(([^)\s]+)\)").unwrap())
+}
+
+/// 文本中的 Markdown 链接：`(标签, 地址)`
+pub(crate) fn markdown_links(text: &str) -> Vec<(&str, &str)> {
+    markdown_link_regex()
+        .captures_iter(text)
+        .filter_map(|captures| Some((captures.get(1)?.as_str(), captures.get(2)?.as_str())))
+        .collect()
+}
+
+/// 同 [`markdown_links`]，另外给出每个链接所在小节的标题（之前最近的二级或三级标题）
+pub(crate) fn markdown_links_by_section(markdown: &str) -> Vec<(Option<String>, &str, &str)> {
+    let mut headings = Vec::new();
+    let mut offset = 0;
+    for line in markdown.split_inclusive('\n') {
+        let trimmed = line.trim_end();
+        if let Some(heading) = trimmed.strip_prefix("### ").or_else(|| trimmed.strip_prefix("## ")) {
+            headings.push((offset, clean_text(&markdown_inline(heading, false))));
+        }
+        offset += line.len();
+    }
+    markdown_link_regex()
+        .captures_iter(markdown)
+        .filter_map(|captures| {
+            let start = captures.get(0)?.start();
+            let section = headings.iter().rev().find(|(at, _)| *at <= start).map(|(_, title)| title.clone());
+            Some((section, captures.get(1)?.as_str(), captures.get(2)?.as_str()))
+        })
+        .collect()
+}
+
+/// 去掉行内的 Markdown 标记：链接只保留标签（`with_urls` 时为 `标签 (地址)`），去掉加粗的 `**` / `__`
+pub(crate) fn markdown_inline(text: &str, with_urls: bool) -> String {
+    let replacement = if with_urls { "$1 ($2)" } else { "$1" };
+    markdown_link_regex().replace_all(text, replacement).replace("**", "").replace("__", "")
+}
+
+fn array(value: Option<&Value>) -> &[Value] {
+    value.and_then(Value::as_array).map(Vec::as_slice).unwrap_or_default()
+}
+
+/// 将块级内容（段落、标题、代码、列表等）转换为文本
+pub(crate) fn block_text(block: &Value, references: &Value, blocks: &mut Vec<String>) {
+    match block.get("type").and_then(Value::as_str) {
+        Some("heading") => {
+            if let Some(text) = block.get("text").and_then(Value::as_str) {
+                blocks.push(text.to_string());
+            }
+        }
+        Some("paragraph") => {
+            if let Some(inline) = block.get("inlineContent") {
+                blocks.push(inline_text(inline, references));
+            }
+        }
+        Some("codeListing") => {
+            let code: Vec<&str> = array(block.get("code"))
+                .iter()
+                .filter_map(Value::as_str)
+                .collect();
+            blocks.push(code.join("\n"));
+        }
+        Some("aside") => {
+            for child in array(block.get("content")) {
+                block_text(child, references, blocks);
+            }
+        }
+        Some("unorderedList") | Some("orderedList") => {
+            for item in array(block.get("items")) {
+                for child in array(item.get("content")) {
+                    block_text(child, references, blocks);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 将行内内容数组拼接为文本，引用会替换为被引用文档的标题
+fn inline_text(inline: &Value, references: &Value) -> String {
+    let mut text = String::new();
+    for item in array(Some(inline)) {
+        match item.get("type").and_then(Value::as_str) {
+            Some("text") => text.push_str(item.get("text").and_then(Value::as_str).unwrap_or_default()),
+            Some("codeVoice") => text.push_str(item.get("code").and_then(Value::as_str).unwrap_or_default()),
+            Some("reference") => {
+                let title = item
+                    .get("identifier")
+                    .and_then(Value::as_str)
+                    .and_then(|id| references.get(id))
+                    .and_then(|reference| reference.get("title"))
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                text.push_str(title);
+            }
+            Some("emphasis") | Some("strong") | Some("newTerm") => {
+                if let Some(children) = item.get("inlineContent") {
+                    text.push_str(&inline_text(children, references));
+                }
+            }
+            _ => {}
+        }
+    }
+    text
+}
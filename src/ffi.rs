@@ -0,0 +1,125 @@
+//! C ABI 绑定，编译为 cdylib 后可以从 Swift / C 等语言直接调用爬虫，
+//! 无需另起子进程。头文件见 `include/adoc.h`。
+//!
+//! 所有由本模块返回的字符串都必须通过 [`adoc_string_free`] 释放。
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+
+use crate::blocking::Crawler;
+use crate::crawler::CrawlerConfig;
+use crate::tr;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// `adoc_crawl` 的选项，字段均可省略，默认值与命令行一致
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+struct CrawlOptions {
+    recursive: bool,
+    concurrency: usize,
+    max_retries: u32,
+    timeout_secs: u64,
+}
+
+impl Default for CrawlOptions {
+    fn default() -> Self {
+        Self {
+            recursive: false,
+            concurrency: 5,
+            max_retries: 3,
+            timeout_secs: 30,
+        }
+    }
+}
+
+fn crawl(input: *const c_char, options_json: *const c_char) -> Result<String> {
+    if input.is_null() {
+        return Err(anyhow!(tr!("url 不能为空", "url must not be empty")));
+    }
+    let input = unsafe { CStr::from_ptr(input) }.to_str()?;
+    let options: CrawlOptions = if options_json.is_null() {
+        CrawlOptions::default()
+    } else {
+        let json = unsafe { CStr::from_ptr(options_json) }.to_str()?;
+        if json.trim().is_empty() {
+            CrawlOptions::default()
+        } else {
+            serde_json::from_str(json)?
+        }
+    };
+
+    let config = CrawlerConfig {
+        max_retries: options.max_retries,
+        concurrency: options.concurrency,
+        timeout: Duration::from_secs(options.timeout_secs),
+        ..Default::default()
+    };
+    let mut crawler = Crawler::new(config);
+    let (results, _report) = if input.starts_with("http") {
+        crawler.crawl_url(input, options.recursive)?
+    } else {
+        crawler.search_and_crawl(input, options.recursive)?
+    };
+
+    Ok(serde_json::to_string(&results)?)
+}
+
+fn set_last_error(message: String) {
+    let message = CString::new(message.replace('\0', "")).unwrap_or_default();
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = Some(message));
+}
+
+/// 爬取 `url`（或按关键字搜索），返回 `DocPage` 数组的 JSON 字符串。
+///
+/// `options_json` 可以为 NULL，例如 `{"recursive": true, "concurrency": 4}`。
+/// 失败时返回 NULL，可通过 [`adoc_last_error`] 获取错误信息。
+///
+/// # Safety
+///
+/// `url` 必须是有效的、以 NUL 结尾的 UTF-8 字符串；`options_json` 为 NULL
+/// 或同样有效的字符串。返回值需要用 [`adoc_string_free`] 释放。
+#[no_mangle]
+pub unsafe extern "C" fn adoc_crawl(url: *const c_char, options_json: *const c_char) -> *mut c_char {
+    let result = std::panic::catch_unwind(|| crawl(url, options_json))
+        .unwrap_or_else(|_| Err(anyhow!(tr!("爬取过程中发生 panic", "panic while crawling"))));
+
+    match result.and_then(|json| Ok(CString::new(json)?)) {
+        Ok(json) => json.into_raw(),
+        Err(e) => {
+            set_last_error(format!("{:#}", e));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// 返回当前线程最近一次失败的错误信息，没有错误时返回 NULL。
+///
+/// 返回的字符串需要用 [`adoc_string_free`] 释放。
+#[no_mangle]
+pub extern "C" fn adoc_last_error() -> *mut c_char {
+    LAST_ERROR.with(|slot| {
+        slot.borrow_mut()
+            .take()
+            .map(CString::into_raw)
+            .unwrap_or(std::ptr::null_mut())
+    })
+}
+
+/// 释放由本库返回的字符串。
+///
+/// # Safety
+///
+/// `s` 必须是由本库返回且尚未释放的指针，或 NULL。
+#[no_mangle]
+pub unsafe extern "C" fn adoc_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
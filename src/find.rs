@@ -0,0 +1,100 @@
+//! 标题和符号名的模糊匹配（nucleo 算法），用于快速定位文档；以及搜索无结果时的框架名拼写建议。
+
+use nucleo_matcher::pattern::{CaseMatching, Normalization, Pattern};
+use nucleo_matcher::{Config, Matcher, Utf32Str};
+
+use crate::extract::DocPage;
+
+/// 模糊匹配结果
+#[derive(Debug)]
+pub struct FuzzyMatch {
+    pub score: u32,
+    pub page: DocPage,
+}
+
+/// 在页面标题和符号名（URL 层级的最后一段）中模糊匹配 `pattern`，
+/// 按得分从高到低返回最多 `limit` 个结果
+pub fn fuzzy_find(pages: Vec<DocPage>, pattern: &str, limit: usize) -> Vec<FuzzyMatch> {
+    let mut matcher = Matcher::new(Config::DEFAULT);
+    let pattern = Pattern::parse(pattern, CaseMatching::Ignore, Normalization::Smart);
+    let mut buf = Vec::new();
+
+    let mut matches: Vec<FuzzyMatch> = pages
+        .into_iter()
+        .filter_map(|page| {
+            let symbol = page.hierarchy().pop().unwrap_or_default();
+            let score = [page.title.as_str(), symbol.as_str()]
+                .into_iter()
+                .filter_map(|candidate| pattern.score(Utf32Str::new(candidate, &mut buf), &mut matcher))
+                .max()?;
+            Some(FuzzyMatch { score, page })
+        })
+        .collect();
+
+    // 得分相同时，标题更短（更精确）的排在前面
+    matches.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| a.page.title.len().cmp(&b.page.title.len()))
+    });
+    matches.truncate(limit);
+    matches
+}
+
+/// 常用的 Apple 框架，关键字搜索没有结果时从中给出拼写建议
+pub const FRAMEWORKS: &[&str] = &[
+    "Accelerate", "Accessibility", "ActivityKit", "AppIntents", "AppKit", "ARKit", "AuthenticationServices",
+    "AVFAudio", "AVFoundation", "AVKit", "BackgroundTasks", "CallKit", "CarPlay", "Charts", "CloudKit",
+    "Combine", "Contacts", "CoreAudio", "CoreBluetooth", "CoreData", "CoreFoundation", "CoreGraphics",
+    "CoreHaptics", "CoreImage", "CoreLocation", "CoreML", "CoreMotion", "CoreNFC", "CoreSpotlight", "CoreText",
+    "CryptoKit", "DeviceCheck", "EventKit", "FileProvider", "Foundation", "GameController", "GameKit",
+    "GameplayKit", "HealthKit", "HomeKit", "ImageIO", "LocalAuthentication", "MapKit", "MediaPlayer", "Metal",
+    "MetalKit", "MultipeerConnectivity", "NaturalLanguage", "Network", "NetworkExtension", "Observation",
+    "PassKit", "PencilKit", "PhotoKit", "Photos", "PhotosUI", "QuartzCore", "QuickLook", "RealityKit",
+    "SafariServices", "SceneKit", "ScreenCaptureKit", "Security", "ShazamKit", "SharePlay", "SiriKit",
+    "SpriteKit", "StoreKit", "Swift", "SwiftData", "SwiftUI", "SystemConfiguration", "TipKit",
+    "UniformTypeIdentifiers", "UIKit", "UserNotifications", "Vision", "VisionKit", "WatchConnectivity",
+    "WatchKit", "WeatherKit", "WebKit", "WidgetKit", "XCTest",
+];
+
+/// 与关键字拼写相近（编辑距离较小）或包含关键字的框架名，按相似程度排序，最多 `limit` 个
+pub fn suggest_frameworks(keyword: &str, limit: usize) -> Vec<&'static str> {
+    let keyword: Vec<char> = keyword.trim().to_lowercase().chars().collect();
+    if keyword.is_empty() {
+        return Vec::new();
+    }
+    let max_distance = (keyword.len() / 3).max(1);
+    let mut suggestions: Vec<(usize, &str)> = FRAMEWORKS
+        .iter()
+        .filter_map(|&name| {
+            let candidate: Vec<char> = name.to_lowercase().chars().collect();
+            let distance = edit_distance(&keyword, &candidate);
+            let contains = keyword.len() >= 3 && candidate.windows(keyword.len()).any(|window| window == keyword);
+            (distance <= max_distance || contains).then_some((distance, name))
+        })
+        .collect();
+    suggestions.sort_by_key(|&(distance, name)| (distance, name.len()));
+    suggestions.into_iter().take(limit).map(|(_, name)| name).collect()
+}
+
+/// 编辑距离，相邻字符互换计为一次编辑（optimal string alignment）
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (rows[i - 1][j] + 1).min(rows[i][j - 1] + 1).min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = distance;
+        }
+    }
+    rows[a.len()][b.len()]
+}
@@ -0,0 +1,104 @@
+//! 符号术语表（`--glossary`）。
+//!
+//! 从结果中的符号页面整理出每个符号的标题、类型、一句话简介和链接，按标题排序，
+//! 保存为单独的 JSON 或 CSV 文件（按扩展名），Markdown 输出的末尾同时附上术语表，
+//! 可以当作框架的速查索引。
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+
+use crate::extract::DocPage;
+use crate::tr;
+
+/// 简介的最大字符数
+const ABSTRACT_CHARS: usize = 160;
+
+/// 术语表中的一个符号
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GlossaryEntry {
+    pub title: String,
+    /// 符号类型，见 [`DocPage::symbol_kind`]
+    pub kind: String,
+    /// 一句话简介，取正文中第一个不是声明或平台标记的段落
+    #[serde(rename = "abstract")]
+    pub summary: String,
+    pub url: String,
+}
+
+/// 整理结果中所有符号页面的术语表，按标题排序（不区分大小写）；文章等不是符号的页面不列出
+pub fn build_glossary(pages: &[DocPage]) -> Vec<GlossaryEntry> {
+    let mut entries: Vec<GlossaryEntry> = pages
+        .iter()
+        .filter(|page| page.status.is_none() && !page.title.is_empty())
+        .filter_map(|page| {
+            let kind = page.symbol_kind()?;
+            Some(GlossaryEntry {
+                title: page.title.clone(),
+                kind: kind.to_string(),
+                summary: shorten(page.abstract_line()),
+                url: page.url.clone(),
+            })
+        })
+        .collect();
+    entries.sort_by(|a, b| a.title.to_lowercase().cmp(&b.title.to_lowercase()).then_with(|| a.url.cmp(&b.url)));
+    entries
+}
+
+/// 简介超出 `ABSTRACT_CHARS` 时截断
+fn shorten(line: &str) -> String {
+    if line.chars().count() > ABSTRACT_CHARS {
+        format!("{}…", line.chars().take(ABSTRACT_CHARS).collect::<String>().trim_end())
+    } else {
+        line.to_string()
+    }
+}
+
+/// 保存术语表：扩展名为 `.csv` 时保存为 CSV（含表头），否则保存为 JSON 数组
+pub fn save_glossary(entries: &[GlossaryEntry], path: &Path) -> Result<()> {
+    let is_csv = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("csv"));
+    let content = if is_csv {
+        to_csv(entries)
+    } else {
+        serde_json::to_string_pretty(entries)?
+    };
+    std::fs::write(path, content)
+        .with_context(|| tr!("无法保存术语表: {}", "cannot save glossary: {}", path.display()))
+}
+
+/// CSV 格式：`title,kind,abstract,url`，含逗号、引号或换行的字段加引号
+pub fn to_csv(entries: &[GlossaryEntry]) -> String {
+    let mut csv = String::from("title,kind,abstract,url\n");
+    for entry in entries {
+        let fields = [&entry.title, &entry.kind, &entry.summary, &entry.url];
+        let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Markdown 输出末尾的术语表：每个符号一行，标题链接到文档页面
+pub fn write_markdown(entries: &[GlossaryEntry], out: &mut impl Write) -> Result<()> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+    write!(out, "{}", tr!("## 术语表\n\n", "## Glossary\n\n"))?;
+    write!(out, "{}", tr!("| 符号 | 类型 | 简介 |\n", "| Symbol | Kind | Abstract |\n"))?;
+    writeln!(out, "| --- | --- | --- |")?;
+    for entry in entries {
+        let cell = |text: &str| text.replace('|', "\\|");
+        writeln!(out, "| [{}]({}) | {} | {} |", cell(&entry.title), entry.url, entry.kind, cell(&entry.summary))?;
+    }
+    writeln!(out)?;
+    Ok(())
+}
@@ -0,0 +1,113 @@
+//! gRPC 服务模式，提供 Crawl / Search / GetPage 三个 RPC，
+//! 页面以流的形式逐个返回。协议定义见 `proto/adoc.proto`。
+
+// tonic 的接口要求以 `Status` 作为错误类型
+#![allow(clippy::result_large_err)]
+
+use anyhow::Result;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use tokio_stream::Stream;
+use tonic::{Request, Response, Status};
+use tracing::info;
+
+use crate::crawler::{Crawler, CrawlerConfig, DocPage};
+use crate::tr;
+
+pub mod proto {
+    tonic::include_proto!("adoc.v1");
+}
+
+use proto::doc_crawler_server::{DocCrawler, DocCrawlerServer};
+use proto::{CrawlRequest, GetPageRequest, SearchRequest};
+
+type PageStream = Pin<Box<dyn Stream<Item = Result<proto::DocPage, Status>> + Send>>;
+
+impl From<DocPage> for proto::DocPage {
+    fn from(page: DocPage) -> Self {
+        Self {
+            title: page.title,
+            content: page.content,
+            url: page.url,
+            related_links: page.related_links.into_iter().map(|link| link.url).collect(),
+        }
+    }
+}
+
+fn page_stream(pages: Vec<DocPage>) -> PageStream {
+    Box::pin(tokio_stream::iter(pages.into_iter().map(|page| Ok(page.into()))))
+}
+
+fn crawl_error(e: anyhow::Error) -> Status {
+    Status::unavailable(tr!("爬取失败: {:#}", "crawl failed: {:#}", e))
+}
+
+/// gRPC 服务实现，每个请求使用独立的爬虫实例
+pub struct CrawlService {
+    config: CrawlerConfig,
+}
+
+#[tonic::async_trait]
+impl DocCrawler for CrawlService {
+    type CrawlStream = PageStream;
+    type SearchStream = PageStream;
+
+    async fn crawl(&self, request: Request<CrawlRequest>) -> Result<Response<PageStream>, Status> {
+        let request = request.into_inner();
+        if !request.url.starts_with("http") {
+            return Err(Status::invalid_argument(tr!("url 必须以 http 或 https 开头", "url must start with http or https")));
+        }
+        info!("gRPC Crawl: {}", request.url);
+
+        let mut crawler = Crawler::new(self.config.clone());
+        let (pages, _report) = crawler
+            .crawl_url(&request.url, request.recursive)
+            .await
+            .map_err(crawl_error)?;
+        Ok(Response::new(page_stream(pages)))
+    }
+
+    async fn search(&self, request: Request<SearchRequest>) -> Result<Response<PageStream>, Status> {
+        let request = request.into_inner();
+        if request.keyword.trim().is_empty() {
+            return Err(Status::invalid_argument(tr!("keyword 不能为空", "keyword must not be empty")));
+        }
+        info!("gRPC Search: {}", request.keyword);
+
+        let mut crawler = Crawler::new(self.config.clone());
+        let (pages, _report) = crawler
+            .search_and_crawl(&request.keyword, request.recursive)
+            .await
+            .map_err(crawl_error)?;
+        Ok(Response::new(page_stream(pages)))
+    }
+
+    async fn get_page(&self, request: Request<GetPageRequest>) -> Result<Response<proto::DocPage>, Status> {
+        let request = request.into_inner();
+        if !request.url.starts_with("http") {
+            return Err(Status::invalid_argument(tr!("url 必须以 http 或 https 开头", "url must start with http or https")));
+        }
+        info!("gRPC GetPage: {}", request.url);
+
+        let mut crawler = Crawler::new(self.config.clone());
+        let (pages, _report) = crawler
+            .crawl_url(&request.url, false)
+            .await
+            .map_err(crawl_error)?;
+        pages
+            .into_iter()
+            .next()
+            .map(|page| Response::new(page.into()))
+            .ok_or_else(|| Status::not_found(tr!("未获取到页面: {}", "page not fetched: {}", request.url)))
+    }
+}
+
+/// 启动 gRPC 服务并阻塞直到退出
+pub async fn serve(config: CrawlerConfig, addr: SocketAddr) -> Result<()> {
+    info!("{}", tr!("gRPC 服务已启动: {}", "gRPC server started: {}", addr));
+    tonic::transport::Server::builder()
+        .add_service(DocCrawlerServer::new(CrawlService { config }))
+        .serve(addr)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,16 @@
+//! 缓存、快照、增量导出和已访问集合共用的哈希函数。
+
+use sha2::{Digest, Sha256};
+
+/// 64 位 FNV-1a 的初始值
+pub const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// SHA-256 摘要的十六进制表示
+pub fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data).iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// 以 `seed` 为初始值的 64 位 FNV-1a 哈希，结果与平台和版本无关；`seed` 为 [`FNV_OFFSET`] 时即标准 FNV-1a
+pub fn fnv1a(bytes: &[u8], seed: u64) -> u64 {
+    bytes.iter().fold(seed, |hash, byte| (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3))
+}
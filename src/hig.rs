@@ -0,0 +1,295 @@
+//! 人机界面指南（Human Interface Guidelines，`developer.apple.com/design/human-interface-guidelines`）页面的提取。
+//!
+//! 指南页面的结构与 API 文档不同，以分节标题、指导性文字和大量配图为主，通用的提取逻辑几乎取不到内容。
+//! 这里保留页面的结构：标题写成 `## 标题`，列表项写成 `- 内容`，配图写成 `[Figure: 替代文字] 说明 (图片地址)`，
+//! 并根据 “Platform considerations” 一节在正文开头写出 `Platforms: iOS, macOS, ...`。
+
+use anyhow::Result;
+use regex::Regex;
+use scraper::{ElementRef, Html, Selector};
+use serde_json::Value;
+use std::sync::OnceLock;
+use url::Url;
+
+use crate::extract::{clean_text, links_with_sections, reference_sections, DocPage, LinkCollector, LinkFilter};
+use crate::profile::SiteProfile;
+
+/// 指南页面的路径前缀
+const HIG_PATH: &str = "/design/human-interface-guidelines";
+
+/// 平台的标准顺序，`Platforms:` 行按此排列
+const PLATFORMS: &[&str] = &["iOS", "iPadOS", "macOS", "tvOS", "visionOS", "watchOS"];
+
+/// 是否为人机界面指南的页面
+pub fn is_hig_url(url: &str) -> bool {
+    url.contains("developer.apple.com") && Url::parse(url).is_ok_and(|url| url.path().contains(HIG_PATH))
+}
+
+/// 提取过程中的一个内容块
+enum Block {
+    Heading(usize, String),
+    Text(String),
+    Item(String),
+    Figure { alt: String, caption: String, src: Option<String> },
+}
+
+/// 从指南页面的 HTML 中提取 `DocPage`
+pub fn extract_hig_html(profile: &SiteProfile, url: &str, html: &str, links: &LinkFilter) -> Result<DocPage> {
+    static MAIN: OnceLock<Selector> = OnceLock::new();
+    let main = MAIN.get_or_init(|| Selector::parse("main, article, body").unwrap());
+    let document = Html::parse_document(html);
+    let base_url = Url::parse(url)?;
+
+    let mut title = String::new();
+    let mut blocks = Vec::new();
+    if let Some(root) = document.select(main).next() {
+        walk(root, &base_url, &mut title, &mut blocks);
+    }
+
+    let mut related_links = LinkCollector::new(profile, url, links)?;
+    for (el, section) in links_with_sections(&document, &profile.selectors.link) {
+        if let Some(link) = el.value().attr("href").and_then(|href| base_url.join(href).ok()) {
+            related_links.push(link, &el.text().collect::<String>(), section.as_deref());
+        }
+    }
+
+    Ok(DocPage {
+        title,
+        content: render(&blocks),
+        url: url.to_string(),
+        related_links: related_links.finish(),
+        ..Default::default()
+    })
+}
+
+/// 按文档顺序遍历元素，收集标题、段落、列表项和配图
+fn walk(element: ElementRef, base_url: &Url, title: &mut String, blocks: &mut Vec<Block>) {
+    let text = |el: ElementRef| clean_text(&el.text().collect::<String>());
+    for child in element.children().filter_map(ElementRef::wrap) {
+        match child.value().name() {
+            "h1" => {
+                if title.is_empty() {
+                    *title = text(child);
+                }
+            }
+            name @ ("h2" | "h3" | "h4") => {
+                let level = name[1..].parse().unwrap_or(2);
+                blocks.push(Block::Heading(level, text(child)));
+            }
+            "p" | "pre" => blocks.push(Block::Text(text(child))),
+            "li" => blocks.push(Block::Item(text(child))),
+            "figure" => {
+                static IMG: OnceLock<Selector> = OnceLock::new();
+                static CAPTION: OnceLock<Selector> = OnceLock::new();
+                let img = child.select(IMG.get_or_init(|| Selector::parse("img").unwrap())).next();
+                let caption = child
+                    .select(CAPTION.get_or_init(|| Selector::parse("figcaption").unwrap()))
+                    .next()
+                    .map(text)
+                    .unwrap_or_default();
+                blocks.push(figure(img, caption, base_url));
+            }
+            // 没有替代文字的图片通常是装饰性图标
+            "img" if child.value().attr("alt").is_some_and(|alt| !alt.trim().is_empty()) => {
+                blocks.push(figure(Some(child), String::new(), base_url))
+            }
+            "script" | "style" | "noscript" | "nav" | "header" | "footer" | "svg" => {}
+            _ => walk(child, base_url, title, blocks),
+        }
+    }
+}
+
+fn figure(img: Option<ElementRef>, caption: String, base_url: &Url) -> Block {
+    let alt = img.and_then(|img| img.value().attr("alt")).map(clean_text).unwrap_or_default();
+    let src = img
+        .and_then(|img| img.value().attr("src"))
+        .and_then(|src| base_url.join(src).ok())
+        .map(String::from);
+    Block::Figure { alt, caption, src }
+}
+
+/// 从指南页面的 DocC JSON 数据中提取 `DocPage`
+pub fn extract_hig_json(profile: &SiteProfile, url: &str, data: &Value, links: &LinkFilter) -> Result<DocPage> {
+    let references = data.get("references").unwrap_or(&Value::Null);
+    let title = data.pointer("/metadata/title").and_then(Value::as_str).map(clean_text).unwrap_or_default();
+
+    let mut blocks = Vec::new();
+    if let Some(abstract_) = data.get("abstract") {
+        blocks.push(Block::Text(inline(abstract_, references)));
+    }
+    for section in array(data.get("primaryContentSections")) {
+        for block in array(section.get("content")) {
+            json_block(block, references, &mut blocks);
+        }
+    }
+
+    let base_url = Url::parse(url)?;
+    let sections = reference_sections(data);
+    let mut related_links = LinkCollector::new(profile, url, links)?;
+    for (identifier, reference) in references.as_object().into_iter().flatten() {
+        // 图片的引用是图片地址，不是相关页面
+        if reference.get("type").and_then(Value::as_str) == Some("image") {
+            continue;
+        }
+        let Some(link) = reference.get("url").and_then(Value::as_str).and_then(|href| base_url.join(href).ok())
+        else {
+            continue;
+        };
+        let title = reference.get("title").and_then(Value::as_str).unwrap_or_default();
+        related_links.push(link, title, sections.get(identifier.as_str()).copied());
+    }
+
+    Ok(DocPage {
+        title,
+        content: render(&blocks),
+        url: url.to_string(),
+        related_links: related_links.finish(),
+        ..Default::default()
+    })
+}
+
+fn json_block(block: &Value, references: &Value, blocks: &mut Vec<Block>) {
+    match block.get("type").and_then(Value::as_str) {
+        Some("heading") => {
+            let level = block.get("level").and_then(Value::as_u64).unwrap_or(2) as usize;
+            let text = block.get("text").and_then(Value::as_str).map(clean_text).unwrap_or_default();
+            blocks.push(Block::Heading(level, text));
+        }
+        Some("paragraph") | Some("small") => {
+            blocks.push(Block::Text(inline(block.get("inlineContent").unwrap_or(&Value::Null), references)));
+        }
+        Some("unorderedList") | Some("orderedList") => {
+            for item in array(block.get("items")) {
+                let text: Vec<String> = array(item.get("content"))
+                    .iter()
+                    .filter_map(|child| child.get("inlineContent"))
+                    .map(|content| inline(content, references))
+                    .collect();
+                blocks.push(Block::Item(text.join(" ")));
+            }
+        }
+        Some("aside") => {
+            for child in array(block.get("content")) {
+                json_block(child, references, blocks);
+            }
+        }
+        // 指南常用多栏布局和选项卡并排展示配图
+        Some("row") => {
+            for column in array(block.get("columns")) {
+                for child in array(column.get("content")) {
+                    json_block(child, references, blocks);
+                }
+            }
+        }
+        Some("tabNavigator") => {
+            for tab in array(block.get("tabs")) {
+                for child in array(tab.get("content")) {
+                    json_block(child, references, blocks);
+                }
+            }
+        }
+        _ => {}
+    }
+    // 图片在段落的行内内容中或作为独立的块出现
+    let images = std::iter::once(block).chain(array(block.get("inlineContent")));
+    for image in images.filter(|item| item.get("type").and_then(Value::as_str) == Some("image")) {
+        let reference = image.get("identifier").and_then(Value::as_str).and_then(|id| references.get(id));
+        let caption = image
+            .pointer("/metadata/abstract")
+            .map(|abstract_| inline(abstract_, references))
+            .unwrap_or_default();
+        blocks.push(Block::Figure {
+            alt: reference.and_then(|r| r.get("alt")).and_then(Value::as_str).map(clean_text).unwrap_or_default(),
+            caption,
+            src: reference
+                .and_then(|r| r.pointer("/variants/0/url"))
+                .and_then(Value::as_str)
+                .map(str::to_string),
+        });
+    }
+}
+
+/// 行内内容的文本，引用替换为被引用页面的标题，图片单独作为配图记录
+fn inline(content: &Value, references: &Value) -> String {
+    let mut text = String::new();
+    for item in array(Some(content)) {
+        match item.get("type").and_then(Value::as_str) {
+            Some("text") => text.push_str(item.get("text").and_then(Value::as_str).unwrap_or_default()),
+            Some("codeVoice") => text.push_str(item.get("code").and_then(Value::as_str).unwrap_or_default()),
+            Some("reference") => {
+                let title = item
+                    .get("identifier")
+                    .and_then(Value::as_str)
+                    .and_then(|id| references.get(id))
+                    .and_then(|reference| reference.get("title"))
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                text.push_str(title);
+            }
+            Some("emphasis") | Some("strong") | Some("newTerm") => {
+                if let Some(children) = item.get("inlineContent") {
+                    text.push_str(&inline(children, references));
+                }
+            }
+            _ => {}
+        }
+    }
+    clean_text(&text)
+}
+
+fn array(value: Option<&Value>) -> &[Value] {
+    value.and_then(Value::as_array).map(Vec::as_slice).unwrap_or_default()
+}
+
+/// “Platform considerations” 一节中提到的平台，包括没有额外注意事项的平台
+fn platforms(blocks: &[Block]) -> Vec<&'static str> {
+    static PLATFORM: OnceLock<Regex> = OnceLock::new();
+    let regex = PLATFORM.get_or_init(|| Regex::new(r"\b(iOS|iPadOS|macOS|tvOS|visionOS|watchOS)\b").unwrap());
+    let Some(start) = blocks.iter().position(|block| {
+        matches!(block, Block::Heading(_, text) if text.eq_ignore_ascii_case("Platform considerations"))
+    }) else {
+        return Vec::new();
+    };
+    let Block::Heading(level, _) = blocks[start] else {
+        return Vec::new();
+    };
+    let mut found = Vec::new();
+    for block in &blocks[start + 1..] {
+        let text = match block {
+            Block::Heading(next, _) if *next <= level => break,
+            Block::Heading(_, text) | Block::Text(text) | Block::Item(text) => text,
+            Block::Figure { .. } => continue,
+        };
+        found.extend(regex.find_iter(text).map(|platform| platform.as_str()));
+    }
+    PLATFORMS.iter().copied().filter(|platform| found.contains(platform)).collect()
+}
+
+fn render(blocks: &[Block]) -> String {
+    let mut lines = Vec::new();
+    let platforms = platforms(blocks);
+    if !platforms.is_empty() {
+        lines.push(format!("Platforms: {}", platforms.join(", ")));
+    }
+    for block in blocks {
+        let line = match block {
+            Block::Heading(level, text) if !text.is_empty() => format!("{} {}", "#".repeat(*level), text),
+            Block::Text(text) if !text.is_empty() => text.clone(),
+            Block::Item(text) if !text.is_empty() => format!("- {}", text),
+            Block::Figure { alt, caption, src } => {
+                let mut line = format!("[Figure: {}]", alt);
+                if !caption.is_empty() {
+                    line.push(' ');
+                    line.push_str(caption);
+                }
+                if let Some(src) = src {
+                    line.push_str(&format!(" ({})", src));
+                }
+                line
+            }
+            _ => continue,
+        };
+        lines.push(line);
+    }
+    lines.join("\n")
+}
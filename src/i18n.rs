@@ -0,0 +1,60 @@
+//! 命令行提示、日志和输出模板的中英文切换。
+//!
+//! 语言是进程级设置，库默认使用中文；命令行启动时根据 `--lang` 或 `LANG` 调用
+//! [`set_lang`]。需要翻译的文本用 [`tr!`](crate::tr) 同时写出两种语言：
+//!
+//! ```
+//! let count = 3;
+//! let message = adoc::tr!("共获取 {} 个页面", "Fetched {} pages", count);
+//! assert_eq!(message, "共获取 3 个页面");
+//! ```
+
+use clap::ValueEnum;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "lowercase")]
+pub enum Lang {
+    Zh,
+    En,
+}
+
+static LANG: AtomicU8 = AtomicU8::new(Lang::Zh as u8);
+
+pub fn set_lang(lang: Lang) {
+    LANG.store(lang as u8, Ordering::Relaxed);
+}
+
+pub fn lang() -> Lang {
+    if LANG.load(Ordering::Relaxed) == Lang::En as u8 {
+        Lang::En
+    } else {
+        Lang::Zh
+    }
+}
+
+/// 根据 `LC_ALL` / `LC_MESSAGES` / `LANG` 判断语言，中文环境用中文，其他用英文
+pub fn lang_from_env() -> Option<Lang> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .map(|value| {
+            if value.to_lowercase().starts_with("zh") {
+                Lang::Zh
+            } else {
+                Lang::En
+            }
+        })
+}
+
+/// 按当前语言选择文本并格式化：`tr!("中文 {}", "English {}", arg)`
+#[macro_export]
+macro_rules! tr {
+    ($zh:literal, $en:literal $(, $arg:expr)* $(,)?) => {
+        match $crate::i18n::lang() {
+            $crate::i18n::Lang::Zh => format!($zh $(, $arg)*),
+            $crate::i18n::Lang::En => format!($en $(, $arg)*),
+        }
+    };
+}
@@ -0,0 +1,193 @@
+//! 基于 tantivy 的本地全文索引。
+//!
+//! `adoc index` 将爬取结果写入索引目录，`adoc query` 在索引中搜索，
+//! 避免在几百 MB 的 JSON 文件里 grep。
+
+use anyhow::{bail, Context, Result};
+use std::path::Path;
+use tantivy::collector::TopDocs;
+use tantivy::query::{AllQuery, QueryParser};
+use tantivy::schema::{Field, Schema, Value, STORED, STRING, TEXT};
+use tantivy::{doc, Index, IndexReader, IndexWriter, ReloadPolicy, TantivyDocument};
+
+use crate::extract::{DocPage, RelatedLink};
+use crate::tr;
+
+/// 索引写入时使用的内存预算
+const WRITER_MEMORY: usize = 50_000_000;
+
+struct Fields {
+    title: Field,
+    content: Field,
+    url: Field,
+    hierarchy: Field,
+    related_links: Field,
+    /// 加入标签之前建立的索引没有这个字段
+    tags: Option<Field>,
+}
+
+fn schema() -> (Schema, Fields) {
+    let mut builder = Schema::builder();
+    let fields = Fields {
+        title: builder.add_text_field("title", TEXT | STORED),
+        // 声明（declarations）作为正文的一部分被提取，随 content 一起索引
+        content: builder.add_text_field("content", TEXT | STORED),
+        url: builder.add_text_field("url", STRING | STORED),
+        hierarchy: builder.add_text_field("hierarchy", TEXT | STORED),
+        related_links: builder.add_text_field("related_links", STORED),
+        tags: Some(builder.add_text_field("tags", TEXT | STORED)),
+    };
+    (builder.build(), fields)
+}
+
+fn fields(schema: &Schema) -> Result<Fields> {
+    Ok(Fields {
+        title: schema.get_field("title")?,
+        content: schema.get_field("content")?,
+        url: schema.get_field("url")?,
+        hierarchy: schema.get_field("hierarchy")?,
+        related_links: schema.get_field("related_links")?,
+        tags: schema.get_field("tags").ok(),
+    })
+}
+
+/// 搜索命中的页面及其相关度得分
+#[derive(Debug)]
+pub struct SearchHit {
+    pub score: f32,
+    pub page: DocPage,
+}
+
+/// 从爬取结果构建索引，已存在的索引会被覆盖
+pub fn build_index(pages: &[DocPage], dir: &Path) -> Result<()> {
+    if dir.join("meta.json").exists() {
+        std::fs::remove_dir_all(dir)
+            .with_context(|| tr!("无法清理旧索引: {}", "cannot clear old index: {}", dir.display()))?;
+    }
+    std::fs::create_dir_all(dir)?;
+
+    let (schema, fields) = schema();
+    let index = Index::create_in_dir(dir, schema)
+        .with_context(|| tr!("无法创建索引: {}", "cannot create index: {}", dir.display()))?;
+    let mut writer: IndexWriter = index.writer(WRITER_MEMORY)?;
+
+    for page in pages {
+        let mut document = doc!(
+            fields.title => page.title.as_str(),
+            fields.content => page.content.as_str(),
+            fields.url => page.url.as_str(),
+            fields.hierarchy => page.hierarchy().join(" / "),
+        );
+        for link in &page.related_links {
+            document.add_text(fields.related_links, serde_json::to_string(link)?);
+        }
+        if let Some(field) = fields.tags {
+            for tag in &page.tags {
+                document.add_text(field, tag);
+            }
+        }
+        writer.add_document(document)?;
+    }
+
+    writer.commit()?;
+    Ok(())
+}
+
+fn open(dir: &Path) -> Result<(Index, Fields)> {
+    let index = Index::open_in_dir(dir)
+        .with_context(|| {
+            tr!("无法打开索引: {}，请先运行 adoc index", "cannot open index: {}, run adoc index first", dir.display())
+        })?;
+    let fields = fields(&index.schema())?;
+    Ok((index, fields))
+}
+
+/// 命令行只做一次性查询，不需要监听索引变化
+fn reader(index: &Index) -> Result<IndexReader> {
+    Ok(index.reader_builder().reload_policy(ReloadPolicy::Manual).try_into()?)
+}
+
+fn to_page(document: &TantivyDocument, fields: &Fields) -> DocPage {
+    let text = |field: Field| {
+        document
+            .get_first(field)
+            .and_then(|value| value.as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    DocPage {
+        title: text(fields.title),
+        content: text(fields.content),
+        url: text(fields.url),
+        related_links: document
+            .get_all(fields.related_links)
+            .filter_map(|value| value.as_str())
+            .map(|link| serde_json::from_str(link).unwrap_or_else(|_| RelatedLink::from(link.to_string())))
+            .collect(),
+        tags: fields
+            .tags
+            .map(|field| document.get_all(field).filter_map(|value| value.as_str()).map(str::to_string).collect())
+            .unwrap_or_default(),
+        ..Default::default()
+    }
+}
+
+/// 在索引中搜索，标题命中的权重高于正文
+pub fn search(dir: &Path, query: &str, limit: usize) -> Result<Vec<SearchHit>> {
+    search_tagged(dir, query, &[], limit)
+}
+
+/// 与 [`search`] 相同，只返回带有所有给定标签的页面
+pub fn search_tagged(dir: &Path, query: &str, tags: &[String], limit: usize) -> Result<Vec<SearchHit>> {
+    let (index, fields) = open(dir)?;
+
+    let mut default_fields = vec![fields.title, fields.content, fields.hierarchy];
+    default_fields.extend(fields.tags);
+    let mut parser = QueryParser::for_index(&index, default_fields);
+    parser.set_field_boost(fields.title, 3.0);
+    let query = if tags.is_empty() {
+        query.to_string()
+    } else {
+        if fields.tags.is_none() {
+            bail!(tr!(
+                "索引中没有标签，请用 --tags 生成的结果重新运行 adoc index",
+                "the index has no tags, rebuild it with adoc index from results crawled with --tags"
+            ));
+        }
+        let required: Vec<String> = tags.iter().map(|tag| format!("+tags:\"{}\"", tag.replace('"', ""))).collect();
+        format!("+({}) {}", query, required.join(" "))
+    };
+    let (query, _errors) = parser.parse_query_lenient(&query);
+
+    let searcher = reader(&index)?.searcher();
+    let top_docs = searcher.search(&query, &TopDocs::with_limit(limit))?;
+
+    let mut hits = Vec::with_capacity(top_docs.len());
+    for (score, address) in top_docs {
+        let document: TantivyDocument = searcher.doc(address)?;
+        hits.push(SearchHit {
+            score,
+            page: to_page(&document, &fields),
+        });
+    }
+    Ok(hits)
+}
+
+/// 读取索引中的所有页面
+pub fn load_all(dir: &Path) -> Result<Vec<DocPage>> {
+    let (index, fields) = open(dir)?;
+    let searcher = reader(&index)?.searcher();
+    let total = searcher.num_docs() as usize;
+    if total == 0 {
+        return Ok(Vec::new());
+    }
+
+    let top_docs = searcher.search(&AllQuery, &TopDocs::with_limit(total))?;
+    let mut pages = Vec::with_capacity(total);
+    for (_, address) in top_docs {
+        let document: TantivyDocument = searcher.doc(address)?;
+        pages.push(to_page(&document, &fields));
+    }
+    Ok(pages)
+}
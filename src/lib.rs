@@ -1,2 +1,83 @@
+pub mod anchor;
+pub mod archive;
+pub mod arrange;
+#[cfg(feature = "crawler")]
+pub mod blocking;
+#[cfg(feature = "crawler")]
+pub mod breaker;
+#[cfg(feature = "crawler")]
+pub mod cache;
+pub mod changes;
+pub mod charset;
+pub mod chunk;
+#[cfg(feature = "crawler")]
+pub mod concurrency;
+#[cfg(feature = "crawler")]
+pub mod config;
+#[cfg(feature = "crawler")]
 pub mod crawler;
-pub mod output; 
+#[cfg(feature = "dashboard")]
+pub mod dashboard;
+#[cfg(feature = "crawler")]
+pub mod delta;
+pub mod diff;
+#[cfg(feature = "redis")]
+pub mod distributed;
+#[cfg(feature = "crawler")]
+pub mod dns;
+pub mod docc;
+#[cfg(feature = "embed")]
+pub mod embed;
+pub mod evolution;
+pub mod extract;
+#[cfg(feature = "crawler")]
+pub mod ffi;
+pub mod find;
+pub mod glossary;
+pub mod hash;
+pub mod hig;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub mod i18n;
+#[cfg(feature = "index")]
+pub mod index;
+pub mod media;
+pub mod merge;
+#[cfg(feature = "crawler")]
+pub mod metrics;
+#[cfg(feature = "crawler")]
+pub mod notify;
+pub mod output;
+pub mod profile;
+#[cfg(feature = "crawler")]
+pub mod progress;
+pub mod query;
+pub mod raw;
+pub mod report;
+pub mod rest;
+pub mod sample;
+pub mod schema;
+#[cfg(feature = "serve")]
+pub mod serve;
+#[cfg(feature = "crawler")]
+pub mod sink;
+#[cfg(feature = "crawler")]
+pub mod spill;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+#[cfg(feature = "crawler")]
+pub mod snapshot;
+pub mod stats;
+#[cfg(feature = "summarize")]
+pub mod summarize;
+pub mod tags;
+pub mod tokens;
+#[cfg(feature = "crawler")]
+pub mod translate;
+pub mod tutorial;
+#[cfg(feature = "crawler")]
+pub mod update;
+#[cfg(feature = "crawler")]
+pub mod verify;
+pub mod visited;
+pub mod wwdc;
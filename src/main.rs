@@ -1,27 +1,85 @@
-mod crawler;
-mod output;
+use anyhow::{Context, Result};
+use clap::builder::{EnumValueParser, PossibleValue, PossibleValuesParser, RangedU64ValueParser, TypedValueParser};
+use clap::error::ErrorKind;
+use clap::parser::ValueSource;
+use clap_complete::Shell;
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+use std::any::TypeId;
+use std::ffi::OsStr;
+use std::marker::PhantomData;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::io::{IsTerminal, Write};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use adoc::config::{parse_duration, parse_size, FileConfig, LogFormat};
+use adoc::dns::{parse_dns, Dns};
+use adoc::crawler::{
+    failed_page, CrawlReport, Crawler, CrawlerConfig, DistributedVisited, DocPage, HttpVersion, IpFamily,
+    NoSearchResults,
+};
+use adoc::report::load_failed_urls;
+use adoc::notify::{notify, WebhookFormat};
+use adoc::profile::{DocSource, SiteProfile};
+use adoc::progress::{LogWriter, ProgressEvents, ProgressFormat};
+use adoc::output::{
+    load_results, print_pages, print_pages_grouped, save_pages, save_pages_grouped, save_results, print_results,
+    OutputFormat, PageSource,
+};
+use adoc::arrange::{arrange, GroupBy, SortKey};
+use adoc::cache::ResponseCache;
+use adoc::verify::verify_mirror;
+use adoc::chunk::Chunk;
+use adoc::delta::{delta_pages, save_delta};
+use adoc::diff::{diff_pages, DiffFormat};
+use adoc::extract::{ContentLimit, LinkFilter};
+use adoc::find::fuzzy_find;
+use adoc::glossary::{build_glossary, save_glossary};
+use adoc::i18n::{lang, lang_from_env, set_lang, Lang};
+use adoc::tr;
+use adoc::merge::merge_results;
+use adoc::metrics::{serve_metrics, Metrics};
+use adoc::query::{search_pages, Matcher};
+use adoc::raw::RawEncoding;
+use adoc::tags::has_tags;
+use adoc::tokens::TokenEncoding;
+use adoc::translate::{PageTranslator, Translator};
+use adoc::sqlite::is_sqlite_path;
+use adoc::snapshot::GitSnapshot;
+use adoc::spill::SpillBuffer;
+use adoc::stats::{compute_stats, StatsFormat};
+use adoc::visited::{parse_count, SharedVisited, VisitedKind};
+use adoc::sink::{
+    ConfluenceConfig, ConfluenceSink, ElasticsearchConfig, ElasticsearchSink, JsonFileSink, MediaDownloadSink, MeilisearchConfig, MeilisearchSink, NotionConfig,
+    NotionSink, PageSink, SinkKind, WebhookConfig, WebhookSink, DEFAULT_NOTION_URL,
+};
+use tracing::{info, warn};
 
-use anyhow::Result;
-use clap::Parser;
-use std::path::PathBuf;
-use crate::crawler::{Crawler, CrawlerConfig};
-use crate::output::{save_results, print_results, OutputFormat};
-use tracing::{info};
-
-fn setup_logging(level: &str) {
+fn setup_logging(level: &str, format: LogFormat) {
     use tracing_subscriber::{fmt, EnvFilter};
     use time::macros::format_description;
 
     let timer_format = format_description!("[year]-[month]-[day] [hour]:[minute]:[second]");
     
-    fmt()
+    let builder = fmt()
         .with_env_filter(EnvFilter::new(level))
         .with_timer(fmt::time::UtcTime::new(timer_format))
+        .with_writer(|| LogWriter)
         .with_target(false)
         .with_thread_ids(true)
         .with_line_number(true)
-        .with_file(true)
-        .init();
+        .with_file(true);
+    match format {
+        LogFormat::Text => builder.init(),
+        // url、elapsed 等字段与 message 平铺在同一层，便于 Loki 等系统直接查询
+        LogFormat::Json => builder
+            .json()
+            .flatten_event(true)
+            .with_current_span(false)
+            .with_span_list(false)
+            .init(),
+    }
 }
 
 #[derive(Parser, Debug)]
@@ -36,94 +94,2228 @@ fn setup_logging(level: &str) {
     adoc -i \"SwiftUI\" -o swiftui.json            # 搜索 SwiftUI 文档并保存为 JSON
     adoc -i \"UIKit\" -r -o uikit.txt              # 递归爬取 UIKit 相关文档
     adoc -i \"SwiftUI\" --format markdown -o doc.md # 导出为 Markdown 格式
-    adoc -i \"SwiftUI\" -f pretty -o doc.json      # 导出为美化的 JSON"
+    adoc -i \"SwiftUI\" -f pretty -o doc.json      # 导出为美化的 JSON
+    adoc index --from doc.json --out index/       # 为爬取结果构建全文索引
+    adoc query \"scrollTargetBehavior\"            # 在本地索引中搜索
+    adoc query --from doc.json \"NavigationStack\"  # 在已保存的结果中搜索
+    adoc find scrollview --from doc.json          # 模糊查找标题或符号名
+    adoc diff old.json new.json -f markdown       # 比较两次爬取结果
+    adoc diff old.json new.json --webhook $SLACK_URL --webhook-format slack
+    adoc stats --from doc.json                    # 统计爬取结果
+    adoc convert doc.json -f markdown -o doc.md   # 转换已保存结果的格式
+    adoc merge a.json b.json c.json -o all.json   # 合并多个结果并去重
+    adoc serve --from doc.json --port 8080        # 启动本地文档浏览服务
+    adoc --config swiftui.toml                    # 从配置文件读取参数
+    adoc --profile swiftui-nightly                # 使用 adoc.toml 中的命名配置
+    adoc completions zsh > _adoc                  # 生成命令行补全脚本
+
+退出码:
+    0 成功, 1 其他错误, 2 部分页面失败, 3 网络错误, 4 没有提取到内容, 5 输入或配置无效",
+    subcommand_negates_reqs = true,
+    args_conflicts_with_subcommands = true
 )]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Apple 开发者文档 URL 或关键字
     /// 例如: https://developer.apple.com/documentation/swift 或 "SwiftUI"
-    #[arg(short, long, help_heading = "输入选项")]
-    input: String,
+    #[arg(short, long, env = "ADOC_INPUT", help_heading = "输入选项")]
+    input: Option<String>,
+
+    /// 内置的文档来源：docs 按 --input 爬取文档站点；swift-evolution 爬取所有 Swift Evolution 提案，
+    /// 包括状态、评审日期和实现链接；wwdc 爬取 WWDC 等开发者视频的简介、字幕和引用的文档。后两者不需要 --input
+    #[arg(long, env = "ADOC_SOURCE", value_enum, default_value = "docs", help_heading = "输入选项")]
+    source: DocSource,
+
+    /// 配置文件路径，默认读取当前目录的 adoc.toml（如果存在）
+    /// 命令行中给出的参数优先于配置文件
+    #[arg(long, env = "ADOC_CONFIG", global = true, help_heading = "输入选项")]
+    config: Option<PathBuf>,
+
+    /// 使用配置文件中的 [profile.<名称>] 配置
+    #[arg(long, env = "ADOC_PROFILE", global = true, help_heading = "输入选项")]
+    profile: Option<String>,
 
     /// 是否递归爬取相关页面
     /// 启用此选项将爬取文档中引用的其他页面
-    #[arg(short, long, default_value = "false", help_heading = "爬取选项")]
+    #[arg(short, long, env = "ADOC_RECURSIVE", default_value = "false", help_heading = "爬取选项")]
     recursive: bool,
 
+    /// 重新爬取 --failures 输出的失败页面列表中的 URL
+    /// 通常配合 --merge-into 把成功的页面合并回原来的结果
+    #[arg(long, env = "ADOC_RETRY_FAILED", conflicts_with = "input", help_heading = "输入选项")]
+    retry_failed: Option<PathBuf>,
+
+    /// 关键字搜索时直接爬取第一个搜索结果，不再交互选择
+    #[arg(long, env = "ADOC_FIRST", conflicts_with = "top", help_heading = "输入选项")]
+    first: bool,
+
+    /// 关键字搜索时爬取前 N 个搜索结果，不再交互选择
+    #[arg(long, env = "ADOC_TOP", value_name = "N", help_heading = "输入选项")]
+    top: Option<usize>,
+
+    /// 只列出将要爬取的 URL，不下载页面内容
+    /// 递归模式下仍会请求起始页面以发现链接
+    #[arg(long, env = "ADOC_DRY_RUN", help_heading = "爬取选项")]
+    dry_run: bool,
+
     /// 并发任务数
     /// 控制同时进行的爬取任务数量
-    #[arg(short = 'c', long, default_value = "5", help_heading = "爬取选项")]
+    #[arg(
+        short = 'c',
+        long,
+        env = "ADOC_CONCURRENCY",
+        default_value = "5",
+        value_parser = RangedU64ValueParser::<usize>::new().range(1..),
+        global = true,
+        help_heading = "爬取选项"
+    )]
     concurrency: usize,
 
+    /// 根据延迟和错误率自动调整并发数：请求顺利时逐步提高，遇到超时、429、503 时减半
+    /// -c 作为初始并发数
+    #[arg(long, env = "ADOC_ADAPTIVE_CONCURRENCY", help_heading = "爬取选项")]
+    adaptive_concurrency: bool,
+
+    /// 自适应并发时的最大并发数
+    #[arg(
+        long,
+        env = "ADOC_MAX_CONCURRENCY",
+        default_value = "64",
+        value_parser = RangedU64ValueParser::<usize>::new().range(1..),
+        help_heading = "爬取选项"
+    )]
+    max_concurrency: usize,
+
     /// 输出文件路径
     /// 支持 .json 或 .txt 格式，例如: output.json 或 docs.txt
-    #[arg(short, long, help_heading = "输出选项")]
+    #[arg(short, long, env = "ADOC_OUTPUT", help_heading = "输出选项")]
     output: Option<PathBuf>,
 
     /// 输出格式
-    /// 可选值: json, pretty, txt, markdown
+    /// 可选值: json, pretty, txt, markdown, es-bulk, sqlite, alfred, raycast
     #[arg(
         short = 'f',
         long = "format",
+        env = "ADOC_OUTPUT_FORMAT",
         value_enum,
         default_value_t = OutputFormat::Json,
         help_heading = "输出选项"
     )]
     format: OutputFormat,
 
+    /// 边爬取边写入 -o 指定的文件，页面不在内存中累积，适合大规模爬取
+    /// 仅支持 json 和 pretty 格式
+    #[arg(
+        long,
+        env = "ADOC_STREAM",
+        requires = "output",
+        conflicts_with_all = ["chunk_size", "merge_into"],
+        help_heading = "输出选项"
+    )]
+    stream: bool,
+
+    /// 为失效的页面（404 / 410）输出只有 url 和 status 的占位记录，便于下游得知链接已失效
+    /// 仅支持 json 和 pretty 格式
+    #[arg(long, env = "ADOC_TOMBSTONES", conflicts_with = "chunk_size", help_heading = "输出选项")]
+    tombstones: bool,
+
+    /// 爬取统计报告输出路径
+    /// 以 JSON 格式保存成功、跳过、失败页面数及耗时等统计
+    #[arg(long, env = "ADOC_REPORT", help_heading = "输出选项")]
+    report: Option<PathBuf>,
+
+    /// 把爬取结果合并进已有的结果文件（JSON 或 SQLite），同一 URL 以本次爬取为准
+    #[arg(long, env = "ADOC_MERGE_INTO", conflicts_with_all = ["output", "chunk_size"], help_heading = "输出选项")]
+    merge_into: Option<PathBuf>,
+
+    /// 增量爬取时把新增和内容变化的页面（标注 change: added / modified）另外写入该文件，
+    /// 基线为 --merge-into 指定的已有结果文件，格式与 JSON 输出相同
+    #[arg(
+        long,
+        env = "ADOC_DELTA_OUTPUT",
+        requires = "merge_into",
+        conflicts_with = "memory_budget",
+        help_heading = "输出选项"
+    )]
+    delta_output: Option<PathBuf>,
+
+    /// 把每个页面写成 git 仓库中的 Markdown 文件并提交，提交信息汇总新增、修改、删除的页面
+    /// 目录不存在或还不是 git 仓库时自动初始化；起始页面层级下本次没有出现的页面会被删除
+    #[arg(
+        long,
+        env = "ADOC_GIT_REPO",
+        conflicts_with_all = ["output", "merge_into", "stream", "chunk_size"],
+        help_heading = "输出选项"
+    )]
+    git_repo: Option<PathBuf>,
+
+    /// 失败页面列表输出路径
+    /// 每行一个重试后仍然失败的 URL，附带失败类型（timeout、connect、status、extract、other）、状态码和原因，以制表符分隔
+    #[arg(long, env = "ADOC_FAILURES", help_heading = "输出选项")]
+    failures: Option<PathBuf>,
+
+    /// 把页面中的视频和音频文件下载到该目录，已存在的文件不重复下载
+    /// 媒体信息总是记录在输出的 media 字段中，HLS 播放列表（.m3u8）不下载
+    #[arg(long, env = "ADOC_DOWNLOAD_MEDIA", value_name = "DIR", help_heading = "输出选项")]
+    download_media: Option<PathBuf>,
+
+    /// RAG 分块大小（token 数，按词近似）
+    /// 设置后输出 JSONL（-o 为 .sqlite / .db 时输出 SQLite 的 chunks 表），每行一个带来源信息的内容分块，忽略 --format
+    #[arg(long, env = "ADOC_CHUNK_SIZE", help_heading = "输出选项")]
+    chunk_size: Option<usize>,
+
+    /// 相邻分块之间重叠的 token 数
+    #[arg(long, env = "ADOC_CHUNK_OVERLAP", default_value = "0", requires = "chunk_size", help_heading = "输出选项")]
+    chunk_overlap: usize,
+
+    /// 调用 OpenAI 兼容的嵌入接口为每个分块生成向量，随分块一起输出（需要启用 embed 特性）
+    /// 例如 https://api.openai.com/v1/embeddings，或本地 Ollama 的 http://localhost:11434/v1/embeddings
+    #[arg(long, env = "ADOC_EMBED", requires = "chunk_size", help_heading = "输出选项")]
+    embed: Option<String>,
+
+    /// 嵌入模型名称
+    #[arg(long, env = "ADOC_EMBED_MODEL", default_value = "text-embedding-3-small", help_heading = "输出选项")]
+    embed_model: String,
+
+    /// 嵌入接口的 API Key
+    #[arg(long, env = "ADOC_EMBED_KEY", hide_env_values = true, help_heading = "输出选项")]
+    embed_key: Option<String>,
+
+    /// 每次请求嵌入接口的分块数
+    #[arg(long, env = "ADOC_EMBED_BATCH_SIZE", default_value = "64", help_heading = "输出选项")]
+    embed_batch_size: usize,
+
+    /// 用本地大模型为每个页面生成 2 到 3 句话的摘要，保存在 summary 字段中（需要启用 summarize 特性）
+    /// 例如 ollama://llama3，或指定 Ollama 服务的 ollama://192.168.1.10:11434/llama3
+    #[arg(
+        long,
+        env = "ADOC_SUMMARIZE",
+        value_name = "MODEL",
+        conflicts_with_all = ["stream", "memory_budget"],
+        help_heading = "输出选项"
+    )]
+    summarize: Option<String>,
+
+    /// 同时生成摘要的页面数
+    #[arg(long, env = "ADOC_SUMMARIZE_WORKERS", default_value = "4", help_heading = "输出选项")]
+    summarize_workers: usize,
+
+    /// 把每个页面的正文翻译为指定语言，保存在 content_translated 字段中，例如 zh-CN；需要配合 --translator
+    #[arg(
+        long,
+        env = "ADOC_TRANSLATE",
+        value_name = "LANG",
+        requires = "translator",
+        conflicts_with_all = ["stream", "memory_budget"],
+        help_heading = "输出选项"
+    )]
+    translate: Option<String>,
+
+    /// 翻译器：cmd:<命令>（正文从标准输入传入，目标语言为最后一个参数，标准输出为译文），
+    /// 或 LibreTranslate 兼容的 HTTP 接口，例如 http://localhost:5000/translate
+    #[arg(long, env = "ADOC_TRANSLATOR", value_name = "TRANSLATOR", requires = "translate", help_heading = "输出选项")]
+    translator: Option<String>,
+
+    /// 统计每个页面正文的词数、字符数和 token 数，保存在 size 字段中，合计写入爬取报告；
+    /// 编码默认为 cl100k（需要启用 tokens 特性），words 以空白分隔的词近似
+    #[arg(
+        long,
+        env = "ADOC_TOKEN_STATS",
+        value_enum,
+        value_name = "ENCODING",
+        num_args = 0..=1,
+        default_missing_value = "cl100k",
+        conflicts_with_all = ["stream", "memory_budget"],
+        help_heading = "输出选项"
+    )]
+    token_stats: Option<TokenEncoding>,
+
+    /// 正文只保留第一句简介（跳过声明和平台标记），适合只需要页面清单的爬取
+    #[arg(long, env = "ADOC_EXCERPT_ONLY", help_heading = "输出选项")]
+    excerpt_only: bool,
+
+    /// 正文的最大字符数，超出时在提取后截断并以 … 结尾
+    #[arg(long, env = "ADOC_MAX_CONTENT_CHARS", value_name = "N", help_heading = "输出选项")]
+    max_content_chars: Option<usize>,
+
+    /// 在每个页面的 http 字段中记录状态码、正文字节数、内容类型、抓取时间和请求耗时
+    #[arg(long, env = "ADOC_WITH_HTTP_META", help_heading = "输出选项")]
+    with_http_meta: bool,
+
+    /// 在每个页面的 raw_html 字段中保存下载到的正文，便于之后用其他提取逻辑处理而不必重新爬取；
+    /// 默认原样保存，gzip 压缩后以 base64 保存
+    #[arg(
+        long,
+        env = "ADOC_INCLUDE_RAW_HTML",
+        value_enum,
+        value_name = "ENCODING",
+        num_args = 0..=1,
+        default_missing_value = "plain",
+        help_heading = "输出选项"
+    )]
+    include_raw_html: Option<RawEncoding>,
+
+    /// 把结果中所有符号的标题、类型、一句话简介和链接整理为术语表，按扩展名保存为 JSON 或 CSV；
+    /// 输出格式为 Markdown 时同时附在输出末尾
+    #[arg(long, env = "ADOC_GLOSSARY", conflicts_with_all = ["stream", "memory_budget"], help_heading = "输出选项")]
+    glossary: Option<PathBuf>,
+
+    /// 输出前按标题、URL 或文档层级排序，默认为爬取完成的顺序；convert、merge 等命令同样适用
+    #[arg(long, env = "ADOC_SORT", value_enum, global = true, help_heading = "输出选项")]
+    sort: Option<SortKey>,
+
+    /// 输出前按框架、符号类型或上级页面分组，组内按 --sort 排列；Markdown 输出为每组加上标题
+    #[arg(long, env = "ADOC_GROUP_BY", value_enum, global = true, help_heading = "输出选项")]
+    group_by: Option<GroupBy>,
+
+    /// 所有页面提取完成后生成标签（框架、平台、提到的符号和关键词），保存在 tags 字段中
+    #[arg(long, env = "ADOC_TAGS", conflicts_with_all = ["stream", "memory_budget"], help_heading = "输出选项")]
+    tags: bool,
+
+    /// 爬取过程中将页面实时推送到外部服务
+    #[arg(long, env = "ADOC_SINK", value_enum, help_heading = "推送选项")]
+    sink: Option<SinkKind>,
+
+    /// 推送目标地址，例如 http://localhost:7700、http://localhost:9200 或 webhook 地址；Notion 默认为 https://api.notion.com
+    #[arg(long, env = "ADOC_SINK_URL", help_heading = "推送选项")]
+    sink_url: Option<String>,
+
+    /// 推送目标的 API Key
+    #[arg(long, env = "ADOC_SINK_KEY", hide_env_values = true, help_heading = "推送选项")]
+    sink_key: Option<String>,
+
+    /// 推送目标的索引名称
+    #[arg(long, env = "ADOC_SINK_INDEX", default_value = "apple-docs", help_heading = "推送选项")]
+    sink_index: String,
+
+    /// 文档主键字段名
+    #[arg(long, env = "ADOC_SINK_PRIMARY_KEY", default_value = "id", help_heading = "推送选项")]
+    sink_primary_key: String,
+
+    /// 可搜索字段，逗号分隔
+    #[arg(long, env = "ADOC_SINK_SEARCHABLE", value_delimiter = ',', default_value = "title,content", help_heading = "推送选项")]
+    sink_searchable: Vec<String>,
+
+    /// 每批推送的页面数，默认 Meilisearch 为 100，Elasticsearch 为 500，webhook 为 1（逐页推送）
+    #[arg(long, env = "ADOC_SINK_BATCH_SIZE", help_heading = "推送选项")]
+    sink_batch_size: Option<usize>,
+
+    /// 写入的 Notion 数据库 ID
+    #[arg(long, env = "ADOC_DATABASE_ID", help_heading = "推送选项")]
+    database_id: Option<String>,
+
+    /// 发布到的 Confluence 空间 key
+    #[arg(long, env = "ADOC_SPACE", help_heading = "推送选项")]
+    space: Option<String>,
+
+    /// Confluence 父页面 ID，页面都发布在该页面之下
+    #[arg(long, env = "ADOC_PARENT_PAGE", help_heading = "推送选项")]
+    parent_page: Option<String>,
+
+    /// Kafka broker 地址，逗号分隔，例如 localhost:9092
+    #[arg(long, env = "ADOC_BROKERS", help_heading = "推送选项")]
+    brokers: Option<String>,
+
+    /// 发布到的 Kafka 主题
+    #[arg(long, env = "ADOC_TOPIC", default_value = "apple-docs", help_heading = "推送选项")]
+    topic: String,
+
     /// 网络请求最大重试次数
-    #[arg(short, long, default_value = "3", help_heading = "网络选项")]
+    #[arg(short, long, env = "ADOC_MAX_RETRIES", default_value = "3", global = true, help_heading = "网络选项")]
     max_retries: u32,
 
     /// 请求超时时间（秒）
-    #[arg(short = 't', long, default_value = "30", help_heading = "网络选项")]
+    #[arg(short = 't', long, env = "ADOC_TIMEOUT", default_value = "30", global = true, help_heading = "网络选项")]
     timeout: u64,
 
+    /// 每个主机保留的最大空闲连接数，默认不限制
+    #[arg(long, env = "ADOC_POOL_MAX_IDLE_PER_HOST", global = true, help_heading = "网络选项")]
+    pool_max_idle_per_host: Option<usize>,
+
+    /// 空闲连接保留时间，例如 30s、2m，默认 90 秒
+    #[arg(long, env = "ADOC_POOL_IDLE_TIMEOUT", value_parser = parse_duration, global = true, help_heading = "网络选项")]
+    pool_idle_timeout: Option<Duration>,
+
+    /// TCP keepalive 间隔，例如 60s，默认不设置
+    #[arg(long, env = "ADOC_TCP_KEEPALIVE", value_parser = parse_duration, global = true, help_heading = "网络选项")]
+    tcp_keepalive: Option<Duration>,
+
+    /// 使用的 HTTP 版本：auto 按 TLS 协商结果；1.1 只用 HTTP/1.1，适用于中间设备会破坏 HTTP/2 流的网络；2 直接使用 HTTP/2
+    #[arg(long, env = "ADOC_HTTP_VERSION", value_enum, default_value = "auto", global = true, help_heading = "网络选项")]
+    http_version: HttpVersion,
+
+    /// 启用 HTTP/2 自适应流控窗口，高并发时可提高单个连接的吞吐
+    #[arg(long, env = "ADOC_HTTP2_ADAPTIVE_WINDOW", global = true, help_heading = "网络选项")]
+    http2_adaptive_window: bool,
+
+    /// 额外信任的 CA 证书（PEM 或 DER），例如重新签发 HTTPS 流量的企业代理的根证书
+    #[arg(long, env = "ADOC_CA_CERT", global = true, help_heading = "网络选项")]
+    ca_cert: Option<PathBuf>,
+
+    /// 不校验服务器证书（危险），仅在无法获得代理 CA 证书时临时使用
+    #[arg(long, env = "ADOC_INSECURE", global = true, help_heading = "网络选项")]
+    insecure: bool,
+
+    /// 只通过 IPv4 连接，适用于 IPv6 路由不通、请求总要等到超时的网络
+    #[arg(long, env = "ADOC_IPV4_ONLY", conflicts_with = "ipv6_only", global = true, help_heading = "网络选项")]
+    ipv4_only: bool,
+
+    /// 只通过 IPv6 连接
+    #[arg(long, env = "ADOC_IPV6_ONLY", global = true, help_heading = "网络选项")]
+    ipv6_only: bool,
+
+    /// 域名解析方式：system（默认）或 doh:<DoH 地址>，例如 doh:https://1.1.1.1/dns-query，适用于系统解析器屏蔽或污染 Apple 域名的网络
+    #[arg(long, env = "ADOC_DNS", value_parser = parse_dns, global = true, help_heading = "网络选项")]
+    dns: Option<Dns>,
+
+    /// 响应体的最大大小，例如 10M；超过时中止下载并记为 too-large 失败，默认不限制
+    #[arg(long, env = "ADOC_MAX_BODY_SIZE", value_parser = parse_size, global = true, help_heading = "网络选项")]
+    max_body_size: Option<usize>,
+
+    /// 响应缓存目录：保存爬取中下载的页面、搜索结果页和 JSON 数据，之后可以用 --offline 离线爬取
+    #[arg(long, env = "ADOC_CACHE_DIR", global = true, help_heading = "网络选项")]
+    cache_dir: Option<PathBuf>,
+
+    /// 离线模式：不访问网络，页面只从 --cache-dir 的缓存读取，缓存中没有的页面记为 offline 失败
+    #[arg(long, env = "ADOC_OFFLINE", global = true, help_heading = "网络选项")]
+    offline: bool,
+
+    /// 关键字搜索时最多读取的结果页数，汇总去重后供选择或爬取
+    #[arg(long, env = "ADOC_SEARCH_PAGES", default_value = "1", help_heading = "爬取选项")]
+    search_pages: usize,
+
+    /// 文档站点配置（TOML）：站点域名、标题和正文的 CSS 选择器或 JSON 字段映射、搜索地址模板
+    /// 用于爬取 Apple 以外的文档站点，默认使用内置的 Apple 开发者文档配置
+    #[arg(long, env = "ADOC_SITE_PROFILE", help_heading = "爬取选项")]
+    site_profile: Option<PathBuf>,
+
+    /// 除 Apple 开发者文档外额外爬取的域名（含子域名），可重复或逗号分隔，例如 swift.org、github.com/apple
+    #[arg(long, env = "ADOC_ALLOW_DOMAIN", value_delimiter = ',', help_heading = "爬取选项")]
+    allow_domain: Vec<String>,
+
+    /// 爬取任意域名的链接，而不只是 Apple 开发者文档
+    #[arg(long, env = "ADOC_ALLOW_EXTERNAL", help_heading = "爬取选项")]
+    allow_external: bool,
+
+    /// 示例代码页面下载项目压缩包，把其中的 README 记录在 sample.readme 中
+    #[arg(long, env = "ADOC_SAMPLE_README", help_heading = "爬取选项")]
+    sample_readme: bool,
+
+    /// 爬取的时间预算，例如 90s、10m、1h30m
+    /// 超过后不再发起新的请求，等待进行中的请求完成后输出已获取的结果，并在报告中标记 truncated
+    #[arg(long, env = "ADOC_MAX_DURATION", value_parser = parse_duration, help_heading = "爬取选项")]
+    max_duration: Option<Duration>,
+
+    /// 连续失败多少个请求后熔断（超时、连接失败、403、429、5xx），0 表示不熔断
+    #[arg(long, env = "ADOC_BREAKER_THRESHOLD", default_value = "20", help_heading = "爬取选项")]
+    breaker_threshold: u32,
+
+    /// 熔断后暂停的时间，例如 30s、5m，之后重新尝试；不设置时熔断即放弃剩余的链接并输出已获取的结果
+    #[arg(long, env = "ADOC_BREAKER_COOLDOWN", value_parser = parse_duration, help_heading = "爬取选项")]
+    breaker_cooldown: Option<Duration>,
+
+    /// 已访问 URL 集合的实现方式
+    /// exact 保存完整 URL；bloom 使用布隆过滤器，误判率约 0.1%（少量新链接会被当作已访问跳过），内存占用低得多
+    #[arg(long, env = "ADOC_VISITED", value_enum, default_value = "exact", help_heading = "爬取选项")]
+    visited: VisitedKind,
+
+    /// 预计爬取的 URL 数量，用于确定布隆过滤器的大小，支持 K、M 后缀，例如 5M
+    #[arg(long, env = "ADOC_EXPECTED_URLS", value_parser = parse_count, default_value = "1M", help_heading = "爬取选项")]
+    expected_urls: usize,
+
+    /// 与其他 adoc 进程通过 Redis 共享已访问集合，例如 redis://localhost:6379/0（需要启用 redis 特性）
+    /// 递归爬取时各进程分担起始页面的链接，不重复请求彼此已爬取的页面
+    #[arg(long, env = "ADOC_REDIS_URL", help_heading = "爬取选项")]
+    redis_url: Option<String>,
+
+    /// 共享已访问集合的 Redis 键名，重新完整爬取时换一个键名
+    #[arg(long, env = "ADOC_REDIS_KEY", default_value = "adoc:visited", help_heading = "爬取选项")]
+    redis_key: String,
+
+    /// 内存中保留页面的上限，例如 512M、2G；超过后页面溢出到临时文件，保存时再逐页读出
+    /// 适用于所有输出格式；未设置时所有页面都保留在内存中
+    #[arg(
+        long,
+        env = "ADOC_MEMORY_BUDGET",
+        value_parser = parse_size,
+        conflicts_with_all = ["stream", "chunk_size", "merge_into"],
+        help_heading = "爬取选项"
+    )]
+    memory_budget: Option<usize>,
+
     /// 日志级别
     /// 可选: error, warn, info, debug, trace
-    #[arg(short = 'l', long, default_value = "info", help_heading = "日志选项")]
+    #[arg(short = 'l', long, env = "ADOC_LOG_LEVEL", default_value = "info", global = true, help_heading = "日志选项")]
     log_level: String,
+
+    /// 进度显示方式
+    /// json 时不显示进度条，改为逐行输出 JSON 进度事件（queued、fetching、done、skipped、failed）
+    #[arg(
+        long,
+        env = "ADOC_PROGRESS",
+        value_enum,
+        default_value_t = ProgressFormat::Bar,
+        help_heading = "日志选项"
+    )]
+    progress: ProgressFormat,
+
+    /// JSON 进度事件的写入路径（文件或 FIFO），默认写到 stderr
+    #[arg(long, env = "ADOC_PROGRESS_FILE", help_heading = "日志选项")]
+    progress_file: Option<PathBuf>,
+
+    /// 用终端监控面板代替进度条：总体进度、排队和进行中的请求、每秒页面数走势、计数和最近的失败
+    /// 需要启用 dashboard 特性，显示期间的日志在面板关闭后输出
+    #[arg(long, env = "ADOC_DASHBOARD", conflicts_with = "progress", help_heading = "日志选项")]
+    dashboard: bool,
+
+    /// 在该地址上以 Prometheus 格式提供运行指标（GET /metrics），例如 0.0.0.0:9900
+    /// 爬取时包括请求数、重试、按类别的失败、下载字节数、请求耗时、速度和待爬取链接数；serve 时包括按路由的请求数
+    #[arg(long, env = "ADOC_METRICS_ADDR", global = true, help_heading = "日志选项")]
+    metrics_addr: Option<SocketAddr>,
+
+    /// 日志格式
+    /// json 时每条日志输出一个 JSON 对象（timestamp、level、url、elapsed、message 等字段）
+    #[arg(
+        long,
+        env = "ADOC_LOG_FORMAT",
+        value_enum,
+        default_value_t = LogFormat::Text,
+        global = true,
+        help_heading = "日志选项"
+    )]
+    log_format: LogFormat,
+
+    /// 提示、日志和输出模板的语言
+    /// 默认根据 LANG 环境变量选择，中文环境使用中文，其他使用英文
+    #[arg(long, env = "ADOC_LANG", value_enum, global = true, help_heading = "日志选项")]
+    lang: Option<Lang>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// 从爬取结果构建全文搜索索引
+    Index {
+        /// 爬取结果文件（JSON 或 SQLite）
+        #[arg(long)]
+        from: PathBuf,
+
+        /// 索引输出目录
+        #[arg(long, default_value = "index")]
+        out: PathBuf,
+    },
+
+    /// 在本地全文索引或已保存的爬取结果中搜索
+    Query {
+        /// 搜索词，使用索引时支持 tantivy 查询语法，例如 title:View
+        query: String,
+
+        /// 索引目录
+        #[arg(long, default_value = "index")]
+        index: PathBuf,
+
+        /// 直接在爬取结果文件（JSON 或 SQLite）中搜索标题和正文，不使用索引
+        #[arg(long, conflicts_with = "index")]
+        from: Option<PathBuf>,
+
+        /// 将搜索词作为正则表达式（需配合 --from）
+        #[arg(long, requires = "from")]
+        regex: bool,
+
+        /// 只显示带有该标签的页面（不区分大小写），可以多次指定；需要用 --tags 爬取的结果
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+
+        /// 最多显示的结果数
+        #[arg(short = 'n', long, default_value = "10")]
+        limit: usize,
+
+        /// 以指定格式输出匹配的页面，默认只列出标题和链接
+        #[arg(short = 'f', long = "format", value_enum)]
+        format: Option<OutputFormat>,
+    },
+
+    /// 模糊查找文档标题或符号名
+    Find {
+        /// 查找内容，例如 scrollview
+        pattern: String,
+
+        /// 爬取结果文件（JSON 或 SQLite），不指定时使用本地索引
+        #[arg(long)]
+        from: Option<PathBuf>,
+
+        /// 索引目录
+        #[arg(long, default_value = "index", conflicts_with = "from")]
+        index: PathBuf,
+
+        /// 最多显示的结果数
+        #[arg(short = 'n', long, default_value = "10")]
+        limit: usize,
+    },
+
+    /// 比较两次爬取结果，列出新增、删除和变更的页面
+    Diff {
+        /// 旧的爬取结果（JSON 或 SQLite）
+        old: PathBuf,
+
+        /// 新的爬取结果（JSON 或 SQLite）
+        new: PathBuf,
+
+        /// 输出格式
+        #[arg(short = 'f', long = "format", value_enum, default_value_t = DiffFormat::Text)]
+        format: DiffFormat,
+
+        /// 输出文件路径，不指定时打印到控制台
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// 有变化时把变更摘要 POST 到该 webhook
+        #[arg(long)]
+        webhook: Option<String>,
+
+        /// webhook 负载格式
+        #[arg(long, value_enum, default_value_t = WebhookFormat::Json, requires = "webhook")]
+        webhook_format: WebhookFormat,
+    },
+
+    /// 将已保存的爬取结果转换为其他输出格式，无需重新爬取
+    Convert {
+        /// 爬取结果文件（JSON 或 SQLite）
+        input: PathBuf,
+
+        /// 输出格式
+        #[arg(short = 'f', long = "format", value_enum)]
+        format: OutputFormat,
+
+        /// 输出文件路径，不指定时打印到控制台
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// 导入 DocC 归档（.doccarchive），转换为与爬取结果相同的页面记录和输出格式
+    ImportDocc {
+        /// DocC 归档目录，例如 MyFramework.doccarchive
+        archive: PathBuf,
+
+        /// 页面 URL 的前缀，即归档部署后的地址；默认为 docc preview 的本地预览地址
+        #[arg(long, default_value = adoc::docc::DEFAULT_BASE_URL)]
+        base_url: String,
+
+        /// 输出格式
+        #[arg(short = 'f', long = "format", value_enum, default_value_t = OutputFormat::Json)]
+        format: OutputFormat,
+
+        /// 输出文件路径，不指定时打印到控制台
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// 合并多个爬取结果，按 URL 去重，同一页面保留最新的版本
+    Merge {
+        /// 要合并的结果文件（JSON 或 SQLite），以文件修改时间判断新旧
+        #[arg(required = true, num_args = 2..)]
+        inputs: Vec<PathBuf>,
+
+        /// 输出文件路径
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// 输出格式
+        #[arg(short = 'f', long = "format", value_enum, default_value_t = OutputFormat::Json)]
+        format: OutputFormat,
+    },
+
+    /// 统计爬取结果：框架/类型分布、正文长度、弃用和 Beta API、链接情况
+    Stats {
+        /// 爬取结果文件（JSON 或 SQLite）
+        #[arg(long)]
+        from: PathBuf,
+
+        /// 输出格式
+        #[arg(short = 'f', long = "format", value_enum, default_value_t = StatsFormat::Table)]
+        format: StatsFormat,
+    },
+
+    /// 启动本地文档浏览服务
+    Serve {
+        /// 爬取结果文件（JSON 或 SQLite）
+        #[arg(long)]
+        from: PathBuf,
+
+        /// 监听端口
+        #[arg(short, long, default_value = "8080")]
+        port: u16,
+
+        /// 监听地址
+        #[arg(long, default_value = "127.0.0.1")]
+        host: std::net::IpAddr,
+    },
+
+    /// 生成命令行补全脚本，例如: adoc completions zsh > _adoc
+    Completions {
+        /// 目标 shell
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+
+    /// 打印 JSON 输出格式的 JSON Schema，包括格式版本号（schema_version）
+    Schema {
+        /// 只打印单个页面的 Schema，用于逐页发送的 sink（Kafka、Webhook 等）
+        #[arg(long)]
+        page: bool,
+    },
+
+    /// 从 GitHub Releases 更新到最新版本，校验 SHA-256 后替换当前可执行文件
+    SelfUpdate {
+        /// 只检查是否有新版本，不下载
+        #[arg(long)]
+        check: bool,
+    },
+
+    /// 按清单检查 --cache-dir 缓存镜像，列出缺失和损坏的文件，可以只重新下载有问题的条目
+    Verify {
+        /// 缓存目录，即爬取时的 --cache-dir
+        #[arg(long)]
+        mirror: PathBuf,
+
+        /// 应当在镜像中的 URL 列表（与 --failures 格式相同，每行第一列为 URL）
+        #[arg(long)]
+        expected: Option<PathBuf>,
+
+        /// 重新下载缺失、损坏和未收录的条目，然后再检查一次
+        #[arg(long)]
+        refetch: bool,
+    },
+
+    /// 以 gRPC 服务模式运行，提供 Crawl / Search / GetPage 接口
+    #[cfg(feature = "grpc")]
+    Grpcd {
+        /// 监听地址
+        #[arg(long, default_value = "127.0.0.1:50051")]
+        addr: std::net::SocketAddr,
+    },
+}
+
+/// 区分失败类型的退出码，网络错误以外的其他错误退出码为 1
+#[derive(Debug, Clone, Copy)]
+enum Failure {
+    /// 部分页面爬取失败，其余结果已正常输出
+    Partial,
+    /// 爬取完成，但没有提取到任何内容
+    NothingExtracted,
+    /// 命令行参数、环境变量或配置文件无效
+    InvalidInput,
+}
+
+/// 交互选择时最多列出的搜索结果数
+const PICKER_MAX_RESULTS: usize = 20;
+
+/// 非交互环境下未指定 `--first` / `--top` 时爬取的搜索结果数
+const DEFAULT_SEARCH_TOP: usize = 10;
+
+/// 网络请求失败（起始页面无法获取等）
+const EXIT_NETWORK: u8 = 3;
+
+impl Failure {
+    fn exit_code(self) -> u8 {
+        match self {
+            Failure::Partial => 2,
+            Failure::NothingExtracted => 4,
+            Failure::InvalidInput => 5,
+        }
+    }
+}
+
+impl std::fmt::Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Partial => write!(f, "{}", tr!("部分页面爬取失败", "some pages failed")),
+            Failure::NothingExtracted => write!(f, "{}", tr!("没有提取到任何内容", "nothing was extracted")),
+            Failure::InvalidInput => write!(f, "{}", tr!("输入或配置无效", "invalid input or config")),
+        }
+    }
+}
+
+fn exit_code(error: &anyhow::Error) -> u8 {
+    if let Some(failure) = error.downcast_ref::<Failure>() {
+        failure.exit_code()
+    } else if error.is::<clap::Error>() {
+        Failure::InvalidInput.exit_code()
+    } else if error.chain().any(|cause| cause.is::<reqwest::Error>()) {
+        EXIT_NETWORK
+    } else {
+        1
+    }
+}
+
+/// 解析命令行之前确定语言，使 --help 和参数错误也使用对应语言：
+/// 依次查看 `--lang`、`ADOC_LANG` 和 `LANG` 等环境变量
+fn initial_lang() -> Lang {
+    let mut argv = std::env::args().skip(1);
+    while let Some(arg) = argv.next() {
+        let value = if arg == "--lang" {
+            argv.next()
+        } else if let Some(value) = arg.strip_prefix("--lang=") {
+            Some(value.to_string())
+        } else {
+            continue;
+        };
+        if let Some(lang) = value.and_then(|value| Lang::from_str(&value, true).ok()) {
+            return lang;
+        }
+    }
+    std::env::var("ADOC_LANG")
+        .ok()
+        .and_then(|value| Lang::from_str(&value, true).ok())
+        .or_else(lang_from_env)
+        .unwrap_or(Lang::Zh)
+}
+
+const ABOUT_EN: &str = "Apple Documentation Crawler";
+
+const LONG_ABOUT_EN: &str = "A fast, efficient crawler for Apple developer documentation.
+
+Examples:
+    adoc -i \"SwiftUI\" -o swiftui.json            # search SwiftUI docs and save as JSON
+    adoc -i \"UIKit\" -r -o uikit.txt              # recursively crawl UIKit docs
+    adoc -i \"SwiftUI\" --format markdown -o doc.md # export as Markdown
+    adoc -i \"SwiftUI\" -f pretty -o doc.json      # export as pretty-printed JSON
+    adoc index --from doc.json --out index/       # build a full-text index of the results
+    adoc query \"scrollTargetBehavior\"            # search the local index
+    adoc query --from doc.json \"NavigationStack\"  # search saved results
+    adoc find scrollview --from doc.json          # fuzzy-find titles or symbol names
+    adoc diff old.json new.json -f markdown       # compare two crawls
+    adoc diff old.json new.json --webhook $SLACK_URL --webhook-format slack
+    adoc stats --from doc.json                    # show crawl statistics
+    adoc convert doc.json -f markdown -o doc.md   # convert saved results to another format
+    adoc merge a.json b.json c.json -o all.json   # merge and deduplicate results
+    adoc serve --from doc.json --port 8080        # browse the docs locally
+    adoc --config swiftui.toml                    # read options from a config file
+    adoc --profile swiftui-nightly                # use a named profile from adoc.toml
+    adoc completions zsh > _adoc                  # generate shell completions
+
+Exit codes:
+    0 success, 1 other error, 2 some pages failed, 3 network error, 4 nothing extracted, 5 invalid input or config";
+
+fn help_heading_en(heading: &str) -> Option<&'static str> {
+    Some(match heading {
+        "输入选项" => "Input",
+        "爬取选项" => "Crawling",
+        "输出选项" => "Output",
+        "推送选项" => "Sink",
+        "网络选项" => "Network",
+        "日志选项" => "Logging",
+        _ => return None,
+    })
+}
+
+/// 子命令说明
+fn about_en(command: &str) -> Option<&'static str> {
+    Some(match command {
+        "index" => "Build a full-text search index from crawl results",
+        "query" => "Search the local full-text index or saved crawl results",
+        "find" => "Fuzzy-find documentation titles or symbol names",
+        "diff" => "Compare two crawls and list added, removed and changed pages",
+        "convert" => "Convert saved crawl results to another output format without re-crawling",
+        "import-docc" => "Import a DocC archive (.doccarchive) into the same page records and output formats as a crawl",
+        "merge" => "Merge several crawl results, deduplicating by URL and keeping the newest version",
+        "stats" => "Crawl statistics: framework/kind breakdown, content length, deprecated and beta APIs, links",
+        "serve" => "Serve the documentation locally for browsing",
+        "completions" => "Generate shell completions, e.g. adoc completions zsh > _adoc",
+        "schema" => "Print the JSON Schema of the JSON output format, including its version (schema_version)",
+        "self-update" => "Update to the latest GitHub release, verifying its SHA-256 checksum before replacing the current executable",
+        "verify" => "Check a --cache-dir mirror against its manifest, listing missing and corrupted files; can re-fetch just the broken entries",
+        "grpcd" => "Run as a gRPC service exposing Crawl / Search / GetPage",
+        _ => return None,
+    })
+}
+
+/// 参数说明，`command` 为子命令名，顶层参数为空字符串
+fn arg_help_en(command: &str, arg: &str) -> Option<&'static str> {
+    Some(match (command, arg) {
+        ("", "input") => "Apple developer documentation URL or keyword, e.g. https://developer.apple.com/documentation/swift or \"SwiftUI\"",
+        ("", "config") => "Config file path, defaults to adoc.toml in the current directory (if present); command-line options take precedence",
+        ("", "profile") => "Use the [profile.<name>] section of the config file",
+        ("", "recursive") => "Recursively crawl pages referenced by the documentation",
+        ("", "retry_failed") => "Re-crawl the URLs in a failed pages list written by --failures; usually combined with --merge-into to merge successes back into the previous results",
+        ("", "first") => "For keyword searches, crawl the first search result without prompting",
+        ("", "top") => "For keyword searches, crawl the first N search results without prompting",
+        ("", "dry_run") => "Only list the URLs that would be crawled; recursive mode still fetches the start page to discover links",
+        ("", "concurrency") => "Number of concurrent crawl tasks",
+        ("", "adaptive_concurrency") => "Adjust concurrency from latency and error rate: raise it gradually while requests go well, halve it on timeouts, 429 and 503; -c is the starting value",
+        ("", "max_concurrency") => "Maximum concurrency with --adaptive-concurrency",
+        ("", "output") => "Output file path, e.g. output.json or docs.txt",
+        ("", "format") => "Output format: json, pretty, txt, markdown, es-bulk, sqlite, alfred, raycast",
+        ("", "tombstones") => "Write a placeholder entry with only url and status for gone pages (404 / 410) so downstream consumers know the link is dead; json and pretty formats only",
+        ("", "stream") => "Write pages to the -o file as they are crawled instead of keeping them in memory, for large crawls; json and pretty formats only",
+        ("", "report") => "Path for the crawl report, saved as JSON with succeeded, skipped and failed counts and timings",
+        ("", "git_repo") => "Write each page as a Markdown file in a git working tree and commit, summarizing added, changed and removed pages in the message; the repository is initialized if needed, and pages under the start page that were not seen this time are removed",
+        ("", "merge_into") => "Merge the crawled pages into an existing results file (JSON or SQLite); pages from this crawl win for the same URL",
+        ("", "delta_output") => "Also write only the new and changed pages (marked change: added / modified) to this file; the baseline is the existing --merge-into results file, and the format matches the JSON output",
+        ("", "failures") => "Path for the list of failed pages: one URL per line that still failed after retries, with the failure kind (timeout, connect, status, extract, other), status code and reason, tab separated",
+        ("", "download_media") => "Download the video and audio files found on pages into this directory, skipping files that already exist. Media entries are always recorded in the media field of the output; HLS playlists (.m3u8) are not downloaded",
+        ("", "chunk_size") => "RAG chunk size (tokens, approximated by words); outputs JSONL chunks with source info (a chunks table when -o is .sqlite / .db) and ignores --format",
+        ("", "chunk_overlap") => "Number of tokens shared by adjacent chunks",
+        ("", "embed") => "Generate a vector for each chunk through an OpenAI-compatible embeddings endpoint and store it with the chunk (requires the embed feature), e.g. https://api.openai.com/v1/embeddings or Ollama's http://localhost:11434/v1/embeddings",
+        ("", "embed_model") => "Embedding model name",
+        ("", "embed_key") => "API key for the embeddings endpoint",
+        ("", "embed_batch_size") => "Number of chunks per embeddings request",
+        ("", "summarize") => "Generate a 2-3 sentence summary of each page with a local model and store it in the summary field (requires the summarize feature), e.g. ollama://llama3, or ollama://192.168.1.10:11434/llama3 for another Ollama server",
+        ("", "summarize_workers") => "Number of pages summarized concurrently",
+        ("", "translate") => "Translate the content of each page into this language and store it in the content_translated field, e.g. zh-CN; requires --translator",
+        ("", "translator") => "Translator: cmd:<command> (content on stdin, target language as the last argument, translation on stdout), or a LibreTranslate-compatible HTTP endpoint such as http://localhost:5000/translate",
+        ("", "excerpt_only") => "Keep only the first sentence of the abstract in the content (skipping declarations and platform markers), for inventory-style crawls",
+        ("", "max_content_chars") => "Maximum number of characters of content; longer content is truncated after extraction and ends with …",
+        ("", "with_http_meta") => "Record the status code, body size, content type, fetch time and request duration in each page's http field",
+        ("", "include_raw_html") => "Keep the downloaded body in each page's raw_html field so other extraction logic can be tried without crawling again; stored as-is by default, gzip compresses it and stores it as base64",
+        ("", "token_stats") => "Count words, characters and tokens of each page's content, stored in the size field with totals in the crawl report; the encoding defaults to cl100k (requires the tokens feature), words approximates tokens by whitespace-separated words",
+        ("", "sort") => "Sort pages by title, URL or documentation hierarchy before output instead of crawl completion order; also applies to convert, merge and other commands",
+        ("", "group_by") => "Group pages by framework, symbol kind or parent page before output, ordered by --sort within each group; Markdown output gets a heading per group",
+        ("", "glossary") => "Write a glossary of every symbol in the results (title, kind, one-line abstract, URL) to this file, as CSV for .csv and JSON otherwise; Markdown output also gets it appended",
+        ("", "tags") => "After extraction, tag each page with its framework, platforms, mentioned symbols and keywords, stored in the tags field",
+        ("", "sink") => "Push pages to an external service while crawling",
+        ("", "sink_url") => "Sink URL, e.g. http://localhost:7700, http://localhost:9200 or a webhook URL; defaults to https://api.notion.com for Notion",
+        ("", "sink_key") => "Sink API key",
+        ("", "sink_index") => "Sink index name",
+        ("", "sink_primary_key") => "Document primary key field",
+        ("", "sink_searchable") => "Searchable fields, comma separated",
+        ("", "sink_batch_size") => "Pages per push; defaults to 100 for Meilisearch, 500 for Elasticsearch, 1 for webhook",
+        ("", "database_id") => "Notion database ID to write pages to",
+        ("", "space") => "Confluence space key to publish pages to",
+        ("", "parent_page") => "Confluence parent page ID to publish pages under",
+        ("", "brokers") => "Kafka brokers, comma separated, e.g. localhost:9092",
+        ("", "topic") => "Kafka topic to publish pages to",
+        ("", "max_retries") => "Maximum retries for network requests",
+        ("", "timeout") => "Request timeout (seconds)",
+        ("", "visited") => "How visited URLs are tracked: exact stores full URLs; bloom uses a Bloom filter with about 0.1% false positives (a few new links are skipped as already visited) and far less memory",
+        ("", "expected_urls") => "Expected number of URLs, used to size the Bloom filter; accepts K and M suffixes, e.g. 5M",
+        ("", "redis_url") => "Share the visited set with other adoc processes through Redis, e.g. redis://localhost:6379/0 (requires the redis feature); recursive crawls split the start page's links instead of refetching each other's pages",
+        ("", "redis_key") => "Redis key of the shared visited set; use a new key for a fresh full crawl",
+        ("", "memory_budget") => "Upper bound for pages kept in memory, e.g. 512M, 2G; beyond it pages spill to a temporary file and are read back page by page when saving. Works with every output format; without it all pages stay in memory",
+        ("", "pool_max_idle_per_host") => "Maximum idle connections kept per host, unlimited by default",
+        ("", "pool_idle_timeout") => "How long idle connections are kept, e.g. 30s, 2m; 90 seconds by default",
+        ("", "tcp_keepalive") => "TCP keepalive interval, e.g. 60s; not set by default",
+        ("", "ca_cert") => "Extra CA certificate to trust (PEM or DER), e.g. the root of a TLS-intercepting corporate proxy",
+        ("", "insecure") => "Do not verify server certificates (dangerous); only as a stopgap when the proxy CA is not available",
+        ("", "ipv4_only") => "Connect over IPv4 only, for networks where broken IPv6 routing makes every request wait for a timeout",
+        ("", "ipv6_only") => "Connect over IPv6 only",
+        ("", "dns") => "How to resolve host names: system (default) or doh:<url>, e.g. doh:https://1.1.1.1/dns-query, for networks whose resolver blocks or poisons Apple domains",
+        ("", "max_body_size") => "Maximum response body size, e.g. 10M; larger downloads are aborted and recorded as too-large failures. Unlimited by default",
+        ("", "cache_dir") => "Response cache directory: pages, search result pages and JSON data fetched during the crawl are saved here, so the crawl can later be repeated with --offline",
+        ("", "offline") => "Offline mode: no network access; pages are read only from the --cache-dir cache, and pages missing from it are recorded as offline failures",
+        ("", "http_version") => "HTTP version to use: auto negotiates over TLS; 1.1 pins HTTP/1.1, for networks where a middlebox corrupts HTTP/2 streams; 2 uses HTTP/2 directly",
+        ("", "http2_adaptive_window") => "Enable the HTTP/2 adaptive flow-control window, which can raise per-connection throughput at high concurrency",
+        ("", "search_pages") => "Maximum number of search result pages to read for a keyword; hits are merged and deduplicated before picking or crawling",
+        ("", "source") => "Built-in documentation source: docs crawls the site given by --input; swift-evolution crawls every Swift Evolution proposal, including status, review dates and implementation links; wwdc crawls WWDC and other developer videos with their description, transcript and referenced documentation. The last two need no --input",
+        ("", "site_profile") => "Documentation site profile (TOML): site domains, CSS selectors or a JSON field mapping for title and content, and a search URL template. Use it to crawl sites other than Apple's; defaults to the built-in Apple developer docs profile",
+        ("", "allow_domain") => "Extra domains (including subdomains) to crawl besides Apple developer docs; repeatable or comma separated, e.g. swift.org, github.com/apple",
+        ("", "allow_external") => "Follow links to any domain, not just Apple developer docs",
+        ("", "sample_readme") => "For sample code pages, download the project archive and record its README in sample.readme",
+        ("", "breaker_threshold") => "Trip the circuit breaker after this many consecutive failed requests (timeouts, connection errors, 403, 429, 5xx); 0 disables it",
+        ("", "breaker_cooldown") => "How long to pause after the breaker trips, e.g. 30s, 5m, before trying again; without it the crawl gives up on the remaining links and writes the results so far",
+        ("", "max_duration") => "Time budget for the crawl, e.g. 90s, 10m, 1h30m; once exceeded no new requests are started, in-flight ones finish, the pages fetched so far are written and the report is marked truncated",
+        ("", "log_level") => "Log level: error, warn, info, debug, trace",
+        ("", "progress") => "Progress display; json prints newline-delimited JSON progress events (queued, fetching, done, skipped, failed) instead of bars",
+        ("", "progress_file") => "Path (file or FIFO) for JSON progress events, defaults to stderr",
+        ("", "dashboard") => "Replace the progress bars with a terminal dashboard: overall progress, queued and active requests, a pages/sec sparkline, counters and recent failures; needs the dashboard feature, and logs are printed once the dashboard closes",
+        ("", "metrics_addr") => "Serve Prometheus metrics (GET /metrics) on this address, e.g. 0.0.0.0:9900; crawls report requests, retries, failures by kind, bytes, fetch latency, pages/sec and queue depth, serve reports requests by route",
+        ("", "log_format") => "Log format; json prints one JSON object per event (timestamp, level, url, elapsed, message, ...)",
+        ("", "lang") => "Language of messages, logs and output templates; defaults to Chinese under a zh LANG and English otherwise",
+        ("index", "from") | ("convert", "input") | ("stats", "from") | ("serve", "from") => "Crawl results file (JSON or SQLite)",
+        ("index", "out") => "Index output directory",
+        ("query", "query") => "Search terms; tantivy query syntax is supported with an index, e.g. title:View",
+        ("query" | "find", "index") => "Index directory",
+        ("query", "from") => "Search titles and content of a crawl results file (JSON or SQLite) directly, without an index",
+        ("query", "regex") => "Treat the search terms as a regular expression (requires --from)",
+        ("query", "tags") => "Only show pages with this tag (case-insensitive); can be repeated; needs results crawled with --tags",
+        ("query" | "find", "limit") => "Maximum number of results",
+        ("query", "format") => "Print matching pages in this format instead of listing titles and links",
+        ("find", "pattern") => "Text to look for, e.g. scrollview",
+        ("find", "from") => "Crawl results file (JSON or SQLite); the local index is used when omitted",
+        ("diff", "old") => "Previous crawl results (JSON or SQLite)",
+        ("diff", "new") => "New crawl results (JSON or SQLite)",
+        ("diff" | "convert" | "import-docc" | "merge" | "stats", "format") => "Output format",
+        ("diff" | "convert" | "import-docc", "output") => "Output file path, printed to the console when omitted",
+        ("import-docc", "archive") => "DocC archive directory, e.g. MyFramework.doccarchive",
+        ("import-docc", "base_url") => "URL prefix for the pages, i.e. where the archive is hosted; defaults to the docc preview address",
+        ("diff", "webhook") => "POST a change summary to this webhook when something changed",
+        ("diff", "webhook_format") => "Webhook payload format",
+        ("merge", "inputs") => "Result files to merge (JSON or SQLite); file modification time decides which is newer",
+        ("merge", "output") => "Output file path",
+        ("serve", "port") => "Port to listen on",
+        ("serve" | "grpcd", "host" | "addr") => "Address to listen on",
+        ("completions", "shell") => "Target shell",
+        ("schema", "page") => "Print the schema of a single page only, for sinks that send pages one at a time (Kafka, webhook, ...)",
+        ("self-update", "check") => "Only check whether a newer version exists, without downloading it",
+        ("verify", "mirror") => "Cache directory, i.e. the --cache-dir used for the crawl",
+        ("verify", "expected") => "URLs that should be in the mirror (same format as --failures, the URL is the first column of each line)",
+        ("verify", "refetch") => "Re-fetch missing, corrupted and unlisted entries, then check again",
+        _ => return None,
+    })
+}
+
+/// 按 `E` 的可选值解析，只把帮助中列出的可选值换成 `values`
+#[derive(Clone)]
+struct LocalizedValues<E> {
+    values: Vec<PossibleValue>,
+    marker: PhantomData<E>,
+}
+
+impl<E: ValueEnum + Clone + Send + Sync + 'static> TypedValueParser for LocalizedValues<E> {
+    type Value = E;
+
+    fn parse_ref(&self, cmd: &clap::Command, arg: Option<&clap::Arg>, value: &OsStr) -> Result<E, clap::Error> {
+        EnumValueParser::<E>::new().parse_ref(cmd, arg, value)
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(self.values.iter().cloned()))
+    }
+}
+
+/// 参数按 `E` 解析时，用 `help` 替换各个可选值的说明
+fn localize_values<E>(arg: clap::Arg, help: fn(&str) -> Option<&'static str>) -> clap::Arg
+where
+    E: ValueEnum + Clone + Send + Sync + 'static,
+{
+    if arg.get_value_parser().type_id() != TypeId::of::<E>() {
+        return arg;
+    }
+    let values = E::value_variants()
+        .iter()
+        .filter_map(ValueEnum::to_possible_value)
+        .map(|value| match help(value.get_name()) {
+            Some(help) => value.help(help),
+            None => value,
+        })
+        .collect();
+    arg.value_parser(LocalizedValues::<E> {
+        values,
+        marker: PhantomData,
+    })
+}
+
+/// 各个枚举参数可选值的英文说明
+fn localize_value_help(arg: clap::Arg) -> clap::Arg {
+    macro_rules! values_en {
+        ($arg:expr, $($ty:ty => {$($value:literal => $help:literal),* $(,)?}),* $(,)?) => {{
+            let mut arg = $arg;
+            $(arg = localize_values::<$ty>(arg, |value| match value {
+                $($value => Some($help),)*
+                _ => None,
+            });)*
+            arg
+        }};
+    }
+    values_en!(arg,
+        DocSource => {
+            "docs" => "Crawl the documentation site from the URL or keyword given with `--input`",
+            "swift-evolution" => "Swift Evolution proposals: crawl every proposal, starting from the proposal index",
+            "wwdc" => "WWDC and other videos: crawl descriptions and transcripts, starting from the full video list",
+        },
+        VisitedKind => {
+            "exact" => "Store full URLs, with no false positives",
+            "bloom" => "Bloom filter; memory use does not depend on URL length",
+        },
+        OutputFormat => {
+            "es-bulk" => "NDJSON for the Elasticsearch / OpenSearch bulk API",
+            "sqlite" => "SQLite database with an FTS5 full-text index; file output only",
+            "alfred" => "Alfred Script Filter JSON, for offline documentation search on macOS",
+            "raycast" => "JSON array read by the Raycast extension, with icons by symbol kind",
+            "openapi" => "Draft OpenAPI 3 document assembled from REST API reference pages; other pages are ignored",
+        },
+        TokenEncoding => {
+            "words" => "Approximate by whitespace-separated words; does not need the tokens feature",
+            "cl100k" => "cl100k_base, used by GPT-4, GPT-3.5 and the text-embedding-3 series",
+            "o200k" => "o200k_base, used by the GPT-4o series",
+        },
+        RawEncoding => {
+            "plain" => "Store as is",
+            "gzip" => "Compress with gzip, then store as base64",
+        },
+        SortKey => {
+            "title" => "By title (case-insensitive), then by URL",
+            "hierarchy" => "By documentation hierarchy; parent pages come before the pages under them",
+        },
+        GroupBy => {
+            "framework" => "Framework (the first segment of the hierarchy)",
+            "kind" => "Symbol kind; articles and other pages without a declaration are not grouped",
+            "hierarchy" => "Parent hierarchy, e.g. `swiftui / view`; a framework's top page is grouped with its pages",
+        },
+        SinkKind => {
+            "elasticsearch" => "Elasticsearch or OpenSearch",
+            "notion" => "Notion database",
+            "confluence" => "Confluence Cloud space",
+            "webhook" => "Any HTTP endpoint; pages are POSTed as a JSON array",
+            "kafka" => "Kafka topic; requires the kafka feature",
+        },
+        HttpVersion => {
+            "auto" => "Negotiate through TLS ALPN and use HTTP/2 when the server supports it",
+            "1.1" => "HTTP/1.1 only, e.g. when middleboxes on the network break HTTP/2 streams",
+            "2" => "HTTP/2 directly; servers without HTTP/2 support fail to connect",
+        },
+        ProgressFormat => {
+            "bar" => "Terminal progress bar",
+            "json" => "One JSON progress event per line",
+        },
+        LogFormat => {
+            "text" => "Human-readable text",
+            "json" => "One JSON object per log entry, for log pipelines",
+        },
+        WebhookFormat => {
+            "json" => "Generic JSON: counts plus page lists",
+            "slack" => "Slack incoming webhook compatible message",
+        },
+    )
+}
+
+fn localize_args(command: clap::Command, name: &str) -> clap::Command {
+    command.mut_args(|arg| {
+        let arg = localize_value_help(arg);
+        let heading = arg.get_help_heading().and_then(help_heading_en);
+        let help = arg_help_en(name, arg.get_id().as_str());
+        let arg = match help {
+            Some(help) => arg.help(help).long_help(None::<&str>),
+            None => arg,
+        };
+        match heading {
+            Some(heading) => arg.help_heading(heading),
+            None => arg,
+        }
+    })
+}
+
+/// 当前语言为英文时，把命令行帮助替换为英文
+fn localize(command: clap::Command) -> clap::Command {
+    if lang() != Lang::En {
+        return command;
+    }
+    let mut command = localize_args(command.about(ABOUT_EN).long_about(LONG_ABOUT_EN), "");
+    let names: Vec<String> = command.get_subcommands().map(|subcommand| subcommand.get_name().to_string()).collect();
+    for name in names {
+        command = command.mut_subcommand(&name, |subcommand| {
+            let subcommand = match about_en(&name) {
+                Some(about) => subcommand.about(about).long_about(None::<&str>),
+                None => subcommand,
+            };
+            localize_args(subcommand, &name)
+        });
+    }
+    command
 }
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    let args = Args::parse();
-    
+async fn main() -> ExitCode {
+    match run().await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            match e.downcast_ref::<clap::Error>() {
+                Some(usage) => {
+                    let _ = usage.print();
+                }
+                None => eprintln!("Error: {:?}", e),
+            }
+            ExitCode::from(exit_code(&e))
+        }
+    }
+}
+
+async fn run() -> Result<()> {
+    set_lang(initial_lang());
+    let matches = match localize(Args::command()).try_get_matches() {
+        Ok(matches) => matches,
+        // --help / --version 照常输出并退出
+        Err(e) if !e.use_stderr() => e.exit(),
+        Err(e) => return Err(e.into()),
+    };
+    let mut args = Args::from_arg_matches(&matches)?;
+    let mut file_config = FileConfig::discover(args.config.as_deref()).context(Failure::InvalidInput)?;
+    if let Some(profile) = &args.profile {
+        file_config = file_config.with_profile(profile).context(Failure::InvalidInput)?;
+    }
+    apply_config(&mut args, &matches, file_config);
+    if let Some(lang) = args.lang {
+        set_lang(lang);
+    }
+    if args.command.is_none() && args.input.is_none() && args.retry_failed.is_none() && args.source.seed().is_none() {
+        return Err(Args::command()
+            .error(
+                ErrorKind::MissingRequiredArgument,
+                tr!(
+                    "需要通过 --input、--retry-failed 或配置文件指定爬取的 URL 或关键字",
+                    "a URL or keyword to crawl is required, via --input, --retry-failed or the config file"
+                ),
+            )
+            .into());
+    }
+
     // 设置日志
-    setup_logging(&args.log_level);
-    info!("启动 adoc 爬虫工具...");
-    
-    let config = CrawlerConfig {
+    setup_logging(&args.log_level, args.log_format);
+
+    match args.command {
+        Some(Command::Index { ref from, ref out }) => build_index(from, out),
+        Some(Command::Query { ref query, ref index, ref from, regex, ref tags, limit, format }) => {
+            let results = match from {
+                Some(from) if is_sqlite_path(from) && !tags.is_empty() => {
+                    return Err(anyhow::anyhow!(tr!(
+                        "SQLite 结果不保存标签，--tag 需要 JSON 结果或索引",
+                        "SQLite results do not store tags, --tag needs JSON results or an index"
+                    )));
+                }
+                // SQLite 输出自带 FTS5 索引，直接用全文搜索
+                Some(from) if is_sqlite_path(from) && !regex => adoc::sqlite::search(from, query, limit)?,
+                Some(from) => {
+                    let matcher = if regex {
+                        Matcher::regex(query)?
+                    } else {
+                        Matcher::substring(query)
+                    };
+                    let mut pages = load_results(from)?;
+                    pages.retain(|page| has_tags(page, tags));
+                    search_pages(pages, &matcher, limit)
+                }
+                None => adoc::index::search_tagged(index, query, tags, limit)?
+                    .into_iter()
+                    .map(|hit| hit.page)
+                    .collect(),
+            };
+            show_query_results(query, &results, format)
+        }
+        Some(Command::Find { ref pattern, ref from, ref index, limit }) => {
+            let pages = match from {
+                Some(from) => load_results(from)?,
+                None => adoc::index::load_all(index)?,
+            };
+            let matches = fuzzy_find(pages, pattern, limit);
+            if matches.is_empty() {
+                println!("{}", tr!("没有找到与 \"{}\" 匹配的文档", "No documents match \"{}\"", pattern));
+            }
+            for found in matches {
+                println!("{:>4}  {}", found.score, found.page.title);
+                println!("      {}", found.page.url);
+            }
+            Ok(())
+        }
+        Some(Command::Diff { ref old, ref new, format, ref output, ref webhook, webhook_format }) => {
+            let diff = diff_pages(&load_results(old)?, &load_results(new)?);
+            info!("{}", diff.summary());
+            let content = diff.render(format)?;
+            match output {
+                Some(path) => std::fs::write(path, content)?,
+                None => print!("{}", content),
+            }
+            if let Some(webhook) = webhook {
+                if args.offline {
+                    return Err(anyhow::anyhow!(tr!(
+                        "离线模式下不发送 webhook 通知",
+                        "webhook notifications are not sent in offline mode"
+                    ))
+                    .context(Failure::InvalidInput));
+                }
+                notify(webhook, &diff, webhook_format).await?;
+            }
+            Ok(())
+        }
+        Some(Command::Convert { ref input, format, ref output }) => {
+            let mut results = load_results(input)?;
+            arrange(&mut results, args.sort, args.group_by);
+            match output {
+                Some(path) => {
+                    save_pages_grouped(results.as_slice(), path, format, args.group_by)?;
+                    info!(
+                        "{}",
+                        tr!(
+                            "已将 {} 个页面转换为 {} 格式: {}",
+                            "Converted {} pages to {}: {}",
+                            results.len(),
+                            format,
+                            path.display()
+                        )
+                    );
+                    Ok(())
+                }
+                None => print_pages_grouped(results.as_slice(), format, args.group_by),
+            }
+        }
+        Some(Command::ImportDocc { ref archive, ref base_url, format, ref output }) => {
+            let mut pages = adoc::docc::import_docc(archive, base_url).context(Failure::InvalidInput)?;
+            info!("{}", tr!("从 {} 导入 {} 个页面", "Imported {} pages from {}", pages.len(), archive.display()));
+            arrange(&mut pages, args.sort, args.group_by);
+            match output {
+                Some(path) => save_pages_grouped(pages.as_slice(), path, format, args.group_by),
+                None => print_pages_grouped(pages.as_slice(), format, args.group_by),
+            }
+        }
+        Some(Command::Merge { ref inputs, ref output, format }) => merge(&args, inputs, output, format),
+        Some(Command::Stats { ref from, format }) => {
+            let stats = compute_stats(&load_results(from)?);
+            match format {
+                StatsFormat::Table => print!("{}", stats),
+                StatsFormat::Json => println!("{}", serde_json::to_string_pretty(&stats)?),
+            }
+            Ok(())
+        }
+        Some(Command::Serve { ref from, port, host }) => {
+            let mut results = load_results(from)?;
+            arrange(&mut results, args.sort, args.group_by);
+            info!("{}", tr!("加载 {} 个页面: {}", "Loaded {} pages: {}", results.len(), from.display()));
+            adoc::serve::serve(results, (host, port).into(), start_metrics(&args).await?).await
+        }
+        Some(Command::Completions { shell }) => {
+            let mut command = localize(Args::command());
+            // 补全 --profile 时列出生成脚本时配置文件中定义的 profile
+            let config = FileConfig::discover(args.config.as_deref()).context(Failure::InvalidInput)?;
+            if !config.profile.is_empty() {
+                let profiles = PossibleValuesParser::new(config.profile.into_keys());
+                command = command.mut_arg("profile", |arg| arg.value_parser(profiles));
+            }
+            clap_complete::generate(shell, &mut command, "adoc", &mut std::io::stdout());
+            Ok(())
+        }
+        Some(Command::Schema { page }) => {
+            println!("{}", serde_json::to_string_pretty(&adoc::schema::json_schema(page))?);
+            Ok(())
+        }
+        Some(Command::SelfUpdate { check }) => {
+            if args.offline {
+                return Err(anyhow::anyhow!(tr!("离线模式下无法检查更新", "cannot check for updates in offline mode"))
+                    .context(Failure::InvalidInput));
+            }
+            adoc::update::self_update(check).await?;
+            Ok(())
+        }
+        Some(Command::Verify { ref mirror, ref expected, refetch }) => verify(&args, mirror, expected.as_deref(), refetch).await,
+        #[cfg(feature = "grpc")]
+        Some(Command::Grpcd { addr }) => adoc::grpc::serve(crawler_config(&args)?, addr).await,
+        None => crawl(args).await,
+    }
+}
+
+/// 参数是否应当取配置文件中的值（命令行未显式给出）
+fn from_config(matches: &ArgMatches, id: &str) -> bool {
+    matches!(matches.value_source(id), None | Some(ValueSource::DefaultValue))
+}
+
+/// 用配置文件中的值填充命令行未给出的参数
+fn apply_config(args: &mut Args, matches: &ArgMatches, file: FileConfig) {
+    macro_rules! merge {
+        ($($field:ident),*) => {$(
+            if let Some(value) = file.$field {
+                if from_config(matches, stringify!($field)) {
+                    args.$field = value;
+                }
+            }
+        )*};
+    }
+    macro_rules! merge_optional {
+        ($($field:ident),*) => {$(
+            if file.$field.is_some() && from_config(matches, stringify!($field)) {
+                args.$field = file.$field;
+            }
+        )*};
+    }
+
+    merge!(
+        recursive, dry_run, stream, concurrency, adaptive_concurrency, max_concurrency, format, chunk_overlap,
+        embed_model, embed_batch_size, summarize_workers, tags, sink_index, sink_primary_key, sink_searchable,
+        topic, redis_key, max_retries, timeout, http_version, http2_adaptive_window, visited, expected_urls,
+        progress, dashboard, log_level, log_format, first, source, allow_domain, allow_external,
+        sample_readme, with_http_meta, excerpt_only, search_pages, tombstones, breaker_threshold, offline,
+        insecure, ipv4_only, ipv6_only
+    );
+    merge_optional!(
+        site_profile, redis_url, input, retry_failed, top, output, report, merge_into, delta_output, git_repo,
+        failures, download_media, chunk_size, embed, embed_key, summarize, translate, translator, token_stats,
+        include_raw_html, glossary, sort, group_by, sink, sink_url, sink_key, sink_batch_size, database_id,
+        space, parent_page, brokers, progress_file, metrics_addr, max_duration, breaker_cooldown,
+        memory_budget, pool_max_idle_per_host, pool_idle_timeout, tcp_keepalive, max_body_size,
+        max_content_chars, cache_dir, ca_cert, dns, lang
+    );
+}
+
+fn crawler_config(args: &Args) -> Result<CrawlerConfig> {
+    let ca_certs = match &args.ca_cert {
+        Some(path) => adoc::crawler::load_ca_certs(path).context(Failure::InvalidInput)?,
+        None => Vec::new(),
+    };
+    if args.insecure {
+        warn!("{}", tr!("已关闭 HTTPS 证书校验", "HTTPS certificate verification is disabled"));
+    }
+    let ip_family = match (args.ipv4_only, args.ipv6_only) {
+        (false, false) => IpFamily::Any,
+        (true, false) => IpFamily::V4,
+        (false, true) => IpFamily::V6,
+        (true, true) => {
+            return Err(anyhow::anyhow!(tr!(
+                "ipv4-only 和 ipv6-only 不能同时设置",
+                "ipv4-only and ipv6-only cannot both be set"
+            ))
+            .context(Failure::InvalidInput))
+        }
+    };
+    let profile = match (&args.site_profile, args.source.profile()) {
+        (Some(_), Some(_)) => {
+            return Err(anyhow::anyhow!(tr!(
+                "--site-profile 不能与 --source {} 同时使用",
+                "--site-profile cannot be combined with --source {}",
+                args.source
+            ))
+            .context(Failure::InvalidInput))
+        }
+        (None, Some(profile)) => profile,
+        (Some(path), None) => {
+            let profile = SiteProfile::load(path).context(Failure::InvalidInput)?;
+            info!("{}", tr!("使用站点配置: {}", "Using site profile: {}", profile.name));
+            profile
+        }
+        (None, None) => SiteProfile::default(),
+    };
+    let cache = match &args.cache_dir {
+        Some(dir) => Some(Arc::new(ResponseCache::open(dir, args.offline).context(Failure::InvalidInput)?)),
+        None if args.offline => {
+            return Err(anyhow::anyhow!(tr!(
+                "--offline 需要用 --cache-dir 指定缓存目录",
+                "--offline needs --cache-dir to read pages from"
+            ))
+            .context(Failure::InvalidInput))
+        }
+        None => None,
+    };
+    if let (Some(dir), true) = (&args.cache_dir, args.offline) {
+        info!("{}", tr!("离线模式，只从缓存读取: {}", "Offline mode, reading from cache only: {}", dir.display()));
+    }
+    Ok(CrawlerConfig {
         max_retries: args.max_retries,
         concurrency: args.concurrency,
         timeout: std::time::Duration::from_secs(args.timeout),
+        pool_max_idle_per_host: args.pool_max_idle_per_host,
+        pool_idle_timeout: args.pool_idle_timeout,
+        tcp_keepalive: args.tcp_keepalive,
+        http_version: args.http_version,
+        http2_adaptive_window: args.http2_adaptive_window,
+        max_body_size: args.max_body_size,
+        link_filter: LinkFilter {
+            allowed_domains: args.allow_domain.clone(),
+            allow_external: args.allow_external,
+        },
+        profile: Arc::new(profile),
+        search_pages: args.search_pages,
+        ca_certs,
+        insecure: args.insecure,
+        ip_family,
+        dns: args.dns.clone().unwrap_or_default(),
+        sample_readme: args.sample_readme,
+        http_meta: args.with_http_meta,
+        raw_html: args.include_raw_html,
+        content_limit: ContentLimit {
+            excerpt_only: args.excerpt_only,
+            max_chars: args.max_content_chars,
+        },
+        cache,
+    })
+}
+
+/// 检查缓存镜像，`refetch` 时重新下载有问题的条目后再检查一次
+async fn verify(args: &Args, mirror: &Path, expected: Option<&Path>, refetch: bool) -> Result<()> {
+    let expected = match expected {
+        Some(path) => load_failed_urls(path).context(Failure::InvalidInput)?,
+        None => Vec::new(),
+    };
+    let mut report = verify_mirror(mirror, &expected).context(Failure::InvalidInput)?;
+    let broken = report.broken_urls();
+    if refetch && !broken.is_empty() {
+        if args.offline {
+            return Err(anyhow::anyhow!(tr!("离线模式下无法重新下载", "cannot re-fetch in offline mode"))
+                .context(Failure::InvalidInput));
+        }
+        info!("{}", tr!("重新下载 {} 个条目", "Re-fetching {} entries", broken.len()));
+        let cache = Arc::new(ResponseCache::open(mirror, false)?);
+        let mut crawler = Crawler::new(CrawlerConfig {
+            cache: Some(cache.clone()),
+            ..crawler_config(args)?
+        });
+        for url in &broken {
+            if let Err(e) = crawler.crawl_url(url, false).await {
+                warn!("{}", tr!("重新下载失败: {}, 错误: {:#}", "Re-fetch failed: {}, error: {:#}", url, e));
+            }
+        }
+        cache.save_manifest()?;
+        report = verify_mirror(mirror, &expected)?;
+    }
+    print!("{}", report);
+    if !report.is_ok() {
+        return Err(anyhow::anyhow!(tr!(
+            "镜像校验发现 {} 个问题",
+            "mirror verification found {} problems",
+            report.problems()
+        ))
+        .context(Failure::Partial));
+    }
+    Ok(())
+}
+
+/// 把本次写入缓存的文件记录到缓存清单，失败时只给出警告
+fn save_cache_manifest(cache: Option<&ResponseCache>) {
+    let Some(cache) = cache.filter(|cache| !cache.is_offline()) else {
+        return;
+    };
+    match cache.save_manifest() {
+        Ok(count) => info!(
+            "{}",
+            tr!(
+                "缓存清单共 {} 个条目: {}",
+                "Cache manifest has {} entries: {}",
+                count,
+                cache.manifest_path().display()
+            )
+        ),
+        Err(e) => warn!("{}", tr!("无法保存缓存清单: {:#}", "Cannot save cache manifest: {:#}", e)),
+    }
+}
+
+fn merge(args: &Args, inputs: &[PathBuf], output: &Path, format: OutputFormat) -> Result<()> {
+    // 按修改时间从旧到新排列，时间相同时保持命令行中的顺序
+    let mut inputs = inputs
+        .iter()
+        .map(|path| Ok((std::fs::metadata(path)?.modified()?, path)))
+        .collect::<Result<Vec<_>>>()?;
+    inputs.sort_by_key(|(modified, _)| *modified);
+
+    let mut sources = Vec::new();
+    for (_, path) in inputs {
+        let pages = load_results(path)?;
+        info!("{}", tr!("加载 {} 个页面: {}", "Loaded {} pages: {}", pages.len(), path.display()));
+        sources.push((path.display().to_string(), pages));
+    }
+
+    let mut merged = merge_results(sources);
+    arrange(&mut merged.pages, args.sort, args.group_by);
+    for conflict in &merged.conflicts {
+        warn!(
+            "{}",
+            tr!(
+                "内容冲突: {}，保留 {}，覆盖 {}",
+                "Conflicting content: {}, kept {}, replaced {}",
+                conflict.url,
+                conflict.kept,
+                conflict.replaced.join(", ")
+            )
+        );
+    }
+    save_pages_grouped(merged.pages.as_slice(), output, format, args.group_by)?;
+    info!(
+        "{}",
+        tr!(
+            "合并完成: {} 个页面，去除 {} 个重复，{} 个冲突，保存到 {}",
+            "Merged {} pages, {} duplicates removed, {} conflicts, saved to {}",
+            merged.pages.len(),
+            merged.duplicates,
+            merged.conflicts.len(),
+            output.display()
+        )
+    );
+    Ok(())
+}
+
+/// 把本次爬取的页面合并进已有的结果文件，文件不存在时直接创建；设置 `delta_output` 时先把相对于已有结果的变更写入该文件
+fn merge_into(results: Vec<DocPage>, path: &Path, format: OutputFormat, delta_output: Option<&Path>) -> Result<()> {
+    let existing = if path.exists() { load_results(path)? } else { Vec::new() };
+    if let Some(delta_path) = delta_output {
+        let delta = delta_pages(&existing, &results);
+        save_delta(delta_path, &delta, matches!(format, OutputFormat::PrettyJson))?;
+        info!(
+            "{}",
+            tr!(
+                "{} 个页面新增或变化，已写入 {}",
+                "{} pages added or changed, written to {}",
+                delta.len(),
+                delta_path.display()
+            )
+        );
+    }
+    let merged = merge_results(vec![
+        (path.display().to_string(), existing),
+        ("crawl".to_string(), results),
+    ]);
+    // 合并结果需要能再次读取，只能保存为 JSON 或 SQLite
+    let format = match format {
+        _ if is_sqlite_path(path) => OutputFormat::Sqlite,
+        OutputFormat::PrettyJson => OutputFormat::PrettyJson,
+        _ => OutputFormat::Json,
+    };
+    save_results(&merged.pages, path, format)?;
+    info!(
+        "{}",
+        tr!(
+            "已合并到 {}: 共 {} 个页面，更新 {} 个",
+            "Merged into {}: {} pages, {} updated",
+            path.display(),
+            merged.pages.len(),
+            merged.duplicates
+        )
+    );
+    Ok(())
+}
+
+/// 设置了 `--embed` 时为分块生成嵌入向量
+#[cfg(feature = "embed")]
+async fn embed_chunks(args: &Args, chunks: &mut [Chunk]) -> Result<()> {
+    let Some(url) = &args.embed else {
+        return Ok(());
     };
+    info!("{}", tr!("生成嵌入向量: {}", "Generating embeddings: {}", args.embed_model));
+    let client = adoc::embed::EmbeddingClient::new(adoc::embed::EmbeddingConfig {
+        url: url.clone(),
+        model: args.embed_model.clone(),
+        api_key: args.embed_key.clone(),
+        batch_size: args.embed_batch_size,
+    });
+    client.embed_chunks(chunks).await
+}
+
+/// 未启用 embed 特性时 `--embed` 在开始爬取前已报错，这里无事可做
+#[cfg(not(feature = "embed"))]
+async fn embed_chunks(_args: &Args, _chunks: &mut [Chunk]) -> Result<()> {
+    Ok(())
+}
+
+/// 设置了 `--dashboard` 时显示监控面板，由面板接收进度事件
+#[cfg(feature = "dashboard")]
+fn start_dashboard(args: &Args, crawler: &mut Crawler) -> Result<Option<adoc::dashboard::Dashboard>> {
+    if !args.dashboard {
+        return Ok(None);
+    }
+    let dashboard = adoc::dashboard::Dashboard::start()?;
+    crawler.set_progress_events(dashboard.events());
+    Ok(Some(dashboard))
+}
+
+/// 未启用 dashboard 特性时 `--dashboard` 在开始爬取前已报错，这里无事可做
+#[cfg(not(feature = "dashboard"))]
+fn start_dashboard(_args: &Args, _crawler: &mut Crawler) -> Result<Option<()>> {
+    Ok(None)
+}
+
+/// 设置了 `--summarize` 时为每个页面生成摘要
+#[cfg(feature = "summarize")]
+async fn summarize_pages(args: &Args, pages: &mut [DocPage]) -> Result<()> {
+    let Some(target) = &args.summarize else {
+        return Ok(());
+    };
+    let config = adoc::summarize::SummarizerConfig::parse(target, args.summarize_workers)
+        .context(Failure::InvalidInput)?;
+    info!("{}", tr!("生成页面摘要: {} ({})", "Summarizing pages: {} ({})", config.model, config.url));
+    let summarizer = adoc::summarize::Summarizer::new(config);
+    let summarized = summarizer.summarize_pages(pages).await;
+    info!("{}", tr!("已为 {} 个页面生成摘要", "Summarized {} pages", summarized));
+    Ok(())
+}
+
+/// 未启用 summarize 特性时 `--summarize` 在开始爬取前已报错，这里无事可做
+#[cfg(not(feature = "summarize"))]
+async fn summarize_pages(_args: &Args, _pages: &mut [DocPage]) -> Result<()> {
+    Ok(())
+}
+
+/// 设置了 `--translate` 时翻译每个页面的正文
+async fn translate_pages(args: &Args, pages: &mut [DocPage]) -> Result<()> {
+    let (Some(target), Some(spec)) = (&args.translate, &args.translator) else {
+        return Ok(());
+    };
+    let translator = Translator::parse(spec).context(Failure::InvalidInput)?;
+    info!("{}", tr!("翻译页面正文为 {}", "Translating page content into {}", target));
+    let translated = PageTranslator::new(translator, target).translate_pages(pages).await;
+    info!("{}", tr!("已翻译 {} 个页面", "Translated {} pages", translated));
+    Ok(())
+}
+
+/// 把结果写入 git 快照并提交。只有完整的递归爬取才删除起始页面层级下没有出现的页面，
+/// 部分结果（超时、熔断、重试失败页面）中缺少的页面不代表已被删除
+fn save_snapshot(
+    repo: &Path,
+    pages: &(impl PageSource + ?Sized),
+    seeds: &[String],
+    report: &CrawlReport,
+    args: &Args,
+) -> Result<()> {
+    let snapshot = GitSnapshot::open(repo)?;
+    let complete = args.recursive && args.retry_failed.is_none() && !report.truncated && !report.circuit_broken;
+    let scopes = if complete { seeds } else { &[] };
+    let keep: Vec<String> = report.failures.iter().map(|failure| failure.url.clone()).collect();
+    let changes = snapshot.write(pages, scopes, &keep)?;
+    if snapshot.commit(&changes)? {
+        info!(
+            "{}",
+            tr!(
+                "已提交到 {}: 新增 {}，修改 {}，删除 {}",
+                "Committed to {}: {} added, {} changed, {} removed",
+                repo.display(),
+                changes.added.len(),
+                changes.changed.len(),
+                changes.removed.len()
+            )
+        );
+    } else {
+        info!("{}", tr!("文档没有变化，无需提交", "No changes to commit"));
+    }
+    Ok(())
+}
+
+/// 推送目标缺少的必需选项
+fn missing_sink_option(args: &Args) -> Option<String> {
+    match args.sink? {
+        SinkKind::Notion if args.sink_key.is_none() || args.database_id.is_none() => Some(tr!(
+            "推送到 Notion 时必须指定 sink-key 和 database-id",
+            "sink-key and database-id are required for the Notion sink"
+        )),
+        SinkKind::Notion => None,
+        SinkKind::Kafka if args.brokers.is_none() => Some(tr!(
+            "推送到 Kafka 时必须指定 brokers",
+            "brokers is required for the Kafka sink"
+        )),
+        SinkKind::Kafka => None,
+        _ if args.sink_url.is_none() => Some(tr!(
+            "设置 sink 时必须同时指定 sink-url",
+            "sink-url is required when sink is set"
+        )),
+        SinkKind::Confluence if args.sink_key.is_none() || args.space.is_none() => Some(tr!(
+            "发布到 Confluence 时必须指定 sink-key 和 space",
+            "sink-key and space are required for the Confluence sink"
+        )),
+        _ => None,
+    }
+}
+
+async fn create_sink(args: &Args, kind: SinkKind) -> Result<Box<dyn PageSink>> {
+    let url = match (&args.sink_url, kind) {
+        (Some(url), _) => url.clone(),
+        (None, SinkKind::Notion) => DEFAULT_NOTION_URL.to_string(),
+        (None, _) => String::new(),
+    };
+    info!("{}", tr!("推送目标: {:?} ({})", "Sink: {:?} ({})", kind, url));
+    match kind {
+        SinkKind::Meilisearch => {
+            let config = MeilisearchConfig {
+                url,
+                api_key: args.sink_key.clone(),
+                index: args.sink_index.clone(),
+                primary_key: args.sink_primary_key.clone(),
+                searchable_fields: args.sink_searchable.clone(),
+                batch_size: args.sink_batch_size.unwrap_or(100),
+            };
+            Ok(Box::new(MeilisearchSink::new(config).await?))
+        }
+        SinkKind::Elasticsearch => {
+            let config = ElasticsearchConfig {
+                url,
+                api_key: args.sink_key.clone(),
+                index: args.sink_index.clone(),
+                batch_size: args.sink_batch_size.unwrap_or(500),
+            };
+            Ok(Box::new(ElasticsearchSink::new(config)))
+        }
+        SinkKind::Notion => {
+            let config = NotionConfig {
+                url,
+                api_key: args.sink_key.clone().unwrap_or_default(),
+                database_id: args.database_id.clone().unwrap_or_default(),
+            };
+            Ok(Box::new(NotionSink::new(config).await?))
+        }
+        SinkKind::Confluence => {
+            let config = ConfluenceConfig {
+                url,
+                api_key: args.sink_key.clone().unwrap_or_default(),
+                space: args.space.clone().unwrap_or_default(),
+                parent_page: args.parent_page.clone(),
+            };
+            Ok(Box::new(ConfluenceSink::new(config)))
+        }
+        SinkKind::Webhook => {
+            let config = WebhookConfig {
+                url,
+                api_key: args.sink_key.clone(),
+                batch_size: args.sink_batch_size.unwrap_or(1),
+                max_retries: args.max_retries,
+            };
+            Ok(Box::new(WebhookSink::new(config)))
+        }
+        SinkKind::Kafka => kafka_sink(args),
+    }
+}
+
+#[cfg(feature = "redis")]
+async fn redis_visited(url: &str, key: &str) -> Result<Arc<dyn DistributedVisited>> {
+    let visited = adoc::distributed::RedisVisited::connect(url, key).await?;
+    info!("{}", tr!("通过 Redis 共享已访问集合: {}", "Sharing visited URLs through Redis: {}", key));
+    Ok(Arc::new(visited))
+}
+
+#[cfg(not(feature = "redis"))]
+async fn redis_visited(_url: &str, _key: &str) -> Result<Arc<dyn DistributedVisited>> {
+    Err(anyhow::anyhow!(tr!(
+        "未启用 redis 特性，无法通过 Redis 共享已访问集合",
+        "the redis feature is disabled, cannot share visited URLs through Redis"
+    ))
+    .context(Failure::InvalidInput))
+}
+
+#[cfg(feature = "kafka")]
+fn kafka_sink(args: &Args) -> Result<Box<dyn PageSink>> {
+    let config = adoc::sink::KafkaConfig {
+        brokers: args.brokers.clone().unwrap_or_default(),
+        topic: args.topic.clone(),
+        timeout: Duration::from_secs(args.timeout),
+    };
+    Ok(Box::new(adoc::sink::KafkaSink::new(config)?))
+}
+
+#[cfg(not(feature = "kafka"))]
+fn kafka_sink(_args: &Args) -> Result<Box<dyn PageSink>> {
+    Err(anyhow::anyhow!(tr!(
+        "未启用 kafka 特性，无法推送到 Kafka",
+        "the kafka feature is disabled, cannot publish to Kafka"
+    ))
+    .context(Failure::InvalidInput))
+}
+
+/// 设置了 `--metrics-addr` 时启动指标服务
+async fn start_metrics(args: &Args) -> Result<Option<Arc<Metrics>>> {
+    let Some(addr) = args.metrics_addr else {
+        return Ok(None);
+    };
+    let metrics = Arc::new(Metrics::new());
+    serve_metrics(metrics.clone(), addr)
+        .await
+        .with_context(|| tr!("无法在 {} 上提供指标", "cannot serve metrics on {}", addr))
+        .context(Failure::InvalidInput)?;
+    Ok(Some(metrics))
+}
+
+fn build_index(from: &Path, out: &Path) -> Result<()> {
+    let results = load_results(from)?;
+    info!(
+        "{}",
+        tr!("为 {} 个页面构建索引: {}", "Building index for {} pages: {}", results.len(), out.display())
+    );
+    adoc::index::build_index(&results, out)?;
+    info!("{}", tr!("索引构建完成", "Index built"));
+    Ok(())
+}
+
+fn show_query_results(query: &str, results: &[DocPage], format: Option<OutputFormat>) -> Result<()> {
+    if let Some(format) = format {
+        return print_results(results, format);
+    }
+    if results.is_empty() {
+        println!("{}", tr!("没有找到与 \"{}\" 匹配的文档", "No documents match \"{}\"", query));
+    }
+    for (i, page) in results.iter().enumerate() {
+        println!("{}. {}", i + 1, page.title);
+        println!("   {}", page.url);
+    }
+    Ok(())
+}
+
+/// 关键字搜索时确定要爬取的起始页面
+///
+/// `--first` / `--top N` 直接取搜索结果；在终端中运行时列出结果供选择；
+/// 非交互环境下爬取汇总去重后的前 [`DEFAULT_SEARCH_TOP`] 个结果，不爬取搜索页本身。
+/// 没有任何文档结果时返回带拼写建议的 [`NoSearchResults`]。
+async fn resolve_search(crawler: &Crawler, keyword: &str, args: &Args) -> Result<Vec<String>> {
+    let interactive = std::io::stdin().is_terminal() && std::io::stderr().is_terminal();
+    let count = match (args.first, args.top) {
+        (true, _) => 1,
+        (false, Some(top)) => top,
+        (false, None) if interactive => 0,
+        (false, None) => DEFAULT_SEARCH_TOP,
+    };
+
+    let results = crawler.search(keyword).await?;
+    if results.is_empty() {
+        return Err(anyhow::Error::new(NoSearchResults::new(keyword)).context(Failure::NothingExtracted));
+    }
+    if count > 0 {
+        if !args.first && args.top.is_none() && results.len() > count {
+            info!(
+                "{}",
+                tr!(
+                    "共 {} 个搜索结果，只爬取前 {} 个，可用 --top N 调整",
+                    "{} search results; crawling the first {}, use --top N to change",
+                    results.len(),
+                    count
+                )
+            );
+        }
+        return Ok(results.into_iter().take(count).map(|result| result.url).collect());
+    }
+
+    for (i, result) in results.iter().take(PICKER_MAX_RESULTS).enumerate() {
+        eprintln!("{:>3}. {}  {}", i + 1, result.title, result.url);
+        if !result.description.is_empty() {
+            eprintln!("     {}", result.description);
+        }
+    }
+    let shown = results.len().min(PICKER_MAX_RESULTS);
+    loop {
+        eprint!(
+            "{}",
+            tr!(
+                "选择要爬取的结果（序号，多个用逗号分隔，直接回车选择第 1 个）: ",
+                "Pick results to crawl (numbers, comma separated; Enter for the first): "
+            )
+        );
+        std::io::stderr().flush()?;
+        let mut line = String::new();
+        if std::io::stdin().read_line(&mut line)? == 0 {
+            return Err(anyhow::anyhow!(tr!(
+                "没有选择搜索结果",
+                "no search result selected"
+            ))
+            .context(Failure::InvalidInput));
+        }
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(vec![results[0].url.clone()]);
+        }
+        let picked: Option<Vec<String>> = line
+            .split(',')
+            .map(|n| match n.trim().parse::<usize>() {
+                Ok(n) if (1..=shown).contains(&n) => Some(results[n - 1].url.clone()),
+                _ => None,
+            })
+            .collect();
+        match picked {
+            Some(urls) => return Ok(urls),
+            None => eprintln!("{}", tr!("请输入 1 到 {} 之间的序号", "Enter a number between 1 and {}", shown)),
+        }
+    }
+}
+
+async fn crawl(args: Args) -> Result<()> {
+    let input = args.input.clone().unwrap_or_default();
+    if let Some(chunk_size) = args.chunk_size {
+        adoc::chunk::check_options(chunk_size, args.chunk_overlap).context(Failure::InvalidInput)?;
+    }
+    if args.embed.is_some() && !cfg!(feature = "embed") {
+        return Err(anyhow::anyhow!(tr!(
+            "未启用 embed 特性，无法生成嵌入向量",
+            "the embed feature is disabled, cannot generate embeddings"
+        ))
+        .context(Failure::InvalidInput));
+    }
+    if args.summarize.is_some() && !cfg!(feature = "summarize") {
+        return Err(anyhow::anyhow!(tr!(
+            "未启用 summarize 特性，无法生成页面摘要",
+            "the summarize feature is disabled, cannot summarize pages"
+        ))
+        .context(Failure::InvalidInput));
+    }
+    if args.dashboard && !cfg!(feature = "dashboard") {
+        return Err(anyhow::anyhow!(tr!(
+            "未启用 dashboard 特性，无法显示监控面板",
+            "the dashboard feature is disabled, cannot show the dashboard"
+        ))
+        .context(Failure::InvalidInput));
+    }
+    if args.dashboard && !std::io::stderr().is_terminal() {
+        return Err(anyhow::anyhow!(tr!(
+            "--dashboard 需要在终端中运行（stderr 不是终端）",
+            "--dashboard needs a terminal (stderr is not a terminal)"
+        ))
+        .context(Failure::InvalidInput));
+    }
+    // 全局选项不能用 conflicts_with 限制，否则子命令中无法使用
+    if (args.sort.is_some() || args.group_by.is_some()) && (args.stream || args.memory_budget.is_some()) {
+        return Err(anyhow::anyhow!(tr!(
+            "--sort 和 --group-by 需要在输出前得到所有页面，不能与 --stream 或 --memory-budget 同时使用",
+            "--sort and --group-by need all pages before output and cannot be used with --stream or --memory-budget"
+        ))
+        .context(Failure::InvalidInput));
+    }
+    // 翻译在爬取完成后才开始，先检查翻译器，避免白白爬取一遍
+    if let Some(spec) = &args.translator {
+        Translator::parse(spec).context(Failure::InvalidInput)?;
+    }
+    if let Some(encoding) = args.token_stats {
+        encoding.check().context(Failure::InvalidInput)?;
+    }
+    // 示例项目和媒体文件不在缓存中，离线时无法下载
+    if args.offline {
+        let needs_network = [("--sample-readme", args.sample_readme), ("--download-media", args.download_media.is_some())];
+        if let Some((option, _)) = needs_network.iter().find(|(_, set)| *set) {
+            return Err(anyhow::anyhow!(tr!(
+                "{} 需要访问网络，不能与 --offline 同时使用",
+                "{} needs network access and cannot be combined with --offline",
+                option
+            ))
+            .context(Failure::InvalidInput));
+        }
+    }
+    if args.tombstones && !matches!(args.format, OutputFormat::Json | OutputFormat::PrettyJson) {
+        return Err(anyhow::anyhow!(tr!(
+            "--tombstones 不支持 {} 格式",
+            "--tombstones does not support the {} format",
+            args.format
+        ))
+        .context(Failure::InvalidInput));
+    }
+    info!("{}", tr!("启动 adoc 爬虫工具...", "Starting adoc..."));
+    
+    let config = crawler_config(&args)?;
     
     info!(
-        "配置信息: 并发数={}, 超时={}s, 重试次数={}", 
-        config.concurrency, 
-        config.timeout.as_secs(), 
-        config.max_retries
+        "{}",
+        tr!(
+            "配置信息: 并发数={}, 超时={}s, 重试次数={}",
+            "Config: concurrency={}, timeout={}s, retries={}",
+            config.concurrency,
+            config.timeout.as_secs(),
+            config.max_retries
+        )
     );
     
+    if let Some(message) = missing_sink_option(&args) {
+        return Err(anyhow::anyhow!(message).context(Failure::InvalidInput));
+    }
+
+    let cache = config.cache.clone();
     let mut crawler = Crawler::new(config);
+    crawler.set_tombstones(args.tombstones);
+    if args.adaptive_concurrency {
+        crawler.set_adaptive_concurrency(args.max_concurrency);
+    }
+    if args.visited == VisitedKind::Bloom {
+        let visited = SharedVisited::new(args.visited, args.expected_urls);
+        info!(
+            "{}",
+            tr!(
+                "使用布隆过滤器记录已访问 URL，预计 {} 个，占用 {} KB",
+                "Tracking visited URLs with a Bloom filter: {} expected, {} KB",
+                args.expected_urls,
+                visited.size_bytes() / 1024
+            )
+        );
+        crawler.set_visited(visited);
+    }
+    if let Some(metrics) = start_metrics(&args).await? {
+        crawler.set_metrics(metrics);
+    }
+    if let Some(url) = &args.redis_url {
+        crawler.set_distributed_visited(redis_visited(url, &args.redis_key).await?);
+    }
+    if let Some(max_duration) = args.max_duration {
+        crawler.set_deadline(Instant::now() + max_duration);
+    }
+    if args.breaker_threshold > 0 {
+        crawler.set_circuit_breaker(args.breaker_threshold, args.breaker_cooldown);
+    }
+    if args.progress == ProgressFormat::Json {
+        let events = match &args.progress_file {
+            Some(path) => ProgressEvents::file(path)?,
+            None => ProgressEvents::stderr(),
+        };
+        crawler.set_progress_events(events);
+    }
+    // 内置来源从起始地址递归爬取，起始地址本身只是索引
+    let recursive = args.recursive || args.source.seed().is_some();
+    let seeds = if let Some(path) = &args.retry_failed {
+        let urls = load_failed_urls(path).context(Failure::InvalidInput)?;
+        info!("{}", tr!("重新爬取 {} 个失败页面", "Retrying {} failed pages", urls.len()));
+        urls
+    } else if let Some(seed) = args.source.seed() {
+        vec![seed.to_string()]
+    } else if input.starts_with("http") {
+        vec![input]
+    } else {
+        resolve_search(&crawler, &input, &args).await?
+    };
+    if args.dry_run {
+        let mut urls: Vec<String> = Vec::new();
+        for seed in &seeds {
+            for url in crawler.plan_url(seed, recursive).await? {
+                if !urls.contains(&url) {
+                    urls.push(url);
+                }
+            }
+        }
+        for url in &urls {
+            println!("{}", url);
+        }
+        info!("{}", tr!("将爬取 {} 个页面", "{} pages would be crawled", urls.len()));
+        return Ok(());
+    }
+    if let Some(kind) = args.sink {
+        crawler.add_sink(create_sink(&args, kind).await?);
+    }
+    if let Some(dir) = &args.download_media {
+        info!("{}", tr!("下载媒体文件到: {}", "Downloading media files to: {}", dir.display()));
+        let timeout = Duration::from_secs(args.timeout);
+        crawler.add_sink(Box::new(MediaDownloadSink::create(dir, timeout).context(Failure::InvalidInput)?));
+    }
+    if let (true, Some(output_path)) = (args.stream, &args.output) {
+        let pretty = match args.format {
+            OutputFormat::Json => false,
+            OutputFormat::PrettyJson => true,
+            format => {
+                return Err(anyhow::anyhow!(tr!(
+                    "--stream 不支持 {} 格式",
+                    "--stream does not support the {} format",
+                    format
+                ))
+                .context(Failure::InvalidInput))
+            }
+        };
+        crawler.add_sink(Box::new(JsonFileSink::create(output_path, pretty)?));
+        crawler.set_retain_pages(false);
+    }
+    let spill = args.memory_budget.map(|budget| Arc::new(SpillBuffer::new(budget)));
+    if let Some(spill) = &spill {
+        crawler.add_sink(Box::new(spill.clone()));
+        crawler.set_retain_pages(false);
+    }
     
-    info!("开始爬取: {}", args.input);
-    let results = if args.input.starts_with("http") {
-        crawler.crawl_url(&args.input, args.recursive).await?
+    let mut results = Vec::new();
+    let mut report = CrawlReport::default();
+    {
+        // 监控面板在交互选择搜索结果之后才占用终端，离开这个作用域时关闭
+        let _dashboard = start_dashboard(&args, &mut crawler)?;
+        for seed in &seeds {
+            info!("{}", tr!("开始爬取: {}", "Crawling: {}", seed));
+            match crawler.crawl_url(seed, recursive).await {
+                Ok((pages, seed_report)) => {
+                    results.extend(pages);
+                    report.merge(seed_report);
+                }
+                // 重试时单个页面失败不影响其余页面，仍记录到失败列表中
+                Err(e) if args.retry_failed.is_some() => {
+                    warn!("{}", tr!("重试失败: {}, 错误: {:#}", "Retry failed: {}, error: {:#}", seed, e));
+                    report.record_failed(failed_page(seed, &e), 0);
+                }
+                Err(e) => {
+                    save_cache_manifest(cache.as_deref());
+                    return Err(e);
+                }
+            }
+        }
+    }
+    save_cache_manifest(cache.as_deref());
+    let spilled = spill.map(|spill| spill.finish()).transpose()?;
+    if let Some(spilled) = spilled.as_ref().filter(|spilled| spilled.spilled() > 0) {
+        info!(
+            "{}",
+            tr!(
+                "超出内存预算，{} 个页面已溢出到临时文件",
+                "Memory budget exceeded, {} pages spilled to a temporary file",
+                spilled.spilled()
+            )
+        );
+    }
+    let fetched = spilled.as_ref().map_or(results.len(), |spilled| spilled.len());
+    info!("{}", tr!("爬取完成，共获取 {} 个页面", "Crawl finished, fetched {} pages", fetched));
+    if report.truncated {
+        warn!("{}", tr!("已超出 --max-duration，输出部分结果", "--max-duration exceeded, writing partial results"));
+    }
+    if report.circuit_broken {
+        warn!("{}", tr!("连续失败触发熔断，输出部分结果", "Circuit breaker tripped, writing partial results"));
+    }
+    if let Some(encoding) = args.token_stats {
+        report.content = Some(adoc::tokens::measure_pages(&mut results, encoding)?);
+    }
+    eprintln!("{}", report);
+
+    if let Some(report_path) = &args.report {
+        info!("{}", tr!("保存爬取报告: {}", "Saving crawl report: {}", report_path.display()));
+        report.save(report_path)?;
+    }
+    if let Some(failures_path) = &args.failures {
+        info!("{}", tr!("保存失败页面列表: {}", "Saving failed pages: {}", failures_path.display()));
+        report.save_failures(failures_path)?;
+    }
+
+    let nothing_extracted = if args.stream {
+        report.pages_fetched == 0
+    } else if let Some(spilled) = &spilled {
+        !spilled.has_content()
     } else {
-        crawler.search_and_crawl(&args.input, args.recursive).await?
+        results.iter().all(|page| page.title.trim().is_empty() && page.content.trim().is_empty())
     };
-    info!("爬取完成，共获取 {} 个页面", results.len());
+    if nothing_extracted {
+        return Err(anyhow::anyhow!(Failure::NothingExtracted));
+    }
+    summarize_pages(&args, &mut results).await?;
+    translate_pages(&args, &mut results).await?;
+    if args.tags {
+        adoc::tags::tag_pages(&mut results);
+    }
+    arrange(&mut results, args.sort, args.group_by);
+    let glossary = args.glossary.as_ref().map(|_| build_glossary(&results));
+    if let (Some(path), Some(glossary)) = (&args.glossary, &glossary) {
+        info!("{}", tr!("保存术语表（{} 个符号）: {}", "Saving glossary ({} symbols): {}", glossary.len(), path.display()));
+        save_glossary(glossary, path)?;
+    }
+    // 术语表附在 Markdown 输出的末尾
+    let markdown_glossary = glossary.filter(|_| matches!(args.format, OutputFormat::Markdown));
 
-    if let Some(output_path) = args.output {
-        info!("保存结果到文件: {}", output_path.display());
-        save_results(&results, &output_path, args.format)?;
-        info!("文件保存成功");
+    if let Some(chunk_size) = args.chunk_size {
+        let mut chunks = adoc::chunk::chunk_pages(&results, chunk_size, args.chunk_overlap)?;
+        info!("{}", tr!("共生成 {} 个内容分块", "Generated {} chunks", chunks.len()));
+        embed_chunks(&args, &mut chunks).await?;
+        match args.output {
+            Some(output_path) if is_sqlite_path(&output_path) => {
+                info!("{}", tr!("保存分块到数据库: {}", "Saving chunks to database: {}", output_path.display()));
+                adoc::sqlite::save_chunks(&chunks, &output_path)?;
+            }
+            Some(output_path) => {
+                let content = adoc::chunk::to_jsonl(&chunks)?;
+                info!("{}", tr!("保存分块到文件: {}", "Saving chunks to: {}", output_path.display()));
+                std::fs::write(&output_path, content)?;
+            }
+            None => print!("{}", adoc::chunk::to_jsonl(&chunks)?),
+        }
+    } else if args.stream {
+        info!("{}", tr!("结果已写入文件", "Results written"));
+    } else if let Some(repo) = &args.git_repo {
+        match &spilled {
+            Some(spilled) => save_snapshot(repo, spilled, &seeds, &report, &args)?,
+            None => save_snapshot(repo, results.as_slice(), &seeds, &report, &args)?,
+        }
+    } else if let Some(spilled) = &spilled {
+        match &args.output {
+            Some(output_path) => {
+                info!("{}", tr!("保存结果到文件: {}", "Saving results to: {}", output_path.display()));
+                save_pages(spilled, output_path, args.format)?;
+                info!("{}", tr!("文件保存成功", "Saved"));
+            }
+            None => print_pages(spilled, args.format)?,
+        }
+    } else if let Some(merge_path) = &args.merge_into {
+        merge_into(results, merge_path, args.format, args.delta_output.as_deref())?;
+    } else if let Some(output_path) = args.output {
+        info!("{}", tr!("保存结果到文件: {}", "Saving results to: {}", output_path.display()));
+        save_pages_grouped(results.as_slice(), &output_path, args.format, args.group_by)?;
+        if let Some(glossary) = &markdown_glossary {
+            let mut file = std::fs::OpenOptions::new().append(true).open(&output_path)?;
+            adoc::glossary::write_markdown(glossary, &mut file)?;
+        }
+        info!("{}", tr!("文件保存成功", "Saved"));
     } else {
-        info!("打印结果到控制台");
-        print_results(&results, args.format);
+        info!("{}", tr!("打印结果到控制台", "Printing results"));
+        print_pages_grouped(results.as_slice(), args.format, args.group_by)?;
+        if let Some(glossary) = &markdown_glossary {
+            adoc::glossary::write_markdown(glossary, &mut std::io::stdout().lock())?;
+        }
     }
 
-    info!("任务完成");
+    if report.pages_failed > 0 {
+        return Err(anyhow::anyhow!(tr!(
+            "{} 个页面爬取失败",
+            "{} pages failed",
+            report.pages_failed
+        ))
+        .context(Failure::Partial));
+    }
+    info!("{}", tr!("任务完成", "Done"));
     Ok(())
 }
@@ -0,0 +1,227 @@
+//! 页面中嵌入或链接的视频、音频的提取。
+//!
+//! 文档页面中的演示视频、WWDC 视频页面的播放地址和下载链接原本只能从正文里找，这里把它们提取为结构化的
+//! [`Media`]（类型、标题、时长、地址），保存在 [`DocPage::media`](crate::extract::DocPage::media) 中：
+//! HTML 页面取 `<video>`、`<audio>`、`og:video` 和指向媒体文件的链接，DocC render JSON 取 `video` 类型的引用。
+//! 设置 `--download-media` 时由 [`crate::sink::MediaDownloadSink`] 把媒体文件下载到本地目录。
+
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use serde_json::Value;
+use std::sync::OnceLock;
+use url::Url;
+
+use crate::extract::clean_text;
+
+/// 媒体类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaKind {
+    Video,
+    Audio,
+}
+
+impl std::fmt::Display for MediaKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            MediaKind::Video => "video",
+            MediaKind::Audio => "audio",
+        })
+    }
+}
+
+/// 页面中的一个视频或音频
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Media {
+    #[serde(rename = "type")]
+    pub kind: MediaKind,
+    #[serde(default)]
+    pub title: String,
+    /// 时长（秒），页面没有给出时为 `None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration: Option<u64>,
+    pub url: String,
+}
+
+/// 按文件扩展名判断地址是否指向媒体文件，`.m3u8` 是 HLS 播放列表，也视为视频
+pub fn media_kind(url: &str) -> Option<MediaKind> {
+    let url = Url::parse(url).ok()?;
+    let (_, extension) = url.path().rsplit_once('.')?;
+    match extension.to_ascii_lowercase().as_str() {
+        "mp4" | "m4v" | "mov" | "webm" | "m3u8" => Some(MediaKind::Video),
+        "mp3" | "m4a" | "aac" | "wav" => Some(MediaKind::Audio),
+        _ => None,
+    }
+}
+
+/// 解析时长，支持秒数（`754`）、`12:34` / `1:02:03` 和 ISO 8601（`PT12M34S`）
+pub fn parse_duration(text: &str) -> Option<u64> {
+    let text = text.trim();
+    if let Some(iso) = text.strip_prefix("PT").or_else(|| text.strip_prefix("pt")) {
+        let mut seconds = 0;
+        let mut number = String::new();
+        for c in iso.chars() {
+            match c.to_ascii_uppercase() {
+                '0'..='9' | '.' => number.push(c),
+                unit @ ('H' | 'M' | 'S') => {
+                    let value = number.parse::<f64>().ok()?;
+                    let scale = match unit {
+                        'H' => 3600.0,
+                        'M' => 60.0,
+                        _ => 1.0,
+                    };
+                    seconds += (value * scale).round() as u64;
+                    number.clear();
+                }
+                _ => return None,
+            }
+        }
+        return number.is_empty().then_some(seconds);
+    }
+    if text.contains(':') {
+        let mut seconds = 0;
+        for part in text.split(':') {
+            seconds = seconds * 60 + part.parse::<u64>().ok()?;
+        }
+        return Some(seconds);
+    }
+    text.parse::<f64>().ok().filter(|seconds| *seconds >= 0.0).map(|seconds| seconds.round() as u64)
+}
+
+/// 时长的显示形式，例如 `12:34`、`1:02:03`
+pub fn format_duration(seconds: u64) -> String {
+    match seconds / 3600 {
+        0 => format!("{}:{:02}", seconds / 60, seconds % 60),
+        hours => format!("{}:{:02}:{:02}", hours, seconds / 60 % 60, seconds % 60),
+    }
+}
+
+fn selector(cell: &'static OnceLock<Selector>, selector: &str) -> &'static Selector {
+    cell.get_or_init(|| Selector::parse(selector).unwrap())
+}
+
+/// 从页面 HTML 中提取媒体；页面中没有媒体标签或媒体文件地址时不解析 HTML
+pub fn from_html(url: &str, html: &str) -> Vec<Media> {
+    const MARKERS: [&str; 8] = ["<video", "<audio", "og:video", ".mp4", ".m4v", ".mov", ".m3u8", ".mp3"];
+    let Ok(base_url) = Url::parse(url) else {
+        return Vec::new();
+    };
+    if !MARKERS.iter().any(|marker| html.contains(marker)) {
+        return Vec::new();
+    }
+    let document = Html::parse_document(html);
+    let absolute = |src: &str| base_url.join(src).ok().map(String::from);
+    let attr_duration = |el: ElementRef| {
+        ["data-duration", "duration"].iter().find_map(|name| el.value().attr(name)).and_then(parse_duration)
+    };
+
+    static TITLE: OnceLock<Selector> = OnceLock::new();
+    static META_DURATION: OnceLock<Selector> = OnceLock::new();
+    // 页面级的标题和时长，用于没有自带标题的播放器
+    let page_title = document
+        .select(selector(&TITLE, "main h1, h1, title"))
+        .map(|el| clean_text(&el.text().collect::<String>()))
+        .find(|title| !title.is_empty())
+        .unwrap_or_default();
+    let page_duration = document
+        .select(selector(
+            &META_DURATION,
+            "meta[property=\"video:duration\"], meta[property=\"og:video:duration\"], meta[itemprop=\"duration\"]",
+        ))
+        .find_map(|meta| meta.value().attr("content").and_then(parse_duration));
+
+    let mut media: Vec<Media> = Vec::new();
+    let mut add = |entry: Media| {
+        if !media.iter().any(|existing| existing.url == entry.url) {
+            media.push(entry);
+        }
+    };
+
+    static PLAYER: OnceLock<Selector> = OnceLock::new();
+    static SOURCE: OnceLock<Selector> = OnceLock::new();
+    for el in document.select(selector(&PLAYER, "video, audio")) {
+        let kind = if el.value().name() == "audio" { MediaKind::Audio } else { MediaKind::Video };
+        let sources = el.value().attr("src").into_iter().chain(
+            el.select(selector(&SOURCE, "source[src]")).filter_map(|source| source.value().attr("src")),
+        );
+        let title = ["title", "aria-label"]
+            .iter()
+            .find_map(|name| el.value().attr(name))
+            .map(clean_text)
+            .unwrap_or_else(|| page_title.clone());
+        for src in sources.filter_map(absolute) {
+            add(Media {
+                kind,
+                title: title.clone(),
+                duration: attr_duration(el).or(page_duration),
+                url: src,
+            });
+        }
+    }
+
+    static OG_VIDEO: OnceLock<Selector> = OnceLock::new();
+    let og_video = selector(&OG_VIDEO, "meta[property=\"og:video\"], meta[property=\"og:video:url\"]");
+    for src in document.select(og_video).filter_map(|meta| meta.value().attr("content")).filter_map(absolute) {
+        add(Media { kind: MediaKind::Video, title: page_title.clone(), duration: page_duration, url: src });
+    }
+
+    // 指向媒体文件的链接，例如 WWDC 视频页面的 HD / SD 下载链接
+    static LINK: OnceLock<Selector> = OnceLock::new();
+    for el in document.select(selector(&LINK, "a[href]")) {
+        let Some(link) = el.value().attr("href").and_then(absolute) else {
+            continue;
+        };
+        let Some(kind) = media_kind(&link) else {
+            continue;
+        };
+        let label = clean_text(&el.text().collect::<String>()).replace('\n', " ");
+        let title = match label.is_empty() {
+            true => page_title.clone(),
+            false if page_title.is_empty() || label.contains(&page_title) => label,
+            false => format!("{} ({})", page_title, label),
+        };
+        add(Media { kind, title, duration: attr_duration(el).or(page_duration), url: link });
+    }
+    media
+}
+
+/// 从 DocC render JSON 的 `video` 引用中提取媒体，每个视频取第一个变体（通常是浅色模式）的地址
+pub fn from_json(url: &str, data: &Value) -> Vec<Media> {
+    let Ok(base_url) = Url::parse(url) else {
+        return Vec::new();
+    };
+    let Some(references) = data.get("references").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    let mut media: Vec<Media> = Vec::new();
+    for reference in references.values() {
+        if reference.get("type").and_then(Value::as_str) != Some("video") {
+            continue;
+        }
+        let src = reference
+            .pointer("/variants/0/url")
+            .or_else(|| reference.get("url"))
+            .and_then(Value::as_str)
+            .and_then(|src| base_url.join(src).ok());
+        let Some(src) = src else {
+            continue;
+        };
+        let title = ["alt", "title", "identifier"]
+            .iter()
+            .find_map(|key| reference.get(key).and_then(Value::as_str))
+            .map(clean_text)
+            .unwrap_or_default();
+        let duration = reference.get("duration").and_then(|duration| match duration {
+            Value::String(text) => parse_duration(text),
+            value => value.as_f64().map(|seconds| seconds.round() as u64),
+        });
+        let src = String::from(src);
+        if !media.iter().any(|existing| existing.url == src) {
+            media.push(Media { kind: MediaKind::Video, title, duration, url: src });
+        }
+    }
+    // 引用是无序的映射，按地址排序保证输出稳定
+    media.sort_by(|a, b| a.url.cmp(&b.url));
+    media
+}
@@ -0,0 +1,63 @@
+//! 合并多个爬取结果文件，按 URL 去重。
+
+use std::collections::BTreeMap;
+
+use crate::extract::DocPage;
+
+/// 同一个 URL 在多个结果文件中内容不一致
+#[derive(Debug, Clone)]
+pub struct MergeConflict {
+    pub url: String,
+    /// 保留版本的来源
+    pub kept: String,
+    /// 被覆盖版本的来源
+    pub replaced: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MergeResult {
+    /// 按 URL 排序的合并结果
+    pub pages: Vec<DocPage>,
+    pub duplicates: usize,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+/// 去掉锚点和末尾斜杠，作为去重的键
+pub fn canonical_url(url: &str) -> &str {
+    url.split('#').next().unwrap_or(url).trim_end_matches('/')
+}
+
+/// 合并多组结果，`sources` 按从旧到新排列，同一 URL 保留最新的版本
+pub fn merge_results(sources: Vec<(String, Vec<DocPage>)>) -> MergeResult {
+    let mut merged: BTreeMap<String, (String, DocPage)> = BTreeMap::new();
+    let mut conflicts: BTreeMap<String, MergeConflict> = BTreeMap::new();
+    let mut duplicates = 0;
+
+    for (source, pages) in sources {
+        for page in pages {
+            let url = canonical_url(&page.url).to_string();
+            let Some((previous_source, previous)) = merged.get(&url) else {
+                merged.insert(url, (source.clone(), page));
+                continue;
+            };
+
+            duplicates += 1;
+            if previous.title != page.title || previous.content != page.content {
+                let conflict = conflicts.entry(url.clone()).or_insert_with(|| MergeConflict {
+                    url: url.clone(),
+                    kept: String::new(),
+                    replaced: Vec::new(),
+                });
+                conflict.replaced.push(previous_source.clone());
+                conflict.kept = source.clone();
+            }
+            merged.insert(url, (source.clone(), page));
+        }
+    }
+
+    MergeResult {
+        pages: merged.into_values().map(|(_, page)| page).collect(),
+        duplicates,
+        conflicts: conflicts.into_values().collect(),
+    }
+}
@@ -0,0 +1,238 @@
+//! Prometheus 格式的运行指标（`--metrics-addr`）。
+//!
+//! 爬取时记录请求、重试、按类别的失败、下载字节数、请求耗时和待爬取链接数；`adoc serve` 时记录
+//! 按路由的请求数和耗时。指标通过一个只响应 `GET /metrics` 的 HTTP 服务暴露，供 Prometheus 抓取。
+
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tracing::{debug, info};
+
+use crate::tr;
+
+/// 耗时直方图的桶上限（秒）
+const DURATION_BUCKETS: &[f64] = &[0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+
+/// 固定桶的耗时直方图，总和以微秒累计
+#[derive(Debug)]
+struct Histogram {
+    buckets: Vec<AtomicU64>,
+    count: AtomicU64,
+    sum_micros: AtomicU64,
+}
+
+impl Histogram {
+    fn new() -> Self {
+        Self {
+            buckets: DURATION_BUCKETS.iter().map(|_| AtomicU64::new(0)).collect(),
+            count: AtomicU64::new(0),
+            sum_micros: AtomicU64::new(0),
+        }
+    }
+
+    fn observe(&self, duration: Duration) {
+        let seconds = duration.as_secs_f64();
+        for (bucket, upper) in self.buckets.iter().zip(DURATION_BUCKETS) {
+            if seconds <= *upper {
+                bucket.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_micros.fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    fn render(&self, out: &mut String, name: &str, help: &str) {
+        header(out, name, help, "histogram");
+        for (bucket, upper) in self.buckets.iter().zip(DURATION_BUCKETS) {
+            let _ = writeln!(out, "{}_bucket{{le=\"{}\"}} {}", name, upper, bucket.load(Ordering::Relaxed));
+        }
+        let count = self.count.load(Ordering::Relaxed);
+        let _ = writeln!(out, "{}_bucket{{le=\"+Inf\"}} {}", name, count);
+        let sum = self.sum_micros.load(Ordering::Relaxed) as f64 / 1e6;
+        let _ = writeln!(out, "{}_sum {}", name, sum);
+        let _ = writeln!(out, "{}_count {}", name, count);
+    }
+}
+
+/// 爬取和文档服务的运行指标，可在多个任务间共享
+#[derive(Debug)]
+pub struct Metrics {
+    requests: AtomicU64,
+    retries: AtomicU64,
+    bytes: AtomicU64,
+    pages_fetched: AtomicU64,
+    pages_skipped: AtomicU64,
+    pages_gone: AtomicU64,
+    /// 失败类别 → 页面数
+    failures: Mutex<BTreeMap<String, u64>>,
+    fetch_duration: Histogram,
+    /// 已发现、还没有结果的链接数
+    queue_depth: AtomicI64,
+    /// 上次抓取指标的时间和当时已获取的页面数，用于计算两次抓取之间的速度
+    last_scrape: Mutex<(Instant, u64)>,
+    /// `adoc serve` 的路由 → 请求数
+    http_requests: Mutex<BTreeMap<String, u64>>,
+    http_duration: Histogram,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self {
+            requests: AtomicU64::new(0),
+            retries: AtomicU64::new(0),
+            bytes: AtomicU64::new(0),
+            pages_fetched: AtomicU64::new(0),
+            pages_skipped: AtomicU64::new(0),
+            pages_gone: AtomicU64::new(0),
+            failures: Mutex::new(BTreeMap::new()),
+            fetch_duration: Histogram::new(),
+            queue_depth: AtomicI64::new(0),
+            last_scrape: Mutex::new((Instant::now(), 0)),
+            http_requests: Mutex::new(BTreeMap::new()),
+            http_duration: Histogram::new(),
+        }
+    }
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 成功获取一个页面，`retries` 次重试后成功
+    pub fn record_fetched(&self, bytes: u64, latency: Duration, retries: u32) {
+        self.requests.fetch_add(1 + retries as u64, Ordering::Relaxed);
+        self.retries.fetch_add(retries as u64, Ordering::Relaxed);
+        self.bytes.fetch_add(bytes, Ordering::Relaxed);
+        self.pages_fetched.fetch_add(1, Ordering::Relaxed);
+        self.fetch_duration.observe(latency);
+    }
+
+    /// 页面重试 `retries` 次后仍然失败，`kind` 为失败类别，例如 `timeout`
+    pub fn record_failed(&self, kind: &str, retries: u32) {
+        self.requests.fetch_add(1 + retries as u64, Ordering::Relaxed);
+        self.retries.fetch_add(retries as u64, Ordering::Relaxed);
+        *self.failures.lock().unwrap().entry(kind.to_string()).or_default() += 1;
+    }
+
+    /// 页面已失效（404 / 410）
+    pub fn record_gone(&self) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        self.pages_gone.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_skipped(&self) {
+        self.pages_skipped.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 发现 `count` 个待爬取的链接
+    pub fn enqueue(&self, count: usize) {
+        self.queue_depth.fetch_add(count as i64, Ordering::Relaxed);
+    }
+
+    /// 一个待爬取的链接有了结果（获取、跳过或失败）
+    pub fn dequeue(&self) {
+        self.queue_depth.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    /// `adoc serve` 处理了一个请求，`route` 为匹配的路由，例如 `/page/:id`
+    pub fn record_http(&self, route: &str, duration: Duration) {
+        *self.http_requests.lock().unwrap().entry(route.to_string()).or_default() += 1;
+        self.http_duration.observe(duration);
+    }
+
+    /// Prometheus 文本格式（0.0.4）的所有指标
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let counter = |out: &mut String, name: &str, help: &str, value: &AtomicU64| {
+            header(out, name, help, "counter");
+            let _ = writeln!(out, "{} {}", name, value.load(Ordering::Relaxed));
+        };
+        counter(&mut out, "adoc_requests_total", "HTTP requests sent, including retries", &self.requests);
+        counter(&mut out, "adoc_retries_total", "Retried HTTP requests", &self.retries);
+        counter(&mut out, "adoc_bytes_total", "Bytes of page bodies downloaded", &self.bytes);
+        counter(&mut out, "adoc_pages_fetched_total", "Pages fetched", &self.pages_fetched);
+        counter(&mut out, "adoc_pages_skipped_total", "Pages skipped", &self.pages_skipped);
+        counter(&mut out, "adoc_pages_gone_total", "Pages that returned 404 or 410", &self.pages_gone);
+
+        header(&mut out, "adoc_pages_failed_total", "Pages that failed after retries, by failure kind", "counter");
+        for (kind, count) in self.failures.lock().unwrap().iter() {
+            let _ = writeln!(out, "adoc_pages_failed_total{{kind=\"{}\"}} {}", kind, count);
+        }
+
+        self.fetch_duration
+            .render(&mut out, "adoc_fetch_duration_seconds", "Time to fetch a page, including retries");
+
+        header(&mut out, "adoc_queue_depth", "Discovered links waiting for a result", "gauge");
+        let _ = writeln!(out, "adoc_queue_depth {}", self.queue_depth.load(Ordering::Relaxed).max(0));
+
+        // 两次抓取之间的平均速度，Prometheus 中也可以用 rate(adoc_pages_fetched_total[1m])
+        let fetched = self.pages_fetched.load(Ordering::Relaxed);
+        let rate = {
+            let mut last = self.last_scrape.lock().unwrap();
+            let elapsed = last.0.elapsed().as_secs_f64();
+            let rate = if elapsed > 0.0 { (fetched - last.1) as f64 / elapsed } else { 0.0 };
+            *last = (Instant::now(), fetched);
+            rate
+        };
+        header(&mut out, "adoc_pages_per_second", "Pages fetched per second since the previous scrape", "gauge");
+        let _ = writeln!(out, "adoc_pages_per_second {:.3}", rate);
+
+        header(&mut out, "adoc_http_requests_total", "Requests handled by adoc serve, by route", "counter");
+        for (route, count) in self.http_requests.lock().unwrap().iter() {
+            let _ = writeln!(out, "adoc_http_requests_total{{route=\"{}\"}} {}", route, count);
+        }
+        self.http_duration
+            .render(&mut out, "adoc_http_request_duration_seconds", "Time to handle an adoc serve request");
+        out
+    }
+}
+
+fn header(out: &mut String, name: &str, help: &str, kind: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+/// 在 `addr` 上提供 `GET /metrics`，绑定成功后在后台运行，返回实际监听的地址
+pub async fn serve_metrics(metrics: Arc<Metrics>, addr: SocketAddr) -> Result<SocketAddr> {
+    let listener = TcpListener::bind(addr).await?;
+    let local = listener.local_addr()?;
+    info!("{}", tr!("指标服务已启动: http://{}/metrics", "Serving metrics at http://{}/metrics", local));
+    tokio::spawn(async move {
+        loop {
+            let Ok((mut stream, _)) = listener.accept().await else {
+                continue;
+            };
+            let metrics = metrics.clone();
+            tokio::spawn(async move {
+                // 只需要请求行，不读取请求体
+                let mut buffer = [0; 1024];
+                let Ok(read) = stream.read(&mut buffer).await else {
+                    return;
+                };
+                let request = String::from_utf8_lossy(&buffer[..read]);
+                let target = request.split_whitespace().nth(1).unwrap_or_default();
+                let response = if target == "/metrics" || target.starts_with("/metrics?") {
+                    let body = metrics.render();
+                    format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    )
+                } else {
+                    "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+                };
+                if let Err(e) = stream.write_all(response.as_bytes()).await {
+                    debug!("{}", tr!("无法返回指标: {}", "Cannot write metrics: {}", e));
+                }
+            });
+        }
+    });
+    Ok(local)
+}
@@ -0,0 +1,97 @@
+//! 文档发生变化时向 webhook 推送变更摘要。
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use reqwest::Client;
+use serde_json::{json, Value};
+use tracing::info;
+
+use crate::diff::{CrawlDiff, PageRef};
+use crate::tr;
+
+/// Slack 消息中每一类最多列出的页面数
+const SLACK_MAX_PAGES: usize = 20;
+
+/// webhook 负载格式
+#[derive(Debug, Clone, Copy, ValueEnum)]
+#[value(rename_all = "lowercase")]
+pub enum WebhookFormat {
+    /// 通用 JSON：计数加页面列表
+    Json,
+    /// Slack incoming webhook 兼容的消息
+    Slack,
+}
+
+fn summary(diff: &CrawlDiff) -> String {
+    tr!("Apple 文档有更新：{}", "Apple documentation changed: {}", diff.summary())
+}
+
+fn slack_section(heading: &str, pages: &[PageRef]) -> Option<Value> {
+    if pages.is_empty() {
+        return None;
+    }
+    let mut text = format!("*{}*", heading);
+    for page in pages.iter().take(SLACK_MAX_PAGES) {
+        text.push_str(&format!("\n• <{}|{}>", page.url, page.title));
+    }
+    if pages.len() > SLACK_MAX_PAGES {
+        text.push_str(&tr!(
+            "\n…另有 {} 个页面",
+            "\n…and {} more",
+            pages.len() - SLACK_MAX_PAGES
+        ));
+    }
+    Some(json!({ "type": "section", "text": { "type": "mrkdwn", "text": text } }))
+}
+
+/// 生成 webhook 请求体
+pub fn payload(diff: &CrawlDiff, format: WebhookFormat) -> Value {
+    match format {
+        WebhookFormat::Json => json!({
+            "summary": summary(diff),
+            "added": diff.added,
+            "removed": diff.removed,
+            "changed": diff.changed.iter().map(|page| json!({
+                "url": page.url,
+                "title": page.title,
+                "availability": page.availability,
+                "deprecated": page.deprecated,
+            })).collect::<Vec<_>>(),
+        }),
+        WebhookFormat::Slack => {
+            let changed: Vec<PageRef> = diff
+                .changed
+                .iter()
+                .map(|page| PageRef {
+                    url: page.url.clone(),
+                    title: page.title.clone(),
+                })
+                .collect();
+            let mut blocks = vec![json!({
+                "type": "section",
+                "text": { "type": "mrkdwn", "text": summary(diff) }
+            })];
+            blocks.extend(slack_section(&tr!("新增", "Added"), &diff.added));
+            blocks.extend(slack_section(&tr!("删除", "Removed"), &diff.removed));
+            blocks.extend(slack_section(&tr!("变更", "Changed"), &changed));
+            json!({ "text": summary(diff), "blocks": blocks })
+        }
+    }
+}
+
+/// 将变更摘要 POST 到 webhook，没有变化时不发送
+pub async fn notify(url: &str, diff: &CrawlDiff, format: WebhookFormat) -> Result<()> {
+    if diff.is_empty() {
+        info!("{}", tr!("没有变化，跳过通知", "No changes, skipping notification"));
+        return Ok(());
+    }
+    Client::new()
+        .post(url)
+        .json(&payload(diff, format))
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .with_context(|| tr!("webhook 请求失败: {}", "webhook request failed: {}", url))?;
+    info!("{}", tr!("已发送变更通知: {}", "Sent change notification: {}", url));
+    Ok(())
+}
@@ -1,7 +1,17 @@
-use anyhow::Result;
-use clap::{ValueEnum, Parser};
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use std::collections::HashMap;
+use std::io::{BufWriter, Write};
 use std::path::Path;
-use crate::crawler::DocPage;
+use crate::anchor::{self, Anchor};
+use crate::arrange::GroupBy;
+use crate::extract::{DocPage, RelatedLink};
+use crate::media::format_duration;
+use crate::merge::canonical_url;
+use crate::schema::{parse_results, SCHEMA_VERSION};
+use crate::tags::hashtag;
+use crate::tr;
+use crate::tutorial;
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
 #[value(rename_all = "lowercase")]
@@ -11,6 +21,18 @@ pub enum OutputFormat {
     PrettyJson,
     Txt,
     Markdown,
+    /// Elasticsearch / OpenSearch bulk API 的 NDJSON
+    #[value(name = "es-bulk")]
+    EsBulk,
+    /// SQLite 数据库（含 FTS5 全文索引），只能写入文件
+    Sqlite,
+    /// Alfred Script Filter 的 JSON，用于 macOS 上的离线文档搜索
+    Alfred,
+    /// Raycast 扩展读取的 JSON 数组，图标按符号类型区分
+    Raycast,
+    /// 由 REST API 参考页面汇总的 OpenAPI 3 文档草稿，其他页面忽略
+    #[value(name = "openapi")]
+    OpenApi,
 }
 
 impl std::fmt::Display for OutputFormat {
@@ -20,87 +42,445 @@ impl std::fmt::Display for OutputFormat {
             OutputFormat::PrettyJson => write!(f, "pretty"),
             OutputFormat::Txt => write!(f, "txt"),
             OutputFormat::Markdown => write!(f, "markdown"),
+            OutputFormat::EsBulk => write!(f, "es-bulk"),
+            OutputFormat::Sqlite => write!(f, "sqlite"),
+            OutputFormat::Alfred => write!(f, "alfred"),
+            OutputFormat::Raycast => write!(f, "raycast"),
+            OutputFormat::OpenApi => write!(f, "openapi"),
         }
     }
 }
 
+/// 保存时逐页读取的页面来源，不要求所有页面同时在内存中（例如溢出到磁盘的结果）
+pub trait PageSource {
+    /// 按顺序对每个页面调用 `f`，可以多次遍历
+    fn for_each_page(&self, f: &mut dyn FnMut(&DocPage) -> Result<()>) -> Result<()>;
+
+    /// 页面全部在内存中时返回切片，保存时可以并行序列化
+    fn as_slice(&self) -> Option<&[DocPage]> {
+        None
+    }
+}
+
+impl PageSource for [DocPage] {
+    fn for_each_page(&self, f: &mut dyn FnMut(&DocPage) -> Result<()>) -> Result<()> {
+        self.iter().try_for_each(f)
+    }
+
+    fn as_slice(&self) -> Option<&[DocPage]> {
+        Some(self)
+    }
+}
+
+/// 并行序列化时每批的页面数，限制同时在内存中的输出
+const BATCH_SIZE: usize = 1024;
+
+/// 每个并行任务处理的页面数，同一任务的页面写入同一个缓冲区
+const TASK_SIZE: usize = 32;
+
+/// 按顺序写出每个页面（及其序号）生成的内容，返回页面数
+///
+/// 内存中的页面按批用 rayon 并行生成，再按原顺序写出；其他来源逐页生成。
+fn write_entries<F>(pages: &(impl PageSource + ?Sized), out: &mut impl Write, entry: F) -> Result<usize>
+where
+    F: Fn(usize, &DocPage, &mut Vec<u8>) -> Result<()> + Sync,
+{
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(pages) = pages.as_slice() {
+        use rayon::prelude::*;
+        for (batch, chunk) in pages.chunks(BATCH_SIZE).enumerate() {
+            let buffers = chunk
+                .par_chunks(TASK_SIZE)
+                .enumerate()
+                .map(|(task, pages)| {
+                    let mut buffer = Vec::new();
+                    for (i, page) in pages.iter().enumerate() {
+                        entry(batch * BATCH_SIZE + task * TASK_SIZE + i, page, &mut buffer)?;
+                    }
+                    Ok(buffer)
+                })
+                .collect::<Result<Vec<Vec<u8>>>>()?;
+            for buffer in buffers {
+                out.write_all(&buffer)?;
+            }
+        }
+        return Ok(pages.len());
+    }
+
+    let mut index = 0;
+    let mut buffer = Vec::new();
+    pages.for_each_page(&mut |page| {
+        entry(index, page, &mut buffer)?;
+        out.write_all(&buffer)?;
+        buffer.clear();
+        index += 1;
+        Ok(())
+    })?;
+    Ok(index)
+}
+
+/// 将结果以文本格式逐页写入 `out`，不支持 sqlite
+pub fn write_pages(pages: &(impl PageSource + ?Sized), format: OutputFormat, out: &mut impl Write) -> Result<()> {
+    write_pages_grouped(pages, format, None, out)
+}
+
+/// 与 [`write_pages`] 相同；页面已按 `group_by` 分组排列（见 [`crate::arrange`]）时，Markdown 输出为每组加上标题
+pub fn write_pages_grouped(
+    pages: &(impl PageSource + ?Sized),
+    format: OutputFormat,
+    group_by: Option<GroupBy>,
+    out: &mut impl Write,
+) -> Result<()> {
+    match format {
+        OutputFormat::Json => {
+            write!(out, "{{\"schema_version\":{},\"pages\":[", SCHEMA_VERSION)?;
+            write_entries(pages, out, |i, page, buffer| {
+                if i > 0 {
+                    buffer.push(b',');
+                }
+                Ok(serde_json::to_writer(buffer, page)?)
+            })?;
+            out.write_all(b"]}")?;
+        }
+        OutputFormat::PrettyJson => {
+            // 与 serde_json::to_string_pretty 整个信封的输出相同：页面缩进两级
+            write!(out, "{{\n  \"schema_version\": {},\n  \"pages\": ", SCHEMA_VERSION)?;
+            let count = write_entries(pages, out, |i, page, buffer| {
+                buffer.extend_from_slice(if i == 0 { b"[\n    " } else { b",\n    " });
+                buffer.extend_from_slice(serde_json::to_string_pretty(page)?.replace('\n', "\n    ").as_bytes());
+                Ok(())
+            })?;
+            out.write_all(if count == 0 { b"[]\n}" } else { b"\n  ]\n}" })?;
+        }
+        OutputFormat::Txt => {
+            write_entries(pages, out, |_, page, buffer| text_entry(page, buffer))?;
+        }
+        OutputFormat::Markdown => write_markdown(pages, group_by, out)?,
+        OutputFormat::EsBulk => {
+            write_entries(pages, out, |_, page, buffer| es_bulk_entry(page, buffer))?;
+        }
+        OutputFormat::Alfred => {
+            out.write_all(b"{\"items\":[")?;
+            write_entries(pages, out, |i, page, buffer| {
+                if i > 0 {
+                    buffer.push(b',');
+                }
+                alfred_entry(page, buffer)
+            })?;
+            out.write_all(b"]}")?;
+        }
+        OutputFormat::Raycast => {
+            out.write_all(b"[")?;
+            write_entries(pages, out, |i, page, buffer| {
+                if i > 0 {
+                    buffer.push(b',');
+                }
+                raycast_entry(page, buffer)
+            })?;
+            out.write_all(b"]")?;
+        }
+        OutputFormat::OpenApi => {
+            serde_json::to_writer_pretty(&mut *out, &crate::rest::openapi(pages)?)?;
+            out.write_all(b"\n")?;
+        }
+        OutputFormat::Sqlite => bail!(tr!(
+            "sqlite 格式只能输出到文件，请使用 -o 指定路径",
+            "the sqlite format can only be written to a file, use -o to set a path"
+        )),
+    }
+    Ok(())
+}
+
 pub fn save_results(results: &[DocPage], output_path: &Path, format: OutputFormat) -> Result<()> {
-    let content = match format {
-        OutputFormat::Json => serde_json::to_string(results)?,
-        OutputFormat::PrettyJson => serde_json::to_string_pretty(results)?,
-        OutputFormat::Txt => format_as_text(results),
-        OutputFormat::Markdown => format_as_markdown(results),
-    };
+    save_pages(results, output_path, format)
+}
+
+/// 逐页保存结果，支持所有输出格式
+pub fn save_pages(pages: &(impl PageSource + ?Sized), output_path: &Path, format: OutputFormat) -> Result<()> {
+    save_pages_grouped(pages, output_path, format, None)
+}
+
+/// 与 [`save_pages`] 相同，Markdown 输出按 `group_by` 为每组加上标题，见 [`write_pages_grouped`]
+pub fn save_pages_grouped(
+    pages: &(impl PageSource + ?Sized),
+    output_path: &Path,
+    format: OutputFormat,
+    group_by: Option<GroupBy>,
+) -> Result<()> {
+    if let OutputFormat::Sqlite = format {
+        #[cfg(feature = "sqlite")]
+        return crate::sqlite::save_pages(pages, output_path);
+        #[cfg(not(feature = "sqlite"))]
+        bail!(tr!("未启用 sqlite 特性，无法输出 SQLite 数据库", "the sqlite feature is disabled"));
+    }
 
-    std::fs::write(output_path, content)?;
+    let mut out = BufWriter::new(std::fs::File::create(output_path)?);
+    write_pages_grouped(pages, format, group_by, &mut out)?;
+    out.flush()?;
     Ok(())
 }
 
-pub fn print_results(results: &[DocPage], format: OutputFormat) {
-    let content = match format {
-        OutputFormat::Json => serde_json::to_string(results).unwrap(),
-        OutputFormat::PrettyJson => serde_json::to_string_pretty(results).unwrap(),
-        OutputFormat::Txt => format_as_text(results),
-        OutputFormat::Markdown => format_as_markdown(results),
-    };
-    println!("{}", content);
+/// 读取之前保存的结果文件，支持 JSON（带版本号的信封或早期版本的页面数组）和 SQLite（.sqlite / .sqlite3 / .db）
+pub fn load_results(path: &Path) -> Result<Vec<DocPage>> {
+    #[cfg(feature = "sqlite")]
+    if crate::sqlite::is_sqlite_path(path) {
+        return crate::sqlite::load_results(path);
+    }
+
+    let file = std::fs::File::open(path)
+        .with_context(|| tr!("无法打开结果文件: {}", "cannot open results file: {}", path.display()))?;
+    let results = parse_results(std::io::BufReader::new(file))
+        .with_context(|| tr!("无法解析结果文件: {}", "cannot parse results file: {}", path.display()))?;
+    Ok(results)
+}
+
+pub fn print_results(results: &[DocPage], format: OutputFormat) -> Result<()> {
+    print_pages(results, format)
+}
+
+/// 逐页打印结果到标准输出
+pub fn print_pages(pages: &(impl PageSource + ?Sized), format: OutputFormat) -> Result<()> {
+    print_pages_grouped(pages, format, None)
+}
+
+/// 与 [`print_pages`] 相同，Markdown 输出按 `group_by` 为每组加上标题，见 [`write_pages_grouped`]
+pub fn print_pages_grouped(
+    pages: &(impl PageSource + ?Sized),
+    format: OutputFormat,
+    group_by: Option<GroupBy>,
+) -> Result<()> {
+    let mut out = BufWriter::new(std::io::stdout().lock());
+    write_pages_grouped(pages, format, group_by, &mut out)?;
+    writeln!(out)?;
+    out.flush()?;
+    Ok(())
 }
 
-fn format_as_text(results: &[DocPage]) -> String {
-    let mut content = String::new();
+fn text_entry(page: &DocPage, out: &mut impl Write) -> Result<()> {
+    write!(out, "{}", tr!("标题: {}\n", "Title: {}\n", page.title))?;
+    writeln!(out, "URL: {}", page.url)?;
+    if let Some(summary) = &page.summary {
+        write!(out, "{}", tr!("摘要: {}\n", "Summary: {}\n", summary))?;
+    }
+    if !page.tags.is_empty() {
+        write!(out, "{}", tr!("标签: {}\n", "Tags: {}\n", page.tags.join(", ")))?;
+    }
+    write!(out, "{}", tr!("内容:\n{}\n", "Content:\n{}\n", page.content))?;
+    if let Some(translated) = &page.content_translated {
+        write!(out, "{}", tr!("译文:\n{}\n", "Translation:\n{}\n", translated))?;
+    }
+    write!(out, "\n---\n\n")?;
+    Ok(())
+}
+
+/// 生成 bulk API 的 NDJSON：每个页面一行 index 动作加一行文档，
+/// 不指定 `_index`，可直接 POST 到 `/{index}/_bulk`
+pub fn format_as_es_bulk(results: &[DocPage]) -> Result<String> {
+    let mut content = Vec::new();
     for page in results {
-        content.push_str(&format!("标题: {}\n", page.title));
-        content.push_str(&format!("URL: {}\n", page.url));
-        content.push_str(&format!("内容:\n{}\n", page.content));
-        content.push_str("\n---\n\n");
+        es_bulk_entry(page, &mut content)?;
+    }
+    Ok(String::from_utf8(content)?)
+}
+
+fn es_bulk_entry(page: &DocPage, out: &mut impl Write) -> Result<()> {
+    let action = serde_json::json!({ "index": { "_id": page.id() } });
+    let document = serde_json::json!({
+        "title": page.title,
+        "content": page.content,
+        "url": page.url,
+        "related_links": page.related_links,
+        "hierarchy": page.hierarchy(),
+        "framework": page.framework(),
+        "tags": page.tags,
+    });
+    serde_json::to_writer(&mut *out, &action)?;
+    out.write_all(b"\n")?;
+    serde_json::to_writer(&mut *out, &document)?;
+    out.write_all(b"\n")?;
+    Ok(())
+}
+
+/// Alfred、Raycast 结果条目中摘要的最大字符数
+const SUBTITLE_CHARS: usize = 120;
+
+/// 结果条目的摘要：正文第一段，没有正文时为文档层级
+fn subtitle(page: &DocPage) -> String {
+    let summary = page
+        .content
+        .split("\n\n")
+        .map(|paragraph| paragraph.split_whitespace().collect::<Vec<_>>().join(" "))
+        .find(|paragraph| !paragraph.is_empty())
+        .unwrap_or_else(|| page.hierarchy().join(" › "));
+    if summary.chars().count() > SUBTITLE_CHARS {
+        format!("{}…", summary.chars().take(SUBTITLE_CHARS).collect::<String>().trim_end())
+    } else {
+        summary
     }
-    content
 }
 
-fn format_as_markdown(results: &[DocPage]) -> String {
-    let mut content = String::new();
-    
+/// Script Filter 的一个条目：回车打开文档链接，Shift 快速预览，⌘C 复制链接
+fn alfred_entry(page: &DocPage, out: &mut impl Write) -> Result<()> {
+    let subtitle = subtitle(page);
+    let item = serde_json::json!({
+        "uid": page.id(),
+        "title": page.title,
+        "subtitle": subtitle,
+        "arg": page.url,
+        "quicklookurl": page.url,
+        "match": format!("{} {}", page.title, page.hierarchy().join(" ")),
+        "text": { "copy": page.url, "largetype": page.title },
+    });
+    serde_json::to_writer(out, &item)?;
+    Ok(())
+}
+
+/// Raycast 扩展的一个条目。`icon` 是扩展 assets 目录中按符号类型命名的图标文件，
+/// 没有声明的文章等页面为 `doc.png`；`keywords` 用于 List 的过滤
+fn raycast_entry(page: &DocPage, out: &mut impl Write) -> Result<()> {
+    let kind = page.symbol_kind();
+    let mut keywords = page.hierarchy();
+    keywords.extend(kind.map(str::to_string));
+    keywords.dedup();
+    keywords.extend(page.tags.iter().filter(|tag| !keywords.contains(tag)).cloned().collect::<Vec<_>>());
+    let item = serde_json::json!({
+        "id": page.id(),
+        "title": page.title,
+        "subtitle": subtitle(page),
+        "icon": format!("{}.png", kind.unwrap_or("doc")),
+        "kind": kind,
+        "url": page.url,
+        "keywords": keywords,
+    });
+    serde_json::to_writer(out, &item)?;
+    Ok(())
+}
+
+/// 输出中的页面：规范化的 URL → (序号, 标题, 小节)，用于把站内链接改写为文档内的锚点
+type Anchors = HashMap<String, (usize, String, Vec<Anchor>)>;
+
+/// 先遍历一次页面收集锚点和分组，目录和正文再各遍历一次
+fn write_markdown(pages: &(impl PageSource + ?Sized), group_by: Option<GroupBy>, out: &mut impl Write) -> Result<()> {
+    let mut anchors = Anchors::new();
+    let mut groups = Vec::new();
+    pages.for_each_page(&mut |page| {
+        let number = anchors.len() + 1;
+        let entry = (number, page.title.clone(), page.anchors.clone());
+        anchors.entry(canonical_url(&page.url).to_string()).or_insert(entry);
+        if let Some(group_by) = group_by {
+            groups.push(group_by.group(page));
+        }
+        Ok(())
+    })?;
+    // 第 i 个页面开始一个新的组时返回组名
+    let group_start = |i: usize| {
+        let group = groups.get(i)?;
+        (i == 0 || groups.get(i - 1) != Some(group))
+            .then(|| group.clone().unwrap_or_else(|| tr!("其他", "Other")))
+    };
+
     // 添加文档标题
-    content.push_str("# Apple 开发者文档\n\n");
-    content.push_str("*由 adoc 工具爬取的文档内容*\n\n");
-    
+    write!(out, "{}", tr!("# Apple 开发者文档\n\n", "# Apple Developer Documentation\n\n"))?;
+    write!(out, "{}", tr!("*由 adoc 工具爬取的文档内容*\n\n", "*Documentation crawled by adoc*\n\n"))?;
+
     // 添加目录
-    content.push_str("## 目录\n\n");
-    for (i, page) in results.iter().enumerate() {
-        content.push_str(&format!("{}. [{}](#doc-{})\n", i + 1, page.title, i + 1));
-    }
-    content.push_str("\n---\n\n");
+    write!(out, "{}", tr!("## 目录\n\n", "## Contents\n\n"))?;
+    write_entries(pages, out, |i, page, buffer| {
+        if let Some(group) = group_start(i) {
+            write!(buffer, "{}### {}\n\n", if i == 0 { "" } else { "\n" }, group)?;
+        }
+        Ok(writeln!(buffer, "{}. [{}](#doc-{})", i + 1, page.title, i + 1)?)
+    })?;
+    write!(out, "\n---\n\n")?;
 
     // 添加每个文档的详细内容
-    for (i, page) in results.iter().enumerate() {
-        // 文档标题和链接
-        content.push_str(&format!("## <a id=\"doc-{}\">{}</a>\n\n", i + 1, page.title));
-        content.push_str(&format!("> 原始链接: [{}]({})\n\n", page.url, page.url));
-        
-        // 文档内容
-        content.push_str("### 内容\n\n");
-        // 将内容按段落分割并格式化
-        for paragraph in page.content.split("\n\n") {
+    write_entries(pages, out, |i, page, buffer| {
+        if let Some(group) = group_start(i) {
+            write!(buffer, "# {}\n\n", group)?;
+        }
+        markdown_entry(i + 1, page, &anchors, buffer)
+    })?;
+
+    // 添加页脚
+    write!(out, "{}", tr!("## 关于\n\n", "## About\n\n"))?;
+    write!(
+        out,
+        "{}",
+        tr!(
+            "本文档由 [adoc](https://github.com/king-open/adoc) 自动生成。\n",
+            "Generated by [adoc](https://github.com/king-open/adoc).\n"
+        )
+    )?;
+    Ok(())
+}
+
+fn markdown_entry(number: usize, page: &DocPage, anchors: &Anchors, out: &mut impl Write) -> Result<()> {
+    // 文档标题和链接
+    write!(out, "## <a id=\"doc-{}\">{}</a>\n\n", number, page.title)?;
+    write!(out, "{}", tr!("> 原始链接: [{}]({})\n\n", "> Source: [{}]({})\n\n", page.url, page.url))?;
+
+    // 标签写成 #标签，Obsidian 等笔记工具可以识别
+    if !page.tags.is_empty() {
+        let tags: Vec<String> = page.tags.iter().map(|tag| hashtag(tag)).collect();
+        write!(out, "{}", tr!("标签: {}\n\n", "Tags: {}\n\n", tags.join(" ")))?;
+    }
+
+    // 摘要放在正文之前，便于快速浏览
+    if let Some(summary) = &page.summary {
+        write!(out, "{}", tr!("### 摘要\n\n{}\n\n", "### Summary\n\n{}\n\n", summary))?;
+    }
+
+    // 文档内容
+    write!(out, "{}", tr!("### 内容\n\n", "### Content\n\n"))?;
+    // 按段落格式化，小节标题写成四级标题，锚点加上页面序号以免与其他页面重复
+    let id_prefix = format!("doc-{}-", number);
+    write!(out, "{}", anchor::markdown(&page.content, &page.anchors, 4, &id_prefix))?;
+
+    // 译文紧跟原文，段落格式相同
+    if let Some(translated) = &page.content_translated {
+        write!(out, "{}", tr!("### 译文\n\n", "### Translation\n\n"))?;
+        for paragraph in translated.split("\n\n") {
             if !paragraph.trim().is_empty() {
-                content.push_str(&format!("{}\n\n", paragraph.trim()));
+                write!(out, "{}\n\n", paragraph.trim())?;
             }
         }
-        
-        // 相关链接
-        if !page.related_links.is_empty() {
-            content.push_str("### 相关链接\n\n");
-            for link in &page.related_links {
-                content.push_str(&format!("- [{}]({})\n", link, link));
+    }
+
+    // 教程步骤及每一步的代码变化
+    if !page.steps.is_empty() {
+        write!(out, "{}", tr!("### 步骤\n\n", "### Steps\n\n"))?;
+        write!(out, "{}", tutorial::render_markdown(&page.steps))?;
+    }
+
+    // 视频和音频
+    if !page.media.is_empty() {
+        write!(out, "{}", tr!("### 媒体\n\n", "### Media\n\n"))?;
+        for media in &page.media {
+            let title = if media.title.is_empty() { &media.url } else { &media.title };
+            match media.duration {
+                Some(duration) => writeln!(out, "- [{}]({}) ({}, {})", title, media.url, media.kind, format_duration(duration))?,
+                None => writeln!(out, "- [{}]({}) ({})", title, media.url, media.kind)?,
             }
-            content.push_str("\n");
         }
-        
-        content.push_str("---\n\n");
+        writeln!(out)?;
     }
 
-    // 添加页脚
-    content.push_str("## 关于\n\n");
-    content.push_str("本文档由 [adoc](https://github.com/king-open/adoc) 自动生成。\n");
-    
-    content
-} 
+    // 相关链接
+    if !page.related_links.is_empty() {
+        write!(out, "{}", tr!("### 相关链接\n\n", "### Related links\n\n"))?;
+        // 指向本文档中其他页面的链接改为锚点，离线阅读时不需要访问网络
+        for RelatedLink { url: link, text, .. } in &page.related_links {
+            match anchors.get(canonical_url(link)) {
+                Some((target, title, sections)) => match anchor::link_target(link, sections) {
+                    Some(id) => writeln!(out, "- [{}](#doc-{}-{})", title, target, id)?,
+                    None => writeln!(out, "- [{}](#doc-{})", title, target)?,
+                },
+                None => writeln!(out, "- [{}]({})", if text.is_empty() { link } else { text }, link)?,
+            }
+        }
+        writeln!(out)?;
+    }
+
+    write!(out, "---\n\n")?;
+    Ok(())
+}
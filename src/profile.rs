@@ -0,0 +1,301 @@
+//! 站点配置（`--site-profile`）：描述一个文档站点，使同一套爬取、提取和输出流程可以用于其他文档站点。
+//!
+//! 站点配置包括哪些链接属于文档站点、从 HTML 中提取标题和正文的 CSS 选择器、JSON 响应的字段映射、
+//! 空页面对应的 JSON 数据地址以及关键字搜索的地址模板。内置的 [`SiteProfile::apple`] 即 Apple 开发者文档，
+//! 内置的 [`DocSource`] 各自带有站点配置和起始地址，其他站点从 TOML 文件加载：
+//!
+//! ```toml
+//! name = "rust-std"
+//! domains = ["doc.rust-lang.org/std"]
+//! search_url = "https://doc.rust-lang.org/std/?search={query}"
+//!
+//! [selectors]
+//! title = "h1"
+//! content = "#main-content"
+//!
+//! # 可选：JSON 响应按 JSON Pointer 取字段，不设置时按 DocC render JSON 提取
+//! [json]
+//! title = "/title"
+//! content = "/body"
+//! links = "/links"
+//! ```
+
+use anyhow::{anyhow, Context, Result};
+use clap::ValueEnum;
+use scraper::Selector;
+use serde::Deserialize;
+use std::path::Path;
+use std::sync::OnceLock;
+use url::Url;
+
+use crate::evolution;
+use crate::tr;
+use crate::wwdc;
+
+/// 内置的文档来源（`--source`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum DocSource {
+    /// 按 `--input` 给出的 URL 或关键字爬取文档站点
+    #[default]
+    Docs,
+    /// Swift Evolution 提案：从提案索引开始爬取所有提案
+    SwiftEvolution,
+    /// WWDC 等开发者视频：从全部视频的列表开始爬取各个视频的简介和字幕
+    Wwdc,
+}
+
+impl DocSource {
+    /// 来源的站点配置，`Docs` 为 `None`，即使用 `--site-profile` 或默认的 Apple 配置
+    pub fn profile(self) -> Option<SiteProfile> {
+        match self {
+            DocSource::Docs => None,
+            DocSource::SwiftEvolution => Some(SiteProfile::swift_evolution()),
+            DocSource::Wwdc => Some(SiteProfile::apple().clone()),
+        }
+    }
+
+    /// 来源的起始地址，从这里递归爬取；`Docs` 为 `None`
+    pub fn seed(self) -> Option<&'static str> {
+        match self {
+            DocSource::Docs => None,
+            DocSource::SwiftEvolution => Some(evolution::INDEX_URL),
+            DocSource::Wwdc => Some(wwdc::ALL_VIDEOS_URL),
+        }
+    }
+}
+
+impl std::fmt::Display for DocSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DocSource::Docs => write!(f, "docs"),
+            DocSource::SwiftEvolution => write!(f, "swift-evolution"),
+            DocSource::Wwdc => write!(f, "wwdc"),
+        }
+    }
+}
+
+/// 一个文档站点的爬取和提取方式
+#[derive(Debug, Clone)]
+pub struct SiteProfile {
+    pub name: String,
+    /// URL 包含其中任一项时视为文档站点的链接，例如 `developer.apple.com`
+    pub domains: Vec<String>,
+    pub(crate) selectors: Selectors,
+    /// JSON 响应的字段映射，`None` 时按 DocC render JSON 提取
+    pub json: Option<JsonMapping>,
+    /// 空页面对应的 JSON 数据地址，按顺序使用第一个匹配的映射，为空时不回退
+    pub json_data: Vec<JsonDataUrl>,
+    /// 关键字搜索的地址模板，`{query}` 替换为编码后的关键字，`None` 时不支持关键字搜索
+    pub search_url: Option<String>,
+    /// 第 2 页起的搜索结果追加的页码参数名
+    pub search_page_param: String,
+    /// 搜索结果中的链接路径需包含该片段，例如 `/documentation/`，`None` 时不限制
+    pub search_result_path: Option<String>,
+}
+
+/// 预先解析的 CSS 选择器，同一站点的所有页面共用
+#[derive(Debug, Clone)]
+pub(crate) struct Selectors {
+    pub(crate) title: Selector,
+    pub(crate) content: Selector,
+    pub(crate) link: Selector,
+    pub(crate) search_item: Selector,
+    pub(crate) search_title: Selector,
+    pub(crate) search_description: Selector,
+}
+
+/// JSON 响应中各字段的 JSON Pointer（RFC 6901），例如 `/metadata/title`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JsonMapping {
+    pub title: String,
+    /// 字符串，或字符串数组（每项一段）
+    pub content: String,
+    /// URL 字符串数组，相对链接按页面地址解析
+    #[serde(default)]
+    pub links: Option<String>,
+}
+
+/// 文档页到 JSON 数据地址的映射：路径以 `path_prefix` 开头的页面，路径替换为 `url` 中的 `{path}`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct JsonDataUrl {
+    pub path_prefix: String,
+    pub url: String,
+}
+
+/// TOML 文件中的站点配置，选择器未设置时使用与 Apple 相同的通用选择器
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProfileFile {
+    name: Option<String>,
+    domains: Vec<String>,
+    #[serde(default)]
+    selectors: SelectorsFile,
+    json: Option<JsonMapping>,
+    #[serde(default)]
+    json_data: Vec<JsonDataUrl>,
+    search_url: Option<String>,
+    search_page_param: Option<String>,
+    search_result_path: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct SelectorsFile {
+    title: String,
+    content: String,
+    link: String,
+    search_item: String,
+    search_title: String,
+    search_description: String,
+}
+
+impl Default for SelectorsFile {
+    fn default() -> Self {
+        Self {
+            title: "h1".to_string(),
+            content: "article".to_string(),
+            link: "a[href]".to_string(),
+            search_item: "li.search-result".to_string(),
+            search_title: ".result-title".to_string(),
+            search_description: ".result-description".to_string(),
+        }
+    }
+}
+
+impl SelectorsFile {
+    fn parse(&self) -> Result<Selectors> {
+        let parse = |name: &str, selector: &str| {
+            Selector::parse(selector).map_err(|e| {
+                anyhow!(tr!(
+                    "无效的 CSS 选择器 {} = \"{}\": {}",
+                    "invalid CSS selector {} = \"{}\": {}",
+                    name,
+                    selector,
+                    e
+                ))
+            })
+        };
+        Ok(Selectors {
+            title: parse("title", &self.title)?,
+            content: parse("content", &self.content)?,
+            link: parse("link", &self.link)?,
+            search_item: parse("search_item", &self.search_item)?,
+            search_title: parse("search_title", &self.search_title)?,
+            search_description: parse("search_description", &self.search_description)?,
+        })
+    }
+}
+
+impl Default for SiteProfile {
+    fn default() -> Self {
+        Self::apple().clone()
+    }
+}
+
+impl SiteProfile {
+    /// 内置的 Apple 开发者文档站点，也是默认的站点配置
+    pub fn apple() -> &'static SiteProfile {
+        static APPLE: OnceLock<SiteProfile> = OnceLock::new();
+        APPLE.get_or_init(|| SiteProfile {
+            name: "apple".to_string(),
+            domains: vec!["developer.apple.com".to_string()],
+            selectors: SelectorsFile::default().parse().unwrap(),
+            json: None,
+            json_data: ["/documentation/", "/design/", "/tutorials/"]
+                .into_iter()
+                .map(|prefix| JsonDataUrl {
+                    path_prefix: prefix.to_string(),
+                    url: "/tutorials/data{path}.json".to_string(),
+                })
+                .collect(),
+            search_url: Some("https://developer.apple.com/search/index.php?q={query}".to_string()),
+            search_page_param: "page".to_string(),
+            search_result_path: Some("/documentation/".to_string()),
+        })
+    }
+
+    /// Swift Evolution 提案：提案索引和 GitHub 上的提案原文，没有关键字搜索
+    pub fn swift_evolution() -> SiteProfile {
+        SiteProfile {
+            name: "swift-evolution".to_string(),
+            domains: vec![
+                "download.swift.org/swift-evolution/".to_string(),
+                "raw.githubusercontent.com/swiftlang/swift-evolution/".to_string(),
+            ],
+            selectors: SelectorsFile::default().parse().unwrap(),
+            json: None,
+            json_data: Vec::new(),
+            search_url: None,
+            search_page_param: "page".to_string(),
+            search_result_path: None,
+        }
+    }
+
+    /// 从 TOML 文件加载站点配置
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| tr!("无法读取站点配置: {}", "cannot read site profile: {}", path.display()))?;
+        let name = path.file_stem().map(|stem| stem.to_string_lossy().into_owned());
+        Self::parse(&text, name.as_deref().unwrap_or("custom"))
+            .with_context(|| tr!("无效的站点配置: {}", "invalid site profile: {}", path.display()))
+    }
+
+    /// 解析 TOML 格式的站点配置，文件中没有 `name` 时使用 `default_name`
+    pub fn parse(text: &str, default_name: &str) -> Result<Self> {
+        let file: ProfileFile = toml::from_str(text)?;
+        if file.domains.iter().all(|domain| domain.trim().is_empty()) {
+            return Err(anyhow!(tr!("domains 不能为空", "domains must not be empty")));
+        }
+        if let Some(search_url) = &file.search_url {
+            if !search_url.contains("{query}") {
+                return Err(anyhow!(tr!(
+                    "search_url 需要包含 {{query}}: {}",
+                    "search_url must contain {{query}}: {}",
+                    search_url
+                )));
+            }
+        }
+        Ok(Self {
+            name: file.name.unwrap_or_else(|| default_name.to_string()),
+            domains: file.domains.into_iter().map(|domain| domain.trim().to_string()).collect(),
+            selectors: file.selectors.parse()?,
+            json: file.json,
+            json_data: file.json_data,
+            search_url: file.search_url,
+            search_page_param: file.search_page_param.unwrap_or_else(|| "page".to_string()),
+            search_result_path: file.search_result_path,
+        })
+    }
+
+    /// 是否为该文档站点的链接
+    pub fn is_site_link(&self, url: &str) -> bool {
+        self.domains.iter().any(|domain| url.contains(domain.as_str()))
+    }
+
+    /// 关键字搜索结果的第 `page` 页（从 1 开始），站点不支持搜索时返回 `None`
+    pub fn search_page_url(&self, keyword: &str, page: usize) -> Option<String> {
+        let url = self.search_url.as_ref()?.replace("{query}", &urlencoding::encode(keyword));
+        Some(match page {
+            0 | 1 => url,
+            page => {
+                let separator = if url.contains('?') { '&' } else { '?' };
+                format!("{}{}{}={}", url, separator, self.search_page_param, page)
+            }
+        })
+    }
+
+    /// 文档页对应的 JSON 数据地址，页面不在任何映射的 `path_prefix` 下时返回 `None`
+    pub fn json_data_url(&self, url: &str) -> Option<String> {
+        let mut url = Url::parse(url).ok()?;
+        let path = url.path().trim_end_matches('/');
+        let mapping = self.json_data.iter().find(|mapping| path.starts_with(&mapping.path_prefix))?;
+        let path = mapping.url.replace("{path}", path);
+        url.set_path(&path);
+        url.set_query(None);
+        url.set_fragment(None);
+        Some(url.into())
+    }
+}
@@ -0,0 +1,177 @@
+//! 机器可读的爬取进度事件，每行一个 JSON 对象。
+//!
+//! ```text
+//! {"event":"queued","url":"...","completed":0,"total":1,"percent":0.0}
+//! {"event":"fetching","url":"...","completed":0,"total":1,"percent":0.0}
+//! {"event":"done","url":"...","completed":1,"total":1,"percent":100.0}
+//! ```
+//!
+//! 同样的事件也可以交给回调处理，`--dashboard` 的终端监控面板即由此更新。
+
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// 进度显示方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "lowercase")]
+pub enum ProgressFormat {
+    /// 终端进度条
+    Bar,
+    /// 每行一个 JSON 进度事件
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProgressEventKind {
+    Queued,
+    Fetching,
+    Done,
+    Skipped,
+    Failed,
+}
+
+#[derive(Debug, Serialize)]
+struct ProgressEvent<'a> {
+    event: ProgressEventKind,
+    url: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<&'a str>,
+    completed: usize,
+    total: usize,
+    percent: f64,
+}
+
+/// 处理进度事件的回调：事件类型、URL 和原因
+pub type ProgressCallback = Box<dyn Fn(ProgressEventKind, &str, Option<&str>) + Send>;
+
+/// 进度事件的去向
+enum Target {
+    /// 每行一个 JSON 对象
+    Lines(Box<dyn Write + Send>),
+    Callback(ProgressCallback),
+}
+
+struct State {
+    target: Target,
+    completed: usize,
+    total: usize,
+}
+
+/// 将进度事件写入 stderr、文件或 FIFO
+pub struct ProgressEvents {
+    state: Mutex<State>,
+}
+
+impl ProgressEvents {
+    pub fn new(writer: Box<dyn Write + Send>) -> Self {
+        Self::with_target(Target::Lines(writer))
+    }
+
+    /// 把事件交给回调，不写出 JSON
+    pub fn callback(callback: ProgressCallback) -> Self {
+        Self::with_target(Target::Callback(callback))
+    }
+
+    fn with_target(target: Target) -> Self {
+        Self {
+            state: Mutex::new(State {
+                target,
+                completed: 0,
+                total: 0,
+            }),
+        }
+    }
+
+    pub fn stderr() -> Self {
+        Self::new(Box::new(std::io::stderr()))
+    }
+
+    /// 打开文件或 FIFO 写入事件（写 FIFO 时会等待读取方打开）
+    pub fn file(path: &std::path::Path) -> Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        Ok(Self::new(Box::new(file)))
+    }
+
+    /// 写入一个事件，写入失败（例如读取方已关闭）不影响爬取
+    pub fn emit(&self, event: ProgressEventKind, url: &str, reason: Option<&str>) {
+        let mut state = self.state.lock().unwrap();
+        if let Target::Callback(callback) = &state.target {
+            callback(event, url, reason);
+            return;
+        }
+        match event {
+            ProgressEventKind::Queued => state.total += 1,
+            ProgressEventKind::Done | ProgressEventKind::Skipped | ProgressEventKind::Failed => {
+                state.completed += 1
+            }
+            ProgressEventKind::Fetching => {}
+        }
+        let percent = if state.total == 0 {
+            0.0
+        } else {
+            (state.completed as f64 * 1000.0 / state.total as f64).round() / 10.0
+        };
+        let event = ProgressEvent {
+            event,
+            url,
+            reason,
+            completed: state.completed,
+            total: state.total,
+            percent,
+        };
+        if let (Ok(line), Target::Lines(writer)) = (serde_json::to_string(&event), &mut state.target) {
+            let _ = writeln!(writer, "{}", line);
+            let _ = writer.flush();
+        }
+    }
+}
+
+/// 暂存日志时最多保留的字节数，超出后丢弃较早的日志
+const HELD_LOGS_LIMIT: usize = 1 << 20;
+
+static LOGS_HELD: AtomicBool = AtomicBool::new(false);
+static HELD_LOGS: Mutex<Vec<u8>> = Mutex::new(Vec::new());
+
+/// 日志写入 stderr；[`hold_logs`] 之后暂存在内存中，避免覆盖占用终端的监控面板
+pub struct LogWriter;
+
+impl Write for LogWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if !LOGS_HELD.load(Ordering::Acquire) {
+            return std::io::stderr().write(buf);
+        }
+        let mut held = HELD_LOGS.lock().unwrap();
+        held.extend_from_slice(buf);
+        if held.len() > HELD_LOGS_LIMIT {
+            // 从换行处截断，保留完整的日志行
+            let excess = held.len() - HELD_LOGS_LIMIT;
+            let cut = held[excess..].iter().position(|&b| b == b'\n').map_or(excess, |pos| excess + pos + 1);
+            held.drain(..cut);
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        std::io::stderr().flush()
+    }
+}
+
+/// 开始暂存日志
+pub fn hold_logs() {
+    LOGS_HELD.store(true, Ordering::Release);
+}
+
+/// 停止暂存，把暂存的日志写到 stderr
+pub fn release_logs() {
+    LOGS_HELD.store(false, Ordering::Release);
+    let held = std::mem::take(&mut *HELD_LOGS.lock().unwrap());
+    let _ = std::io::stderr().write_all(&held);
+}
@@ -0,0 +1,51 @@
+//! 在已保存的爬取结果中离线搜索，不访问网络。
+
+use anyhow::{Context, Result};
+use regex::Regex;
+
+use crate::extract::DocPage;
+use crate::tr;
+
+/// 匹配规则：不区分大小写的子串，或正则表达式
+pub enum Matcher {
+    Substring(String),
+    Regex(Regex),
+}
+
+impl Matcher {
+    pub fn substring(pattern: &str) -> Self {
+        Matcher::Substring(pattern.to_lowercase())
+    }
+
+    pub fn regex(pattern: &str) -> Result<Self> {
+        let regex = Regex::new(pattern).with_context(|| tr!("无效的正则表达式: {}", "invalid regex: {}", pattern))?;
+        Ok(Matcher::Regex(regex))
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        match self {
+            Matcher::Substring(pattern) => text.to_lowercase().contains(pattern),
+            Matcher::Regex(regex) => regex.is_match(text),
+        }
+    }
+}
+
+/// 筛选标题、标签或正文匹配的页面，依次按标题、标签、正文命中排序，最多返回 `limit` 个
+pub fn search_pages(pages: Vec<DocPage>, matcher: &Matcher, limit: usize) -> Vec<DocPage> {
+    let mut title_hits = Vec::new();
+    let mut tag_hits = Vec::new();
+    let mut content_hits = Vec::new();
+    for page in pages {
+        if matcher.is_match(&page.title) {
+            title_hits.push(page);
+        } else if page.tags.iter().any(|tag| matcher.is_match(tag)) {
+            tag_hits.push(page);
+        } else if matcher.is_match(&page.content) {
+            content_hits.push(page);
+        }
+    }
+    title_hits.extend(tag_hits);
+    title_hits.extend(content_hits);
+    title_hits.truncate(limit);
+    title_hits
+}
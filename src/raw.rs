@@ -0,0 +1,67 @@
+//! 页面的原始正文（`--include-raw-html`）。
+//!
+//! 设置后每个页面的 `raw_html` 字段保存下载到的正文，下游可以用自己的提取逻辑做实验而不必重新爬取。
+//! HTML 页面为 HTML，直接从 JSON 数据提取的页面为 JSON。正文较大，可以选择 gzip 压缩后以 base64 保存：
+//!
+//! ```json
+//! "raw_html": {"encoding": "gzip", "data": "H4sIAAAAAAAA/7PJMLTzS8xNtdNPzs8rSc0rAQA..."}
+//! ```
+
+use anyhow::{bail, Result};
+use base64::Engine;
+use clap::ValueEnum;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use std::io::{Read, Write};
+
+use crate::tr;
+
+/// 原始正文的保存方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+#[value(rename_all = "lowercase")]
+pub enum RawEncoding {
+    /// 原样保存
+    Plain,
+    /// gzip 压缩后以 base64 保存
+    Gzip,
+}
+
+/// 页面下载到的原始正文
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct RawHtml {
+    pub encoding: RawEncoding,
+    pub data: String,
+}
+
+impl RawHtml {
+    pub fn encode(body: &str, encoding: RawEncoding) -> Result<Self> {
+        let data = match encoding {
+            RawEncoding::Plain => body.to_string(),
+            RawEncoding::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(body.as_bytes())?;
+                base64::engine::general_purpose::STANDARD.encode(encoder.finish()?)
+            }
+        };
+        Ok(Self { encoding, data })
+    }
+
+    /// 还原为原始正文
+    pub fn decode(&self) -> Result<String> {
+        match self.encoding {
+            RawEncoding::Plain => Ok(self.data.clone()),
+            RawEncoding::Gzip => {
+                let compressed = base64::engine::general_purpose::STANDARD.decode(&self.data)?;
+                let mut body = String::new();
+                if GzDecoder::new(compressed.as_slice()).read_to_string(&mut body).is_err() {
+                    bail!(tr!("原始正文不是有效的 gzip 数据", "raw body is not valid gzip data"));
+                }
+                Ok(body)
+            }
+        }
+    }
+}
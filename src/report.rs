@@ -0,0 +1,288 @@
+//! 单次爬取的统计信息。
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+
+use crate::tokens::ContentSize;
+use crate::tr;
+
+/// 失败原因分类
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FailureKind {
+    /// 请求超时
+    Timeout,
+    /// 无法建立连接（DNS、拒绝连接等）
+    Connect,
+    /// 服务器返回了非 2xx 状态码
+    Status,
+    /// 页面已下载，但无法提取内容
+    Extract,
+    /// 响应体超过 `--max-body-size`，已中止下载
+    #[serde(rename = "too-large")]
+    TooLarge,
+    /// 离线模式下缓存中没有该页面
+    Offline,
+    #[default]
+    Other,
+}
+
+impl fmt::Display for FailureKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            FailureKind::Timeout => "timeout",
+            FailureKind::Connect => "connect",
+            FailureKind::Status => "status",
+            FailureKind::Extract => "extract",
+            FailureKind::TooLarge => "too-large",
+            FailureKind::Offline => "offline",
+            FailureKind::Other => "other",
+        };
+        f.write_str(name)
+    }
+}
+
+/// 爬取失败的页面及原因
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailedPage {
+    pub url: String,
+    pub reason: String,
+    #[serde(default)]
+    pub kind: FailureKind,
+    /// HTTP 状态码，只有 [`FailureKind::Status`] 时才有
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+}
+
+/// 爬取报告：成功、跳过、失败的页面数，下载量，耗时和重试次数
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CrawlReport {
+    pub pages_fetched: usize,
+    pub pages_skipped: usize,
+    pub pages_failed: usize,
+    pub failures: Vec<FailedPage>,
+    pub bytes_downloaded: u64,
+    pub total_latency_ms: u64,
+    pub average_latency_ms: u64,
+    pub retries: u32,
+    /// 已失效（404 / 410）的页面数，不计入失败
+    #[serde(default)]
+    pub pages_gone: usize,
+    /// 超过 `--max-duration` 后提前结束，仍有链接未爬取
+    #[serde(default)]
+    pub truncated: bool,
+    /// 连续失败触发熔断后放弃了剩余的链接
+    #[serde(default)]
+    pub circuit_broken: bool,
+    /// HTML 只是空的页面外壳、改从 JSON 数据接口提取正文的页面
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub json_fallbacks: Vec<String>,
+    /// 全部页面正文的词数、字符数和 token 数，只在设置 `--token-stats` 时统计
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<ContentSize>,
+}
+
+impl CrawlReport {
+    /// 记录一个成功获取的页面
+    pub fn record_fetched(&mut self, bytes: u64, latency: Duration, retries: u32) {
+        self.pages_fetched += 1;
+        self.bytes_downloaded += bytes;
+        self.total_latency_ms += latency.as_millis() as u64;
+        self.average_latency_ms = self.total_latency_ms / self.pages_fetched as u64;
+        self.retries += retries;
+    }
+
+    /// 记录一个因已访问而跳过的页面
+    pub fn record_skipped(&mut self) {
+        self.pages_skipped += 1;
+    }
+
+    /// 记录一个重试后仍然失败的页面
+    pub fn record_failed(&mut self, failure: FailedPage, retries: u32) {
+        self.pages_failed += 1;
+        self.retries += retries;
+        self.failures.push(failure);
+    }
+
+    /// 记录一个已失效（404 / 410）的页面
+    pub fn record_gone(&mut self) {
+        self.pages_gone += 1;
+    }
+
+    /// 记录一个改从 JSON 数据接口提取的页面
+    pub fn record_json_fallback(&mut self, url: &str) {
+        self.json_fallbacks.push(url.to_string());
+    }
+
+    /// 记录因超出时间预算而未爬取的链接
+    pub fn record_truncated(&mut self) {
+        self.truncated = true;
+    }
+
+    /// 记录因熔断而放弃的链接
+    pub fn record_circuit_broken(&mut self) {
+        self.circuit_broken = true;
+    }
+
+    /// 合并另一次爬取的统计（例如同一次运行中爬取了多个起始页面）
+    pub fn merge(&mut self, other: CrawlReport) {
+        self.pages_fetched += other.pages_fetched;
+        self.pages_skipped += other.pages_skipped;
+        self.pages_failed += other.pages_failed;
+        self.pages_gone += other.pages_gone;
+        self.truncated |= other.truncated;
+        self.circuit_broken |= other.circuit_broken;
+        self.failures.extend(other.failures);
+        self.json_fallbacks.extend(other.json_fallbacks);
+        self.bytes_downloaded += other.bytes_downloaded;
+        self.total_latency_ms += other.total_latency_ms;
+        if self.pages_fetched > 0 {
+            self.average_latency_ms = self.total_latency_ms / self.pages_fetched as u64;
+        }
+        self.retries += other.retries;
+    }
+
+    /// 保存为 JSON 文件
+    pub fn save(&self, path: &std::path::Path) -> anyhow::Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// 保存失败页面列表，每行一个页面：URL、失败类型、状态码（没有时为 `-`）、原因，以制表符分隔
+    pub fn save_failures(&self, path: &std::path::Path) -> anyhow::Result<()> {
+        let mut content = String::new();
+        for failure in &self.failures {
+            let status = failure.status.map_or("-".to_string(), |status| status.to_string());
+            let reason = failure.reason.replace(['\t', '\n', '\r'], " ");
+            content.push_str(&format!("{}\t{}\t{}\t{}\n", failure.url, failure.kind, status, reason));
+        }
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+}
+
+/// 读取 [`CrawlReport::save_failures`] 写出的失败页面列表，返回其中的 URL
+pub fn load_failed_urls(path: &std::path::Path) -> anyhow::Result<Vec<String>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| tr!("无法读取失败页面列表: {}", "cannot read failed pages: {}", path.display()))?;
+    let mut urls: Vec<String> = Vec::new();
+    for line in content.lines() {
+        let url = line.split('\t').next().unwrap_or_default().trim();
+        if !url.is_empty() && !urls.iter().any(|existing| existing == url) {
+            urls.push(url.to_string());
+        }
+    }
+    Ok(urls)
+}
+
+/// 终端显示宽度，中文字符按两个宽度计算
+pub(crate) fn display_width(text: &str) -> usize {
+    text.chars().map(|c| if c.is_ascii() { 1 } else { 2 }).sum()
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+impl fmt::Display for CrawlReport {
+    /// 以表格形式输出统计信息
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut rows = vec![
+            (tr!("成功页面", "Fetched"), self.pages_fetched.to_string()),
+            (tr!("跳过页面", "Skipped"), self.pages_skipped.to_string()),
+            (tr!("失败页面", "Failed"), self.pages_failed.to_string()),
+            (tr!("失效页面", "Gone"), self.pages_gone.to_string()),
+            (tr!("下载数据", "Downloaded"), format_bytes(self.bytes_downloaded)),
+            (tr!("总请求耗时", "Total latency"), format!("{:.2}s", self.total_latency_ms as f64 / 1000.0)),
+            (tr!("平均耗时", "Avg latency"), format!("{}ms", self.average_latency_ms)),
+            (tr!("重试次数", "Retries"), self.retries.to_string()),
+        ];
+        if let Some(content) = &self.content {
+            rows.push((tr!("正文词数", "Words"), content.words.to_string()));
+            rows.push((tr!("正文字符数", "Characters"), content.chars.to_string()));
+            rows.push((tr!("Token 数", "Tokens"), content.tokens.to_string()));
+        }
+        let heading = tr!("爬取统计", "Crawl report");
+
+        writeln!(f, "+---------------+----------------+")?;
+        writeln!(f, "| {}{} |                |", heading, " ".repeat(13 - display_width(&heading)))?;
+        writeln!(f, "+---------------+----------------+")?;
+        for (name, value) in rows {
+            let padding = 13usize.saturating_sub(display_width(&name));
+            writeln!(f, "| {}{} | {:>14} |", name, " ".repeat(padding), value)?;
+        }
+        write!(f, "+---------------+----------------+")?;
+
+        if let Some(content) = &self.content {
+            writeln!(f)?;
+            write!(f, "{}", tr!("Token 数按 {} 编码计算", "Tokens counted with {}", content.encoding))?;
+            if let Some(largest) = &content.largest_page {
+                write!(
+                    f,
+                    "{}",
+                    tr!(
+                        "，最大的页面 {} 个 token: {}",
+                        "; largest page has {} tokens: {}",
+                        content.largest_page_tokens,
+                        largest
+                    )
+                )?;
+            }
+        }
+
+        if self.truncated {
+            writeln!(f)?;
+            write!(f, "{}", tr!("已超出时间预算，部分链接未爬取", "Time budget exceeded, some links were not crawled"))?;
+        }
+
+        if self.circuit_broken {
+            writeln!(f)?;
+            write!(
+                f,
+                "{}",
+                tr!(
+                    "连续失败触发熔断，部分链接未爬取",
+                    "Too many consecutive failures, some links were not crawled"
+                )
+            )?;
+        }
+
+        if !self.json_fallbacks.is_empty() {
+            writeln!(f)?;
+            write!(
+                f,
+                "{}",
+                tr!(
+                    "{} 个页面的 HTML 没有正文，已改从 JSON 数据提取",
+                    "{} pages had no content in their HTML and were extracted from JSON data",
+                    self.json_fallbacks.len()
+                )
+            )?;
+        }
+
+        if !self.failures.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "{}", tr!("失败页面:", "Failed pages:"))?;
+            for (i, failure) in self.failures.iter().enumerate() {
+                write!(f, "  {}. {} ({})", i + 1, failure.url, failure.reason)?;
+                if i + 1 < self.failures.len() {
+                    writeln!(f)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
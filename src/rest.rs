@@ -0,0 +1,352 @@
+//! REST API 参考页面（例如 App Store Connect API）的结构化字段和 OpenAPI 草稿导出。
+//!
+//! Apple 的 Web 服务文档以 DocC render JSON 描述接口：`restEndpoint`（方法和地址）、`restParameters`、
+//! `restBody`、`restResponses` 以及对象类型页面的 `properties`。这些内容提取为 [`RestApi`]，
+//! 保存在 [`DocPage::rest`] 中，`--format openapi` 再把所有接口和对象类型汇总为一份 OpenAPI 3 文档。
+//! 类型只保留名称，文档中没有的信息（例如枚举值、格式）不会出现在草稿里，需要人工补充。
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use serde_json::{json, Map, Value};
+use url::Url;
+
+use crate::extract::{block_text, clean_text, DocPage};
+use crate::output::PageSource;
+
+/// REST API 参考页面中的接口或对象类型定义
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct RestApi {
+    /// HTTP 方法，例如 `GET`，对象类型页面没有
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    /// 服务地址，例如 `https://api.appstoreconnect.apple.com/`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// 接口路径，路径参数写成 `{id}`，例如 `v1/apps/{id}`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<RestField>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<RestBody>,
+    /// 响应，4xx / 5xx 为错误码
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub responses: Vec<RestResponse>,
+    /// 对象类型页面的属性
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub properties: Vec<RestField>,
+}
+
+/// 参数或属性
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct RestField {
+    pub name: String,
+    /// 参数的位置：`path`、`query`、`header` 等，属性没有
+    #[serde(rename = "in", default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(rename = "type", default)]
+    pub type_name: String,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct RestBody {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    #[serde(rename = "type", default)]
+    pub type_name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    /// 表单等请求体的字段
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<RestField>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct RestResponse {
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub reason: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    #[serde(rename = "type", default, skip_serializing_if = "String::is_empty")]
+    pub type_name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+}
+
+impl RestApi {
+    /// 从 DocC render JSON 中提取，页面没有 REST 相关内容时返回 `None`
+    pub fn from_render_json(data: &Value) -> Option<Self> {
+        let references = data.get("references").unwrap_or(&Value::Null);
+        let mut api = RestApi::default();
+        for section in array(data.get("primaryContentSections")) {
+            match section.get("kind").and_then(Value::as_str) {
+                Some("restEndpoint") => {
+                    let mut base_url = String::new();
+                    let mut path = String::new();
+                    for token in array(section.get("tokens")) {
+                        let text = token.get("text").and_then(Value::as_str).unwrap_or_default();
+                        match token.get("kind").and_then(Value::as_str) {
+                            Some("method") => api.method = Some(text.trim().to_ascii_uppercase()),
+                            Some("baseURL") => base_url.push_str(text),
+                            Some("path") | Some("parameter") => path.push_str(text),
+                            _ => {}
+                        }
+                    }
+                    api.base_url = Some(base_url.trim().to_string()).filter(|url| !url.is_empty());
+                    api.path = Some(path.trim().to_string()).filter(|path| !path.is_empty());
+                }
+                Some("restParameters") => {
+                    let location = section.get("source").and_then(Value::as_str).map(str::to_string);
+                    for item in array(section.get("items")) {
+                        api.parameters.push(RestField { location: location.clone(), ..field(item, references) });
+                    }
+                }
+                Some("restBody") => {
+                    api.request_body = Some(RestBody {
+                        mime_type: section.get("mimeType").and_then(Value::as_str).map(str::to_string),
+                        type_name: tokens(section.get("bodyContentType")),
+                        description: description(section.get("content"), references),
+                        parameters: array(section.get("parameters")).iter().map(|item| field(item, references)).collect(),
+                    });
+                }
+                Some("restResponses") => {
+                    for item in array(section.get("items")) {
+                        api.responses.push(RestResponse {
+                            status: item.get("status").and_then(Value::as_u64).unwrap_or_default() as u16,
+                            reason: item.get("reason").and_then(Value::as_str).unwrap_or_default().to_string(),
+                            mime_type: item.get("mimeType").and_then(Value::as_str).map(str::to_string),
+                            type_name: tokens(item.get("type")),
+                            description: description(item.get("content"), references),
+                        });
+                    }
+                }
+                Some("properties") => {
+                    api.properties.extend(array(section.get("items")).iter().map(|item| field(item, references)));
+                }
+                _ => {}
+            }
+        }
+        (api != RestApi::default()).then_some(api)
+    }
+
+    /// 写入正文的文本形式，使文本、Markdown 等输出中也能看到接口定义
+    pub fn summary_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if let (Some(method), Some(path)) = (&self.method, &self.path) {
+            lines.push(format!("{} {}{}", method, self.base_url.as_deref().unwrap_or_default(), path));
+        }
+        let field_line = |field: &RestField| {
+            let required = if field.required { ", required" } else { "" };
+            let mut line = format!("{} ({}{})", field.name, field.type_name, required);
+            if !field.description.is_empty() {
+                line.push_str(": ");
+                line.push_str(&field.description);
+            }
+            line
+        };
+        lines.extend(self.parameters.iter().map(field_line));
+        if let Some(body) = &self.request_body {
+            lines.push(format!("Body: {}", body.type_name));
+            lines.extend(body.parameters.iter().map(field_line));
+        }
+        for response in &self.responses {
+            let mut line = format!("{} {}", response.status, response.reason);
+            if !response.type_name.is_empty() {
+                line.push_str(": ");
+                line.push_str(&response.type_name);
+            }
+            lines.push(line.trim().to_string());
+        }
+        lines.extend(self.properties.iter().map(field_line));
+        lines
+    }
+}
+
+fn field(item: &Value, references: &Value) -> RestField {
+    RestField {
+        name: item.get("name").and_then(Value::as_str).unwrap_or_default().to_string(),
+        location: None,
+        type_name: tokens(item.get("type")),
+        required: item.get("required").and_then(Value::as_bool).unwrap_or(false),
+        description: description(item.get("content"), references),
+    }
+}
+
+/// 声明片段拼接成的类型名，例如 `[string]`、`AppResponse`
+fn tokens(value: Option<&Value>) -> String {
+    array(value)
+        .iter()
+        .filter_map(|token| token.get("text").and_then(Value::as_str))
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+fn description(content: Option<&Value>, references: &Value) -> String {
+    let mut blocks = Vec::new();
+    for block in array(content) {
+        block_text(block, references, &mut blocks);
+    }
+    clean_text(&blocks.join("\n")).replace('\n', " ")
+}
+
+fn array(value: Option<&Value>) -> &[Value] {
+    value.and_then(Value::as_array).map(Vec::as_slice).unwrap_or_default()
+}
+
+/// 汇总所有带 [`DocPage::rest`] 的页面，生成 OpenAPI 3.0 文档：接口写入 `paths`，对象类型写入
+/// `components.schemas`，引用的类型名不是基本类型时写成 `$ref`
+pub fn openapi(pages: &(impl PageSource + ?Sized)) -> Result<Value> {
+    let mut paths = Map::new();
+    let mut schemas = Map::new();
+    let mut servers: Vec<String> = Vec::new();
+    let mut frameworks: Vec<String> = Vec::new();
+    pages.for_each_page(&mut |page| {
+        let Some(api) = &page.rest else {
+            return Ok(());
+        };
+        if let Some(framework) = page.framework() {
+            if !frameworks.contains(&framework) {
+                frameworks.push(framework);
+            }
+        }
+        match (&api.method, &api.path) {
+            (Some(method), Some(path)) => {
+                let (server, path) = split_endpoint(api.base_url.as_deref(), path);
+                if let Some(server) = server.filter(|server| !servers.contains(server)) {
+                    servers.push(server);
+                }
+                let entry = paths.entry(path).or_insert_with(|| json!({}));
+                entry[method.to_ascii_lowercase()] = operation(page, api);
+            }
+            _ if !api.properties.is_empty() => {
+                schemas.insert(schema_name(&page.title), object_schema(page, api));
+            }
+            _ => {}
+        }
+        Ok(())
+    })?;
+    let title = if frameworks.is_empty() { "adoc".to_string() } else { frameworks.join(", ") };
+    Ok(json!({
+        "openapi": "3.0.3",
+        "info": { "title": title, "version": "draft", "description": "Generated by adoc from the API reference." },
+        "servers": servers.iter().map(|url| json!({ "url": url })).collect::<Vec<_>>(),
+        "paths": paths,
+        "components": { "schemas": schemas },
+    }))
+}
+
+/// 接口地址拆成服务地址和以 `/` 开头的路径；服务地址带路径前缀时前缀归入路径
+fn split_endpoint(base_url: Option<&str>, path: &str) -> (Option<String>, String) {
+    let path = path.trim_start_matches('/');
+    let Some(base) = base_url.and_then(|base| Url::parse(base).ok()) else {
+        return (base_url.map(str::to_string), format!("/{}", path));
+    };
+    let prefix = base.path().trim_matches('/');
+    let server = format!("{}://{}", base.scheme(), base.host_str().unwrap_or_default());
+    let path = if prefix.is_empty() { format!("/{}", path) } else { format!("/{}/{}", prefix, path) };
+    (Some(server), path)
+}
+
+fn operation(page: &DocPage, api: &RestApi) -> Value {
+    let mut operation = json!({
+        "summary": page.title,
+        "operationId": page.hierarchy().last().cloned().unwrap_or_else(|| page.id()),
+        "externalDocs": { "url": page.url },
+        "responses": {},
+    });
+    if !api.parameters.is_empty() {
+        operation["parameters"] = api
+            .parameters
+            .iter()
+            .map(|parameter| {
+                let location = parameter.location.as_deref().unwrap_or("query");
+                json!({
+                    "name": parameter.name,
+                    "in": location,
+                    // OpenAPI 要求路径参数必填
+                    "required": parameter.required || location == "path",
+                    "description": parameter.description,
+                    "schema": type_schema(&parameter.type_name),
+                })
+            })
+            .collect();
+    }
+    if let Some(body) = &api.request_body {
+        let mime_type = body.mime_type.as_deref().unwrap_or("application/json");
+        let schema = if body.parameters.is_empty() {
+            type_schema(&body.type_name)
+        } else {
+            fields_schema(&body.parameters)
+        };
+        operation["requestBody"] = json!({
+            "description": body.description,
+            "required": true,
+            "content": { mime_type: { "schema": schema } },
+        });
+    }
+    for response in &api.responses {
+        let mut entry = json!({
+            "description": if response.description.is_empty() { &response.reason } else { &response.description },
+        });
+        if !response.type_name.is_empty() {
+            let mime_type = response.mime_type.as_deref().unwrap_or("application/json");
+            entry["content"] = json!({ mime_type: { "schema": type_schema(&response.type_name) } });
+        }
+        operation["responses"][response.status.to_string()] = entry;
+    }
+    operation
+}
+
+fn object_schema(page: &DocPage, api: &RestApi) -> Value {
+    let mut schema = fields_schema(&api.properties);
+    schema["externalDocs"] = json!({ "url": page.url });
+    schema
+}
+
+fn fields_schema(fields: &[RestField]) -> Value {
+    let mut properties = Map::new();
+    for field in fields {
+        let mut property = type_schema(&field.type_name);
+        // 与 $ref 并列的其他字段会被忽略，引用类型不写说明
+        if !field.description.is_empty() && property.get("$ref").is_none() {
+            property["description"] = json!(field.description);
+        }
+        properties.insert(field.name.clone(), property);
+    }
+    let required: Vec<&str> = fields.iter().filter(|field| field.required).map(|field| field.name.as_str()).collect();
+    let mut schema = json!({ "type": "object", "properties": properties });
+    if !required.is_empty() {
+        schema["required"] = json!(required);
+    }
+    schema
+}
+
+/// 文档中的类型名对应的 schema：基本类型直接写出，`[T]` 为数组，其他类型引用同名的对象类型
+fn type_schema(type_name: &str) -> Value {
+    let type_name = type_name.trim();
+    if let Some(item) = type_name.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) {
+        return json!({ "type": "array", "items": type_schema(item) });
+    }
+    match type_name.to_ascii_lowercase().as_str() {
+        "" => json!({}),
+        "string" | "integer" | "number" | "boolean" | "object" => json!({ "type": type_name.to_ascii_lowercase() }),
+        "int" | "int32" | "int64" => json!({ "type": "integer" }),
+        "double" | "float" => json!({ "type": "number" }),
+        "bool" => json!({ "type": "boolean" }),
+        _ => json!({ "$ref": format!("#/components/schemas/{}", schema_name(type_name)) }),
+    }
+}
+
+/// schema 名称只能包含字母、数字和 `._-`
+fn schema_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') { c } else { '_' })
+        .collect()
+}
@@ -0,0 +1,137 @@
+//! 示例代码（sample code）页面的项目信息。
+//!
+//! 示例代码页面的 DocC render JSON 中 `metadata.role` 为 `sampleCode`：`metadata.platforms` 给出最低系统版本和
+//! Xcode 版本，`metadata.modules` 为用到的框架，`sampleCodeDownload` 引用项目压缩包。这里整理为
+//! [`SampleCode`] 保存在 [`DocPage::sample`](crate::extract::DocPage::sample) 中，并在正文的摘要之后写入
+//! `Requires: ...`、`Frameworks: ...`、`Download: ...` 三行。设置 `--sample-readme` 时爬虫下载压缩包，
+//! 把项目中的 README 保存在 `readme` 中。
+
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use serde_json::Value;
+use url::Url;
+
+use crate::extract::clean_text;
+
+/// 示例代码项目的信息
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct SampleCode {
+    /// 项目简介，即页面摘要
+    #[serde(default)]
+    pub description: String,
+    /// 最低系统版本，例如 `iOS 17.0+`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub platforms: Vec<String>,
+    /// 需要的 Xcode 版本，例如 `15.0`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub xcode: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub frameworks: Vec<String>,
+    /// 项目压缩包的下载地址
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub download_url: Option<String>,
+    /// 项目中的 README，只在设置 `--sample-readme` 时下载
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub readme: Option<String>,
+}
+
+impl SampleCode {
+    /// 从示例代码页面的 DocC render JSON 中提取，不是示例代码页面时返回 `None`
+    pub fn from_render_json(url: &str, data: &Value) -> Option<Self> {
+        if data.pointer("/metadata/role").and_then(Value::as_str) != Some("sampleCode") {
+            return None;
+        }
+        let references = data.get("references").unwrap_or(&Value::Null);
+        let description = data
+            .get("abstract")
+            .and_then(Value::as_array)
+            .map(|inline| {
+                inline
+                    .iter()
+                    .filter_map(|item| item.get("text").or_else(|| item.get("code")).and_then(Value::as_str))
+                    .collect::<String>()
+            })
+            .map(|text| clean_text(&text))
+            .unwrap_or_default();
+
+        let mut sample = SampleCode { description, ..Default::default() };
+        for platform in data.pointer("/metadata/platforms").and_then(Value::as_array).into_iter().flatten() {
+            let Some(name) = platform.get("name").and_then(Value::as_str) else {
+                continue;
+            };
+            let version = platform.get("introducedAt").and_then(Value::as_str).unwrap_or_default();
+            if name == "Xcode" {
+                sample.xcode = Some(version.to_string()).filter(|version| !version.is_empty());
+            } else if version.is_empty() {
+                sample.platforms.push(name.to_string());
+            } else {
+                sample.platforms.push(format!("{} {}+", name, version));
+            }
+        }
+        for module in data.pointer("/metadata/modules").and_then(Value::as_array).into_iter().flatten() {
+            if let Some(name) = module.get("name").and_then(Value::as_str) {
+                if !sample.frameworks.iter().any(|framework| framework == name) {
+                    sample.frameworks.push(name.to_string());
+                }
+            }
+        }
+        // 下载按钮引用的 `download` 类型引用给出压缩包地址
+        sample.download_url = data
+            .pointer("/sampleCodeDownload/action/identifier")
+            .and_then(Value::as_str)
+            .and_then(|id| references.get(id))
+            .and_then(|reference| reference.get("url"))
+            .and_then(Value::as_str)
+            .and_then(|href| Url::parse(url).ok()?.join(href).ok())
+            .map(String::from);
+        Some(sample)
+    }
+
+    /// 写入正文的项目信息：`Requires: iOS 17.0+, Xcode 15.0+`、`Frameworks: SwiftUI`、`Download: 地址`
+    pub fn summary_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        let mut requires = self.platforms.clone();
+        requires.extend(self.xcode.iter().map(|xcode| format!("Xcode {}+", xcode)));
+        if !requires.is_empty() {
+            lines.push(format!("Requires: {}", requires.join(", ")));
+        }
+        if !self.frameworks.is_empty() {
+            lines.push(format!("Frameworks: {}", self.frameworks.join(", ")));
+        }
+        if let Some(download_url) = &self.download_url {
+            lines.push(format!("Download: {}", download_url));
+        }
+        lines
+    }
+}
+
+/// 从项目压缩包中读取 README：取层级最浅的 `README.md`（不区分大小写，也接受 `README` 和 `README.txt`），
+/// 忽略 macOS 打包时附带的 `__MACOSX` 目录
+#[cfg(feature = "crawler")]
+pub fn readme_from_zip(bytes: &[u8]) -> anyhow::Result<Option<String>> {
+    use std::io::Read;
+
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes))?;
+    let mut best: Option<(usize, usize)> = None;
+    for index in 0..archive.len() {
+        let entry = archive.by_index(index)?;
+        let name = entry.name();
+        if entry.is_dir() || name.starts_with("__MACOSX/") {
+            continue;
+        }
+        let file_name = name.rsplit('/').next().unwrap_or(name).to_ascii_lowercase();
+        if !matches!(file_name.as_str(), "readme.md" | "readme" | "readme.txt") {
+            continue;
+        }
+        let depth = name.matches('/').count();
+        if best.is_none_or(|(best_depth, _)| depth < best_depth) {
+            best = Some((depth, index));
+        }
+    }
+    let Some((_, index)) = best else {
+        return Ok(None);
+    };
+    let mut readme = Vec::new();
+    archive.by_index(index)?.read_to_end(&mut readme)?;
+    Ok(Some(String::from_utf8_lossy(&readme).trim().to_string()))
+}
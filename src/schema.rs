@@ -0,0 +1,78 @@
+//! 输出格式的版本与 JSON Schema（`adoc schema`）。
+//!
+//! JSON 输出（`-f json`、`-f pretty` 和 `--stream`）写成带版本号的信封，下游可以先检查版本再读取页面：
+//!
+//! ```json
+//! {"schema_version": 1, "pages": [{"title": "View", "content": "...", "url": "...", "related_links": []}]}
+//! ```
+//!
+//! `DocPage` 增加、删除字段或改变字段含义时都递增 [`SCHEMA_VERSION`]，`tests/fixtures/schema-v<版本>.json`
+//! 保存每个版本的 JSON Schema，测试会在两者不一致时失败，避免输出格式在版本号不变的情况下悄悄变化。
+//! 读取结果文件时同时接受信封和早期版本的页面数组。
+
+use anyhow::{bail, Result};
+use schemars::generate::SchemaSettings;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::extract::DocPage;
+use crate::tr;
+
+/// 当前输出格式的版本
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// JSON 输出的信封
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Envelope {
+    /// 输出格式的版本
+    pub schema_version: u32,
+    pub pages: Vec<DocPage>,
+}
+
+impl Envelope {
+    pub fn new(pages: Vec<DocPage>) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            pages,
+        }
+    }
+}
+
+/// 结果文件的内容：信封，或没有版本号的页面数组
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Results {
+    Envelope(Envelope),
+    Pages(Vec<DocPage>),
+}
+
+/// 解析 JSON 结果；版本比当前程序支持的更新时报错，而不是悄悄丢掉不认识的字段
+pub fn parse_results(reader: impl std::io::Read) -> Result<Vec<DocPage>> {
+    match serde_json::from_reader(reader)? {
+        Results::Envelope(envelope) if envelope.schema_version > SCHEMA_VERSION => bail!(tr!(
+            "结果文件的格式版本为 {}，当前程序只支持到 {}，请升级 adoc",
+            "the results use schema version {} but this build only supports up to {}, please upgrade adoc",
+            envelope.schema_version,
+            SCHEMA_VERSION
+        )),
+        Results::Envelope(envelope) => Ok(envelope.pages),
+        Results::Pages(pages) => Ok(pages),
+    }
+}
+
+/// 输出格式的 JSON Schema（Draft 2020-12）；`page_only` 时只描述单个页面，
+/// 用于 Kafka、Webhook 等逐页发送的 sink，否则描述 JSON 输出的信封
+pub fn json_schema(page_only: bool) -> Value {
+    let generator = SchemaSettings::draft2020_12().for_serialize().into_generator();
+    let mut schema = if page_only {
+        generator.into_root_schema_for::<DocPage>()
+    } else {
+        generator.into_root_schema_for::<Envelope>()
+    };
+    // 信封中的版本号固定为当前版本
+    if let Some(Value::Object(version)) = schema.pointer_mut("/properties/schema_version") {
+        version.insert("const".to_string(), SCHEMA_VERSION.into());
+    }
+    schema.to_value()
+}
@@ -0,0 +1,235 @@
+//! 本地文档浏览服务：按框架列出页面、渲染单个页面、简单搜索，
+//! 可在离线环境中作为文档浏览器使用。
+
+use anyhow::Result;
+use axum::extract::{MatchedPath, Path, Query, Request, State};
+use axum::http::StatusCode;
+use axum::middleware::{self, Next};
+use axum::response::{Html, IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::info;
+
+use crate::anchor::{link_target, match_headings};
+use crate::extract::{DocPage, RelatedLink};
+use crate::merge::canonical_url;
+use crate::metrics::Metrics;
+use crate::tr;
+
+/// 服务端持有的爬取结果
+struct Site {
+    pages: Vec<DocPage>,
+    /// URL → 页面序号，用于把站内链接改写为本地链接
+    by_url: HashMap<String, usize>,
+    /// 框架 → 页面序号列表
+    frameworks: BTreeMap<String, Vec<usize>>,
+}
+
+impl Site {
+    fn new(pages: Vec<DocPage>) -> Self {
+        let mut by_url = HashMap::new();
+        let mut frameworks: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        for (i, page) in pages.iter().enumerate() {
+            by_url.insert(canonical_url(&page.url).to_string(), i);
+            let framework = page.framework().unwrap_or_else(|| "其他".to_string());
+            frameworks.entry(framework).or_default().push(i);
+        }
+        Self { pages, by_url, frameworks }
+    }
+}
+
+#[derive(Deserialize)]
+struct SearchParams {
+    #[serde(default)]
+    q: String,
+}
+
+/// 启动本地服务并阻塞直到退出，设置了 `metrics` 时记录每个路由的请求数和耗时
+pub async fn serve(pages: Vec<DocPage>, addr: SocketAddr, metrics: Option<Arc<Metrics>>) -> Result<()> {
+    let site = Arc::new(Site::new(pages));
+    let mut app = Router::new()
+        .route("/", get(index))
+        .route("/framework/:name", get(framework))
+        .route("/page/:id", get(page))
+        .route("/search", get(search))
+        .with_state(site);
+    if let Some(metrics) = metrics {
+        app = app.route_layer(middleware::from_fn_with_state(metrics, track));
+    }
+
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    info!("{}", tr!("文档服务已启动: http://{}", "Serving docs at http://{}", listener.local_addr()?));
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+/// 按路由模板（例如 `/page/:id`）记录请求，避免每个页面 ID 一个标签
+async fn track(State(metrics): State<Arc<Metrics>>, request: Request, next: Next) -> Response {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or_else(|| request.uri().path().to_string(), |path| path.as_str().to_string());
+    let started = Instant::now();
+    let response = next.run(request).await;
+    metrics.record_http(&route, started.elapsed());
+    response
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn layout(title: &str, body: &str) -> Html<String> {
+    Html(format!(
+        r#"<!DOCTYPE html>
+<html lang="zh">
+<head>
+<meta charset="utf-8">
+<title>{title} - adoc</title>
+<style>
+body {{ font-family: -apple-system, sans-serif; max-width: 960px; margin: 0 auto; padding: 1em; line-height: 1.6; }}
+header {{ display: flex; gap: 1em; align-items: center; border-bottom: 1px solid #ddd; padding-bottom: .5em; }}
+pre {{ white-space: pre-wrap; }}
+</style>
+</head>
+<body>
+<header>
+<a href="/">首页</a>
+<form action="/search"><input name="q" placeholder="搜索文档"> <button>搜索</button></form>
+</header>
+{body}
+</body>
+</html>"#,
+        title = escape(title),
+        body = body
+    ))
+}
+
+fn page_list(site: &Site, ids: &[usize]) -> String {
+    let mut html = String::from("<ul>\n");
+    for &id in ids {
+        let page = &site.pages[id];
+        html.push_str(&format!(
+            "<li><a href=\"/page/{}\">{}</a></li>\n",
+            id,
+            escape(&page.title)
+        ));
+    }
+    html.push_str("</ul>\n");
+    html
+}
+
+async fn index(State(site): State<Arc<Site>>) -> Html<String> {
+    let mut body = format!("<h1>Apple 开发者文档</h1>\n<p>共 {} 个页面</p>\n<ul>\n", site.pages.len());
+    for (name, ids) in &site.frameworks {
+        body.push_str(&format!(
+            "<li><a href=\"/framework/{}\">{}</a> ({})</li>\n",
+            urlencoding::encode(name),
+            escape(name),
+            ids.len()
+        ));
+    }
+    body.push_str("</ul>\n");
+    layout("首页", &body)
+}
+
+async fn framework(State(site): State<Arc<Site>>, Path(name): Path<String>) -> Response {
+    match site.frameworks.get(&name) {
+        Some(ids) => {
+            let body = format!("<h1>{}</h1>\n{}", escape(&name), page_list(&site, ids));
+            layout(&name, &body).into_response()
+        }
+        None => not_found(),
+    }
+}
+
+async fn page(State(site): State<Arc<Site>>, Path(id): Path<usize>) -> Response {
+    let Some(page) = site.pages.get(id) else {
+        return not_found();
+    };
+
+    let mut body = format!(
+        "<h1>{}</h1>\n<p>原始链接: <a href=\"{url}\">{url}</a></p>\n",
+        escape(&page.title),
+        url = escape(&page.url)
+    );
+    // 小节标题保留原页面的锚点，`/page/1#discussion` 可以直接跳到小节
+    for (line, heading) in match_headings(&page.content, &page.anchors) {
+        match heading {
+            Some(heading) => body.push_str(&format!(
+                "<h2 id=\"{}\">{}</h2>\n",
+                escape(&heading.id),
+                escape(&heading.title)
+            )),
+            None => body.push_str(&format!("<p>{}</p>\n", escape(line))),
+        }
+    }
+
+    if !page.related_links.is_empty() {
+        body.push_str("<h2>相关链接</h2>\n<ul>\n");
+        for RelatedLink { url: link, text, .. } in &page.related_links {
+            // 站内页面链接到本地，其余保留原始链接
+            match site.by_url.get(canonical_url(link)) {
+                Some(&target) => {
+                    let fragment = link_target(link, &site.pages[target].anchors)
+                        .map(|id| format!("#{}", escape(id)))
+                        .unwrap_or_default();
+                    body.push_str(&format!(
+                        "<li><a href=\"/page/{}{}\">{}</a></li>\n",
+                        target,
+                        fragment,
+                        escape(&site.pages[target].title)
+                    ))
+                }
+                None => body.push_str(&format!(
+                    "<li><a href=\"{}\">{}</a></li>\n",
+                    escape(link),
+                    escape(if text.is_empty() { link } else { text })
+                )),
+            }
+        }
+        body.push_str("</ul>\n");
+    }
+
+    layout(&page.title, &body).into_response()
+}
+
+async fn search(State(site): State<Arc<Site>>, Query(params): Query<SearchParams>) -> Html<String> {
+    let query = params.q.trim().to_lowercase();
+    let ids: Vec<usize> = if query.is_empty() {
+        Vec::new()
+    } else {
+        // 标题命中的排在正文命中之前
+        let (mut title_hits, content_hits): (Vec<usize>, Vec<usize>) = site
+            .pages
+            .iter()
+            .enumerate()
+            .filter(|(_, page)| {
+                page.title.to_lowercase().contains(&query) || page.content.to_lowercase().contains(&query)
+            })
+            .map(|(i, _)| i)
+            .partition(|&i| site.pages[i].title.to_lowercase().contains(&query));
+        title_hits.extend(content_hits);
+        title_hits
+    };
+
+    let body = format!(
+        "<h1>搜索: {}</h1>\n<p>找到 {} 个结果</p>\n{}",
+        escape(&params.q),
+        ids.len(),
+        page_list(&site, &ids)
+    );
+    layout("搜索", &body)
+}
+
+fn not_found() -> Response {
+    (StatusCode::NOT_FOUND, layout("未找到", "<h1>页面不存在</h1>")).into_response()
+}
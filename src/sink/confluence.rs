@@ -0,0 +1,161 @@
+//! 将页面发布到 Confluence Cloud 空间，每个页面对应一个 Confluence 页面。
+//!
+//! 正文转换为 Confluence 存储格式（XHTML）。每个页面带有 `adoc-<ID>` 标签，ID 是根据页面 URL
+//! 生成的稳定 ID（[`DocPage::id`]），再次爬取时按标签找到已有的页面并更新为新版本。
+//! Confluence 要求同一空间内的标题唯一，因此标题后面附上页面的文档层级，例如 `View (swiftui/view)`。
+
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use reqwest::{Client, RequestBuilder};
+use serde_json::{json, Value};
+use tracing::debug;
+
+use super::PageSink;
+use crate::extract::DocPage;
+use crate::tr;
+
+#[derive(Debug, Clone)]
+pub struct ConfluenceConfig {
+    /// 站点地址，例如 https://example.atlassian.net/wiki
+    pub url: String,
+    /// `邮箱:API 令牌` 形式时使用 Basic 认证，否则作为个人访问令牌使用 Bearer 认证
+    pub api_key: String,
+    /// 空间 key
+    pub space: String,
+    /// 父页面 ID，设置后所有页面都创建在该页面之下
+    pub parent_page: Option<String>,
+}
+
+pub struct ConfluenceSink {
+    client: Client,
+    config: ConfluenceConfig,
+}
+
+impl ConfluenceSink {
+    pub fn new(mut config: ConfluenceConfig) -> Self {
+        config.url = config.url.trim_end_matches('/').to_string();
+        Self {
+            client: Client::new(),
+            config,
+        }
+    }
+
+    fn authorize(&self, request: RequestBuilder) -> RequestBuilder {
+        match self.config.api_key.split_once(':') {
+            Some((user, token)) => request.basic_auth(user, Some(token)),
+            None => request.bearer_auth(&self.config.api_key),
+        }
+    }
+
+    /// 发送请求并解析 JSON 响应，失败时带上 Confluence 返回的错误信息
+    async fn request(&self, request: RequestBuilder, url: &str) -> Result<Value> {
+        let response = self
+            .authorize(request)
+            .send()
+            .await
+            .with_context(|| tr!("Confluence 请求失败: {}", "Confluence request failed: {}", url))?;
+        let status = response.status();
+        let body: Value = response.json().await.unwrap_or_default();
+        if !status.is_success() {
+            bail!(tr!(
+                "Confluence 返回 {}: {} ({})",
+                "Confluence returned {}: {} ({})",
+                status,
+                body["message"].as_str().unwrap_or_default(),
+                url
+            ));
+        }
+        Ok(body)
+    }
+
+    /// 按标签查找已有的页面，返回页面 ID 和当前版本号
+    async fn find(&self, label: &str) -> Result<Option<(String, u64)>> {
+        let url = format!("{}/rest/api/content/search", self.config.url);
+        let cql = format!("space = \"{}\" AND label = \"{}\"", self.config.space, label);
+        let response = self
+            .request(self.client.get(&url).query(&[("cql", cql.as_str()), ("expand", "version")]), &url)
+            .await?;
+        let page = &response["results"][0];
+        Ok(page["id"]
+            .as_str()
+            .map(|id| (id.to_string(), page["version"]["number"].as_u64().unwrap_or(1))))
+    }
+
+    /// 创建和更新共用的请求体
+    fn content(&self, page: &DocPage) -> Value {
+        let mut content = json!({
+            "type": "page",
+            "title": page_title(page),
+            "space": { "key": self.config.space },
+            "body": { "storage": { "value": storage_format(page), "representation": "storage" } },
+        });
+        if let Some(parent) = &self.config.parent_page {
+            content["ancestors"] = json!([{ "id": parent }]);
+        }
+        content
+    }
+}
+
+#[async_trait]
+impl PageSink for ConfluenceSink {
+    async fn send(&self, page: &DocPage) -> Result<()> {
+        // 失效页面的占位记录没有内容，不发布
+        if page.status.is_some() {
+            return Ok(());
+        }
+        let label = format!("adoc-{}", page.id());
+        let mut content = self.content(page);
+        match self.find(&label).await? {
+            Some((id, version)) => {
+                debug!("{}", tr!("更新 Confluence 页面: {}", "Updating Confluence page: {}", page.url));
+                let url = format!("{}/rest/api/content/{}", self.config.url, id);
+                content["id"] = json!(id);
+                content["version"] = json!({ "number": version + 1 });
+                self.request(self.client.put(&url).json(&content), &url).await?;
+            }
+            None => {
+                debug!("{}", tr!("创建 Confluence 页面: {}", "Creating Confluence page: {}", page.url));
+                let url = format!("{}/rest/api/content", self.config.url);
+                content["metadata"] = json!({ "labels": [{ "prefix": "global", "name": label }] });
+                self.request(self.client.post(&url).json(&content), &url).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 页面标题加上文档层级，保证在空间内唯一
+fn page_title(page: &DocPage) -> String {
+    let path = page.hierarchy().join("/");
+    if path.is_empty() {
+        page.title.clone()
+    } else {
+        format!("{} ({})", page.title, path)
+    }
+}
+
+/// Confluence 存储格式：原始链接、正文段落和相关链接列表
+fn storage_format(page: &DocPage) -> String {
+    let mut out = format!("<p><a href=\"{}\">{}</a></p>", escape(&page.url), escape(&page.url));
+    for paragraph in page.content.split("\n\n") {
+        if !paragraph.trim().is_empty() {
+            out.push_str(&format!("<p>{}</p>", escape(paragraph.trim()).replace('\n', "<br />")));
+        }
+    }
+    if !page.related_links.is_empty() {
+        out.push_str("<h2>Related links</h2><ul>");
+        for link in &page.related_links {
+            let text = if link.text.is_empty() { &link.url } else { &link.text };
+            out.push_str(&format!("<li><a href=\"{}\">{}</a></li>", escape(&link.url), escape(text)));
+        }
+        out.push_str("</ul>");
+    }
+    out
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
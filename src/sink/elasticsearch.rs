@@ -0,0 +1,93 @@
+//! 通过 bulk API 将页面推送到 Elasticsearch / OpenSearch。
+
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::Value;
+use std::sync::Mutex;
+use tracing::debug;
+
+use super::PageSink;
+use crate::extract::DocPage;
+use crate::output::format_as_es_bulk;
+use crate::tr;
+
+#[derive(Debug, Clone)]
+pub struct ElasticsearchConfig {
+    /// 集群地址，例如 http://localhost:9200
+    pub url: String,
+    /// API Key，设置后以 `Authorization: ApiKey ...` 发送
+    pub api_key: Option<String>,
+    /// 索引名称
+    pub index: String,
+    /// 每批推送的文档数
+    pub batch_size: usize,
+}
+
+pub struct ElasticsearchSink {
+    client: Client,
+    config: ElasticsearchConfig,
+    buffer: Mutex<Vec<DocPage>>,
+}
+
+impl ElasticsearchSink {
+    pub fn new(mut config: ElasticsearchConfig) -> Self {
+        config.url = config.url.trim_end_matches('/').to_string();
+        Self {
+            client: Client::new(),
+            config,
+            buffer: Mutex::new(Vec::new()),
+        }
+    }
+
+    async fn push(&self, pages: Vec<DocPage>) -> Result<()> {
+        if pages.is_empty() {
+            return Ok(());
+        }
+        let url = format!("{}/{}/_bulk", self.config.url, self.config.index);
+        debug!("{}", tr!("推送 {} 个文档到 {}", "Pushing {} documents to {}", pages.len(), url));
+
+        let mut request = self
+            .client
+            .post(&url)
+            .header(reqwest::header::CONTENT_TYPE, "application/x-ndjson")
+            .body(format_as_es_bulk(&pages)?);
+        if let Some(key) = &self.config.api_key {
+            request = request.header(reqwest::header::AUTHORIZATION, format!("ApiKey {}", key));
+        }
+        let response: Value = request
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .with_context(|| tr!("bulk 请求失败: {}", "bulk request failed: {}", url))?
+            .json()
+            .await
+            .unwrap_or_default();
+
+        // bulk API 在部分文档失败时仍返回 200，需要检查 errors 字段
+        if response.get("errors").and_then(Value::as_bool).unwrap_or(false) {
+            bail!(tr!("部分文档写入失败: {}", "some documents failed to index: {}", url));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PageSink for ElasticsearchSink {
+    async fn send(&self, page: &DocPage) -> Result<()> {
+        let batch = {
+            let mut buffer = self.buffer.lock().unwrap();
+            buffer.push(page.clone());
+            if buffer.len() < self.config.batch_size {
+                return Ok(());
+            }
+            std::mem::take(&mut *buffer)
+        };
+        self.push(batch).await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        let batch = std::mem::take(&mut *self.buffer.lock().unwrap());
+        self.push(batch).await
+    }
+}
@@ -0,0 +1,78 @@
+//! 边爬取边把页面写入 JSON 文件，页面不在内存中累积。
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::fs::File;
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+use super::PageSink;
+use crate::extract::DocPage;
+use crate::schema::SCHEMA_VERSION;
+use crate::tr;
+
+/// 文件末尾的数组和信封结束符，追加页面前需要先回退
+const CLOSING: &[u8] = b"\n]}";
+
+struct State {
+    writer: BufWriter<File>,
+    pages: usize,
+    /// 上次 flush 写入了结束符
+    closed: bool,
+}
+
+/// 以 JSON 信封格式（见 [`crate::schema`]）逐个写入页面，每次 flush 后文件都是完整的 JSON，
+/// 可以直接用 [`crate::output::load_results`] 读取
+pub struct JsonFileSink {
+    state: Mutex<State>,
+    pretty: bool,
+}
+
+impl JsonFileSink {
+    pub fn create(path: &Path, pretty: bool) -> Result<Self> {
+        let file = File::create(path)
+            .with_context(|| tr!("无法创建输出文件: {}", "cannot create output file: {}", path.display()))?;
+        let mut writer = BufWriter::new(file);
+        write!(writer, "{{\"schema_version\": {}, \"pages\": [", SCHEMA_VERSION)?;
+        Ok(Self {
+            state: Mutex::new(State {
+                writer,
+                pages: 0,
+                closed: false,
+            }),
+            pretty,
+        })
+    }
+}
+
+#[async_trait]
+impl PageSink for JsonFileSink {
+    async fn send(&self, page: &DocPage) -> Result<()> {
+        let json = if self.pretty {
+            serde_json::to_string_pretty(page)?
+        } else {
+            serde_json::to_string(page)?
+        };
+        let mut state = self.state.lock().unwrap();
+        if state.closed {
+            state.writer.seek(SeekFrom::End(-(CLOSING.len() as i64)))?;
+            state.closed = false;
+        }
+        let separator = if state.pages == 0 { "\n" } else { ",\n" };
+        state.writer.write_all(separator.as_bytes())?;
+        state.writer.write_all(json.as_bytes())?;
+        state.pages += 1;
+        Ok(())
+    }
+
+    async fn flush(&self) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if !state.closed {
+            state.writer.write_all(CLOSING)?;
+            state.closed = true;
+        }
+        state.writer.flush()?;
+        Ok(())
+    }
+}
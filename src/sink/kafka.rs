@@ -0,0 +1,63 @@
+//! 将页面发布到 Kafka 主题，每个页面一条消息。
+//!
+//! 消息的 key 是页面 URL，同一页面的多次爬取落在同一分区、保持顺序，启用日志压缩（compaction）
+//! 的主题只保留每个页面的最新版本；value 是 [`DocPage`] 的 JSON，格式与 JSON 输出中的元素相同。
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use rdkafka::config::ClientConfig;
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use std::time::Duration;
+use tracing::debug;
+
+use super::PageSink;
+use crate::extract::DocPage;
+use crate::tr;
+
+#[derive(Debug, Clone)]
+pub struct KafkaConfig {
+    /// 逗号分隔的 broker 地址，例如 localhost:9092
+    pub brokers: String,
+    pub topic: String,
+    /// 等待消息送达的最长时间，超时后该页面推送失败
+    pub timeout: Duration,
+}
+
+pub struct KafkaSink {
+    producer: FutureProducer,
+    config: KafkaConfig,
+}
+
+impl KafkaSink {
+    /// 创建生产者。连接在发送第一条消息时才建立，broker 不可达时由 [`PageSink::send`] 报错
+    pub fn new(config: KafkaConfig) -> Result<Self> {
+        let producer = ClientConfig::new()
+            .set("bootstrap.servers", &config.brokers)
+            .set("message.timeout.ms", config.timeout.as_millis().to_string())
+            .create()
+            .with_context(|| tr!("无法创建 Kafka 生产者: {}", "cannot create Kafka producer: {}", config.brokers))?;
+        Ok(Self { producer, config })
+    }
+}
+
+#[async_trait]
+impl PageSink for KafkaSink {
+    async fn send(&self, page: &DocPage) -> Result<()> {
+        let payload = serde_json::to_vec(page)?;
+        let record = FutureRecord::to(&self.config.topic).key(&page.url).payload(&payload);
+        debug!("{}", tr!("发布到 Kafka 主题 {}: {}", "Publishing to Kafka topic {}: {}", self.config.topic, page.url));
+        self.producer
+            .send(record, self.config.timeout)
+            .await
+            .map_err(|(error, _)| error)
+            .with_context(|| {
+                tr!(
+                    "发布到 Kafka 主题 {} 失败: {}",
+                    "failed to publish to Kafka topic {}: {}",
+                    self.config.topic,
+                    page.url
+                )
+            })?;
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+//! 把页面中的媒体文件下载到本地目录（`--download-media`）。
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use reqwest::Client;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tracing::{info, warn};
+use url::Url;
+
+use super::PageSink;
+use crate::extract::DocPage;
+use crate::tr;
+
+/// 下载每个页面的 [`DocPage::media`]，文件名取自地址的最后一段路径
+///
+/// 已存在的文件不重复下载，便于中断后重新运行；HLS 播放列表（`.m3u8`）只是分段索引，
+/// 不能直接保存为视频，跳过。下载失败只记录警告，不影响其他文件和爬取。
+pub struct MediaDownloadSink {
+    client: Client,
+    dir: PathBuf,
+    started: Mutex<HashSet<String>>,
+}
+
+impl MediaDownloadSink {
+    /// `timeout` 为建立连接的超时，媒体文件可能很大，不限制整个下载的时长
+    pub fn create(dir: &Path, timeout: Duration) -> Result<Self> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| tr!("无法创建媒体目录: {}", "cannot create media directory: {}", dir.display()))?;
+        let client = Client::builder().connect_timeout(timeout).build()?;
+        Ok(Self {
+            client,
+            dir: dir.to_path_buf(),
+            started: Mutex::new(HashSet::new()),
+        })
+    }
+
+    /// 媒体文件的本地路径，地址没有可用的文件名时返回 `None`
+    pub fn file_path(&self, url: &str) -> Option<PathBuf> {
+        let url = Url::parse(url).ok()?;
+        let name = url.path_segments()?.rev().find(|segment| !segment.is_empty())?;
+        let name = urlencoding::decode(name).ok()?;
+        // 文件名不能跳出下载目录
+        if name.contains(['/', '\\']) || name.starts_with('.') {
+            return None;
+        }
+        Some(self.dir.join(name.as_ref()))
+    }
+
+    async fn download(&self, url: &str, path: &Path) -> Result<()> {
+        let mut response = self
+            .client
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()
+            .with_context(|| tr!("下载失败: {}", "download failed: {}", url))?;
+        // 先写入临时文件，完整下载后再改名，中断时不会留下不完整的文件
+        let partial = path.with_extension("part");
+        let mut file = tokio::fs::File::create(&partial).await?;
+        while let Some(chunk) = response.chunk().await? {
+            file.write_all(&chunk).await?;
+        }
+        file.flush().await?;
+        tokio::fs::rename(&partial, path).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PageSink for MediaDownloadSink {
+    async fn send(&self, page: &DocPage) -> Result<()> {
+        for media in &page.media {
+            if media.url.ends_with(".m3u8") || !self.started.lock().unwrap().insert(media.url.clone()) {
+                continue;
+            }
+            let Some(path) = self.file_path(&media.url) else {
+                continue;
+            };
+            if path.exists() {
+                continue;
+            }
+            info!("{}", tr!("下载媒体: {}", "Downloading media: {}", media.url));
+            if let Err(e) = self.download(&media.url, &path).await {
+                warn!("{}", tr!("媒体下载失败: {}, 错误: {:#}", "Media download failed: {}, error: {:#}", media.url, e));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+//! 将页面推送到 Meilisearch 索引。
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use reqwest::{Client, RequestBuilder};
+use serde_json::{json, Value};
+use std::sync::Mutex;
+use tracing::{debug, info};
+
+use super::PageSink;
+use crate::extract::DocPage;
+use crate::tr;
+
+#[derive(Debug, Clone)]
+pub struct MeilisearchConfig {
+    /// Meilisearch 服务地址，例如 http://localhost:7700
+    pub url: String,
+    pub api_key: Option<String>,
+    /// 索引名称
+    pub index: String,
+    /// 文档主键字段名，值为根据页面 URL 生成的稳定 ID
+    pub primary_key: String,
+    /// 可搜索字段
+    pub searchable_fields: Vec<String>,
+    /// 每批推送的文档数
+    pub batch_size: usize,
+}
+
+pub struct MeilisearchSink {
+    client: Client,
+    config: MeilisearchConfig,
+    buffer: Mutex<Vec<Value>>,
+}
+
+impl MeilisearchSink {
+    /// 创建 sink，并更新索引的可搜索字段设置
+    pub async fn new(mut config: MeilisearchConfig) -> Result<Self> {
+        config.url = config.url.trim_end_matches('/').to_string();
+        let sink = Self {
+            client: Client::new(),
+            config,
+            buffer: Mutex::new(Vec::new()),
+        };
+
+        let settings_url = format!("{}/indexes/{}/settings", sink.config.url, sink.config.index);
+        sink.authorize(sink.client.patch(&settings_url))
+            .json(&json!({ "searchableAttributes": sink.config.searchable_fields }))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .with_context(|| {
+                tr!(
+                    "无法更新 Meilisearch 索引设置: {}",
+                    "cannot update Meilisearch index settings: {}",
+                    settings_url
+                )
+            })?;
+        info!(
+            "{}",
+            tr!(
+                "Meilisearch 索引 {} 已就绪，可搜索字段: {}",
+                "Meilisearch index {} is ready, searchable fields: {}",
+                sink.config.index,
+                sink.config.searchable_fields.join(", ")
+            )
+        );
+
+        Ok(sink)
+    }
+
+    fn authorize(&self, request: RequestBuilder) -> RequestBuilder {
+        match &self.config.api_key {
+            Some(key) => request.bearer_auth(key),
+            None => request,
+        }
+    }
+
+    fn document(&self, page: &DocPage) -> Value {
+        json!({
+            self.config.primary_key.as_str(): page.id(),
+            "title": page.title,
+            "content": page.content,
+            "url": page.url,
+            "related_links": page.related_links,
+            "hierarchy": page.hierarchy(),
+            "framework": page.framework(),
+        })
+    }
+
+    async fn push(&self, documents: Vec<Value>) -> Result<()> {
+        if documents.is_empty() {
+            return Ok(());
+        }
+        let url = format!("{}/indexes/{}/documents", self.config.url, self.config.index);
+        debug!("{}", tr!("推送 {} 个文档到 Meilisearch", "Pushing {} documents to Meilisearch", documents.len()));
+        self.authorize(self.client.post(&url))
+            .query(&[("primaryKey", &self.config.primary_key)])
+            .json(&documents)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .with_context(|| tr!("推送文档到 Meilisearch 失败: {}", "failed to push documents to Meilisearch: {}", url))?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PageSink for MeilisearchSink {
+    async fn send(&self, page: &DocPage) -> Result<()> {
+        let batch = {
+            let mut buffer = self.buffer.lock().unwrap();
+            buffer.push(self.document(page));
+            if buffer.len() < self.config.batch_size {
+                return Ok(());
+            }
+            std::mem::take(&mut *buffer)
+        };
+        self.push(batch).await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        let batch = std::mem::take(&mut *self.buffer.lock().unwrap());
+        self.push(batch).await
+    }
+}
@@ -0,0 +1,71 @@
+//! 爬取过程中接收页面的推送目标（sink）。
+//!
+//! 每爬取到一个页面，[`crate::crawler::Crawler`] 就会调用已注册 sink 的
+//! [`PageSink::send`]，一次爬取结束时调用 [`PageSink::flush`]。
+
+use anyhow::Result;
+use async_trait::async_trait;
+use clap::ValueEnum;
+use std::sync::Arc;
+
+use crate::extract::DocPage;
+
+pub mod confluence;
+pub mod elasticsearch;
+pub mod file;
+pub mod media;
+#[cfg(feature = "kafka")]
+pub mod kafka;
+pub mod meilisearch;
+pub mod notion;
+pub mod webhook;
+
+pub use confluence::{ConfluenceConfig, ConfluenceSink};
+pub use elasticsearch::{ElasticsearchConfig, ElasticsearchSink};
+pub use file::JsonFileSink;
+pub use media::MediaDownloadSink;
+#[cfg(feature = "kafka")]
+pub use kafka::{KafkaConfig, KafkaSink};
+pub use meilisearch::{MeilisearchConfig, MeilisearchSink};
+pub use notion::{NotionConfig, NotionSink, DEFAULT_NOTION_URL};
+pub use webhook::{WebhookConfig, WebhookSink};
+
+/// 可选的推送目标
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "lowercase")]
+pub enum SinkKind {
+    Meilisearch,
+    /// Elasticsearch 或 OpenSearch
+    Elasticsearch,
+    /// Notion 数据库
+    Notion,
+    /// Confluence Cloud 空间
+    Confluence,
+    /// 任意 HTTP 接口，页面以 JSON 数组 POST
+    Webhook,
+    /// Kafka 主题，需要启用 kafka 特性
+    Kafka,
+}
+
+#[async_trait]
+pub trait PageSink: Send + Sync {
+    /// 接收一个新爬取到的页面
+    async fn send(&self, page: &DocPage) -> Result<()>;
+
+    /// 推送缓冲中剩余的数据
+    async fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// 共享的推送目标，调用方保留一个引用，爬取结束后读取其中的数据
+#[async_trait]
+impl<T: PageSink + ?Sized> PageSink for Arc<T> {
+    async fn send(&self, page: &DocPage) -> Result<()> {
+        (**self).send(page).await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        (**self).flush().await
+    }
+}
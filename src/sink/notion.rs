@@ -0,0 +1,263 @@
+//! 将页面写入 Notion 数据库，每个页面对应数据库中的一条记录。
+//!
+//! 记录的属性为标题、`URL`、`Framework` 和 `ID`，正文按段落写成页面内容块。`ID` 是根据页面 URL
+//! 生成的稳定 ID（[`DocPage::id`]），再次爬取时按它找到已有的记录并更新，而不是重复创建。
+
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use reqwest::{Client, RequestBuilder};
+use serde_json::{json, Value};
+use tracing::{debug, info};
+
+use super::PageSink;
+use crate::extract::DocPage;
+use crate::tr;
+
+/// Notion API 的默认地址
+pub const DEFAULT_NOTION_URL: &str = "https://api.notion.com";
+
+/// 请求使用的 Notion API 版本
+const NOTION_VERSION: &str = "2022-06-28";
+
+/// 单个文本对象最多 2000 个字符
+const MAX_TEXT_LENGTH: usize = 2000;
+
+/// 一次请求最多追加 100 个内容块
+const MAX_BLOCKS_PER_REQUEST: usize = 100;
+
+#[derive(Debug, Clone)]
+pub struct NotionConfig {
+    /// Notion API 地址，通常为 [`DEFAULT_NOTION_URL`]
+    pub url: String,
+    /// 集成（integration）的访问令牌，数据库需要共享给该集成
+    pub api_key: String,
+    pub database_id: String,
+}
+
+pub struct NotionSink {
+    client: Client,
+    config: NotionConfig,
+    /// 数据库标题属性的名称，新建的数据库为 `Name`，但可能被改过
+    title_property: String,
+}
+
+impl NotionSink {
+    /// 创建 sink，并在数据库中添加缺少的 `URL`、`Framework`、`ID` 属性
+    pub async fn new(mut config: NotionConfig) -> Result<Self> {
+        config.url = config.url.trim_end_matches('/').to_string();
+        let mut sink = Self {
+            client: Client::new(),
+            config,
+            title_property: "Name".to_string(),
+        };
+
+        let database_url = format!("{}/v1/databases/{}", sink.config.url, sink.config.database_id);
+        let database: Value = sink
+            .authorize(sink.client.patch(&database_url))
+            .json(&json!({
+                "properties": {
+                    "URL": { "url": {} },
+                    "Framework": { "select": {} },
+                    "ID": { "rich_text": {} },
+                }
+            }))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .with_context(|| {
+                tr!(
+                    "无法更新 Notion 数据库属性: {}",
+                    "cannot update Notion database properties: {}",
+                    database_url
+                )
+            })?
+            .json()
+            .await
+            .unwrap_or_default();
+        let title = database["properties"]
+            .as_object()
+            .and_then(|properties| properties.iter().find(|(_, property)| property["type"] == "title"))
+            .map(|(name, _)| name.clone())
+            .unwrap_or_else(|| "Name".to_string());
+        info!(
+            "{}",
+            tr!(
+                "Notion 数据库 {} 已就绪，标题属性: {}",
+                "Notion database {} is ready, title property: {}",
+                sink.config.database_id,
+                title
+            )
+        );
+        sink.title_property = title;
+
+        Ok(sink)
+    }
+
+    fn authorize(&self, request: RequestBuilder) -> RequestBuilder {
+        request.bearer_auth(&self.config.api_key).header("Notion-Version", NOTION_VERSION)
+    }
+
+    /// 发送请求并解析 JSON 响应，失败时带上 Notion 返回的错误信息
+    async fn request(&self, request: RequestBuilder, url: &str) -> Result<Value> {
+        let response = self
+            .authorize(request)
+            .send()
+            .await
+            .with_context(|| tr!("Notion 请求失败: {}", "Notion request failed: {}", url))?;
+        let status = response.status();
+        let body: Value = response.json().await.unwrap_or_default();
+        if !status.is_success() {
+            bail!(tr!(
+                "Notion 返回 {}: {} ({})",
+                "Notion returned {}: {} ({})",
+                status,
+                body["message"].as_str().unwrap_or_default(),
+                url
+            ));
+        }
+        Ok(body)
+    }
+
+    fn properties(&self, page: &DocPage) -> Value {
+        let framework = match page.framework() {
+            Some(framework) => json!({ "name": framework }),
+            None => Value::Null,
+        };
+        json!({
+            self.title_property.as_str(): { "title": rich_text(&page.title) },
+            "URL": { "url": page.url },
+            "Framework": { "select": framework },
+            "ID": { "rich_text": rich_text(&page.id()) },
+        })
+    }
+
+    /// 按 `ID` 属性查找已有的记录
+    async fn find(&self, page: &DocPage) -> Result<Option<String>> {
+        let url = format!("{}/v1/databases/{}/query", self.config.url, self.config.database_id);
+        let response = self
+            .request(
+                self.client.post(&url).json(&json!({
+                    "filter": { "property": "ID", "rich_text": { "equals": page.id() } },
+                    "page_size": 1,
+                })),
+                &url,
+            )
+            .await?;
+        Ok(response["results"][0]["id"].as_str().map(str::to_string))
+    }
+
+    /// 删除记录现有的内容块，再写入新的内容
+    async fn replace_content(&self, notion_page: &str, blocks: &[Value]) -> Result<()> {
+        let url = format!("{}/v1/blocks/{}/children", self.config.url, notion_page);
+        loop {
+            let children = self.request(self.client.get(&url).query(&[("page_size", "100")]), &url).await?;
+            let ids: Vec<&str> = children["results"]
+                .as_array()
+                .map(|results| results.iter().filter_map(|block| block["id"].as_str()).collect())
+                .unwrap_or_default();
+            for id in &ids {
+                let block_url = format!("{}/v1/blocks/{}", self.config.url, id);
+                self.request(self.client.delete(&block_url), &block_url).await?;
+            }
+            if ids.is_empty() || !children["has_more"].as_bool().unwrap_or(false) {
+                break;
+            }
+        }
+        self.append(notion_page, blocks).await
+    }
+
+    async fn append(&self, notion_page: &str, blocks: &[Value]) -> Result<()> {
+        let url = format!("{}/v1/blocks/{}/children", self.config.url, notion_page);
+        for batch in blocks.chunks(MAX_BLOCKS_PER_REQUEST) {
+            self.request(self.client.patch(&url).json(&json!({ "children": batch })), &url).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PageSink for NotionSink {
+    async fn send(&self, page: &DocPage) -> Result<()> {
+        let existing = self.find(page).await?;
+        // 失效页面的占位记录没有内容，已有的记录归档，不新建
+        if page.status.is_some() {
+            if let Some(id) = existing {
+                let url = format!("{}/v1/pages/{}", self.config.url, id);
+                self.request(self.client.patch(&url).json(&json!({ "archived": true })), &url).await?;
+            }
+            return Ok(());
+        }
+
+        let blocks = content_blocks(page);
+        match existing {
+            Some(id) => {
+                debug!("{}", tr!("更新 Notion 页面: {}", "Updating Notion page: {}", page.url));
+                let url = format!("{}/v1/pages/{}", self.config.url, id);
+                let body = json!({ "properties": self.properties(page) });
+                self.request(self.client.patch(&url).json(&body), &url).await?;
+                self.replace_content(&id, &blocks).await
+            }
+            None => {
+                debug!("{}", tr!("创建 Notion 页面: {}", "Creating Notion page: {}", page.url));
+                let url = format!("{}/v1/pages", self.config.url);
+                let (first, rest) = blocks.split_at(blocks.len().min(MAX_BLOCKS_PER_REQUEST));
+                let body = json!({
+                    "parent": { "database_id": self.config.database_id },
+                    "properties": self.properties(page),
+                    "children": first,
+                });
+                let created = self.request(self.client.post(&url).json(&body), &url).await?;
+                match created["id"].as_str() {
+                    Some(id) if !rest.is_empty() => self.append(id, rest).await,
+                    _ => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+/// 文本对象数组，超过长度限制的文本拆成多个对象
+fn rich_text(text: &str) -> Value {
+    let chars: Vec<char> = text.chars().collect();
+    let parts: Vec<Value> = chars
+        .chunks(MAX_TEXT_LENGTH)
+        .map(|part| json!({ "type": "text", "text": { "content": part.iter().collect::<String>() } }))
+        .collect();
+    Value::Array(parts)
+}
+
+/// 正文的每个段落对应一个段落块，相关链接写在最后的列表中
+fn content_blocks(page: &DocPage) -> Vec<Value> {
+    let mut blocks: Vec<Value> = page
+        .content
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|paragraph| !paragraph.is_empty())
+        .map(|paragraph| {
+            json!({ "object": "block", "type": "paragraph", "paragraph": { "rich_text": rich_text(paragraph) } })
+        })
+        .collect();
+    if !page.related_links.is_empty() {
+        blocks.push(json!({
+            "object": "block",
+            "type": "heading_2",
+            "heading_2": { "rich_text": rich_text("Related links") },
+        }));
+        for link in &page.related_links {
+            blocks.push(json!({
+                "object": "block",
+                "type": "bulleted_list_item",
+                "bulleted_list_item": {
+                    "rich_text": [{
+                        "type": "text",
+                        "text": {
+                            "content": if link.text.is_empty() { &link.url } else { &link.text },
+                            "link": { "url": link.url },
+                        },
+                    }]
+                },
+            }));
+        }
+    }
+    blocks
+}
@@ -0,0 +1,106 @@
+//! 将页面以 JSON 数组分批 POST 到任意 HTTP 接口。
+//!
+//! 每批的请求体是 [`DocPage`] 的数组，格式与 JSON 输出中的元素相同。网络错误、429 和 5xx
+//! 响应按指数退避重试，其他错误状态码不重试。
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use backoff::ExponentialBackoff;
+use reqwest::{Client, StatusCode};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+use tracing::{debug, warn};
+
+use super::PageSink;
+use crate::extract::DocPage;
+use crate::tr;
+
+#[derive(Debug, Clone)]
+pub struct WebhookConfig {
+    /// 接收页面的地址
+    pub url: String,
+    /// 设置后以 `Authorization: Bearer ...` 发送
+    pub api_key: Option<String>,
+    /// 每批推送的页面数，为 1 时每个页面单独推送
+    pub batch_size: usize,
+    /// 每批最多重试的次数
+    pub max_retries: u32,
+}
+
+pub struct WebhookSink {
+    client: Client,
+    config: WebhookConfig,
+    buffer: Mutex<Vec<DocPage>>,
+}
+
+impl WebhookSink {
+    pub fn new(config: WebhookConfig) -> Self {
+        Self {
+            client: Client::new(),
+            config,
+            buffer: Mutex::new(Vec::new()),
+        }
+    }
+
+    async fn push(&self, pages: Vec<DocPage>) -> Result<()> {
+        if pages.is_empty() {
+            return Ok(());
+        }
+        let url = &self.config.url;
+        debug!("{}", tr!("推送 {} 个页面到 {}", "Pushing {} pages to {}", pages.len(), url));
+        let attempts = AtomicU32::new(0);
+        backoff::future::retry(ExponentialBackoff::default(), || async {
+            let attempt = attempts.fetch_add(1, Ordering::Relaxed) + 1;
+            let mut request = self.client.post(url).json(&pages);
+            if let Some(key) = &self.config.api_key {
+                request = request.bearer_auth(key);
+            }
+            let error = match request.send().await {
+                Ok(response) if response.status().is_success() => return Ok(()),
+                Ok(response) => {
+                    let status = response.status();
+                    let error = anyhow!(tr!("webhook 返回 {}: {}", "webhook returned {}: {}", status, url));
+                    if status != StatusCode::TOO_MANY_REQUESTS && !status.is_server_error() {
+                        return Err(backoff::Error::permanent(error));
+                    }
+                    error
+                }
+                Err(e) => anyhow!(tr!("webhook 请求失败: {}: {}", "webhook request failed: {}: {}", url, e)),
+            };
+            if attempt > self.config.max_retries {
+                return Err(backoff::Error::permanent(error));
+            }
+            warn!(
+                "{}",
+                tr!(
+                    "推送失败（第 {} 次尝试），准备重试: {}",
+                    "Push failed (attempt {}), retrying: {}",
+                    attempt,
+                    error
+                )
+            );
+            Err(backoff::Error::transient(error))
+        })
+        .await
+    }
+}
+
+#[async_trait]
+impl PageSink for WebhookSink {
+    async fn send(&self, page: &DocPage) -> Result<()> {
+        let batch = {
+            let mut buffer = self.buffer.lock().unwrap();
+            buffer.push(page.clone());
+            if buffer.len() < self.config.batch_size {
+                return Ok(());
+            }
+            std::mem::take(&mut *buffer)
+        };
+        self.push(batch).await
+    }
+
+    async fn flush(&self) -> Result<()> {
+        let batch = std::mem::take(&mut *self.buffer.lock().unwrap());
+        self.push(batch).await
+    }
+}
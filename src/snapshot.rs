@@ -0,0 +1,408 @@
+//! 把爬取结果写成 git 仓库中的快照。
+//!
+//! 每个页面按文档层级写成一个 Markdown 文件，例如 `swiftui/view/frame(width_height_).md`，
+//! 然后提交一次，提交信息汇总新增、修改、删除的页面。之后用 `git log`、`git diff`、`git blame`
+//! 就能查看 Apple 文档的变化。
+//!
+//! 只有起始页面所在层级下、本次爬取没有出现的页面会被删除；爬取失败的页面保留原有文件。
+//! 提交时只暂存快照改动过的文件，仓库中的其他改动不受影响。
+//!
+//! 快照根目录中还有两个随页面一起提交的文件，其他工具不需要猜测目录结构就能读取快照：
+//! [`MANIFEST_FILE`] 列出每个页面的 URL、标题、文件路径、SHA-256 和大小，[`SITEMAP_FILE`] 是列出各页面原始地址的
+//! XML 站点地图。两者都按 URL 排序，内容不变时不会产生新的提交。
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::anchor::{self, Anchor};
+use crate::extract::{DocPage, RelatedLink};
+use crate::hash::sha256_hex;
+use crate::merge::canonical_url;
+use crate::output::PageSource;
+use crate::tr;
+
+/// 提交信息中每类变更最多列出的页面数
+const MAX_LISTED: usize = 50;
+
+/// 快照根目录中清单文件的文件名
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// 快照根目录中 XML 站点地图的文件名
+pub const SITEMAP_FILE: &str = "sitemap.xml";
+
+/// 快照清单中的一个页面文件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotEntry {
+    pub url: String,
+    pub title: String,
+    /// 相对快照根目录的路径，以 `/` 分隔
+    pub path: String,
+    /// 文件内容的 SHA-256
+    pub sha256: String,
+    /// 文件的字节数
+    pub bytes: u64,
+}
+
+/// 快照清单，按 URL 排序
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    pub count: usize,
+    pub entries: Vec<SnapshotEntry>,
+}
+
+impl SnapshotManifest {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| tr!("无法读取快照清单: {}", "cannot read snapshot manifest: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| tr!("无法解析快照清单: {}", "cannot parse snapshot manifest: {}", path.display()))
+    }
+
+    /// XML 站点地图（sitemaps.org 协议），每个页面一个 `<url>`，地址为页面的原始 URL
+    pub fn sitemap(&self) -> String {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n");
+        for entry in &self.entries {
+            out.push_str(&format!("  <url><loc>{}</loc></url>\n", escape(&entry.url)));
+        }
+        out.push_str("</urlset>\n");
+        out
+    }
+}
+
+/// 一次快照中新增、修改、删除的文件，路径相对于仓库根目录
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnapshotChanges {
+    pub added: Vec<PathBuf>,
+    pub changed: Vec<PathBuf>,
+    pub removed: Vec<PathBuf>,
+    /// 有变化的清单和站点地图，不计入页面的变更，随页面一起提交
+    pub listings: Vec<PathBuf>,
+}
+
+impl SnapshotChanges {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty() && self.listings.is_empty()
+    }
+
+    /// 提交信息：第一行是各类变更的数量，之后按类别列出文件
+    pub fn message(&self) -> String {
+        let mut message = tr!(
+            "更新文档快照: 新增 {}，修改 {}，删除 {}",
+            "Update docs snapshot: {} added, {} changed, {} removed",
+            self.added.len(),
+            self.changed.len(),
+            self.removed.len()
+        );
+        message.push('\n');
+        let sections = [
+            (tr!("新增:", "Added:"), &self.added),
+            (tr!("修改:", "Changed:"), &self.changed),
+            (tr!("删除:", "Removed:"), &self.removed),
+        ];
+        for (heading, paths) in sections {
+            if paths.is_empty() {
+                continue;
+            }
+            message.push_str(&format!("\n{}\n", heading));
+            for path in paths.iter().take(MAX_LISTED) {
+                message.push_str(&format!("  {}\n", path.display()));
+            }
+            if paths.len() > MAX_LISTED {
+                message.push_str(&tr!("  ……及其他 {} 个\n", "  ... and {} more\n", paths.len() - MAX_LISTED));
+            }
+        }
+        message
+    }
+}
+
+/// 页面在快照中的路径：按文档层级分目录，不是 Apple 开发者网站的页面放在以主机名命名的目录下。
+/// 层级中在 Windows 上不能用于文件名的字符替换为 `_`
+pub fn page_path(page: &DocPage) -> PathBuf {
+    let mut segments = page.hierarchy();
+    let host = url::Url::parse(&page.url).ok().and_then(|url| url.host_str().map(str::to_string));
+    if let Some(host) = host.filter(|host| host != "developer.apple.com") {
+        segments.insert(0, host);
+    }
+    let mut segments: Vec<String> = segments
+        .iter()
+        .map(|segment| segment.replace(['/', '\\', ':', '*', '?', '"', '<', '>', '|'], "_"))
+        .filter(|segment| !segment.is_empty() && segment != "." && segment != "..")
+        .collect();
+    // 直接追加扩展名，层级中本身带 `.` 的部分（例如主机名）不会被截断
+    let file = format!("{}.md", segments.pop().unwrap_or_else(|| "index".to_string()));
+    segments.into_iter().chain([file]).collect()
+}
+
+/// 从 `from` 文件所在目录到 `to` 文件的相对路径，以 `/` 分隔，例如 `../view/frame(width_height_).md`
+pub fn relative_link(from: &Path, to: &Path) -> String {
+    let from: Vec<_> = from.parent().map(|dir| dir.components().collect()).unwrap_or_default();
+    let to: Vec<_> = to.components().collect();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+    let mut parts: Vec<String> = vec!["..".to_string(); from.len() - common];
+    parts.extend(to[common..].iter().map(|part| part.as_os_str().to_string_lossy().into_owned()));
+    parts.join("/")
+}
+
+/// 本次快照中的页面：规范化的 URL → (快照中的路径, 标题, 小节)
+type LocalPages = HashMap<String, (PathBuf, String, Vec<Anchor>)>;
+
+/// 单个页面的 Markdown。不含随界面语言变化的文字，切换语言不会让所有页面都显示为已修改。
+/// 小节标题写成二级标题并保留原页面的锚点；指向本次快照中其他页面的相关链接改写为相对路径，
+/// 离线浏览时不需要访问网络
+fn page_markdown(page: &DocPage, local: &LocalPages) -> String {
+    let mut out = format!("# {}\n\n<{}>\n\n", page.title.trim(), page.url);
+    out.push_str(&anchor::markdown(&page.content, &page.anchors, 2, ""));
+    if !page.related_links.is_empty() {
+        out.push_str("## Related links\n\n");
+        let path = page_path(page);
+        for RelatedLink { url: link, text, .. } in &page.related_links {
+            match local.get(canonical_url(link)) {
+                Some((target, title, sections)) => {
+                    let mut href = relative_link(&path, target);
+                    if let Some(id) = anchor::link_target(link, sections) {
+                        href = format!("{}#{}", href, id);
+                    }
+                    out.push_str(&format!("- [{}](<{}>)\n", title.trim(), href));
+                }
+                None if text.is_empty() => out.push_str(&format!("- <{}>\n", link)),
+                None => out.push_str(&format!("- [{}](<{}>)\n", text, link)),
+            }
+        }
+        out.push('\n');
+    }
+    format!("{}\n", out.trim_end())
+}
+
+/// 作为快照写入目标的 git 工作区
+pub struct GitSnapshot {
+    repo: PathBuf,
+}
+
+impl GitSnapshot {
+    /// 打开 git 工作区，目录不存在或还不是 git 仓库时创建并初始化
+    pub fn open(repo: &Path) -> Result<Self> {
+        std::fs::create_dir_all(repo)
+            .with_context(|| tr!("无法创建目录: {}", "cannot create directory: {}", repo.display()))?;
+        let snapshot = Self {
+            repo: repo.to_path_buf(),
+        };
+        let inside = snapshot.git(&["rev-parse", "--is-inside-work-tree"], None);
+        if !inside.is_ok_and(|output| output.trim() == "true") {
+            snapshot.git(&["init", "--quiet"], None)?;
+        }
+        Ok(snapshot)
+    }
+
+    /// 写入页面，返回与上次提交相比的变更。`scopes` 为起始页面的 URL，这些页面所在层级下本次没有出现、
+    /// 也不在 `keep`（例如爬取失败的页面）中的文件会被删除；不需要删除时传空切片
+    pub fn write(
+        &self,
+        pages: &(impl PageSource + ?Sized),
+        scopes: &[String],
+        keep: &[String],
+    ) -> Result<SnapshotChanges> {
+        let mut local = LocalPages::new();
+        pages.for_each_page(&mut |page| {
+            if page.status.is_none() {
+                let entry = (page_path(page), page.title.clone(), page.anchors.clone());
+                local.insert(canonical_url(&page.url).to_string(), entry);
+            }
+            Ok(())
+        })?;
+
+        let mut written = HashSet::new();
+        let mut entries = Vec::new();
+        pages.for_each_page(&mut |page| {
+            // 失效页面的占位记录没有内容，不写入，原有的文件按删除处理
+            if page.status.is_some() {
+                return Ok(());
+            }
+            let relative = page_path(page);
+            let path = self.repo.join(&relative);
+            let content = page_markdown(page, &local);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, &content)
+                .with_context(|| tr!("无法写入文件: {}", "cannot write file: {}", path.display()))?;
+            entries.push(SnapshotEntry {
+                url: page.url.clone(),
+                title: page.title.clone(),
+                path: slash_path(&relative),
+                sha256: sha256_hex(content.as_bytes()),
+                bytes: content.len() as u64,
+            });
+            written.insert(relative);
+            Ok(())
+        })?;
+
+        let keep: HashSet<PathBuf> = keep.iter().map(|url| page_path(&url_page(url))).collect();
+        let mut candidates = Vec::new();
+        for scope in scopes {
+            let file = page_path(&url_page(scope));
+            candidates.push(file.clone());
+            collect_markdown(&self.repo, &file.with_extension(""), &mut candidates)?;
+        }
+        let mut touched = written.clone();
+        for relative in candidates {
+            let path = self.repo.join(&relative);
+            if written.contains(&relative) || keep.contains(&relative) || !path.is_file() {
+                continue;
+            }
+            std::fs::remove_file(&path)
+                .with_context(|| tr!("无法删除文件: {}", "cannot remove file: {}", path.display()))?;
+            touched.insert(relative);
+        }
+        touched.extend(self.write_listings(entries)?);
+        self.status(&touched)
+    }
+
+    /// 把本次写入的页面合并到清单，保留之前的清单中文件仍然存在的页面，然后重新生成站点地图；
+    /// 返回两个文件的路径。已有的清单无法读取时重新生成
+    fn write_listings(&self, written: Vec<SnapshotEntry>) -> Result<[PathBuf; 2]> {
+        let manifest_path = self.repo.join(MANIFEST_FILE);
+        let existing = if manifest_path.exists() { SnapshotManifest::load(&manifest_path).ok() } else { None };
+        let written_paths: HashSet<String> = written.iter().map(|entry| entry.path.clone()).collect();
+        let mut entries: BTreeMap<String, SnapshotEntry> = existing
+            .into_iter()
+            .flat_map(|manifest| manifest.entries)
+            .filter(|entry| !written_paths.contains(&entry.path) && self.repo.join(&entry.path).is_file())
+            .map(|entry| (entry.url.clone(), entry))
+            .collect();
+        entries.extend(written.into_iter().map(|entry| (entry.url.clone(), entry)));
+        let manifest = SnapshotManifest {
+            count: entries.len(),
+            entries: entries.into_values().collect(),
+        };
+        let mut json = serde_json::to_string_pretty(&manifest)?;
+        json.push('\n');
+        std::fs::write(&manifest_path, json)
+            .with_context(|| tr!("无法写入文件: {}", "cannot write file: {}", manifest_path.display()))?;
+        let sitemap_path = self.repo.join(SITEMAP_FILE);
+        std::fs::write(&sitemap_path, manifest.sitemap())
+            .with_context(|| tr!("无法写入文件: {}", "cannot write file: {}", sitemap_path.display()))?;
+        Ok([PathBuf::from(MANIFEST_FILE), PathBuf::from(SITEMAP_FILE)])
+    }
+
+    /// `paths` 中与上次提交不同的文件，没有提交过的文件算作新增
+    fn status(&self, paths: &HashSet<PathBuf>) -> Result<SnapshotChanges> {
+        // porcelain 格式的路径总是相对于仓库顶层，快照目录可能只是更大仓库中的子目录
+        let prefix = self.git(&["rev-parse", "--show-prefix"], None)?;
+        let status = self.git(&["status", "--porcelain", "-z", "--untracked-files=all", "--", "."], None)?;
+        let mut changes = SnapshotChanges::default();
+        let mut entries = status.split('\0').filter(|entry| entry.len() > 3);
+        while let Some(entry) = entries.next() {
+            let (code, path) = entry.split_at(3);
+            // 重命名和复制的条目后面还跟着原路径
+            if code.starts_with(['R', 'C']) {
+                entries.next();
+            }
+            let path = PathBuf::from(path.strip_prefix(prefix.trim()).unwrap_or(path));
+            if !paths.contains(&path) {
+                continue;
+            }
+            if path == Path::new(MANIFEST_FILE) || path == Path::new(SITEMAP_FILE) {
+                changes.listings.push(path);
+                continue;
+            }
+            match code.trim() {
+                "??" | "A" | "AM" => changes.added.push(path),
+                code if code.contains('D') => changes.removed.push(path),
+                _ => changes.changed.push(path),
+            }
+        }
+        changes.added.sort();
+        changes.changed.sort();
+        changes.removed.sort();
+        Ok(changes)
+    }
+
+    /// 提交 `changes` 中的文件，没有变更时不提交；返回是否提交
+    pub fn commit(&self, changes: &SnapshotChanges) -> Result<bool> {
+        if changes.is_empty() {
+            return Ok(false);
+        }
+        let mut pathspecs = Vec::new();
+        let paths = [&changes.added, &changes.changed, &changes.removed, &changes.listings];
+        for path in paths.into_iter().flatten() {
+            pathspecs.extend_from_slice(path.to_string_lossy().as_bytes());
+            pathspecs.push(0);
+        }
+        let from_stdin = ["--pathspec-from-file=-", "--pathspec-file-nul"];
+        self.git(&["add", "--all", from_stdin[0], from_stdin[1]], Some(&pathspecs))?;
+        let message = changes.message();
+        self.git(&["commit", "--quiet", "--only", "-m", &message, from_stdin[0], from_stdin[1]], Some(&pathspecs))?;
+        Ok(true)
+    }
+
+    /// 在仓库中运行 git，返回标准输出；路径按字面匹配，不解释通配符
+    fn git(&self, args: &[&str], stdin: Option<&[u8]>) -> Result<String> {
+        let mut child = Command::new("git")
+            .arg("--literal-pathspecs")
+            .arg("-C")
+            .arg(&self.repo)
+            .args(args)
+            .stdin(if stdin.is_some() { Stdio::piped() } else { Stdio::null() })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context(tr!("无法运行 git", "cannot run git"))?;
+        if let (Some(input), Some(mut pipe)) = (stdin, child.stdin.take()) {
+            pipe.write_all(input)?;
+        }
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            bail!(tr!(
+                "git {} 失败: {}",
+                "git {} failed: {}",
+                args[0],
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+/// 只有 URL 的页面，用于计算 URL 对应的快照路径
+fn url_page(url: &str) -> DocPage {
+    DocPage {
+        url: url.to_string(),
+        ..Default::default()
+    }
+}
+
+/// 收集 `dir`（相对于 `root`）下的所有 Markdown 文件
+fn collect_markdown(root: &Path, dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let Ok(entries) = std::fs::read_dir(root.join(dir)) else {
+        return Ok(());
+    };
+    for entry in entries {
+        let entry = entry?;
+        let relative = dir.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            if entry.file_name() != ".git" {
+                collect_markdown(root, &relative, files)?;
+            }
+        } else if relative.extension().is_some_and(|extension| extension == "md") {
+            files.push(relative);
+        }
+    }
+    Ok(())
+}
+
+/// 以 `/` 分隔的相对路径，与平台无关
+fn slash_path(path: &Path) -> String {
+    path.components().map(|part| part.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/")
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,180 @@
+//! 超出内存预算时把页面溢出到临时文件。
+//!
+//! [`SpillBuffer`] 作为推送目标接收爬取到的页面，内存中的页面超过预算后整批以 JSON Lines
+//! 追加到临时文件并清空。保存时 [`SpilledPages`] 先读临时文件、再读内存中剩余的页面，
+//! 逐页写出，任何时候内存中都只有预算内的页面。
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use crate::extract::{DocPage, RelatedLink};
+use crate::output::PageSource;
+use crate::sink::PageSink;
+use crate::tr;
+
+/// 临时文件，删除时一并删除
+struct SpillFile {
+    path: PathBuf,
+    writer: BufWriter<File>,
+}
+
+impl SpillFile {
+    fn create() -> Result<Self> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let path = std::env::temp_dir().join(format!(
+            "adoc-spill-{}-{}.jsonl",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let file = File::create(&path)
+            .with_context(|| tr!("无法创建临时文件: {}", "cannot create temporary file: {}", path.display()))?;
+        Ok(Self {
+            path,
+            writer: BufWriter::new(file),
+        })
+    }
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[derive(Default)]
+struct State {
+    pages: Vec<DocPage>,
+    /// 内存中页面的估算大小
+    bytes: usize,
+    file: Option<SpillFile>,
+    spilled: usize,
+    extracted: bool,
+}
+
+/// 按内存预算缓存页面的推送目标
+pub struct SpillBuffer {
+    state: Mutex<State>,
+    budget: usize,
+}
+
+/// 页面占用内存的估算值：各字符串的长度加上固定开销
+fn estimated_size(page: &DocPage) -> usize {
+    const OVERHEAD: usize = std::mem::size_of::<DocPage>();
+    OVERHEAD
+        + page.title.len()
+        + page.content.len()
+        + page.url.len()
+        + page
+            .related_links
+            .iter()
+            .map(|link| {
+                link.url.len()
+                    + link.text.len()
+                    + link.section.as_ref().map_or(0, String::len)
+                    + std::mem::size_of::<RelatedLink>()
+            })
+            .sum::<usize>()
+}
+
+impl SpillBuffer {
+    /// `budget` 为内存中保留页面的字节数上限
+    pub fn new(budget: usize) -> Self {
+        Self {
+            state: Mutex::new(State::default()),
+            budget,
+        }
+    }
+
+    /// 取出目前收到的所有页面，之后缓冲区为空
+    pub fn finish(&self) -> Result<SpilledPages> {
+        let mut state = std::mem::take(&mut *self.state.lock().unwrap());
+        if let Some(file) = &mut state.file {
+            file.writer.flush()?;
+        }
+        Ok(SpilledPages {
+            file: state.file,
+            pages: state.pages,
+            spilled: state.spilled,
+            extracted: state.extracted,
+        })
+    }
+}
+
+#[async_trait]
+impl PageSink for SpillBuffer {
+    async fn send(&self, page: &DocPage) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        state.extracted |= !page.title.trim().is_empty() || !page.content.trim().is_empty();
+        state.bytes += estimated_size(page);
+        state.pages.push(page.clone());
+        if state.bytes <= self.budget {
+            return Ok(());
+        }
+
+        let state = &mut *state;
+        let file = match &mut state.file {
+            Some(file) => file,
+            None => state.file.insert(SpillFile::create()?),
+        };
+        for page in state.pages.drain(..) {
+            serde_json::to_writer(&mut file.writer, &page)?;
+            file.writer.write_all(b"\n")?;
+            state.spilled += 1;
+        }
+        state.bytes = 0;
+        Ok(())
+    }
+
+    async fn flush(&self) -> Result<()> {
+        if let Some(file) = &mut self.state.lock().unwrap().file {
+            file.writer.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// [`SpillBuffer`] 收集到的页面：临时文件中的页面在前，内存中的在后
+pub struct SpilledPages {
+    file: Option<SpillFile>,
+    pages: Vec<DocPage>,
+    spilled: usize,
+    extracted: bool,
+}
+
+impl SpilledPages {
+    /// 页面总数
+    pub fn len(&self) -> usize {
+        self.spilled + self.pages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 溢出到临时文件的页面数
+    pub fn spilled(&self) -> usize {
+        self.spilled
+    }
+
+    /// 是否至少有一个页面提取到了标题或正文
+    pub fn has_content(&self) -> bool {
+        self.extracted
+    }
+}
+
+impl PageSource for SpilledPages {
+    fn for_each_page(&self, f: &mut dyn FnMut(&DocPage) -> Result<()>) -> Result<()> {
+        if let Some(file) = &self.file {
+            let reader = BufReader::new(File::open(&file.path)?);
+            for line in reader.lines() {
+                f(&serde_json::from_str(&line?)?)?;
+            }
+        }
+        self.pages.iter().try_for_each(f)
+    }
+}
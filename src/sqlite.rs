@@ -0,0 +1,184 @@
+//! SQLite 数据库输出。
+//!
+//! 页面写入 `pages` 表，同时建立 FTS5 全文索引表 `pages_fts`，
+//! 生成的文件可以直接在任意 SQLite 客户端中搜索，例如：
+//!
+//! ```sql
+//! SELECT p.title, p.url FROM pages_fts
+//! JOIN pages p ON p.id = pages_fts.rowid
+//! WHERE pages_fts MATCH 'NavigationStack'
+//! ORDER BY rank;
+//! ```
+//!
+//! RAG 分块（`--chunk-size`）输出到 SQLite 时写入 `chunks` 表，嵌入向量以小端序 `f32` 数组存为 BLOB。
+
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, Row};
+use std::path::Path;
+
+use crate::chunk::Chunk;
+use crate::extract::DocPage;
+use crate::output::PageSource;
+use crate::tr;
+
+const SCHEMA: &str = "
+CREATE TABLE pages (
+    id INTEGER PRIMARY KEY,
+    url TEXT NOT NULL UNIQUE,
+    title TEXT NOT NULL,
+    content TEXT NOT NULL,
+    -- 文档层级，以 ' / ' 连接
+    hierarchy TEXT NOT NULL,
+    -- JSON 数组
+    related_links TEXT NOT NULL
+);
+CREATE VIRTUAL TABLE pages_fts USING fts5(
+    title, content, hierarchy,
+    content = 'pages', content_rowid = 'id'
+);
+";
+
+const CHUNKS_SCHEMA: &str = "
+CREATE TABLE chunks (
+    id INTEGER PRIMARY KEY,
+    url TEXT NOT NULL,
+    title TEXT NOT NULL,
+    -- 文档层级，以 ' / ' 连接
+    hierarchy TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    total INTEGER NOT NULL,
+    token_count INTEGER NOT NULL,
+    content TEXT NOT NULL,
+    -- 小端序 f32 数组，没有嵌入向量时为 NULL
+    embedding BLOB
+);
+";
+
+/// 判断文件是否为 SQLite 输出（按扩展名）
+pub fn is_sqlite_path(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|ext| ext.to_str()),
+        Some("sqlite" | "sqlite3" | "db")
+    )
+}
+
+/// 将结果写入 SQLite 数据库，已存在的文件会被覆盖
+pub fn save_results(results: &[DocPage], path: &Path) -> Result<()> {
+    save_pages(results, path)
+}
+
+/// 逐页写入 SQLite 数据库，已存在的文件会被覆盖
+pub fn save_pages(pages: &(impl PageSource + ?Sized), path: &Path) -> Result<()> {
+    if path.exists() {
+        std::fs::remove_file(path)?;
+    }
+    let mut conn = Connection::open(path)
+        .with_context(|| tr!("无法创建数据库: {}", "cannot create database: {}", path.display()))?;
+    conn.execute_batch(SCHEMA)?;
+
+    let tx = conn.transaction()?;
+    {
+        let mut insert = tx.prepare(
+            "INSERT OR REPLACE INTO pages (url, title, content, hierarchy, related_links)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+        )?;
+        pages.for_each_page(&mut |page| {
+            insert.execute(params![
+                page.url,
+                page.title,
+                page.content,
+                page.hierarchy().join(" / "),
+                serde_json::to_string(&page.related_links)?,
+            ])?;
+            Ok(())
+        })?;
+    }
+    tx.execute("INSERT INTO pages_fts (pages_fts) VALUES ('rebuild')", [])?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// 将 RAG 分块写入 SQLite 数据库，已存在的文件会被覆盖
+pub fn save_chunks(chunks: &[Chunk], path: &Path) -> Result<()> {
+    if path.exists() {
+        std::fs::remove_file(path)?;
+    }
+    let mut conn = Connection::open(path)
+        .with_context(|| tr!("无法创建数据库: {}", "cannot create database: {}", path.display()))?;
+    conn.execute_batch(CHUNKS_SCHEMA)?;
+
+    let tx = conn.transaction()?;
+    {
+        let mut insert = tx.prepare(
+            "INSERT INTO chunks (url, title, hierarchy, position, total, token_count, content, embedding)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        )?;
+        for chunk in chunks {
+            let embedding = chunk
+                .embedding
+                .as_ref()
+                .map(|vector| vector.iter().flat_map(|value| value.to_le_bytes()).collect::<Vec<u8>>());
+            insert.execute(params![
+                chunk.url,
+                chunk.title,
+                chunk.hierarchy.join(" / "),
+                chunk.position as i64,
+                chunk.total as i64,
+                chunk.token_count as i64,
+                chunk.content,
+                embedding,
+            ])?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+fn page_from_row(row: &Row) -> rusqlite::Result<(String, String, String, String)> {
+    Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+}
+
+fn to_page((title, content, url, links): (String, String, String, String)) -> Result<DocPage> {
+    Ok(DocPage {
+        title,
+        content,
+        url,
+        related_links: serde_json::from_str(&links)?,
+        ..Default::default()
+    })
+}
+
+/// 读取数据库中的所有页面
+pub fn load_results(path: &Path) -> Result<Vec<DocPage>> {
+    let conn = Connection::open(path)
+        .with_context(|| tr!("无法打开数据库: {}", "cannot open database: {}", path.display()))?;
+    let mut select = conn.prepare("SELECT title, content, url, related_links FROM pages ORDER BY id")?;
+    let rows = select.query_map([], page_from_row)?;
+    rows.map(|row| to_page(row?)).collect()
+}
+
+/// 使用 FTS5 全文索引搜索，标题命中的权重更高
+///
+/// 查询中的每个词都按字面匹配，不解析 FTS5 查询语法。
+pub fn search(path: &Path, query: &str, limit: usize) -> Result<Vec<DocPage>> {
+    let conn = Connection::open(path)
+        .with_context(|| tr!("无法打开数据库: {}", "cannot open database: {}", path.display()))?;
+    let query = query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ");
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut select = conn.prepare(
+        "SELECT p.title, p.content, p.url, p.related_links
+         FROM pages_fts JOIN pages p ON p.id = pages_fts.rowid
+         WHERE pages_fts MATCH ?1
+         ORDER BY bm25(pages_fts, 10.0, 1.0, 1.0)
+         LIMIT ?2",
+    )?;
+    let rows = select.query_map(params![query, limit as i64], page_from_row)?;
+    rows.map(|row| to_page(row?)).collect()
+}
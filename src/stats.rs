@@ -0,0 +1,202 @@
+//! 爬取结果的统计分析：按框架/类型计数、正文长度分布、弃用和 Beta API、链接情况。
+
+use clap::ValueEnum;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+
+use crate::extract::DocPage;
+use crate::merge::canonical_url;
+use crate::report::display_width;
+use crate::tr;
+
+/// 最常被链接页面的展示数量
+const TOP_LINKED: usize = 10;
+
+/// 正文长度分桶（字符数上限，最后一个桶不设上限）
+const LENGTH_BUCKETS: [(usize, &str); 5] = [
+    (500, "< 500"),
+    (2_000, "500 - 2k"),
+    (10_000, "2k - 10k"),
+    (50_000, "10k - 50k"),
+    (usize::MAX, ">= 50k"),
+];
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+#[value(rename_all = "lowercase")]
+pub enum StatsFormat {
+    Table,
+    Json,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LinkedPage {
+    pub url: String,
+    pub inbound_links: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CrawlStats {
+    pub pages: usize,
+    pub by_framework: BTreeMap<String, usize>,
+    pub by_kind: BTreeMap<String, usize>,
+    pub content_length_min: usize,
+    pub content_length_median: usize,
+    pub content_length_max: usize,
+    pub content_length_distribution: Vec<(String, usize)>,
+    pub deprecated: usize,
+    pub beta: usize,
+    pub most_linked: Vec<LinkedPage>,
+    /// 指向 Apple 文档、但不在本次结果中的链接数（重复链接只计一次）
+    pub broken_links: usize,
+}
+
+/// 根据 URL 粗略判断页面类型
+///
+/// 抓取结果中没有 DocC 的 role 信息，这里只依据 URL 最后一段：
+/// 带括号的是方法/函数，框架根页面只有一层，标题含空格的通常是文章。
+fn page_kind(page: &DocPage) -> &'static str {
+    let hierarchy = page.hierarchy();
+    match hierarchy.last() {
+        None => "other",
+        Some(_) if hierarchy.len() == 1 => "framework",
+        Some(last) if last.contains('(') => "method",
+        Some(_) if page.title.contains(' ') => "article",
+        Some(_) => "symbol",
+    }
+}
+
+pub fn compute_stats(pages: &[DocPage]) -> CrawlStats {
+    let mut stats = CrawlStats {
+        pages: pages.len(),
+        ..Default::default()
+    };
+
+    for page in pages {
+        let framework = page.framework().unwrap_or_else(|| "(unknown)".to_string());
+        *stats.by_framework.entry(framework).or_default() += 1;
+        *stats.by_kind.entry(page_kind(page).to_string()).or_default() += 1;
+        stats.deprecated += page.is_deprecated() as usize;
+        stats.beta += page.is_beta() as usize;
+    }
+
+    let mut lengths: Vec<usize> = pages.iter().map(|page| page.content.chars().count()).collect();
+    lengths.sort_unstable();
+    if let (Some(min), Some(max)) = (lengths.first(), lengths.last()) {
+        stats.content_length_min = *min;
+        stats.content_length_max = *max;
+        stats.content_length_median = lengths[lengths.len() / 2];
+    }
+    stats.content_length_distribution = LENGTH_BUCKETS
+        .iter()
+        .enumerate()
+        .map(|(i, (upper, label))| {
+            let lower = if i == 0 { 0 } else { LENGTH_BUCKETS[i - 1].0 };
+            let count = lengths.iter().filter(|len| **len >= lower && **len < *upper).count();
+            (label.to_string(), count)
+        })
+        .collect();
+
+    let known: HashSet<&str> = pages.iter().map(|page| canonical_url(&page.url)).collect();
+    let mut inbound: HashMap<&str, usize> = HashMap::new();
+    let mut broken: HashSet<&str> = HashSet::new();
+    for page in pages {
+        let targets: HashSet<&str> = page.link_urls().map(canonical_url).collect();
+        for target in targets {
+            if known.contains(target) {
+                *inbound.entry(target).or_default() += 1;
+            } else if target.contains("developer.apple.com/documentation") {
+                broken.insert(target);
+            }
+        }
+    }
+    stats.broken_links = broken.len();
+
+    let mut most_linked: Vec<(&str, usize)> = inbound.into_iter().collect();
+    most_linked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    stats.most_linked = most_linked
+        .into_iter()
+        .take(TOP_LINKED)
+        .map(|(url, inbound_links)| LinkedPage {
+            url: url.to_string(),
+            inbound_links,
+        })
+        .collect();
+
+    stats
+}
+
+fn write_table(f: &mut fmt::Formatter<'_>, heading: &str, rows: &[(String, String)]) -> fmt::Result {
+    let name_width = rows
+        .iter()
+        .map(|(name, _)| display_width(name))
+        .chain(std::iter::once(display_width(heading)))
+        .max()
+        .unwrap_or(0);
+    let value_width = rows.iter().map(|(_, value)| value.len()).max().unwrap_or(0).max(6);
+    let border = format!("+-{}-+-{}-+", "-".repeat(name_width), "-".repeat(value_width));
+
+    writeln!(f, "{}", border)?;
+    writeln!(
+        f,
+        "| {}{} | {} |",
+        heading,
+        " ".repeat(name_width - display_width(heading)),
+        " ".repeat(value_width)
+    )?;
+    writeln!(f, "{}", border)?;
+    for (name, value) in rows {
+        let padding = " ".repeat(name_width - display_width(name));
+        writeln!(f, "| {}{} | {:>width$} |", name, padding, value, width = value_width)?;
+    }
+    writeln!(f, "{}", border)
+}
+
+fn count_rows<'a>(counts: impl IntoIterator<Item = (&'a String, &'a usize)>) -> Vec<(String, String)> {
+    counts
+        .into_iter()
+        .map(|(name, count)| (name.clone(), count.to_string()))
+        .collect()
+}
+
+impl fmt::Display for CrawlStats {
+    /// 以多张表格输出统计信息
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let overview = [
+            (tr!("页面总数", "Pages"), self.pages),
+            (tr!("已弃用", "Deprecated"), self.deprecated),
+            ("Beta".to_string(), self.beta),
+            (tr!("失效链接", "Broken links"), self.broken_links),
+            (tr!("最短正文", "Shortest content"), self.content_length_min),
+            (tr!("正文中位数", "Median content"), self.content_length_median),
+            (tr!("最长正文", "Longest content"), self.content_length_max),
+        ];
+        let overview: Vec<_> = overview
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        write_table(f, &tr!("概览", "Overview"), &overview)?;
+
+        let mut by_framework: Vec<_> = self.by_framework.iter().collect();
+        by_framework.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        write_table(f, &tr!("按框架", "By framework"), &count_rows(by_framework))?;
+        write_table(f, &tr!("按类型", "By kind"), &count_rows(&self.by_kind))?;
+
+        let distribution: Vec<_> = self
+            .content_length_distribution
+            .iter()
+            .map(|(bucket, count)| (bucket.clone(), count.to_string()))
+            .collect();
+        write_table(f, &tr!("正文长度（字符）", "Content length (chars)"), &distribution)?;
+
+        if !self.most_linked.is_empty() {
+            let most_linked: Vec<_> = self
+                .most_linked
+                .iter()
+                .map(|page| (page.url.clone(), page.inbound_links.to_string()))
+                .collect();
+            write_table(f, &tr!("被链接最多的页面", "Most linked pages"), &most_linked)?;
+        }
+        Ok(())
+    }
+}
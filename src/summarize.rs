@@ -0,0 +1,135 @@
+//! 用本地大模型为页面生成摘要（`--summarize ollama://llama3`）。
+//!
+//! 调用 Ollama 的 `/api/generate` 接口，每个页面生成 2 到 3 句话的摘要，写回 [`DocPage::summary`]，
+//! 随页面一起输出；Markdown 和文本格式把摘要放在正文之前，浏览大量页面时不必逐页阅读正文。
+//! 多个页面由 `workers` 个并发请求同时处理，单个页面失败只记录警告。
+
+use anyhow::{bail, Context, Result};
+use futures::stream::{self, StreamExt};
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+use tracing::{debug, warn};
+use url::Url;
+
+use crate::extract::DocPage;
+use crate::tr;
+
+/// 未指定主机时 Ollama 的默认地址
+pub const DEFAULT_OLLAMA_URL: &str = "http://localhost:11434";
+
+/// 送给模型的正文最多字符数，过长的页面只取开头部分
+const MAX_CONTENT_CHARS: usize = 6000;
+
+#[derive(Debug, Clone)]
+pub struct SummarizerConfig {
+    /// Ollama 服务地址，例如 http://localhost:11434
+    pub url: String,
+    pub model: String,
+    /// 同时处理的页面数
+    pub workers: usize,
+}
+
+impl SummarizerConfig {
+    /// 解析 `--summarize` 的取值：`ollama://模型` 使用本机的 Ollama，
+    /// `ollama://主机:端口/模型` 使用指定的 Ollama 服务
+    pub fn parse(target: &str, workers: usize) -> Result<Self> {
+        let Some(rest) = target.strip_prefix("ollama://") else {
+            bail!(tr!(
+                "不支持的摘要模型: {}，应为 ollama://模型 或 ollama://主机:端口/模型",
+                "unsupported summarization model: {}, expected ollama://model or ollama://host:port/model",
+                target
+            ));
+        };
+        let (url, model) = match rest.split_once('/') {
+            Some((host, model)) => (format!("http://{}", host), model),
+            None => (DEFAULT_OLLAMA_URL.to_string(), rest),
+        };
+        if model.is_empty() || Url::parse(&url).is_err() {
+            bail!(tr!("无效的摘要模型: {}", "invalid summarization model: {}", target));
+        }
+        Ok(Self {
+            url,
+            model: model.to_string(),
+            workers: workers.max(1),
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct GenerateResponse {
+    response: String,
+}
+
+pub struct Summarizer {
+    client: Client,
+    config: SummarizerConfig,
+}
+
+impl Summarizer {
+    pub fn new(config: SummarizerConfig) -> Self {
+        Self {
+            client: Client::new(),
+            config,
+        }
+    }
+
+    /// 为一个页面生成摘要
+    pub async fn summarize(&self, page: &DocPage) -> Result<String> {
+        let content: String = page.content.chars().take(MAX_CONTENT_CHARS).collect();
+        let prompt = format!(
+            "Summarize the following developer documentation page in 2-3 sentences. \
+             Reply with the summary only.\n\nTitle: {}\n\n{}",
+            page.title, content
+        );
+        let url = format!("{}/api/generate", self.config.url.trim_end_matches('/'));
+        let response = self
+            .client
+            .post(&url)
+            .json(&json!({ "model": self.config.model, "prompt": prompt, "stream": false }))
+            .send()
+            .await
+            .with_context(|| tr!("无法连接摘要模型: {}", "cannot reach summarization model: {}", url))?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            bail!(tr!("摘要模型返回 {}: {}", "summarization model returned {}: {}", status, body.trim()));
+        }
+        let summary = response
+            .json::<GenerateResponse>()
+            .await
+            .context(tr!("无法解析摘要模型的响应", "cannot parse the summarization response"))?
+            .response;
+        Ok(summary.split_whitespace().collect::<Vec<_>>().join(" "))
+    }
+
+    /// 为所有页面生成摘要，没有正文的页面（例如失效页面的占位记录）和已有摘要的页面跳过；
+    /// 返回成功生成摘要的页面数
+    pub async fn summarize_pages(&self, pages: &mut [DocPage]) -> usize {
+        let total = pages.len();
+        let pending = pages
+            .iter_mut()
+            .filter(|page| page.summary.is_none() && !page.content.trim().is_empty());
+        let summarized = stream::iter(pending)
+            .map(|page| async move {
+                match self.summarize(page).await {
+                    Ok(summary) if !summary.is_empty() => {
+                        page.summary = Some(summary);
+                        debug!("{}", tr!("已生成摘要: {}", "Summarized: {}", page.url));
+                        true
+                    }
+                    Ok(_) => false,
+                    Err(e) => {
+                        warn!("{}", tr!("生成摘要失败: {}, 错误: {:#}", "Failed to summarize: {}, error: {:#}", page.url, e));
+                        false
+                    }
+                }
+            })
+            .buffer_unordered(self.config.workers)
+            .filter(|done| std::future::ready(*done))
+            .count()
+            .await;
+        debug!("{}", tr!("共 {} 个页面，生成 {} 个摘要", "{} pages, {} summarized", total, summarized));
+        summarized
+    }
+}
@@ -0,0 +1,162 @@
+//! 为页面生成标签（`--tags`）。
+//!
+//! 标签依次取自：所属框架、正文中可用性标记的平台（`iOS 17.0+` → `iOS`）、正文中提到的其他符号
+//! （结果中符号页面的标题），以及按 TF-IDF 选出的关键词，去重后保存在 [`DocPage::tags`] 中。
+//! `adoc query --tag` 按标签筛选，Markdown 输出把标签写成 `#标签`，Obsidian 等笔记工具可以直接识别。
+//! 关键词的 IDF 在整个结果集上计算，因此标签只在所有页面提取完成后生成。
+
+use std::collections::{HashMap, HashSet};
+
+use crate::extract::DocPage;
+
+/// 每个页面最多的符号标签数
+const MAX_SYMBOLS: usize = 10;
+
+/// 每个页面的关键词标签数
+const MAX_KEYWORDS: usize = 5;
+
+/// 作为关键词的最短单词长度
+const MIN_KEYWORD_LEN: usize = 4;
+
+/// 不作为关键词的常见英文单词和文档中的套话
+const STOPWORDS: &[&str] = &[
+    "about", "above", "after", "again", "also", "available", "because", "been", "before", "being", "below",
+    "between", "both", "call", "called", "cannot", "case", "could", "current", "declaration", "default",
+    "discussion", "does", "doesn", "done", "each", "example", "first", "following", "from", "have", "here",
+    "however", "instance", "into", "just", "like", "make", "many", "method", "more", "most", "must", "need",
+    "only", "other", "overview", "parameter", "parameters", "property", "provide", "provides", "return",
+    "returns", "same", "should", "some", "such", "than", "that", "their", "them", "then", "there", "these",
+    "they", "this", "those", "through", "type", "under", "until", "used", "uses", "using", "value", "values",
+    "when", "where", "whether", "which", "while", "will", "with", "within", "without", "would", "your",
+];
+
+/// 为所有页面生成标签，覆盖已有的标签；失效页面的占位记录不生成标签
+pub fn tag_pages(pages: &mut [DocPage]) {
+    // 结果中的符号页面：标题是单个标识符的才能在正文中按单词匹配
+    let symbols: HashSet<String> = pages
+        .iter()
+        .filter(|page| page.symbol_kind().is_some_and(|kind| kind != "framework"))
+        .filter(|page| is_identifier(&page.title) && page.title.len() >= 3)
+        .map(|page| page.title.clone())
+        .collect();
+
+    let words: Vec<Vec<String>> = pages.iter().map(|page| keyword_candidates(&page.content)).collect();
+    let mut document_frequency: HashMap<&str, usize> = HashMap::new();
+    for page_words in &words {
+        for word in page_words.iter().map(String::as_str).collect::<HashSet<_>>() {
+            *document_frequency.entry(word).or_default() += 1;
+        }
+    }
+
+    let total = pages.len() as f64;
+    for (page, page_words) in pages.iter_mut().zip(&words) {
+        if page.status.is_some() {
+            page.tags.clear();
+            continue;
+        }
+        let mut tags = Tags::new(&page.title);
+        if let Some(framework) = page.framework() {
+            tags.push(framework);
+        }
+        for platform in page.availability() {
+            let name = platform.rsplit_once(' ').map_or(platform.as_str(), |(name, _)| name);
+            if name != "Xcode" {
+                tags.push(name.to_string());
+            }
+        }
+
+        let mut mentioned = 0;
+        for word in identifiers(&page.content) {
+            if mentioned == MAX_SYMBOLS {
+                break;
+            }
+            if symbols.contains(word) && tags.push(word.to_string()) {
+                mentioned += 1;
+            }
+        }
+
+        let mut term_frequency: HashMap<&str, usize> = HashMap::new();
+        for word in page_words {
+            *term_frequency.entry(word).or_default() += 1;
+        }
+        let mut scored: Vec<(f64, &str)> = term_frequency
+            .into_iter()
+            .map(|(word, count)| {
+                let idf = (total / document_frequency[word] as f64).ln() + 1.0;
+                (count as f64 * idf, word)
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+        let mut keywords = 0;
+        for (_, word) in scored {
+            if keywords == MAX_KEYWORDS {
+                break;
+            }
+            if tags.push(word.to_string()) {
+                keywords += 1;
+            }
+        }
+        page.tags = tags.into_vec();
+    }
+}
+
+/// 页面是否带有所有给定的标签（不区分大小写）
+pub fn has_tags(page: &DocPage, tags: &[String]) -> bool {
+    tags.iter().all(|tag| page.tags.iter().any(|own| own.eq_ignore_ascii_case(tag)))
+}
+
+/// Obsidian 风格的标签：`#标签`，空白替换为 `-`
+pub fn hashtag(tag: &str) -> String {
+    format!("#{}", tag.split_whitespace().collect::<Vec<_>>().join("-").trim_start_matches('#'))
+}
+
+/// 按顺序去重（不区分大小写）的标签列表，不包含页面自己的标题
+struct Tags {
+    seen: HashSet<String>,
+    tags: Vec<String>,
+}
+
+impl Tags {
+    fn new(title: &str) -> Self {
+        Self {
+            seen: HashSet::from([title.to_lowercase()]),
+            tags: Vec::new(),
+        }
+    }
+
+    /// 加入一个标签，返回是否为新标签
+    fn push(&mut self, tag: String) -> bool {
+        if tag.is_empty() || !self.seen.insert(tag.to_lowercase()) {
+            return false;
+        }
+        self.tags.push(tag);
+        true
+    }
+
+    fn into_vec(self) -> Vec<String> {
+        self.tags
+    }
+}
+
+fn is_identifier(text: &str) -> bool {
+    let mut chars = text.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// 正文中按出现顺序排列的标识符
+fn identifiers(content: &str) -> impl Iterator<Item = &str> {
+    content
+        .split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .filter(|word| is_identifier(word))
+}
+
+/// 可作为关键词的单词：只含字母的小写单词，去掉过短的单词和停用词
+fn keyword_candidates(content: &str) -> Vec<String> {
+    content
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .filter(|word| word.len() >= MIN_KEYWORD_LEN)
+        .map(str::to_ascii_lowercase)
+        .filter(|word| !STOPWORDS.contains(&word.as_str()))
+        .collect()
+}
@@ -0,0 +1,127 @@
+//! 页面正文的词数、字符数和 token 数（`--token-stats`）。
+//!
+//! token 数按指定的编码计算：`cl100k`、`o200k` 等 BPE 编码与 OpenAI 模型的计费一致，需要启用 `tokens` 特性；
+//! `words` 以空白分隔的词近似，与 [`crate::chunk`] 的分块方式相同。每个页面的统计保存在
+//! [`DocPage::size`] 中，全部页面的合计写入爬取报告，便于在导入之前估算上下文占用和嵌入费用。
+
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use std::fmt;
+
+use crate::extract::DocPage;
+use crate::tr;
+
+/// 计算 token 数使用的编码
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "lowercase")]
+pub enum TokenEncoding {
+    /// 以空白分隔的词近似，不需要 tokens 特性
+    Words,
+    /// GPT-4、GPT-3.5 和 text-embedding-3 系列使用的 cl100k_base
+    #[value(alias = "cl100k_base")]
+    Cl100k,
+    /// GPT-4o 系列使用的 o200k_base
+    #[value(alias = "o200k_base")]
+    O200k,
+    #[value(alias = "p50k_base")]
+    P50k,
+    #[value(alias = "r50k_base")]
+    R50k,
+}
+
+impl fmt::Display for TokenEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TokenEncoding::Words => "words",
+            TokenEncoding::Cl100k => "cl100k_base",
+            TokenEncoding::O200k => "o200k_base",
+            TokenEncoding::P50k => "p50k_base",
+            TokenEncoding::R50k => "r50k_base",
+        };
+        f.write_str(name)
+    }
+}
+
+impl TokenEncoding {
+    /// 检查编码是否可用：BPE 编码需要启用 tokens 特性
+    pub fn check(self) -> Result<()> {
+        if self != TokenEncoding::Words && !cfg!(feature = "tokens") {
+            anyhow::bail!(tr!(
+                "未启用 tokens 特性，无法按 {} 编码统计 token 数，可以改用 words",
+                "the tokens feature is disabled, cannot count {} tokens; use words instead",
+                self
+            ));
+        }
+        Ok(())
+    }
+
+    /// 文本的 token 数
+    pub fn count(self, text: &str) -> Result<usize> {
+        if self == TokenEncoding::Words {
+            return Ok(text.split_whitespace().count());
+        }
+        self.check()?;
+        #[cfg(feature = "tokens")]
+        {
+            let bpe = match self {
+                TokenEncoding::Cl100k => tiktoken_rs::cl100k_base_singleton(),
+                TokenEncoding::O200k => tiktoken_rs::o200k_base_singleton(),
+                TokenEncoding::P50k => tiktoken_rs::p50k_base_singleton(),
+                TokenEncoding::R50k => tiktoken_rs::r50k_base_singleton(),
+                TokenEncoding::Words => unreachable!(),
+            };
+            Ok(bpe.encode_ordinary(text).len())
+        }
+        #[cfg(not(feature = "tokens"))]
+        unreachable!()
+    }
+}
+
+/// 单个页面正文的大小
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct PageSize {
+    pub words: usize,
+    pub chars: usize,
+    pub tokens: usize,
+}
+
+/// 全部页面正文大小的合计，写入爬取报告
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentSize {
+    pub encoding: String,
+    pub pages: usize,
+    pub words: usize,
+    pub chars: usize,
+    pub tokens: usize,
+    /// token 数最多的页面
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub largest_page: Option<String>,
+    pub largest_page_tokens: usize,
+}
+
+/// 统计每个页面的正文大小并返回合计；失效页面的占位记录不统计
+pub fn measure_pages(pages: &mut [DocPage], encoding: TokenEncoding) -> Result<ContentSize> {
+    let mut total = ContentSize {
+        encoding: encoding.to_string(),
+        ..Default::default()
+    };
+    for page in pages.iter_mut().filter(|page| page.status.is_none()) {
+        let size = PageSize {
+            words: page.content.split_whitespace().count(),
+            chars: page.content.chars().count(),
+            tokens: encoding.count(&page.content)?,
+        };
+        total.pages += 1;
+        total.words += size.words;
+        total.chars += size.chars;
+        total.tokens += size.tokens;
+        if total.largest_page.is_none() || size.tokens > total.largest_page_tokens {
+            total.largest_page = Some(page.url.clone());
+            total.largest_page_tokens = size.tokens;
+        }
+        page.size = Some(size);
+    }
+    Ok(total)
+}
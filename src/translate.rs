@@ -0,0 +1,165 @@
+//! 把页面正文翻译为指定语言（`--translate zh-CN`）。
+//!
+//! 翻译器由 `--translator` 指定，有两种：
+//!
+//! - `cmd:./translate.sh`：每个页面运行一次命令，正文从标准输入传入，目标语言是最后一个参数，
+//!   也可以从环境变量 `ADOC_TRANSLATE_TARGET` 读取，标准输出即译文；
+//! - `http://…` / `https://…`：以 LibreTranslate 兼容的格式 POST `{"q", "source", "target", "format"}`，
+//!   读取响应中的 `translatedText`。
+//!
+//! 译文保存在 [`DocPage::content_translated`] 中，原文不变。单个页面翻译失败只记录警告。
+
+use anyhow::{bail, Context, Result};
+use futures::stream::{self, StreamExt};
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tracing::{debug, warn};
+
+use crate::extract::DocPage;
+use crate::tr;
+
+/// 同时翻译的页面数
+const CONCURRENCY: usize = 4;
+
+/// 翻译器
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Translator {
+    /// 外部命令，通过 `sh -c` 运行
+    Command(String),
+    /// LibreTranslate 兼容的 HTTP 接口
+    Http(String),
+}
+
+impl Translator {
+    /// 解析 `--translator` 的取值：`cmd:命令` 或 HTTP(S) 地址
+    pub fn parse(spec: &str) -> Result<Self> {
+        if let Some(command) = spec.strip_prefix("cmd:") {
+            if command.trim().is_empty() {
+                bail!(tr!("翻译命令为空", "the translator command is empty"));
+            }
+            return Ok(Self::Command(command.to_string()));
+        }
+        if spec.starts_with("http://") || spec.starts_with("https://") {
+            url::Url::parse(spec).with_context(|| tr!("无效的翻译接口: {}", "invalid translator endpoint: {}", spec))?;
+            return Ok(Self::Http(spec.to_string()));
+        }
+        bail!(tr!(
+            "不支持的翻译器: {}，应为 cmd:命令 或 http(s):// 地址",
+            "unsupported translator: {}, expected cmd:<command> or an http(s):// endpoint",
+            spec
+        ))
+    }
+}
+
+#[derive(Deserialize)]
+struct TranslateResponse {
+    #[serde(rename = "translatedText")]
+    translated_text: String,
+}
+
+pub struct PageTranslator {
+    client: Client,
+    translator: Translator,
+    /// 目标语言，例如 zh-CN
+    target: String,
+}
+
+impl PageTranslator {
+    pub fn new(translator: Translator, target: &str) -> Self {
+        Self {
+            client: Client::new(),
+            translator,
+            target: target.to_string(),
+        }
+    }
+
+    /// 翻译一段文本
+    pub async fn translate(&self, text: &str) -> Result<String> {
+        let translated = match &self.translator {
+            Translator::Command(command) => self.run_command(command, text).await?,
+            Translator::Http(endpoint) => self.post(endpoint, text).await?,
+        };
+        Ok(translated.trim_end().to_string())
+    }
+
+    async fn run_command(&self, command: &str, text: &str) -> Result<String> {
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(format!("{} \"$ADOC_TRANSLATE_TARGET\"", command))
+            .env("ADOC_TRANSLATE_TARGET", &self.target)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| tr!("无法运行翻译命令: {}", "cannot run translator command: {}", command))?;
+        // 边写入边读取输出，避免正文较长时双方都阻塞在管道上
+        let mut stdin = child.stdin.take().context("stdin")?;
+        let input = text.as_bytes().to_vec();
+        let writer = tokio::spawn(async move {
+            // 命令可能不读取全部输入就退出，写入失败不作为错误
+            let _ = stdin.write_all(&input).await;
+        });
+        let output = child.wait_with_output().await?;
+        let _ = writer.await;
+        if !output.status.success() {
+            bail!(tr!(
+                "翻译命令失败（{}）: {}",
+                "translator command failed ({}): {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        String::from_utf8(output.stdout).context(tr!("翻译命令的输出不是 UTF-8", "translator output is not UTF-8"))
+    }
+
+    async fn post(&self, endpoint: &str, text: &str) -> Result<String> {
+        let response = self
+            .client
+            .post(endpoint)
+            .json(&json!({ "q": text, "source": "auto", "target": self.target, "format": "text" }))
+            .send()
+            .await
+            .with_context(|| tr!("无法连接翻译接口: {}", "cannot reach translator endpoint: {}", endpoint))?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            bail!(tr!("翻译接口返回 {}: {}", "translator endpoint returned {}: {}", status, body.trim()));
+        }
+        Ok(response
+            .json::<TranslateResponse>()
+            .await
+            .context(tr!("无法解析翻译接口的响应", "cannot parse the translator response"))?
+            .translated_text)
+    }
+
+    /// 翻译所有页面的正文，没有正文的页面和已有译文的页面跳过；返回成功翻译的页面数
+    pub async fn translate_pages(&self, pages: &mut [DocPage]) -> usize {
+        let pending = pages
+            .iter_mut()
+            .filter(|page| page.content_translated.is_none() && !page.content.trim().is_empty());
+        stream::iter(pending)
+            .map(|page| async move {
+                match self.translate(&page.content).await {
+                    Ok(translated) if !translated.trim().is_empty() => {
+                        page.content_translated = Some(translated);
+                        debug!("{}", tr!("已翻译: {}", "Translated: {}", page.url));
+                        true
+                    }
+                    Ok(_) => false,
+                    Err(e) => {
+                        warn!("{}", tr!("翻译失败: {}, 错误: {:#}", "Failed to translate: {}, error: {:#}", page.url, e));
+                        false
+                    }
+                }
+            })
+            .buffer_unordered(CONCURRENCY)
+            .filter(|done| std::future::ready(*done))
+            .count()
+            .await
+    }
+}
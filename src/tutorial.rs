@@ -0,0 +1,184 @@
+//! 教程（`developer.apple.com/tutorials`，例如 SwiftUI、Develop in Swift）页面的提取。
+//!
+//! 教程是分步骤的交互页面，DocC render JSON 的 `kind` 为 `project`：每个任务（task）有说明和若干步骤，
+//! 步骤附带该步完成后的代码文件。这里把步骤按顺序提取为 [`TutorialStep`]，保存在 [`DocPage::steps`] 中，
+//! 同一文件相对上一次出现时的变化写成统一 diff；正文中任务写成 `## 标题`，步骤写成 `1. 说明`，
+//! Markdown 输出中每个步骤附上代码变化。
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use serde_json::Value;
+use similar::TextDiff;
+use std::collections::{HashMap, HashSet};
+use url::Url;
+
+use crate::extract::{block_text, clean_text, reference_sections, DocPage, LinkCollector, LinkFilter};
+use crate::profile::SiteProfile;
+
+/// 教程中的一个步骤
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct TutorialStep {
+    /// 所属任务的标题
+    pub section: String,
+    pub text: String,
+    /// 步骤附带的代码文件名，例如 `ContentView.swift`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// 该步完成后文件的完整代码
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    /// 相对同一文件上一次出现时的统一 diff，文件第一次出现或没有变化时为 `None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+}
+
+/// 是否为教程页面的 DocC render JSON
+pub fn is_tutorial(data: &Value) -> bool {
+    data.get("kind").and_then(Value::as_str) == Some("project") && data.get("sections").is_some()
+}
+
+/// 从教程页面的 DocC render JSON 中提取 `DocPage`，步骤保存在 `steps` 中
+pub fn extract_tutorial_json(profile: &SiteProfile, url: &str, data: &Value, links: &LinkFilter) -> Result<DocPage> {
+    let references = data.get("references").unwrap_or(&Value::Null);
+    let mut title = data.pointer("/metadata/title").and_then(Value::as_str).map(clean_text).unwrap_or_default();
+
+    let mut lines = Vec::new();
+    let mut steps = Vec::new();
+    // 每个文件最近一次的代码，用于计算下一步的变化
+    let mut previous: HashMap<String, String> = HashMap::new();
+    for section in array(data.get("sections")) {
+        match section.get("kind").and_then(Value::as_str) {
+            Some("hero") => {
+                if title.is_empty() {
+                    title = section.get("title").and_then(Value::as_str).map(clean_text).unwrap_or_default();
+                }
+                for block in array(section.get("content")) {
+                    block_text(block, references, &mut lines);
+                }
+                if let Some(minutes) = section.get("estimatedTimeInMinutes").and_then(Value::as_u64) {
+                    lines.push(format!("Estimated time: {} min", minutes));
+                }
+            }
+            Some("tasks") => {
+                for task in array(section.get("tasks")) {
+                    let task_title = task.get("title").and_then(Value::as_str).map(clean_text).unwrap_or_default();
+                    lines.push(format!("## {}", task_title));
+                    for content in array(task.get("contentSection")) {
+                        for block in array(content.get("content")) {
+                            block_text(block, references, &mut lines);
+                        }
+                    }
+                    let mut number = 0;
+                    for item in array(task.get("stepsSection")) {
+                        if item.get("type").and_then(Value::as_str) != Some("step") {
+                            block_text(item, references, &mut lines);
+                            continue;
+                        }
+                        number += 1;
+                        let step = step(&task_title, item, references, &mut previous);
+                        lines.push(format!("{}. {}", number, step.text));
+                        steps.push(step);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let base_url = Url::parse(url)?;
+    let sections = reference_sections(data);
+    let mut related_links = LinkCollector::new(profile, url, links)?;
+    for (identifier, reference) in references.as_object().into_iter().flatten() {
+        let Some(link) = reference.get("url").and_then(Value::as_str).and_then(|href| base_url.join(href).ok())
+        else {
+            continue;
+        };
+        let title = reference.get("title").and_then(Value::as_str).unwrap_or_default();
+        related_links.push(link, title, sections.get(identifier.as_str()).copied());
+    }
+
+    let content = lines
+        .iter()
+        .map(|line| clean_text(line))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    Ok(DocPage {
+        title,
+        content,
+        url: url.to_string(),
+        related_links: related_links.finish(),
+        steps,
+        ..Default::default()
+    })
+}
+
+fn step(section: &str, item: &Value, references: &Value, previous: &mut HashMap<String, String>) -> TutorialStep {
+    let mut blocks = Vec::new();
+    for block in array(item.get("content")).iter().chain(array(item.get("caption"))) {
+        block_text(block, references, &mut blocks);
+    }
+    let text = clean_text(&blocks.join("\n")).replace('\n', " ");
+
+    let file = item.get("code").and_then(Value::as_str).and_then(|id| references.get(id));
+    let Some(file) = file else {
+        return TutorialStep { section: section.to_string(), text, ..Default::default() };
+    };
+    let name = file
+        .get("fileName")
+        .or_else(|| file.get("identifier"))
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    let code = array(file.get("content")).iter().filter_map(Value::as_str).collect::<Vec<_>>().join("\n");
+    let diff = previous.get(&name).filter(|old| **old != code).map(|old| {
+        // 补上末尾的换行，避免 diff 中出现 “No newline at end of file”
+        let (old, new) = (format!("{}\n", old), format!("{}\n", code));
+        TextDiff::from_lines(&old, &new)
+            .unified_diff()
+            .context_radius(2)
+            .header(&name, &name)
+            .to_string()
+    });
+    previous.insert(name.clone(), code.clone());
+    TutorialStep {
+        section: section.to_string(),
+        text,
+        file: Some(name),
+        code: Some(code),
+        diff,
+    }
+}
+
+/// Markdown 输出中的步骤：按任务分组编号，附上代码变化，文件第一次出现时附上完整代码
+pub fn render_markdown(steps: &[TutorialStep]) -> String {
+    let mut out = String::new();
+    let mut section = None;
+    let mut number = 0;
+    let mut shown = HashSet::new();
+    for step in steps {
+        if section != Some(&step.section) {
+            section = Some(&step.section);
+            number = 0;
+            out.push_str(&format!("#### {}\n\n", step.section));
+        }
+        number += 1;
+        out.push_str(&format!("{}. {}\n\n", number, step.text));
+        let first = step.file.as_ref().is_some_and(|file| shown.insert(file.clone()));
+        match (&step.diff, &step.code) {
+            (Some(diff), _) => out.push_str(&format!("```diff\n{}```\n\n", diff)),
+            (None, Some(code)) if first => {
+                let syntax = step.file.as_deref().and_then(|file| file.rsplit_once('.')).map_or("", |(_, ext)| ext);
+                out.push_str(&format!("```{}\n{}\n```\n\n", syntax, code));
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+fn array(value: Option<&Value>) -> &[Value] {
+    value.and_then(Value::as_array).map(Vec::as_slice).unwrap_or_default()
+}
+
@@ -0,0 +1,187 @@
+//! `adoc self-update`：从 GitHub Releases 下载最新版本并替换当前可执行文件。
+//!
+//! 每个 release 需要为各平台上传两个文件，`<target>` 为 Rust 目标三元组：
+//!
+//! ```text
+//! adoc-x86_64-unknown-linux-gnu          可执行文件（Windows 为 adoc-<target>.exe）
+//! adoc-x86_64-unknown-linux-gnu.sha256   SHA-256 校验和，格式同 sha256sum 的输出
+//! ```
+
+use anyhow::{bail, Context, Result};
+use reqwest::Client;
+use serde::Deserialize;
+use std::path::Path;
+use tracing::info;
+
+use crate::crawler::BodyTooLarge;
+use crate::hash::sha256_hex;
+use crate::tr;
+
+/// 最新 release 的查询地址
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/king-open/adoc/releases/latest";
+
+/// 当前可执行文件的版本
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// 编译目标，由 build.rs 写入
+const TARGET: &str = env!("ADOC_TARGET");
+
+/// 可执行文件的下载上限
+const MAX_BINARY_SIZE: usize = 256 << 20;
+
+/// 校验和文件的下载上限
+const MAX_CHECKSUM_SIZE: usize = 4 << 10;
+
+#[derive(Debug, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    #[serde(default)]
+    pub assets: Vec<Asset>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Asset {
+    pub name: String,
+    pub browser_download_url: String,
+}
+
+impl Release {
+    /// 去掉 tag 的 `v` 前缀后的版本号
+    pub fn version(&self) -> &str {
+        self.tag_name.trim_start_matches('v')
+    }
+
+    fn asset(&self, name: &str) -> Result<&Asset> {
+        self.assets
+            .iter()
+            .find(|asset| asset.name == name)
+            .with_context(|| tr!("release {} 中没有文件: {}", "release {} has no asset: {}", self.tag_name, name))
+    }
+}
+
+/// 当前平台对应的可执行文件名
+pub fn asset_name() -> String {
+    if cfg!(windows) {
+        format!("adoc-{}.exe", TARGET)
+    } else {
+        format!("adoc-{}", TARGET)
+    }
+}
+
+/// 按数字逐段比较版本号，`latest` 比 `current` 新时返回 true
+pub fn is_newer(latest: &str, current: &str) -> bool {
+    fn parts(version: &str) -> Vec<u64> {
+        version
+            .split(['.', '-', '+'])
+            .map_while(|part| part.parse().ok())
+            .collect()
+    }
+    parts(latest) > parts(current)
+}
+
+/// 解析 sha256sum 格式的校验和文件，取第一个字段
+pub fn parse_checksum(content: &str) -> Result<String> {
+    let checksum = content.split_whitespace().next().unwrap_or_default().to_lowercase();
+    if checksum.len() != 64 || !checksum.chars().all(|c| c.is_ascii_hexdigit()) {
+        bail!(tr!("无效的校验和文件", "invalid checksum file"));
+    }
+    Ok(checksum)
+}
+
+fn client() -> Result<Client> {
+    // GitHub API 要求请求带 User-Agent
+    Ok(Client::builder()
+        .user_agent(format!("adoc/{}", CURRENT_VERSION))
+        .build()?)
+}
+
+/// 下载 `url`，超过 `limit` 字节时中止并返回 [`BodyTooLarge`]
+async fn download(client: &Client, url: &str, limit: usize) -> Result<Vec<u8>> {
+    let mut response = client
+        .get(url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .with_context(|| tr!("下载失败: {}", "download failed: {}", url))?;
+    if response.content_length().is_some_and(|length| length > limit as u64) {
+        return Err(BodyTooLarge { limit }.into());
+    }
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if bytes.len() + chunk.len() > limit {
+            return Err(BodyTooLarge { limit }.into());
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(bytes)
+}
+
+/// 查询最新 release
+pub async fn latest_release() -> Result<Release> {
+    let release = client()?
+        .get(LATEST_RELEASE_URL)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .with_context(|| tr!("无法获取最新版本信息", "cannot fetch the latest release"))?
+        .json()
+        .await?;
+    Ok(release)
+}
+
+/// 检查并安装新版本，`check_only` 时只报告是否有新版本。返回是否有新版本
+pub async fn self_update(check_only: bool) -> Result<bool> {
+    let release = latest_release().await?;
+    if !is_newer(release.version(), CURRENT_VERSION) {
+        info!("{}", tr!("已是最新版本: {}", "Already up to date: {}", CURRENT_VERSION));
+        return Ok(false);
+    }
+    info!("{}", tr!("发现新版本: {} -> {}", "New version available: {} -> {}", CURRENT_VERSION, release.version()));
+    if check_only {
+        return Ok(true);
+    }
+
+    let name = asset_name();
+    let binary = release.asset(&name)?;
+    let checksum = release.asset(&format!("{}.sha256", name))?;
+    let client = client()?;
+    let expected = download(&client, &checksum.browser_download_url, MAX_CHECKSUM_SIZE).await?;
+    let expected = parse_checksum(&String::from_utf8_lossy(&expected))?;
+    info!("{}", tr!("下载 {}", "Downloading {}", binary.browser_download_url));
+    let bytes = download(&client, &binary.browser_download_url, MAX_BINARY_SIZE).await?;
+    let actual = sha256_hex(&bytes);
+    if actual != expected {
+        bail!(tr!(
+            "校验和不匹配: 期望 {}，实际 {}",
+            "checksum mismatch: expected {}, got {}",
+            expected,
+            actual
+        ));
+    }
+
+    let exe = std::env::current_exe()?;
+    replace_executable(&exe, &bytes)
+        .with_context(|| tr!("无法替换可执行文件: {}", "cannot replace executable: {}", exe.display()))?;
+    info!("{}", tr!("已更新到 {}: {}", "Updated to {}: {}", release.version(), exe.display()));
+    Ok(true)
+}
+
+/// 先写入同目录的临时文件再重命名，避免替换到一半时留下损坏的可执行文件
+fn replace_executable(exe: &Path, bytes: &[u8]) -> Result<()> {
+    let staged = exe.with_extension("new");
+    std::fs::write(&staged, bytes)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o755))?;
+    }
+    // Windows 不能覆盖正在运行的程序，但可以先把它改名
+    #[cfg(windows)]
+    {
+        let old = exe.with_extension("old");
+        let _ = std::fs::remove_file(&old);
+        std::fs::rename(exe, old)?;
+    }
+    std::fs::rename(&staged, exe)?;
+    Ok(())
+}
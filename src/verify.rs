@@ -0,0 +1,185 @@
+//! 检查缓存镜像是否完整（`adoc verify --mirror`）。
+//!
+//! 用 `--cache-dir` 联网爬取后，缓存目录中的 [`Manifest`] 记录了每个缓存文件的路径、SHA-256 和大小。
+//! 复制到其他机器或长期保存的镜像可能缺少文件或被改动过，离线爬取时这些页面会失败或得到错误的内容。
+//! [`verify_mirror`] 按清单逐一检查文件，并列出清单之外的文件、预期但清单中没有的 URL，
+//! `--refetch` 只重新下载有问题的条目。
+
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt;
+use std::path::{Component, Path};
+
+use crate::cache::{manifest_file, CachedResponse, Manifest, ManifestEntry, MANIFEST_FILE};
+use crate::hash::sha256_hex;
+use crate::tr;
+
+/// 内容与清单不符的缓存文件
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CorruptedEntry {
+    pub url: String,
+    pub file: String,
+    pub reason: String,
+}
+
+/// 镜像检查结果
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct MirrorReport {
+    /// 清单中的条目数
+    pub listed: usize,
+    /// 检查通过的条目数
+    pub verified: usize,
+    /// 清单中有但文件不存在的 URL
+    pub missing: Vec<String>,
+    pub corrupted: Vec<CorruptedEntry>,
+    /// 缓存目录中有但清单中没有的文件，相对缓存目录
+    pub unlisted: Vec<String>,
+    /// 预期应在镜像中、但清单中没有的 URL
+    pub not_listed: Vec<String>,
+    /// 清单记录的条目数与实际条目数不同时为 `(记录的条目数, 实际条目数)`
+    pub count_mismatch: Option<(usize, usize)>,
+}
+
+impl MirrorReport {
+    /// 没有缺失、损坏或未收录的条目；清单之外的文件不影响离线爬取，不算问题
+    pub fn is_ok(&self) -> bool {
+        self.problems() == 0
+    }
+
+    /// 问题数
+    pub fn problems(&self) -> usize {
+        self.missing.len() + self.corrupted.len() + self.not_listed.len() + usize::from(self.count_mismatch.is_some())
+    }
+
+    /// 需要重新下载的 URL：缺失、损坏和未收录的条目，按出现顺序去重
+    pub fn broken_urls(&self) -> Vec<String> {
+        let mut seen = HashSet::new();
+        self.missing
+            .iter()
+            .chain(self.corrupted.iter().map(|entry| &entry.url))
+            .chain(&self.not_listed)
+            .filter(|url| seen.insert(url.as_str()))
+            .cloned()
+            .collect()
+    }
+}
+
+impl fmt::Display for MirrorReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{}",
+            tr!(
+                "清单 {} 个条目，{} 个校验通过",
+                "{} manifest entries, {} verified",
+                self.listed,
+                self.verified
+            )
+        )?;
+        if let Some((recorded, actual)) = self.count_mismatch {
+            writeln!(
+                f,
+                "{}",
+                tr!(
+                    "清单记录 {} 个条目，实际有 {} 个",
+                    "manifest records {} entries but has {}",
+                    recorded,
+                    actual
+                )
+            )?;
+        }
+        for url in &self.missing {
+            writeln!(f, "{}\t{}", tr!("缺失", "missing"), url)?;
+        }
+        for entry in &self.corrupted {
+            writeln!(f, "{}\t{}\t{} ({})", tr!("损坏", "corrupted"), entry.url, entry.reason, entry.file)?;
+        }
+        for url in &self.not_listed {
+            writeln!(f, "{}\t{}", tr!("未收录", "not listed"), url)?;
+        }
+        for file in &self.unlisted {
+            writeln!(f, "{}\t{}", tr!("清单之外", "unlisted"), file)?;
+        }
+        Ok(())
+    }
+}
+
+/// 按 `dir` 中的清单检查缓存镜像；`expected` 为应当在镜像中的 URL（例如上一次的失败列表），可以为空
+pub fn verify_mirror(dir: &Path, expected: &[String]) -> Result<MirrorReport> {
+    let manifest = Manifest::load(&dir.join(MANIFEST_FILE))?;
+    let mut report = MirrorReport {
+        listed: manifest.entries.len(),
+        ..Default::default()
+    };
+    if manifest.count != manifest.entries.len() {
+        report.count_mismatch = Some((manifest.count, manifest.entries.len()));
+    }
+    let mut listed_files = HashSet::new();
+    for entry in &manifest.entries {
+        listed_files.insert(entry.file.clone());
+        match check_entry(dir, entry) {
+            Check::Ok => report.verified += 1,
+            Check::Missing => report.missing.push(entry.url.clone()),
+            Check::Corrupted(reason) => report.corrupted.push(CorruptedEntry {
+                url: entry.url.clone(),
+                file: entry.file.clone(),
+                reason,
+            }),
+        }
+    }
+    let listed_urls: HashSet<&str> = manifest.entries.iter().map(|entry| entry.url.as_str()).collect();
+    report.not_listed = expected.iter().filter(|url| !listed_urls.contains(url.as_str())).cloned().collect();
+
+    let mut files = Vec::new();
+    cache_files(dir, dir, &mut files)?;
+    report.unlisted = files.into_iter().filter(|file| !listed_files.contains(file)).collect();
+    report.unlisted.sort();
+    Ok(report)
+}
+
+enum Check {
+    Ok,
+    Missing,
+    Corrupted(String),
+}
+
+fn check_entry(dir: &Path, entry: &ManifestEntry) -> Check {
+    // 清单可能被改动过，不读取缓存目录之外的文件
+    let file = Path::new(&entry.file);
+    if entry.file.is_empty() || !file.components().all(|part| matches!(part, Component::Normal(_))) {
+        return Check::Corrupted(tr!("路径无效", "invalid path"));
+    }
+    let Ok(content) = std::fs::read(dir.join(file)) else {
+        return Check::Missing;
+    };
+    if content.len() as u64 != entry.bytes {
+        return Check::Corrupted(tr!(
+            "大小为 {} 字节，清单记录 {} 字节",
+            "size is {} bytes, manifest says {}",
+            content.len(),
+            entry.bytes
+        ));
+    }
+    if sha256_hex(&content) != entry.sha256 {
+        return Check::Corrupted(tr!("SHA-256 不符", "SHA-256 mismatch"));
+    }
+    match serde_json::from_slice::<CachedResponse>(&content) {
+        Ok(cached) if cached.url == entry.url => Check::Ok,
+        Ok(cached) => Check::Corrupted(tr!("文件中的 URL 为 {}", "file contains {}", cached.url)),
+        Err(_) => Check::Corrupted(tr!("无法解析", "cannot be parsed")),
+    }
+}
+
+/// 缓存目录中的缓存文件（不含清单和未写完的临时文件），路径以 `/` 分隔
+fn cache_files(root: &Path, dir: &Path, files: &mut Vec<String>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            cache_files(root, &path, files)?;
+        } else if path.extension().is_some_and(|ext| ext == "json") && path != root.join(MANIFEST_FILE) {
+            files.push(manifest_file(root, &path));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,184 @@
+//! 爬取过程中已访问 URL 的集合。
+//!
+//! 默认用 `HashSet` 保存完整的 URL；镜像整个文档站点时可以改用布隆过滤器，
+//! 以极小的误判率（新 URL 被当作已访问而跳过）换取远低于前者的内存占用。
+//!
+//! 并发爬取时使用分片的 [`SharedVisited`]：检查和插入在一次加锁内完成，
+//! 锁是同步锁、不会跨越网络请求，不同 URL 大多落在不同分片上，工作任务之间几乎不争用。
+
+use clap::ValueEnum;
+use std::collections::HashSet;
+use std::sync::{Mutex, MutexGuard};
+
+use crate::hash::{fnv1a, FNV_OFFSET};
+use crate::tr;
+
+/// 已访问集合的实现方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+#[value(rename_all = "lowercase")]
+pub enum VisitedKind {
+    /// 保存完整 URL，没有误判
+    #[default]
+    Exact,
+    /// 布隆过滤器，内存占用与 URL 长度无关
+    Bloom,
+}
+
+/// 布隆过滤器的目标误判率
+const FALSE_POSITIVE_RATE: f64 = 0.001;
+
+/// 固定大小的布隆过滤器，按预计元素数量和误判率确定位数和哈希函数个数
+#[derive(Debug, Clone)]
+pub struct BloomFilter {
+    bits: Vec<u64>,
+    hashes: u32,
+}
+
+impl BloomFilter {
+    pub fn new(expected: usize, false_positive_rate: f64) -> Self {
+        let expected = expected.max(1) as f64;
+        let ln2 = std::f64::consts::LN_2;
+        let bits = (-expected * false_positive_rate.ln() / (ln2 * ln2)).ceil().max(64.0) as usize;
+        let hashes = ((bits as f64 / expected) * ln2).round().clamp(1.0, 32.0) as u32;
+        Self {
+            bits: vec![0; bits.div_ceil(64)],
+            hashes,
+        }
+    }
+
+    /// 过滤器占用的字节数
+    pub fn size_bytes(&self) -> usize {
+        self.bits.len() * 8
+    }
+
+    /// 由两个独立的 64 位哈希组合出第 i 个位置（Kirsch–Mitzenmacher 双重哈希）
+    fn positions(&self, item: &str) -> impl Iterator<Item = usize> {
+        let h1 = fnv1a(item.as_bytes(), FNV_OFFSET);
+        let h2 = fnv1a(item.as_bytes(), 0x84222325cbf29ce4) | 1;
+        let len = (self.bits.len() * 64) as u64;
+        (0..self.hashes as u64).map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % len) as usize)
+    }
+
+    pub fn contains(&self, item: &str) -> bool {
+        self.positions(item).all(|bit| self.bits[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+
+    /// 插入元素，元素（可能）已存在时返回 false
+    pub fn insert(&mut self, item: &str) -> bool {
+        let mut inserted = false;
+        for bit in self.positions(item) {
+            let word = &mut self.bits[bit / 64];
+            let mask = 1 << (bit % 64);
+            if *word & mask == 0 {
+                *word |= mask;
+                inserted = true;
+            }
+        }
+        inserted
+    }
+}
+
+/// 已访问 URL 集合
+#[derive(Debug, Clone)]
+pub enum VisitedSet {
+    Exact(HashSet<String>),
+    Bloom(BloomFilter),
+}
+
+impl Default for VisitedSet {
+    fn default() -> Self {
+        VisitedSet::Exact(HashSet::new())
+    }
+}
+
+impl VisitedSet {
+    /// 按实现方式创建集合，`expected` 为预计的 URL 数量，用于确定布隆过滤器的大小
+    pub fn new(kind: VisitedKind, expected: usize) -> Self {
+        match kind {
+            VisitedKind::Exact => VisitedSet::Exact(HashSet::new()),
+            VisitedKind::Bloom => VisitedSet::Bloom(BloomFilter::new(expected, FALSE_POSITIVE_RATE)),
+        }
+    }
+
+    pub fn contains(&self, url: &str) -> bool {
+        match self {
+            VisitedSet::Exact(set) => set.contains(url),
+            VisitedSet::Bloom(filter) => filter.contains(url),
+        }
+    }
+
+    /// 标记为已访问，之前未访问过时返回 true
+    pub fn insert(&mut self, url: &str) -> bool {
+        match self {
+            VisitedSet::Exact(set) => set.insert(url.to_string()),
+            VisitedSet::Bloom(filter) => filter.insert(url),
+        }
+    }
+}
+
+/// [`SharedVisited`] 的分片数
+const SHARDS: usize = 16;
+
+/// 按 URL 哈希分片、可在多个任务间共享的已访问集合
+#[derive(Debug)]
+pub struct SharedVisited {
+    shards: Vec<Mutex<VisitedSet>>,
+}
+
+impl Default for SharedVisited {
+    fn default() -> Self {
+        Self::new(VisitedKind::Exact, 0)
+    }
+}
+
+impl SharedVisited {
+    /// 参数与 [`VisitedSet::new`] 相同，预计数量平均分配到各个分片
+    pub fn new(kind: VisitedKind, expected: usize) -> Self {
+        let per_shard = expected.div_ceil(SHARDS);
+        Self {
+            shards: (0..SHARDS).map(|_| Mutex::new(VisitedSet::new(kind, per_shard))).collect(),
+        }
+    }
+
+    /// URL 所在的分片。用哈希的高位选择分片，与布隆过滤器取位置用的低位无关
+    fn shard(&self, url: &str) -> MutexGuard<'_, VisitedSet> {
+        let index = (fnv1a(url.as_bytes(), 0x9e3779b97f4a7c15) >> 60) as usize % SHARDS;
+        self.shards[index].lock().unwrap()
+    }
+
+    pub fn contains(&self, url: &str) -> bool {
+        self.shard(url).contains(url)
+    }
+
+    /// 标记为已访问，之前未访问过时返回 true；检查和插入是原子的
+    pub fn insert(&self, url: &str) -> bool {
+        self.shard(url).insert(url)
+    }
+
+    /// 布隆过滤器占用的字节数，精确集合时为 0
+    pub fn size_bytes(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| match &*shard.lock().unwrap() {
+                VisitedSet::Bloom(filter) => filter.size_bytes(),
+                VisitedSet::Exact(_) => 0,
+            })
+            .sum()
+    }
+}
+
+/// 解析数量，支持 `K`、`M`、`G` 后缀（不区分大小写），例如 `5M`、`200k`
+pub fn parse_count(value: &str) -> anyhow::Result<usize> {
+    let value = value.trim();
+    let (number, multiplier) = match value.char_indices().last() {
+        Some((index, 'k' | 'K')) => (&value[..index], 1_000),
+        Some((index, 'm' | 'M')) => (&value[..index], 1_000_000),
+        Some((index, 'g' | 'G')) => (&value[..index], 1_000_000_000),
+        _ => (value, 1),
+    };
+    number
+        .parse::<usize>()
+        .ok()
+        .and_then(|number| number.checked_mul(multiplier))
+        .ok_or_else(|| anyhow::anyhow!(tr!("无效的数量: {}", "invalid count: {}", value)))
+}
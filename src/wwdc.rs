@@ -0,0 +1,169 @@
+//! WWDC 等开发者视频（`developer.apple.com/videos`）页面的提取。
+//!
+//! 视频页面（`/videos/play/wwdc2023/10154/`）提取为一个 `DocPage`：正文开头是 `Event: WWDC 2023`、
+//! `Topics: ...` 两行，然后是简介、`## Resources` 中链接的文档（`- 标题 (地址)`）和 `## Transcript` 中的字幕全文，
+//! 相关链接为页面引用的文档页面。视频列表页面（例如 `/videos/all-videos/`、`/videos/wwdc2023/`）的相关链接
+//! 只包含其中的视频页面，`--source wwdc` 从全部视频的列表开始递归爬取。
+
+use anyhow::Result;
+use scraper::{ElementRef, Html, Selector};
+use std::sync::OnceLock;
+use url::Url;
+
+use crate::extract::{clean_text, links_with_sections, DocPage, LinkCollector, LinkFilter};
+use crate::profile::SiteProfile;
+
+/// 全部视频的列表页面
+pub const ALL_VIDEOS_URL: &str = "https://developer.apple.com/videos/all-videos/";
+
+/// 视频页面的路径前缀
+const PLAY_PATH: &str = "/videos/play/";
+
+/// 是否为开发者视频站点的页面（视频页面或视频列表）
+pub fn is_video_url(url: &str) -> bool {
+    url.contains("developer.apple.com") && Url::parse(url).is_ok_and(|url| url.path().starts_with("/videos/"))
+}
+
+/// 是否为单个视频的页面
+pub fn is_session_url(url: &str) -> bool {
+    is_video_url(url) && Url::parse(url).is_ok_and(|url| url.path().starts_with(PLAY_PATH))
+}
+
+/// 视频所属的活动，取自路径，例如 `/videos/play/wwdc2023/10154/` → `WWDC 2023`、`tech-talks` → `Tech Talks`
+pub fn event_name(url: &str) -> Option<String> {
+    let url = Url::parse(url).ok()?;
+    let event = url.path().strip_prefix(PLAY_PATH)?.split('/').next().filter(|event| !event.is_empty())?;
+    if let Some(year) = event.strip_prefix("wwdc").filter(|year| year.chars().all(|c| c.is_ascii_digit())) {
+        return Some(format!("WWDC {}", year));
+    }
+    let words: Vec<String> = event
+        .split('-')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map(|first| first.to_uppercase().chain(chars).collect()).unwrap_or_default()
+        })
+        .collect();
+    Some(words.join(" "))
+}
+
+fn selector(cell: &'static OnceLock<Selector>, selector: &str) -> &'static Selector {
+    cell.get_or_init(|| Selector::parse(selector).unwrap())
+}
+
+/// 从视频页面或视频列表的 HTML 中提取 `DocPage`
+pub fn extract_video_html(profile: &SiteProfile, url: &str, html: &str, links: &LinkFilter) -> Result<DocPage> {
+    let document = Html::parse_document(html);
+    let base_url = Url::parse(url)?;
+    let absolute = |el: ElementRef| el.value().attr("href").and_then(|href| base_url.join(href).ok()).map(String::from);
+    let text = |el: ElementRef| clean_text(&el.text().collect::<String>());
+
+    static TITLE: OnceLock<Selector> = OnceLock::new();
+    let title = document
+        .select(selector(&TITLE, "main h1, h1"))
+        .next()
+        .map(text)
+        .unwrap_or_default();
+
+    let mut related_links = LinkCollector::new(profile, url, links)?;
+    let mut add_link = |link: &str, label: &str, section: Option<&str>| {
+        if let Ok(link) = Url::parse(link) {
+            related_links.push(link, label, section);
+        }
+    };
+
+    static LINK: OnceLock<Selector> = OnceLock::new();
+    if !is_session_url(url) {
+        // 视频列表只保留视频页面的链接，正文为视频标题列表
+        let mut lines = Vec::new();
+        for (el, section) in links_with_sections(&document, selector(&LINK, "a[href]")) {
+            let Some(link) = absolute(el).filter(|link| is_session_url(link)) else {
+                continue;
+            };
+            let label = text(el);
+            add_link(&link, &label, section.as_deref());
+            if !label.is_empty() && !lines.contains(&label) {
+                lines.push(label);
+            }
+        }
+        return Ok(DocPage {
+            title,
+            content: lines.join("\n"),
+            url: url.to_string(),
+            related_links: related_links.finish(),
+            ..Default::default()
+        });
+    }
+
+    let mut lines = Vec::new();
+    if let Some(event) = event_name(url) {
+        lines.push(format!("Event: {}", event));
+    }
+    static TOPIC: OnceLock<Selector> = OnceLock::new();
+    let mut topics: Vec<String> = Vec::new();
+    for topic in document.select(selector(&TOPIC, ".video-tags a, .tags a, a[href*=\"/videos/topics/\"]")).map(text) {
+        if !topic.is_empty() && !topics.contains(&topic) {
+            topics.push(topic);
+        }
+    }
+    if !topics.is_empty() {
+        lines.push(format!("Topics: {}", topics.join(", ")));
+    }
+
+    // 简介是标题所在区域的段落，没有时使用页面描述
+    static DESCRIPTION: OnceLock<Selector> = OnceLock::new();
+    static META: OnceLock<Selector> = OnceLock::new();
+    let description = document
+        .select(selector(&DESCRIPTION, "#details p, .details p"))
+        .map(text)
+        .find(|paragraph| !paragraph.is_empty())
+        .or_else(|| {
+            document
+                .select(selector(&META, "meta[name=\"description\"]"))
+                .next()
+                .and_then(|meta| meta.value().attr("content"))
+                .map(clean_text)
+        });
+    lines.extend(description);
+
+    let mut resources = Vec::new();
+    for (el, section) in links_with_sections(&document, selector(&LINK, "a[href]")) {
+        let Some(link) = absolute(el) else {
+            continue;
+        };
+        // 视频页面引用的文档，其他视频和站点导航不算
+        if !link.contains("/documentation/") {
+            continue;
+        }
+        add_link(&link, &text(el), section.as_deref());
+        let line = format!("- {} ({})", text(el), link);
+        if !resources.contains(&line) {
+            resources.push(line);
+        }
+    }
+    if !resources.is_empty() {
+        lines.push("## Resources".to_string());
+        lines.extend(resources);
+    }
+
+    static TRANSCRIPT: OnceLock<Selector> = OnceLock::new();
+    static PARAGRAPH: OnceLock<Selector> = OnceLock::new();
+    if let Some(transcript) = document.select(selector(&TRANSCRIPT, "#transcript-content, .transcript")).next() {
+        let paragraphs: Vec<String> = transcript
+            .select(selector(&PARAGRAPH, "p"))
+            .map(text)
+            .filter(|paragraph| !paragraph.is_empty())
+            .collect();
+        if !paragraphs.is_empty() {
+            lines.push("## Transcript".to_string());
+            lines.extend(paragraphs);
+        }
+    }
+
+    Ok(DocPage {
+        title,
+        content: lines.join("\n"),
+        url: url.to_string(),
+        related_links: related_links.finish(),
+        ..Default::default()
+    })
+}
@@ -0,0 +1,109 @@
+use adoc::anchor::{self, slugify, Anchor};
+use adoc::extract::{extract_html, extract_json};
+use adoc::output::{write_pages, OutputFormat};
+
+fn anchor(id: &str, title: &str) -> Anchor {
+    Anchor {
+        id: id.to_string(),
+        title: title.to_string(),
+    }
+}
+
+#[test]
+fn test_slugify() {
+    assert_eq!(slugify("Discussion"), "discussion");
+    assert_eq!(slugify("Return Value"), "return-value");
+    assert_eq!(slugify("Getting Started with `View`!"), "getting-started-with-view");
+    assert_eq!(slugify("init(_:)"), "init_");
+    assert_eq!(slugify("概述 Overview"), "概述-overview");
+}
+
+#[test]
+fn test_json_anchors() {
+    let json = r#"{
+        "metadata": {"title": "frame(width:height:alignment:)"},
+        "abstract": [{"type": "text", "text": "Positions this view within an invisible frame."}],
+        "primaryContentSections": [
+            {"kind": "content", "content": [
+                {"type": "heading", "level": 2, "text": "Parameters", "anchor": "parameters"},
+                {"type": "paragraph", "inlineContent": [{"type": "text", "text": "width: A fixed width."}]},
+                {"type": "heading", "level": 2, "text": "Return Value"},
+                {"type": "paragraph", "inlineContent": [{"type": "text", "text": "A view with a fixed size."}]},
+                {"type": "heading", "level": 2, "text": "Discussion", "anchor": "discussion"},
+                {"type": "paragraph", "inlineContent": [{"type": "text", "text": "Use this method to specify a fixed size."}]},
+                {"type": "heading", "level": 3, "text": "Discussion", "anchor": "discussion"}
+            ]}
+        ]
+    }"#;
+    let page = extract_json("https://developer.apple.com/documentation/swiftui/view/frame", json).unwrap();
+    // 原页面的锚点保留，缺少的按标题生成，重复的加上序号
+    assert_eq!(
+        page.anchors,
+        [
+            anchor("parameters", "Parameters"),
+            anchor("return-value", "Return Value"),
+            anchor("discussion", "Discussion"),
+            anchor("discussion-1", "Discussion"),
+        ]
+    );
+}
+
+#[test]
+fn test_html_anchors() {
+    let html = r#"<html><body><nav><h2 id="menu">Menu</h2></nav><article>
+        <h1>Text</h1>
+        <p>A view that displays one or more lines of read-only text.</p>
+        <h2 id="overview">Overview</h2>
+        <p>A text view draws a string in your app's user interface.</p>
+        <h3>Localizing strings</h3>
+        <p>If you initialize a text view with a string literal, the view uses the localized key.</p>
+    </article></body></html>"#;
+    let page = extract_html("https://developer.apple.com/documentation/swiftui/text", html).unwrap();
+    // 只取正文中的标题
+    assert_eq!(page.anchors, [anchor("overview", "Overview"), anchor("localizing-strings", "Localizing strings")]);
+}
+
+#[test]
+fn test_markdown_headings() {
+    let content = "Positions this view.\nParameters\nwidth: A fixed width.\nDiscussion\nUse this method.\n\nAlso this.";
+    let anchors = [anchor("parameters", "Parameters"), anchor("missing", "Missing"), anchor("discussion", "Discussion")];
+    assert_eq!(
+        anchor::markdown(content, &anchors, 2, ""),
+        "Positions this view.\n\n## <a id=\"parameters\">Parameters</a>\n\nwidth: A fixed width.\n\n\
+         ## <a id=\"discussion\">Discussion</a>\n\nUse this method.\n\nAlso this.\n\n"
+    );
+    // 没有小节时与按段落输出相同
+    assert_eq!(anchor::markdown("a\nb\n\nc", &[], 2, ""), "a\nb\n\nc\n\n");
+
+    assert_eq!(anchor::link_target("https://example.com/view#Discussion", &anchors), Some("discussion"));
+    assert_eq!(anchor::link_target("https://example.com/view#topics", &anchors), None);
+    assert_eq!(anchor::link_target("https://example.com/view", &anchors), None);
+}
+
+#[test]
+fn test_markdown_output_anchors() {
+    let json = r#"{
+        "metadata": {"title": "View"},
+        "primaryContentSections": [{"kind": "content", "content": [
+            {"type": "heading", "level": 2, "text": "Overview", "anchor": "overview"},
+            {"type": "paragraph", "inlineContent": [{"type": "text", "text": "A view."}]}
+        ]}],
+        "references": {
+            "doc://text": {"url": "/documentation/swiftui/text#overview"}
+        }
+    }"#;
+    let view = extract_json("https://developer.apple.com/documentation/swiftui/view", json).unwrap();
+    let text = extract_json(
+        "https://developer.apple.com/documentation/swiftui/text",
+        &json.replace("\"View\"", "\"Text\"").replace("/text#overview", "/view#overview"),
+    )
+    .unwrap();
+    let mut out = Vec::new();
+    write_pages([view, text].as_slice(), OutputFormat::Markdown, &mut out).unwrap();
+    let markdown = String::from_utf8(out).unwrap();
+    // 锚点加上页面序号，指向其他页面小节的链接改写到对应锚点
+    assert!(markdown.contains("#### <a id=\"doc-1-overview\">Overview</a>\n\nA view.\n\n"));
+    assert!(markdown.contains("#### <a id=\"doc-2-overview\">Overview</a>\n\n"));
+    assert!(markdown.contains("- [Text](#doc-2-overview)\n"));
+    assert!(markdown.contains("- [View](#doc-1-overview)\n"));
+}
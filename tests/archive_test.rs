@@ -0,0 +1,63 @@
+use adoc::archive::is_archive_url;
+use adoc::extract::{extract_html, extract_links};
+
+const PAGE: &str =
+    "https://developer.apple.com/library/archive/documentation/Cocoa/Conceptual/MemoryMgmt/Articles/mmRules.html";
+
+const HTML: &str = r##"<html><head><title>Memory Management Policy</title></head><body>
+    <div id="tocContainer"><a href="../Articles/MemoryMgmt.html">About Memory Management</a></div>
+    <article id="contents">
+        <div id="pageTitle"><h1 class="chapter-name">Memory Management Policy</h1></div>
+        <section>
+            <a name="//apple_ref/doc/uid/20000994-BAJHFBGH"></a>
+            <p class="para">The memory management model is based on object ownership.</p>
+            <h2 class="jump">Basic Memory Management Rules</h2>
+            <ul class="ul">
+                <li class="li"><p class="para">You own any object you create.</p></li>
+                <li class="li"><p class="para">You can take ownership of an object using <code>retain</code>.</p></li>
+            </ul>
+            <div class="codesample clear"><table>
+                <tr><td scope="row"><pre>- (void)printHello {</pre></td></tr>
+                <tr><td scope="row"><pre>    NSLog(@"Hello");</pre></td></tr>
+                <tr><td scope="row"><pre>}</pre></td></tr>
+            </table></div>
+            <p class="para">See <a href="#//apple_ref/doc/uid/20000994-SW1">Ownership</a> and
+                <a href="mmPractical.html#//apple_ref/doc/uid/TP40004447">Practical Memory Management</a>.</p>
+        </section>
+        <div id="pageNavigationLinks_bottom" class="pageNavigationLinks">
+            <a class="nextLink" href="mmPractical.html">Next</a>
+        </div>
+        <div class="copyright">Copyright © 2012 Apple Inc.</div>
+    </article>
+</body></html>"##;
+
+#[test]
+fn test_is_archive_url() {
+    assert!(is_archive_url(PAGE));
+    assert!(!is_archive_url("https://developer.apple.com/documentation/uikit"));
+    assert!(!is_archive_url("https://example.com/library/archive/documentation/index.html"));
+}
+
+#[test]
+fn test_extract_archive_page() {
+    let page = extract_html(PAGE, HTML).unwrap();
+    assert_eq!(page.title, "Memory Management Policy");
+    let lines: Vec<&str> = page.content.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "The memory management model is based on object ownership.",
+            "## Basic Memory Management Rules",
+            "- You own any object you create.",
+            "- You can take ownership of an object using retain.",
+            "- (void)printHello {",
+            "    NSLog(@\"Hello\");",
+            "}",
+            "See Ownership and Practical Memory Management.",
+        ]
+    );
+    let base = "https://developer.apple.com/library/archive/documentation/Cocoa/Conceptual/MemoryMgmt/Articles";
+    let expected = [format!("{}/MemoryMgmt.html", base), format!("{}/mmPractical.html", base)];
+    assert_eq!(page.link_urls().collect::<Vec<_>>(), expected);
+    assert_eq!(extract_links(PAGE, HTML).unwrap(), expected);
+}
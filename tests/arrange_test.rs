@@ -0,0 +1,77 @@
+mod common;
+
+use adoc::arrange::{arrange, GroupBy, SortKey};
+use adoc::extract::DocPage;
+use adoc::output::{write_pages_grouped, OutputFormat};
+
+/// 爬取完成的顺序
+fn pages() -> Vec<DocPage> {
+    vec![
+        common::page(&common::doc_url("uikit/uiview"), "UIView", "@MainActor class UIView"),
+        common::page(&common::doc_url("swiftui/view"), "View", "protocol View"),
+        common::page(
+            &common::doc_url("swiftui/view/padding(_:)"),
+            "padding(_:)",
+            "func padding(_ length: CGFloat) -> some View",
+        ),
+        common::page(&common::doc_url("swiftui"), "SwiftUI", "Declare the user interface."),
+        common::page(&common::doc_url("swiftui/text"), "Text", "@frozen struct Text"),
+        common::page(
+            &common::doc_url("swiftui/app-organization"),
+            "App organization",
+            "Define the entry point of your app.",
+        ),
+    ]
+}
+
+fn titles(pages: &[DocPage]) -> Vec<&str> {
+    pages.iter().map(|page| page.title.as_str()).collect()
+}
+
+#[test]
+fn test_arrange() {
+    let mut unchanged = pages();
+    arrange(&mut unchanged, None, None);
+    assert_eq!(titles(&unchanged), titles(&pages()));
+
+    let mut sorted = pages();
+    arrange(&mut sorted, Some(SortKey::Title), None);
+    assert_eq!(titles(&sorted), ["App organization", "padding(_:)", "SwiftUI", "Text", "UIView", "View"]);
+
+    let mut sorted = pages();
+    arrange(&mut sorted, Some(SortKey::Hierarchy), None);
+    assert_eq!(titles(&sorted), ["SwiftUI", "App organization", "Text", "View", "padding(_:)", "UIView"]);
+
+    // 组内保持爬取顺序
+    let mut grouped = pages();
+    arrange(&mut grouped, None, Some(GroupBy::Framework));
+    assert_eq!(titles(&grouped), ["View", "padding(_:)", "SwiftUI", "Text", "App organization", "UIView"]);
+
+    // 没有声明的文章排在最后
+    let mut grouped = pages();
+    arrange(&mut grouped, Some(SortKey::Title), Some(GroupBy::Kind));
+    assert_eq!(titles(&grouped), ["UIView", "SwiftUI", "padding(_:)", "View", "Text", "App organization"]);
+
+    let mut grouped = pages();
+    arrange(&mut grouped, Some(SortKey::Url), Some(GroupBy::Hierarchy));
+    assert_eq!(titles(&grouped), ["SwiftUI", "App organization", "Text", "View", "padding(_:)", "UIView"]);
+    assert_eq!(GroupBy::Hierarchy.group(&grouped[4]).as_deref(), Some("swiftui / view"));
+    assert_eq!(GroupBy::Hierarchy.group(&grouped[0]).as_deref(), Some("swiftui"));
+}
+
+#[test]
+fn test_grouped_markdown() {
+    adoc::i18n::set_lang(adoc::i18n::Lang::En);
+    let mut pages = pages();
+    arrange(&mut pages, Some(SortKey::Title), Some(GroupBy::Framework));
+    let mut out = Vec::new();
+    write_pages_grouped(pages.as_slice(), OutputFormat::Markdown, Some(GroupBy::Framework), &mut out).unwrap();
+    let markdown = String::from_utf8(out).unwrap();
+    assert!(markdown.contains(
+        "## Contents\n\n### swiftui\n\n1. [App organization](#doc-1)\n2. [padding(_:)](#doc-2)\n3. [SwiftUI](#doc-3)\n\
+         4. [Text](#doc-4)\n5. [View](#doc-5)\n\n### uikit\n\n6. [UIView](#doc-6)\n"
+    ));
+    assert!(markdown.contains("---\n\n# swiftui\n\n## <a id=\"doc-1\">App organization</a>"));
+    assert!(markdown.contains("# uikit\n\n## <a id=\"doc-6\">UIView</a>"));
+    assert_eq!(markdown.matches("\n# swiftui\n").count(), 1);
+}
@@ -0,0 +1,32 @@
+mod common;
+
+use adoc::blocking::Crawler;
+use adoc::crawler::CrawlerConfig;
+use std::time::Duration;
+
+#[test]
+fn test_blocking_crawl_url() {
+    let base = common::serve(&[(
+        "/documentation/swiftui",
+        "<html><body><h1>SwiftUI</h1><article>Declare the user interface.</article></body></html>",
+    )]);
+
+    let config = CrawlerConfig {
+        max_retries: 3,
+        concurrency: 2,
+        timeout: Duration::from_secs(10),
+        ..Default::default()
+    };
+
+    // 不需要调用方提供 tokio 运行时
+    let mut crawler = Crawler::new(config);
+    let (results, report) = crawler
+        .crawl_url(&format!("{}/documentation/swiftui", base), false)
+        .unwrap();
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].title, "SwiftUI");
+    assert_eq!(results[0].content, "Declare the user interface.");
+    assert_eq!(report.pages_fetched, 1);
+    assert!(report.bytes_downloaded > 0);
+}
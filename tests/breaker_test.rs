@@ -0,0 +1,69 @@
+mod common;
+
+use adoc::breaker::CircuitBreaker;
+use adoc::crawler::{Crawler, CrawlerConfig};
+use std::time::{Duration, Instant};
+
+#[tokio::test]
+async fn test_breaker_aborts_after_consecutive_failures() {
+    let breaker = CircuitBreaker::new(3, None);
+    breaker.record_failure(Instant::now(), "connection refused");
+    breaker.record_failure(Instant::now(), "connection refused");
+    // 成功的请求清零计数
+    breaker.record_success(Instant::now());
+    breaker.record_failure(Instant::now(), "connection refused");
+    breaker.record_failure(Instant::now(), "connection refused");
+    assert!(breaker.ready().await);
+    breaker.record_failure(Instant::now(), "connection refused");
+    assert!(breaker.aborted());
+    assert!(!breaker.ready().await);
+}
+
+#[tokio::test]
+async fn test_breaker_pauses_and_probes() {
+    let breaker = CircuitBreaker::new(2, Some(Duration::from_millis(200)));
+    let before_trip = Instant::now();
+    breaker.record_failure(Instant::now(), "timed out");
+    breaker.record_failure(Instant::now(), "timed out");
+    // 熔断前发起的请求的结果不计入
+    breaker.record_failure(before_trip, "timed out");
+
+    let paused = Instant::now();
+    assert!(breaker.ready().await);
+    assert!(paused.elapsed() >= Duration::from_millis(150));
+    assert!(!breaker.aborted());
+
+    // 冷却后的试探请求仍然失败，立即再次熔断
+    breaker.record_failure(Instant::now(), "timed out");
+    let paused = Instant::now();
+    assert!(breaker.ready().await);
+    assert!(paused.elapsed() >= Duration::from_millis(150));
+
+    // 试探成功后恢复，重新累计到阈值才熔断
+    breaker.record_success(Instant::now());
+    breaker.record_failure(Instant::now(), "timed out");
+    let resumed = Instant::now();
+    assert!(breaker.ready().await);
+    assert!(resumed.elapsed() < Duration::from_millis(100));
+}
+
+#[tokio::test]
+async fn test_crawl_gives_up_when_breaker_trips() {
+    let closed = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+    let links: String = (0..10)
+        .map(|i| format!(r#"<a href="http://{}/developer.apple.com/page{}">Page</a>"#, closed, i))
+        .collect();
+    let start_page = format!("<html><body><h1>SwiftUI</h1><article>{}</article></body></html>", links);
+    let base = common::serve(&[("/documentation/swiftui", &start_page)]);
+
+    let mut crawler = Crawler::new(CrawlerConfig {
+        max_retries: 0,
+        concurrency: 1,
+        ..Default::default()
+    });
+    crawler.set_circuit_breaker(3, None);
+    let (pages, report) = crawler.crawl_url(&format!("{}/documentation/swiftui", base), true).await.unwrap();
+    assert_eq!(pages.len(), 1);
+    assert_eq!(report.pages_failed, 3);
+    assert!(report.circuit_broken);
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use adoc::cache::{CacheMiss, ResponseCache};
+use adoc::crawler::{failed_page, Crawler, CrawlerConfig, PageGone};
+use adoc::report::FailureKind;
+use std::sync::Arc;
+
+#[test]
+fn test_response_cache() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = ResponseCache::open(&dir.path().join("cache"), false).unwrap();
+    let url = "https://developer.apple.com/documentation/swiftui/view";
+    assert!(cache.get(url).is_none());
+
+    cache.put(url, 200, Some("text/html; charset=utf-8"), "<h1>View</h1>").unwrap();
+    let cached = cache.get(url).unwrap();
+    assert_eq!(cached.status, 200);
+    assert_eq!(cached.content_type.as_deref(), Some("text/html; charset=utf-8"));
+    assert_eq!(cached.body, "<h1>View</h1>");
+    // 按主机分目录，查询参数不同的 URL 分别缓存
+    assert!(cache.path(url).starts_with(dir.path().join("cache/developer.apple.com")));
+    assert_ne!(cache.path(url), cache.path(&format!("{}?language=objc", url)));
+    assert!(cache.get(&format!("{}?language=objc", url)).is_none());
+
+    let error = cache.require("https://developer.apple.com/documentation/uikit").unwrap_err();
+    assert!(error.is::<CacheMiss>());
+    assert!(ResponseCache::open(&dir.path().join("missing"), true).is_err());
+}
+
+#[tokio::test]
+async fn test_offline_crawl_from_cache() {
+    let (base, log) = common::serve_status_recording(&[
+        (
+            "/documentation/swiftui",
+            "200 OK",
+            r#"<html><body><h1>SwiftUI</h1><article>
+                <p>Declare the user interface.</p>
+                <a href="/developer.apple.com/view">View</a>
+                <a href="/developer.apple.com/removed">Removed</a>
+            </article></body></html>"#,
+        ),
+        (
+            "/developer.apple.com/view",
+            "200 OK",
+            "<html><body><h1>View</h1><article><p>A type that represents part of your app.</p></article></body></html>",
+        ),
+        ("/developer.apple.com/removed", "404 Not Found", ""),
+    ]);
+    let seed = format!("{}/documentation/swiftui", base);
+    let dir = tempfile::tempdir().unwrap();
+    let config = |offline: bool| CrawlerConfig {
+        cache: Some(Arc::new(ResponseCache::open(dir.path(), offline).unwrap())),
+        ..Default::default()
+    };
+
+    let mut crawler = Crawler::new(config(false));
+    let (online, report) = crawler.crawl_url(&seed, true).await.unwrap();
+    assert_eq!(online.len(), 2);
+    assert_eq!(report.pages_gone, 1);
+    let requests = log.lock().unwrap().len();
+
+    // 离线爬取得到相同的页面，不再发起请求
+    let mut crawler = Crawler::new(config(true));
+    let (offline, report) = crawler.crawl_url(&seed, true).await.unwrap();
+    assert_eq!(log.lock().unwrap().len(), requests);
+    let titles = |pages: &[adoc::crawler::DocPage]| {
+        let mut titles: Vec<(String, String)> =
+            pages.iter().map(|page| (page.title.clone(), page.content.clone())).collect();
+        titles.sort();
+        titles
+    };
+    assert_eq!(titles(&offline), titles(&online));
+    assert_eq!(report.pages_gone, 1);
+    assert_eq!(report.pages_failed, 0);
+    assert_eq!(report.bytes_downloaded, 0);
+
+    // 缓存中没有的页面按 offline 失败
+    let mut crawler = Crawler::new(config(true));
+    let missing = format!("{}/documentation/uikit", base);
+    let error = crawler.crawl_url(&missing, false).await.unwrap_err();
+    assert!(error.to_string().contains(&missing));
+    assert_eq!(failed_page(&missing, &error).kind, FailureKind::Offline);
+    assert_eq!(log.lock().unwrap().len(), requests);
+}
+
+#[tokio::test]
+async fn test_offline_cached_error_status_is_a_failure() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = Arc::new(ResponseCache::open(dir.path(), false).unwrap());
+    let url = "https://developer.apple.com/documentation/swiftui/broken";
+    cache.put(url, 500, None, "").unwrap();
+    cache.put(&Crawler::search_url("broken"), 503, None, "").unwrap();
+    let mut crawler = Crawler::new(CrawlerConfig {
+        cache: Some(Arc::new(ResponseCache::open(dir.path(), true).unwrap())),
+        ..Default::default()
+    });
+
+    // 只有 404/410 按已失效处理，其他状态码在页面和搜索两条路径上都是失败
+    let error = crawler.crawl_url(url, false).await.unwrap_err();
+    assert!(!error.chain().any(|cause| cause.is::<PageGone>()), "{:#}", error);
+    let failure = failed_page(url, &error);
+    assert_eq!((failure.kind, failure.status), (FailureKind::Status, Some(500)));
+
+    let error = crawler.search("broken").await.unwrap_err();
+    assert!(!error.chain().any(|cause| cause.is::<PageGone>()), "{:#}", error);
+    assert_eq!(failed_page(url, &error).status, Some(503));
+}
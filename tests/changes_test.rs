@@ -0,0 +1,128 @@
+use adoc::changes::{is_api_diff, is_release_notes_url, ChangedSymbol, SdkChanges};
+use adoc::diff::diff_pages;
+use adoc::extract::extract_json;
+
+const RELEASE_NOTES: &str =
+    "https://developer.apple.com/documentation/ios-ipados-release-notes/ios-ipados-17-release-notes";
+
+const RELEASE_NOTES_JSON: &str = r#"{
+    "metadata": {"title": "iOS & iPadOS 17 Release Notes"},
+    "abstract": [{"type": "text", "text": "Update your apps to use new features."}],
+    "primaryContentSections": [{"kind": "content", "content": [
+        {"type": "heading", "level": 2, "text": "SwiftUI"},
+        {"type": "heading", "level": 3, "text": "New Features"},
+        {"type": "unorderedList", "items": [
+            {"content": [{"type": "paragraph", "inlineContent": [
+                {"type": "text", "text": "Use "},
+                {"type": "reference", "identifier": "doc://com.apple.documentation/documentation/SwiftUI/Inspector"},
+                {"type": "text", "text": " to show details."}
+            ]}]}
+        ]},
+        {"type": "heading", "level": 3, "text": "Known Issues"},
+        {"type": "paragraph", "inlineContent": [
+            {"type": "reference", "identifier": "doc://com.apple.documentation/documentation/SwiftUI/List"},
+            {"type": "text", "text": " might not scroll."}
+        ]},
+        {"type": "heading", "level": 3, "text": "Deprecations"},
+        {"type": "paragraph", "inlineContent": [
+            {"type": "reference", "identifier": "doc://com.apple.documentation/documentation/SwiftUI/NavigationView"},
+            {"type": "text", "text": " is deprecated."}
+        ]},
+        {"type": "heading", "level": 2, "text": "UIKit"},
+        {"type": "heading", "level": 3, "text": "Resolved Issues"},
+        {"type": "paragraph", "inlineContent": [
+            {"type": "reference", "identifier": "doc://com.apple.documentation/documentation/UIKit/UIButton"},
+            {"type": "text", "text": " now respects "},
+            {"type": "reference", "identifier": "https://developer.apple.com/videos"}
+        ]}
+    ]}],
+    "references": {
+        "doc://com.apple.documentation/documentation/SwiftUI/Inspector": {
+            "type": "topic", "kind": "symbol", "title": "Inspector", "url": "/documentation/swiftui/inspector"
+        },
+        "doc://com.apple.documentation/documentation/SwiftUI/List": {
+            "type": "topic", "kind": "symbol", "title": "List", "url": "/documentation/swiftui/list"
+        },
+        "doc://com.apple.documentation/documentation/SwiftUI/NavigationView": {
+            "type": "topic", "kind": "symbol", "title": "NavigationView", "url": "/documentation/swiftui/navigationview"
+        },
+        "doc://com.apple.documentation/documentation/UIKit/UIButton": {
+            "type": "topic", "kind": "symbol", "title": "UIButton", "url": "/documentation/uikit/uibutton"
+        },
+        "https://developer.apple.com/videos": {
+            "type": "link", "title": "Videos", "url": "https://developer.apple.com/videos"
+        }
+    }
+}"#;
+
+fn symbol(name: &str, path: &str, framework: &str) -> ChangedSymbol {
+    ChangedSymbol {
+        name: name.to_string(),
+        url: Some(format!("https://developer.apple.com{}", path)),
+        framework: Some(framework.to_string()),
+    }
+}
+
+#[test]
+fn test_release_notes_changes() {
+    assert!(is_release_notes_url(RELEASE_NOTES));
+    assert!(!is_release_notes_url("https://developer.apple.com/documentation/swiftui"));
+
+    let page = extract_json(RELEASE_NOTES, RELEASE_NOTES_JSON).unwrap();
+    assert!(page.content.starts_with("Update your apps to use new features.\nSwiftUI\nNew Features\n"));
+    assert_eq!(
+        page.changes,
+        [SdkChanges {
+            sdk: "iOS & iPadOS 17".to_string(),
+            added: vec![symbol("Inspector", "/documentation/swiftui/inspector", "SwiftUI")],
+            modified: vec![symbol("UIButton", "/documentation/uikit/uibutton", "UIKit")],
+            deprecated: vec![symbol("NavigationView", "/documentation/swiftui/navigationview", "SwiftUI")],
+        }]
+    );
+
+    // 其他文档页面不整理变化
+    let page = extract_json("https://developer.apple.com/documentation/swiftui/list", RELEASE_NOTES_JSON).unwrap();
+    assert!(page.changes.is_empty());
+}
+
+fn api_diff(list: &str) -> String {
+    format!(
+        r#"{{"sdk": "iOS 17.0", "changes": {{
+            "doc://com.apple.documentation/documentation/swiftui/inspector": {{"change": "added", "title": "Inspector"}},
+            "doc://com.apple.documentation/documentation/swiftui/list": {{"change": "{}"}},
+            "doc://com.apple.documentation/documentation/uikit/uiwebview": {{"change": "deprecated"}}
+        }}}}"#,
+        list
+    )
+}
+
+#[test]
+fn test_extract_api_diff() {
+    let url = "https://developer.apple.com/tutorials/data/diffs/ios-17.0.json";
+    let data: serde_json::Value = serde_json::from_str(&api_diff("modified")).unwrap();
+    assert!(is_api_diff(&data));
+    assert!(!is_api_diff(&serde_json::json!({"changes": {}})));
+
+    let page = extract_json(url, &api_diff("modified")).unwrap();
+    assert_eq!(page.title, "API Changes: iOS 17.0");
+    let lines: Vec<&str> = page.content.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "## Added",
+            "- Inspector (https://developer.apple.com/documentation/swiftui/inspector)",
+            "## Modified",
+            "- list (https://developer.apple.com/documentation/swiftui/list)",
+            "## Deprecated",
+            "- uiwebview (https://developer.apple.com/documentation/uikit/uiwebview)",
+        ]
+    );
+    assert_eq!(page.changes[0].added[0].framework.as_deref(), Some("swiftui"));
+    assert_eq!(page.related_links.len(), 3);
+
+    // 两次爬取之间符号的变化类型改变时，diff 报告该页面的变化
+    let later = extract_json(url, &api_diff("deprecated")).unwrap();
+    let diff = diff_pages(&[page], &[later]);
+    assert_eq!(diff.changed.len(), 1);
+    assert!(diff.changed[0].content_diff.as_deref().unwrap().contains("+- list (https://"));
+}
@@ -0,0 +1,38 @@
+use adoc::charset::{decode, detect};
+
+#[test]
+fn test_decode_utf8_and_bom() {
+    assert_eq!(decode("视图".as_bytes().to_vec(), None), "视图");
+    // BOM 优先于响应头中的声明
+    let mut utf16 = vec![0xFF, 0xFE];
+    utf16.extend("视图".encode_utf16().flat_map(u16::to_le_bytes));
+    assert_eq!(decode(utf16, Some("text/html; charset=utf-8")), "视图");
+}
+
+#[test]
+fn test_decode_declared_charset() {
+    let (bytes, _, _) = encoding_rs::SHIFT_JIS.encode("<p>ビュー</p>");
+    assert_eq!(decode(bytes.to_vec(), Some("text/html; charset=\"Shift_JIS\"")), "<p>ビュー</p>");
+
+    let html = "<html><head><meta http-equiv=\"Content-Type\" content=\"text/html; charset=euc-kr\"></head><body>보기</body></html>";
+    let (bytes, _, _) = encoding_rs::EUC_KR.encode(html);
+    assert_eq!(decode(bytes.to_vec(), Some("text/html")), html);
+
+    let html = "<meta charset='windows-1252'><p>Café</p>";
+    let (bytes, _, _) = encoding_rs::WINDOWS_1252.encode(html);
+    assert_eq!(decode(bytes.to_vec(), None), html);
+}
+
+#[test]
+fn test_decode_wrong_declaration() {
+    // 声明为 ISO-8859-1 的 UTF-8 页面
+    assert_eq!(decode("Café".as_bytes().to_vec(), Some("text/html; charset=iso-8859-1")), "Café");
+
+    // 声明为 UTF-8 的 GBK 页面：声明的编码无法无损解码，交给 chardetng 猜测
+    let text = "视图是应用程序用户界面的一部分，并提供用于配置视图的修饰符。".repeat(4);
+    let (bytes, _, _) = encoding_rs::GBK.encode(&text);
+    assert_eq!(detect(&bytes), encoding_rs::GBK);
+    let decoded = decode(bytes.to_vec(), Some("text/html; charset=utf-8"));
+    assert_eq!(decoded, text);
+    assert!(!decoded.contains('\u{FFFD}'));
+}
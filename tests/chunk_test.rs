@@ -0,0 +1,45 @@
+use adoc::chunk::{chunk_page, check_options};
+use adoc::extract::DocPage;
+
+#[test]
+fn test_chunk_page_with_overlap() {
+    let page = DocPage {
+        title: "View".to_string(),
+        content: "one two three\nfour five six seven".to_string(),
+        url: "https://developer.apple.com/documentation/swiftui/view".to_string(),
+        ..Default::default()
+    };
+
+    let chunks = chunk_page(&page, 3, 1).unwrap();
+
+    let contents: Vec<_> = chunks.iter().map(|chunk| chunk.content.as_str()).collect();
+    assert_eq!(contents, vec!["one two three", "three\nfour five", "five six seven"]);
+    assert_eq!(chunks[1].position, 1);
+    assert_eq!(chunks[1].total, 3);
+    assert_eq!(chunks[0].hierarchy, vec!["swiftui", "view"]);
+}
+
+#[test]
+fn test_chunk_options() {
+    assert!(check_options(0, 0).is_err());
+    assert!(check_options(10, 10).is_err());
+    assert!(check_options(10, 2).is_ok());
+}
+
+#[test]
+fn test_chunk_embedding_serialization() {
+    let page = DocPage {
+        title: "View".to_string(),
+        content: "one two".to_string(),
+        url: "https://developer.apple.com/documentation/swiftui/view".to_string(),
+        ..Default::default()
+    };
+    let mut chunks = chunk_page(&page, 8, 0).unwrap();
+    assert!(!adoc::chunk::to_jsonl(&chunks).unwrap().contains("embedding"));
+
+    chunks[0].embedding = Some(vec![0.5, 1.0]);
+    let line = adoc::chunk::to_jsonl(&chunks).unwrap();
+    assert!(line.contains(r#""embedding":[0.5,1.0]"#));
+    let parsed: adoc::chunk::Chunk = serde_json::from_str(line.trim()).unwrap();
+    assert_eq!(parsed.embedding, Some(vec![0.5, 1.0]));
+}
@@ -0,0 +1,152 @@
+#![allow(dead_code)]
+
+use adoc::extract::DocPage;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+
+/// 测试服务收到的请求
+#[derive(Debug, Clone)]
+pub struct Recorded {
+    pub method: String,
+    pub path: String,
+    pub headers: HashMap<String, String>,
+    pub body: String,
+}
+
+pub type RequestLog = Arc<Mutex<Vec<Recorded>>>;
+
+/// 启动一个本地 HTTP 服务，按路径返回固定的 HTML 页面，返回服务的基础 URL
+pub fn serve(pages: &[(&str, &str)]) -> String {
+    serve_recording(pages).0
+}
+
+/// 与 [`serve`] 相同，每个页面额外指定 `Content-Type`：`(路径, 内容类型, 正文)`
+pub fn serve_typed(pages: &[(&str, &str, &str)]) -> String {
+    serve_pages(pages.iter().map(|(path, content_type, body)| (*path, "200 OK", *content_type, body.as_bytes()))).0
+}
+
+/// 与 [`serve_typed`] 相同，正文为任意字节，例如压缩包：`(路径, 内容类型, 正文)`
+pub fn serve_bytes(pages: &[(&str, &str, &[u8])]) -> String {
+    serve_pages(pages.iter().map(|(path, content_type, body)| (*path, "200 OK", *content_type, *body))).0
+}
+
+/// 与 [`serve_typed`] 相同，同时记录收到的所有请求
+pub fn serve_typed_recording(pages: &[(&str, &str, &str)]) -> (String, RequestLog) {
+    serve_pages(pages.iter().map(|(path, content_type, body)| (*path, "200 OK", *content_type, body.as_bytes())))
+}
+
+/// 与 [`serve`] 相同，每个页面额外指定状态行：`(路径, 状态, 正文)`，例如 `("/a", "500 Internal Server Error", "")`
+pub fn serve_status(pages: &[(&str, &str, &str)]) -> String {
+    serve_status_recording(pages).0
+}
+
+/// 与 [`serve_status`] 相同，同时记录收到的所有请求
+pub fn serve_status_recording(pages: &[(&str, &str, &str)]) -> (String, RequestLog) {
+    serve_pages(pages.iter().map(|(path, status, body)| (*path, *status, "text/html; charset=utf-8", body.as_bytes())))
+}
+
+/// 与 [`serve`] 相同，同时记录收到的所有请求；非 GET 请求返回以 `"POST /path"` 形式注册的页面，没有时返回 `{}`
+pub fn serve_recording(pages: &[(&str, &str)]) -> (String, RequestLog) {
+    serve_pages(pages.iter().map(|(path, body)| (*path, "200 OK", "text/html; charset=utf-8", body.as_bytes())))
+}
+
+type Page = (String, String, Vec<u8>);
+
+fn serve_pages<'a>(pages: impl Iterator<Item = (&'a str, &'a str, &'a str, &'a [u8])>) -> (String, RequestLog) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let pages: Arc<HashMap<String, Page>> = Arc::new(
+        pages
+            .map(|(path, status, content_type, body)| {
+                (path.to_string(), (status.to_string(), content_type.to_string(), body.to_vec()))
+            })
+            .collect(),
+    );
+    let log: RequestLog = Arc::new(Mutex::new(Vec::new()));
+
+    let server_log = log.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { continue };
+            let pages = pages.clone();
+            let log = server_log.clone();
+            std::thread::spawn(move || {
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                if reader.read_line(&mut request_line).is_err() {
+                    return;
+                }
+                let mut headers = HashMap::new();
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).map(|n| n <= 2).unwrap_or(true) {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+                    }
+                }
+                let length: usize = headers
+                    .get("content-length")
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or(0);
+                let mut body = vec![0; length];
+                let _ = reader.read_exact(&mut body);
+
+                let mut parts = request_line.split_whitespace();
+                let method = parts.next().unwrap_or("GET").to_string();
+                let path = parts.next().unwrap_or("/").to_string();
+                log.lock().unwrap().push(Recorded {
+                    method: method.clone(),
+                    path: path.clone(),
+                    headers,
+                    body: String::from_utf8_lossy(&body).into_owned(),
+                });
+
+                let (status, content_type, body) = if method != "GET" {
+                    match pages.get(&format!("{} {}", method, path)) {
+                        Some((status, content_type, body)) => (status.as_str(), content_type.as_str(), body.as_slice()),
+                        None => ("202 Accepted", "application/json", b"{}".as_slice()),
+                    }
+                } else {
+                    // 没有注册带查询参数的路径时按不带查询参数的路径匹配
+                    let without_query = path.split('?').next().unwrap_or_default();
+                    match pages.get(&path).or_else(|| pages.get(without_query)) {
+                        Some((status, content_type, body)) => (status.as_str(), content_type.as_str(), body.as_slice()),
+                        None => (
+                            "404 Not Found",
+                            "text/html; charset=utf-8",
+                            b"<html><body><h1>Not Found</h1></body></html>".as_slice(),
+                        ),
+                    }
+                };
+                let head = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    status,
+                    content_type,
+                    body.len()
+                );
+                let _ = stream.write_all(&[head.as_bytes(), body].concat());
+            });
+        }
+    });
+
+    (format!("http://{}", addr), log)
+}
+
+/// Apple 开发者文档中 `path` 对应的地址，例如 `swiftui/view`
+pub fn doc_url(path: &str) -> String {
+    format!("https://developer.apple.com/documentation/{}", path)
+}
+
+/// 只有地址、标题和正文的页面，其余字段为默认值
+pub fn page(url: &str, title: &str, content: &str) -> DocPage {
+    DocPage {
+        title: title.to_string(),
+        content: content.to_string(),
+        url: url.to_string(),
+        ..Default::default()
+    }
+}
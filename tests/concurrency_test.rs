@@ -0,0 +1,110 @@
+mod common;
+
+use adoc::concurrency::AdaptiveLimit;
+use adoc::crawler::{Crawler, CrawlerConfig};
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_additive_increase_and_multiplicative_decrease() {
+    let limit = AdaptiveLimit::new(4, 6);
+    // 每轮（当前并发数个）顺利的请求后加一
+    for _ in 0..4 {
+        limit.record_success(Duration::from_millis(100));
+    }
+    assert_eq!(limit.current(), 5);
+    // 延迟远高于历史最低值时不提高
+    for _ in 0..10 {
+        limit.record_success(Duration::from_secs(1));
+    }
+    assert_eq!(limit.current(), 5);
+    for _ in 0..20 {
+        limit.record_success(Duration::from_millis(100));
+    }
+    assert_eq!(limit.current(), 6);
+
+    limit.record_overload(Instant::now());
+    assert_eq!(limit.current(), 3);
+}
+
+#[test]
+fn test_overloads_from_the_same_batch_decrease_once() {
+    let limit = AdaptiveLimit::new(8, 8);
+    let started = Instant::now();
+    limit.record_overload(started);
+    limit.record_overload(started);
+    assert_eq!(limit.current(), 4);
+
+    limit.record_overload(Instant::now());
+    limit.record_overload(Instant::now());
+    assert_eq!(limit.current(), 1);
+    limit.record_overload(Instant::now());
+    assert_eq!(limit.current(), 1);
+}
+
+#[tokio::test]
+async fn test_permits_follow_the_limit() {
+    let limit = AdaptiveLimit::new(4, 8);
+    let held: Vec<_> = futures::future::join_all((0..4).map(|_| limit.acquire())).await;
+    // 许可都被占用时减半，归还的许可中有两个被回收
+    limit.record_overload(Instant::now());
+    drop(held);
+
+    let first = limit.acquire().await;
+    let second = limit.acquire().await;
+    let third = tokio::time::timeout(Duration::from_millis(50), limit.acquire()).await;
+    assert!(third.is_err());
+    drop((first, second));
+}
+
+#[tokio::test]
+async fn test_crawl_with_adaptive_concurrency() {
+    // 链接需包含 developer.apple.com 才会被当作文档链接
+    let mut routes = vec![(
+        "/documentation/swiftui".to_string(),
+        (0..6)
+            .map(|i| format!(r#"<a href="/developer.apple.com/page{}">Page</a>"#, i))
+            .collect::<String>(),
+    )];
+    for i in 0..6 {
+        routes.push((
+            format!("/developer.apple.com/page{}", i),
+            format!("<html><body><h1>Page {}</h1><article>Text</article></body></html>", i),
+        ));
+    }
+    let routes: Vec<(&str, &str)> = routes.iter().map(|(path, body)| (path.as_str(), body.as_str())).collect();
+    let base = common::serve(&routes);
+
+    let mut crawler = Crawler::new(CrawlerConfig {
+        max_retries: 3,
+        concurrency: 1,
+        timeout: Duration::from_secs(10),
+        ..Default::default()
+    });
+    crawler.set_adaptive_concurrency(4);
+    let (pages, report) = crawler
+        .crawl_url(&format!("{}/documentation/swiftui", base), true)
+        .await
+        .unwrap();
+    assert_eq!(pages.len(), 7);
+    assert_eq!(report.pages_failed, 0);
+}
+
+#[tokio::test]
+async fn test_crawl_with_zero_concurrency() {
+    let base = common::serve(&[
+        ("/documentation/swiftui", r#"<h1>SwiftUI</h1><a href="/developer.apple.com/text">Text</a>"#),
+        ("/developer.apple.com/text", "<html><body><h1>Text</h1><article>A view.</article></body></html>"),
+    ]);
+
+    let mut crawler = Crawler::new(CrawlerConfig {
+        concurrency: 0,
+        timeout: Duration::from_secs(10),
+        ..Default::default()
+    });
+    let (pages, report) = crawler
+        .crawl_url(&format!("{}/documentation/swiftui", base), true)
+        .await
+        .unwrap();
+    assert_eq!(pages.len(), 2);
+    assert_eq!(report.pages_failed, 0);
+}
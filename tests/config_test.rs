@@ -0,0 +1,172 @@
+use adoc::config::FileConfig;
+use adoc::output::OutputFormat;
+use adoc::sink::SinkKind;
+
+#[test]
+fn test_parse_config_file() {
+    let config = FileConfig::parse(
+        r#"
+        input = "SwiftUI"
+        recursive = true
+        concurrency = 10
+        format = "es-bulk"
+        sink = "meilisearch"
+        sink-searchable = ["title"]
+        max-retries = 5
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(config.input.as_deref(), Some("SwiftUI"));
+    assert_eq!(config.recursive, Some(true));
+    assert_eq!(config.concurrency, Some(10));
+    assert!(matches!(config.format, Some(OutputFormat::EsBulk)));
+    assert_eq!(config.sink, Some(SinkKind::Meilisearch));
+    assert_eq!(config.sink_searchable, Some(vec!["title".to_string()]));
+    assert_eq!(config.max_retries, Some(5));
+    assert!(config.timeout.is_none());
+}
+
+#[test]
+fn test_reject_unknown_keys_and_values() {
+    assert!(FileConfig::parse("concurency = 10").is_err());
+    assert!(FileConfig::parse("format = \"yaml\"").is_err());
+    assert!(FileConfig::parse("concurrency = 0").is_err());
+}
+
+#[test]
+fn test_select_profile() {
+    let config = FileConfig::parse(
+        r#"
+        concurrency = 10
+        output = "default.json"
+
+        [profile.uikit]
+        input = "UIKit"
+        output = "uikit.json"
+        "#,
+    )
+    .unwrap();
+
+    assert!(config.clone().with_profile("appkit").is_err());
+
+    let uikit = config.with_profile("uikit").unwrap();
+    assert_eq!(uikit.input.as_deref(), Some("UIKit"));
+    assert_eq!(uikit.output.unwrap().to_str(), Some("uikit.json"));
+    assert_eq!(uikit.concurrency, Some(10));
+}
+
+#[test]
+fn test_parse_max_duration() {
+    use adoc::config::parse_duration;
+    use std::time::Duration;
+
+    assert_eq!(parse_duration("90").unwrap(), Duration::from_secs(90));
+    assert_eq!(parse_duration("10m").unwrap(), Duration::from_secs(600));
+    assert_eq!(parse_duration("1h30m").unwrap(), Duration::from_secs(5400));
+    assert!(parse_duration("10x").is_err());
+    assert!(parse_duration("m").is_err());
+
+    let config = FileConfig::parse("max-duration = \"2m30s\"").unwrap();
+    assert_eq!(config.max_duration, Some(Duration::from_secs(150)));
+}
+
+#[test]
+fn test_parse_connection_pool_settings() {
+    use std::time::Duration;
+
+    let config = FileConfig::parse(
+        r#"
+        pool-max-idle-per-host = 32
+        pool-idle-timeout = "2m"
+        tcp-keepalive = "60s"
+        http2-adaptive-window = true
+        "#,
+    )
+    .unwrap();
+    assert_eq!(config.pool_max_idle_per_host, Some(32));
+    assert_eq!(config.pool_idle_timeout, Some(Duration::from_secs(120)));
+    assert_eq!(config.tcp_keepalive, Some(Duration::from_secs(60)));
+    assert_eq!(config.http2_adaptive_window, Some(true));
+    assert_eq!(
+        FileConfig::parse("http-version = \"1.1\"").unwrap().http_version,
+        Some(adoc::crawler::HttpVersion::Http1)
+    );
+}
+
+#[test]
+fn test_parse_visited_settings() {
+    use adoc::visited::VisitedKind;
+
+    let config = FileConfig::parse("visited = \"bloom\"\nexpected-urls = \"5M\"").unwrap();
+    assert_eq!(config.visited, Some(VisitedKind::Bloom));
+    assert_eq!(config.expected_urls, Some(5_000_000));
+    assert_eq!(FileConfig::parse("expected-urls = 2000").unwrap().expected_urls, Some(2000));
+}
+
+#[test]
+fn test_parse_memory_budget() {
+    use adoc::config::parse_size;
+
+    assert_eq!(parse_size("4096").unwrap(), 4096);
+    assert_eq!(parse_size("512M").unwrap(), 512 << 20);
+    assert_eq!(parse_size("2gb").unwrap(), 2 << 30);
+    assert!(parse_size("MB").is_err());
+
+    let config = FileConfig::parse("memory-budget = \"64K\"").unwrap();
+    assert_eq!(config.memory_budget, Some(64 << 10));
+    assert_eq!(FileConfig::parse("max-body-size = \"10M\"").unwrap().max_body_size, Some(10 << 20));
+}
+
+#[test]
+fn test_parse_link_filter_settings() {
+    let config = FileConfig::parse("allow-domain = [\"swift.org\", \"github.com/apple\"]\nallow-external = false").unwrap();
+    assert_eq!(config.allow_domain, Some(vec!["swift.org".to_string(), "github.com/apple".to_string()]));
+    assert_eq!(config.allow_external, Some(false));
+    assert_eq!(FileConfig::parse("search-pages = 3").unwrap().search_pages, Some(3));
+}
+
+#[test]
+fn test_load_ca_certs() {
+    use adoc::crawler::load_ca_certs;
+
+    let bundle = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/ca-bundle.pem");
+    assert_eq!(load_ca_certs(&bundle).unwrap().len(), 2);
+
+    let dir = tempfile::tempdir().unwrap();
+    let garbage = dir.path().join("ca.pem");
+    std::fs::write(&garbage, "not a certificate").unwrap();
+    assert!(load_ca_certs(&garbage).is_err());
+    assert!(load_ca_certs(&dir.path().join("missing.pem")).is_err());
+
+    let config = FileConfig::parse("ca-cert = \"proxy-ca.pem\"\ninsecure = true").unwrap();
+    assert_eq!(config.ca_cert, Some("proxy-ca.pem".into()));
+    assert_eq!(config.insecure, Some(true));
+}
+
+#[test]
+fn test_parse_dns() {
+    use adoc::dns::{parse_dns, Dns};
+
+    assert_eq!(parse_dns("system").unwrap(), Dns::System);
+    assert_eq!(
+        parse_dns("doh:https://1.1.1.1/dns-query").unwrap(),
+        Dns::Doh { host: "1.1.1.1".into(), port: 443 }
+    );
+    assert_eq!(
+        parse_dns("doh:https://dns.example.com:8443").unwrap(),
+        Dns::Doh { host: "dns.example.com".into(), port: 8443 }
+    );
+    assert_eq!(
+        parse_dns("doh:https://[2606:4700:4700::1111]/dns-query").unwrap(),
+        Dns::Doh { host: "2606:4700:4700::1111".into(), port: 443 }
+    );
+    // hickory 只会请求 /dns-query，也不支持明文 HTTP
+    assert!(parse_dns("doh:https://dns.example.com/resolve").is_err());
+    assert!(parse_dns("doh:http://1.1.1.1/dns-query").is_err());
+    assert!(parse_dns("8.8.8.8").is_err());
+
+    let config = FileConfig::parse("dns = \"doh:https://1.1.1.1/dns-query\"").unwrap();
+    assert_eq!(config.dns, Some(Dns::Doh { host: "1.1.1.1".into(), port: 443 }));
+    assert!(FileConfig::parse("dns = \"doh:ftp://1.1.1.1\"").is_err());
+}
@@ -1,10 +1,10 @@
-use adoc::crawler::{Crawler, CrawlerConfig, DocPage};
+use adoc::crawler::{Crawler, CrawlerConfig};
 use std::time::Duration;
 
 #[tokio::test]
 async fn test_crawler_with_logging() {
     // 设置测试日志
-    let subscriber = tracing_subscriber::fmt()
+    tracing_subscriber::fmt()
         .with_env_filter("debug")
         .with_test_writer()
         .init();
@@ -14,12 +14,13 @@ async fn test_crawler_with_logging() {
         max_retries: 3,
         concurrency: 2,
         timeout: Duration::from_secs(30),
+        ..Default::default()
     };
 
     let mut crawler = Crawler::new(config);
 
     // 测试单个页面爬取
-    let results = crawler
+    let (results, _report) = crawler
         .crawl_url("https://developer.apple.com/documentation/swift", false)
         .await
         .unwrap();
@@ -28,7 +29,7 @@ async fn test_crawler_with_logging() {
     assert!(results[0].title.contains("Swift"));
 
     // 测试递归爬取（限制深度）
-    let results = crawler
+    let (results, _report) = crawler
         .crawl_url("https://developer.apple.com/documentation/swiftui", true)
         .await
         .unwrap();
@@ -43,10 +44,11 @@ async fn test_search_and_crawl() {
         max_retries: 3,
         concurrency: 2,
         timeout: Duration::from_secs(30),
+        ..Default::default()
     };
 
     let mut crawler = Crawler::new(config);
-    let results = crawler.search_and_crawl("SwiftUI", false).await.unwrap();
+    let (results, _report) = crawler.search_and_crawl("SwiftUI", false).await.unwrap();
 
     assert!(!results.is_empty());
 } 
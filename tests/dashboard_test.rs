@@ -0,0 +1,50 @@
+#![cfg(feature = "dashboard")]
+
+use adoc::dashboard::DashboardState;
+use adoc::progress::{ProgressEventKind, ProgressEvents};
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_dashboard_state() {
+    let started = Instant::now();
+    let at = |secs: u64| started + Duration::from_secs(secs);
+    let mut state = DashboardState::new(started);
+    for url in ["https://a", "https://b", "https://c", "https://d", "https://e"] {
+        state.record(ProgressEventKind::Queued, url, None, at(0));
+    }
+    for url in ["https://a", "https://b", "https://c", "https://d"] {
+        state.record(ProgressEventKind::Fetching, url, None, at(0));
+    }
+    state.record(ProgressEventKind::Done, "https://a", None, at(1));
+    state.record(ProgressEventKind::Done, "https://b", None, at(1));
+    state.record(ProgressEventKind::Skipped, "https://c", Some("404"), at(2));
+    assert_eq!(state.queued(), 1);
+    assert_eq!(state.active().collect::<Vec<_>>(), ["https://d"]);
+    // 第 1 秒完成 2 个页面，按最近 10 秒平均
+    assert!((state.pages_per_sec(at(11)) - 0.2).abs() < 1e-9);
+
+    state.record(ProgressEventKind::Failed, "https://d", Some("HTTP 500"), at(3));
+    let backend = TestBackend::new(100, 24);
+    let mut terminal = Terminal::new(backend).unwrap();
+    terminal.draw(|frame| state.render(frame, at(3))).unwrap();
+    let screen = terminal.backend().to_string();
+    assert!(screen.contains("4/5"));
+    assert!(screen.contains("https://d  HTTP 500"));
+    // 正在进行的请求结束后不再列出
+    assert!(!screen.contains("s] https://d"));
+}
+
+#[test]
+fn test_progress_callback() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let recorded = seen.clone();
+    let events = ProgressEvents::callback(Box::new(move |event, url, reason| {
+        recorded.lock().unwrap().push(format!("{:?} {} {:?}", event, url, reason));
+    }));
+    events.emit(ProgressEventKind::Fetching, "https://a", None);
+    events.emit(ProgressEventKind::Failed, "https://a", Some("timeout"));
+    assert_eq!(*seen.lock().unwrap(), ["Fetching https://a None", "Failed https://a Some(\"timeout\")"]);
+}
@@ -0,0 +1,43 @@
+mod common;
+
+use adoc::delta::{content_hash, delta_pages, save_delta, ChangeKind};
+use adoc::output::load_results;
+
+#[test]
+fn test_delta_against_baseline() {
+    let baseline = vec![
+        common::page(&common::doc_url("swiftui/view"), "view", "A view."),
+        common::page(&common::doc_url("swiftui/text"), "text", "Text."),
+        common::page(&common::doc_url("swiftui/removed"), "removed", "Gone."),
+    ];
+    let mut unchanged = common::page(&common::doc_url("swiftui/text"), "text", "Text.");
+    // 只有锚点或末尾斜杠不同的地址视为同一页面
+    unchanged.url.push('/');
+    let pages = vec![
+        common::page(&common::doc_url("swiftui/list"), "list", "A list."),
+        unchanged,
+        common::page(&common::doc_url("swiftui/view"), "view", "A view that changed."),
+    ];
+
+    let delta = delta_pages(&baseline, &pages);
+    let summary: Vec<_> = delta.iter().map(|entry| (entry.page.title.as_str(), entry.change)).collect();
+    assert_eq!(summary, [("list", ChangeKind::Added), ("view", ChangeKind::Modified)]);
+    assert_eq!(delta[1].content_hash, content_hash(&pages[2]));
+    assert_ne!(content_hash(&pages[2]), content_hash(&baseline[0]));
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("changes.json");
+    save_delta(&path, &delta, false).unwrap();
+    let value: serde_json::Value = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+    assert_eq!(value["schema_version"], adoc::schema::SCHEMA_VERSION);
+    assert_eq!(value["pages"][0]["change"], "added");
+    assert_eq!(value["pages"][1]["change"], "modified");
+    assert_eq!(value["pages"][1]["content"], "A view that changed.");
+
+    // 变更文件与 JSON 输出格式相同，可以直接读取
+    let loaded = load_results(&path).unwrap();
+    assert_eq!(loaded.len(), 2);
+    assert_eq!(loaded[1].url, pages[2].url);
+
+    assert!(delta_pages(&pages, &pages).is_empty());
+}
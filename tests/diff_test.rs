@@ -0,0 +1,43 @@
+mod common;
+
+use adoc::diff::{diff_pages, DiffFormat};
+
+#[test]
+fn test_diff_added_removed_changed() {
+    let old = vec![
+        common::page(&common::doc_url("swiftui/text"), "text", "A view that displays text.\niOS 13.0+"),
+        common::page(&common::doc_url("swiftui/list"), "list", "A container."),
+        common::page(&common::doc_url("swiftui/label"), "label", "Unchanged."),
+    ];
+    let new = vec![
+        common::page(
+            &common::doc_url("swiftui/text"),
+            "text",
+            "Deprecated\nA view that displays text.\niOS 13.0+\nvisionOS 1.0+",
+        ),
+        common::page(&common::doc_url("swiftui/label"), "label", "Unchanged."),
+        common::page(&common::doc_url("swiftui/grid"), "grid", "A grid."),
+    ];
+
+    let diff = diff_pages(&old, &new);
+    assert_eq!(diff.added.len(), 1);
+    assert_eq!(diff.added[0].title, "grid");
+    assert_eq!(diff.removed.len(), 1);
+    assert_eq!(diff.removed[0].title, "list");
+    assert_eq!(diff.changed.len(), 1);
+
+    let change = &diff.changed[0];
+    assert!(change.content_diff.as_ref().unwrap().contains("+visionOS 1.0+"));
+    let availability = change.availability.as_ref().unwrap();
+    assert_eq!(availability.old, vec!["iOS 13.0+"]);
+    assert_eq!(availability.new, vec!["iOS 13.0+", "visionOS 1.0+"]);
+    let deprecated = change.deprecated.as_ref().unwrap();
+    assert!(!deprecated.old && deprecated.new);
+
+    let markdown = diff.render(DiffFormat::Markdown).unwrap();
+    assert!(markdown.contains("## 新增页面"));
+    assert!(markdown.contains("```diff"));
+
+    let json: serde_json::Value = serde_json::from_str(&diff.render(DiffFormat::Json).unwrap()).unwrap();
+    assert_eq!(json["removed"][0]["title"], "list");
+}
@@ -0,0 +1,86 @@
+use adoc::docc::{import_docc, DEFAULT_BASE_URL};
+use std::path::Path;
+
+fn write(path: &Path, content: &str) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, content).unwrap();
+}
+
+/// 最小的 DocC 归档：框架首页、一个符号页和一篇教程
+fn sample_archive(dir: &Path) -> std::path::PathBuf {
+    let archive = dir.join("Sample.doccarchive");
+    write(&archive.join("index.html"), "<html></html>");
+    write(
+        &archive.join("data/documentation/sample.json"),
+        r#"{
+            "metadata": { "title": "Sample" },
+            "abstract": [ { "type": "text", "text": "Widgets for internal apps." } ],
+            "references": {
+                "doc://com.example.Sample/documentation/Sample/Widget": {
+                    "title": "Widget", "url": "/documentation/sample/widget"
+                },
+                "doc://com.apple.documentation/documentation/swiftui/view": {
+                    "title": "View", "url": "https://developer.apple.com/documentation/swiftui/view"
+                }
+            }
+        }"#,
+    );
+    write(
+        &archive.join("data/documentation/sample/widget.json"),
+        r#"{
+            "metadata": { "title": "Widget" },
+            "primaryContentSections": [
+                { "kind": "declarations", "declarations": [
+                    { "tokens": [ { "text": "struct" }, { "text": " " }, { "text": "Widget" } ] }
+                ] }
+            ]
+        }"#,
+    );
+    write(
+        &archive.join("data/tutorials/sample/getting-started.json"),
+        r#"{ "metadata": { "title": "Getting Started" } }"#,
+    );
+    archive
+}
+
+#[test]
+fn test_import_docc_archive() {
+    let dir = tempfile::tempdir().unwrap();
+    let archive = sample_archive(dir.path());
+
+    let pages = import_docc(&archive, DEFAULT_BASE_URL).unwrap();
+    let urls: Vec<&str> = pages.iter().map(|page| page.url.as_str()).collect();
+    assert_eq!(
+        urls,
+        vec![
+            "http://localhost:8080/documentation/sample",
+            "http://localhost:8080/documentation/sample/widget",
+            "http://localhost:8080/tutorials/sample/getting-started",
+        ]
+    );
+    assert_eq!(pages[0].title, "Sample");
+    assert_eq!(pages[0].content, "Widgets for internal apps.");
+    let mut links: Vec<&str> = pages[0].link_urls().collect();
+    links.sort();
+    assert_eq!(
+        links,
+        vec![
+            "http://localhost:8080/documentation/sample/widget",
+            "https://developer.apple.com/documentation/swiftui/view",
+        ]
+    );
+    assert_eq!(pages[1].content, "struct Widget");
+    assert_eq!(pages[1].hierarchy(), vec!["sample", "widget"]);
+}
+
+#[test]
+fn test_import_docc_with_base_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let archive = sample_archive(dir.path());
+
+    let pages = import_docc(&archive, "https://docs.example.com/internal").unwrap();
+    assert_eq!(pages[0].url, "https://docs.example.com/internal/documentation/sample");
+    assert!(pages[0].link_urls().any(|link| link == "https://docs.example.com/internal/documentation/sample/widget"));
+
+    assert!(import_docc(dir.path(), DEFAULT_BASE_URL).is_err());
+}
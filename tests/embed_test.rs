@@ -0,0 +1,58 @@
+#![cfg(feature = "embed")]
+
+mod common;
+
+use adoc::chunk::chunk_pages;
+use adoc::embed::{EmbeddingClient, EmbeddingConfig};
+use adoc::extract::DocPage;
+
+#[tokio::test]
+async fn test_embed_chunks() {
+    // 接口不按输入顺序返回时按 index 对齐
+    let response = r#"{"data": [
+        {"object": "embedding", "index": 1, "embedding": [0.5, 0.25]},
+        {"object": "embedding", "index": 0, "embedding": [1.0, -1.0]}
+    ]}"#;
+    let (base, log) = common::serve_typed_recording(&[("POST /v1/embeddings", "application/json", response)]);
+    let page = DocPage {
+        title: "Text".to_string(),
+        content: "one two three four".to_string(),
+        url: "https://developer.apple.com/documentation/swiftui/text".to_string(),
+        ..Default::default()
+    };
+    let mut chunks = chunk_pages(&[page], 2, 0).unwrap();
+    assert_eq!(chunks.len(), 2);
+
+    let client = EmbeddingClient::new(EmbeddingConfig {
+        url: format!("{}/v1/embeddings", base),
+        model: "nomic-embed-text".to_string(),
+        api_key: Some("secret".to_string()),
+        batch_size: 16,
+    });
+    client.embed_chunks(&mut chunks).await.unwrap();
+    assert_eq!(chunks[0].embedding, Some(vec![1.0, -1.0]));
+    assert_eq!(chunks[1].embedding, Some(vec![0.5, 0.25]));
+
+    let requests = log.lock().unwrap().clone();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].headers["authorization"], "Bearer secret");
+    let body: serde_json::Value = serde_json::from_str(&requests[0].body).unwrap();
+    assert_eq!(body["model"], "nomic-embed-text");
+    assert_eq!(body["input"], serde_json::json!(["one two", "three four"]));
+
+    // 返回的向量数与请求的分块数不一致时报错
+    let client = EmbeddingClient::new(EmbeddingConfig {
+        url: format!("{}/v1/embeddings", base),
+        model: "nomic-embed-text".to_string(),
+        api_key: None,
+        batch_size: 1,
+    });
+    assert!(client.embed_chunks(&mut chunks).await.is_err());
+    let missing = EmbeddingClient::new(EmbeddingConfig {
+        url: format!("{}/v1/missing", base),
+        model: "nomic-embed-text".to_string(),
+        api_key: None,
+        batch_size: 16,
+    });
+    assert!(missing.embed_chunks(&mut chunks).await.is_err());
+}
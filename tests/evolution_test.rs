@@ -0,0 +1,135 @@
+mod common;
+
+use adoc::crawler::{Crawler, CrawlerConfig};
+use adoc::evolution::{INDEX_URL, PROPOSALS_URL};
+use adoc::extract::{extract_json_for, extract_markdown_for, LinkFilter};
+use adoc::profile::{DocSource, SiteProfile};
+use std::sync::Arc;
+use std::time::Duration;
+
+const INDEX: &str = r#"{
+    "schemaVersion": "1.0.0",
+    "proposals": [
+        {"id": "SE-0409", "title": "Access-level modifiers on import declarations",
+         "status": {"state": "implemented", "version": "6.0"}, "link": "0409-access-level-on-imports.md"},
+        {"id": "SE-0430", "title": "`sending` parameter and result values",
+         "status": {"state": "activeReview", "start": "2024-04-09T00:00:00Z", "end": "2024-04-23T00:00:00Z"},
+         "link": "0430-transferring-parameters-and-results.md"}
+    ]
+}"#;
+
+const PROPOSAL: &str = "# Access-level modifiers on import declarations
+
+* Proposal: [SE-0409](0409-access-level-on-imports.md)
+* Author: [Alexis Laferrière](https://github.com/xymus)
+* Review Manager: [Frederick Kellison-Linn](https://github.com/Jumhyn)
+* Status: **Implemented (Swift 6.0)**
+* Implementation: [apple/swift#68342](https://github.com/apple/swift/pull/68342)
+* Review: ([pitch](https://forums.swift.org/t/61803)) ([review](https://forums.swift.org/t/66024))
+
+## Introduction
+
+Declaring the visibility of a dependency with an **access-level modifier**, see [SE-0338](0338-clarify-execution.md).
+";
+
+#[test]
+fn test_extract_index() {
+    let profile = SiteProfile::swift_evolution();
+    let page = extract_json_for(&profile, INDEX_URL, INDEX, &LinkFilter::default()).unwrap();
+    assert_eq!(page.title, "Swift Evolution");
+    let lines: Vec<&str> = page.content.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "SE-0409: Access-level modifiers on import declarations [Implemented (Swift 6.0)]",
+            "SE-0430: `sending` parameter and result values [Active review (2024-04-09 – 2024-04-23)]",
+        ]
+    );
+    assert_eq!(
+        page.link_urls().collect::<Vec<_>>(),
+        [
+            format!("{}0409-access-level-on-imports.md", PROPOSALS_URL),
+            format!("{}0430-transferring-parameters-and-results.md", PROPOSALS_URL),
+        ]
+    );
+}
+
+#[test]
+fn test_extract_proposal() {
+    let profile = SiteProfile::swift_evolution();
+    let url = format!("{}0409-access-level-on-imports.md", PROPOSALS_URL);
+    let page = extract_markdown_for(&profile, &url, PROPOSAL, &LinkFilter::default()).unwrap();
+    assert_eq!(page.title, "SE-0409: Access-level modifiers on import declarations");
+    let lines: Vec<&str> = page.content.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "Proposal: SE-0409 (0409-access-level-on-imports.md)",
+            "Author: Alexis Laferrière",
+            "Review Manager: Frederick Kellison-Linn",
+            "Status: Implemented (Swift 6.0)",
+            "Implementation: apple/swift#68342 (https://github.com/apple/swift/pull/68342)",
+            "Review: pitch (https://forums.swift.org/t/61803), review (https://forums.swift.org/t/66024)",
+            "## Introduction",
+            "Declaring the visibility of a dependency with an access-level modifier, see SE-0338.",
+        ]
+    );
+    // 只保留提案之间的链接，评审和实现链接在正文中
+    assert_eq!(page.link_urls().collect::<Vec<_>>(), [url, format!("{}0338-clarify-execution.md", PROPOSALS_URL)]);
+}
+
+#[test]
+fn test_generic_markdown() {
+    let page = extract_markdown_for(
+        SiteProfile::apple(),
+        "https://developer.apple.com/notes/readme.md",
+        "# Notes\n\nSee [the guide](/documentation/swiftui) and [elsewhere](https://example.com).\n",
+        &LinkFilter::default(),
+    )
+    .unwrap();
+    assert_eq!(page.title, "Notes");
+    assert_eq!(page.content, "# Notes\nSee the guide and elsewhere.");
+    assert_eq!(page.link_urls().collect::<Vec<_>>(), ["https://developer.apple.com/documentation/swiftui"]);
+}
+
+#[test]
+fn test_doc_source() {
+    assert_eq!(DocSource::default(), DocSource::Docs);
+    assert_eq!(DocSource::Docs.seed(), None);
+    assert_eq!(DocSource::SwiftEvolution.seed(), Some(INDEX_URL));
+    let profile = DocSource::SwiftEvolution.profile().unwrap();
+    assert!(profile.is_site_link(&format!("{}0001-keywords-as-argument-labels.md", PROPOSALS_URL)));
+    assert!(profile.search_url.is_none());
+}
+
+#[tokio::test]
+async fn test_crawl_proposals() {
+    // 提案原文以 text/plain 返回（与 GitHub 相同）或声明为 text/markdown
+    let proposals = common::serve_typed(&[
+        ("/proposals/0409-access-level-on-imports.md", "text/plain; charset=utf-8", PROPOSAL),
+        ("/proposals/0430-transferring-parameters-and-results.md", "text/markdown", "# Transferring\n\nBody text."),
+    ]);
+    let index = INDEX.replace("\"link\": \"", &format!("\"link\": \"{}/proposals/", proposals));
+    let base = common::serve_typed(&[("/evolution.json", "application/json", &index)]);
+
+    let mut crawler = Crawler::new(CrawlerConfig {
+        max_retries: 1,
+        timeout: Duration::from_secs(10),
+        profile: Arc::new(SiteProfile::swift_evolution()),
+        link_filter: LinkFilter {
+            allowed_domains: vec!["127.0.0.1".to_string()],
+            allow_external: false,
+        },
+        ..Default::default()
+    });
+    let (pages, report) = crawler.crawl_url(&format!("{}/evolution.json", base), true).await.unwrap();
+    assert_eq!(report.pages_failed, 0);
+    let mut titles: Vec<&str> = pages.iter().map(|page| page.title.as_str()).collect();
+    titles.sort();
+    assert_eq!(
+        titles,
+        ["SE-0409: Access-level modifiers on import declarations", "Swift Evolution", "Transferring"]
+    );
+    let proposal = pages.iter().find(|page| page.title.starts_with("SE-0409")).unwrap();
+    assert!(proposal.content.contains("Status: Implemented (Swift 6.0)"), "{}", proposal.content);
+}
@@ -0,0 +1,287 @@
+use adoc::extract::{
+    clean_text, extract_html, extract_html_with, extract_json, extract_links, extract_search_results, ContentLimit,
+    DocPage, LinkFilter, LinkRel, RelatedLink,
+};
+
+#[test]
+fn test_clean_text() {
+    assert_eq!(clean_text("  a   b \n\n   c  \n"), "a b\nc");
+}
+
+#[test]
+fn test_extract_html() {
+    let html = r#"<html><body>
+        <h1>  View  </h1>
+        <article><p>A piece of   the user interface.</p></article>
+        <a href="/documentation/swiftui/text">Text</a>
+        <a href="https://example.com/">Elsewhere</a>
+    </body></html>"#;
+
+    let page = extract_html("https://developer.apple.com/documentation/swiftui/view", html).unwrap();
+
+    assert_eq!(page.title, "View");
+    assert_eq!(page.content, "A piece of the user interface.");
+    assert_eq!(
+        page.link_urls().collect::<Vec<_>>(),
+        vec!["https://developer.apple.com/documentation/swiftui/text"]
+    );
+}
+
+#[test]
+fn test_extract_links_matches_full_parse() {
+    let html = r#"<html><head><title>a <a href="/documentation/title"> b</title>
+        <script>if (a <a href="/documentation/script">) {}</script></head><body>
+        <h1>View</h1>
+        <article><a href="/documentation/swiftui/text">Text</a>
+        <A HREF='../image'>Image</A></article>
+        <a href="https://example.com/">Elsewhere</a>
+        <a name="anchor">No href</a>
+    </body></html>"#;
+    let url = "https://developer.apple.com/documentation/swiftui/view";
+
+    let links = extract_links(url, html).unwrap();
+    assert_eq!(
+        links,
+        vec![
+            "https://developer.apple.com/documentation/swiftui/text",
+            "https://developer.apple.com/documentation/image",
+        ]
+    );
+    assert_eq!(links, extract_html(url, html).unwrap().link_urls().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_extract_json() {
+    let json = r#"{
+        "metadata": { "title": "View" },
+        "abstract": [
+            { "type": "text", "text": "A type that represents part of your app's " },
+            { "type": "codeVoice", "code": "body" }
+        ],
+        "primaryContentSections": [
+            { "kind": "declarations", "declarations": [
+                { "tokens": [ { "text": "protocol" }, { "text": " " }, { "text": "View" } ] }
+            ] },
+            { "kind": "content", "content": [
+                { "type": "heading", "text": "Overview" },
+                { "type": "paragraph", "inlineContent": [
+                    { "type": "text", "text": "See " },
+                    { "type": "reference", "identifier": "doc://swiftui/Text" }
+                ] }
+            ] }
+        ],
+        "references": {
+            "doc://swiftui/Text": { "title": "Text", "url": "/documentation/swiftui/text" }
+        }
+    }"#;
+
+    let page = extract_json("https://developer.apple.com/documentation/swiftui/view", json).unwrap();
+
+    assert_eq!(page.title, "View");
+    assert_eq!(
+        page.content,
+        "A type that represents part of your app's body\nprotocol View\nOverview\nSee Text"
+    );
+    assert_eq!(
+        page.link_urls().collect::<Vec<_>>(),
+        vec!["https://developer.apple.com/documentation/swiftui/text"]
+    );
+}
+
+#[test]
+fn test_related_link_details() {
+    let html = r##"<html><body><h1>View</h1>
+        <a href="/documentation/swiftui">SwiftUI</a>
+        <article><p>A piece of the user interface.</p>
+        <h2>Topics</h2><h3>Creating a view</h3>
+        <a href="/documentation/swiftui/view/body-swift.property"><code>body</code></a>
+        <a href="#mentions">Mentioned in</a>
+        <h2>See Also</h2>
+        <a href="https://www.swift.org/documentation/">Swift</a></article>
+    </body></html>"##;
+    let filter = LinkFilter {
+        allow_external: true,
+        ..Default::default()
+    };
+    let page = extract_html_with("https://developer.apple.com/documentation/swiftui/view", html, &filter).unwrap();
+    let link = |url: &str, text: &str, rel: LinkRel, section: Option<&str>| RelatedLink {
+        url: url.to_string(),
+        text: text.to_string(),
+        rel,
+        section: section.map(str::to_string),
+    };
+    assert_eq!(
+        page.related_links,
+        [
+            link("https://developer.apple.com/documentation/swiftui", "SwiftUI", LinkRel::Internal, None),
+            link(
+                "https://developer.apple.com/documentation/swiftui/view/body-swift.property",
+                "body",
+                LinkRel::Internal,
+                Some("Creating a view")
+            ),
+            link(
+                "https://developer.apple.com/documentation/swiftui/view#mentions",
+                "Mentioned in",
+                LinkRel::Anchor,
+                Some("Creating a view")
+            ),
+            link("https://www.swift.org/documentation/", "Swift", LinkRel::External, Some("See Also")),
+        ]
+    );
+    let json = serde_json::to_value(&page.related_links[0]).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "url": "https://developer.apple.com/documentation/swiftui",
+            "text": "SwiftUI",
+            "rel": "internal",
+        })
+    );
+
+    // DocC JSON 的分组标题作为小节
+    let json = r#"{
+        "metadata": { "title": "View" },
+        "topicSections": [{ "title": "Creating a view", "identifiers": ["doc://swiftui/body"] }],
+        "references": {
+            "doc://swiftui/body": { "title": "body", "url": "/documentation/swiftui/view/body-swift.property" },
+            "doc://swiftui/Text": { "title": "Text", "url": "/documentation/swiftui/text" }
+        }
+    }"#;
+    let page = extract_json("https://developer.apple.com/documentation/swiftui/view", json).unwrap();
+    assert_eq!(
+        page.related_links,
+        [
+            link("https://developer.apple.com/documentation/swiftui/text", "Text", LinkRel::Internal, None),
+            link(
+                "https://developer.apple.com/documentation/swiftui/view/body-swift.property",
+                "body",
+                LinkRel::Internal,
+                Some("Creating a view")
+            ),
+        ]
+    );
+
+    // 旧版本结果文件中的相关链接只有地址
+    let page: DocPage = serde_json::from_str(
+        r#"{"title": "View", "content": "", "url": "https://developer.apple.com/documentation/swiftui/view",
+            "related_links": ["https://developer.apple.com/documentation/swiftui/text"]}"#,
+    )
+    .unwrap();
+    let text = link("https://developer.apple.com/documentation/swiftui/text", "", LinkRel::Internal, None);
+    assert_eq!(page.related_links, [text]);
+}
+
+#[test]
+fn test_extract_search_results() {
+    let html = r#"<html><body><ul>
+        <li class="search-result documentation-result">
+            <a href="/documentation/swiftui/scrollview"><h3 class="result-title">ScrollView</h3></a>
+            <p class="result-description">A scrollable view.</p>
+        </li>
+        <li class="search-result">
+            <a href="/videos/play/wwdc2023/10159/">Beyond scroll views</a>
+        </li>
+        <li class="search-result">
+            <a href="/documentation/uikit/uiscrollview"><h3 class="result-title">UIScrollView</h3></a>
+        </li>
+        <li class="search-result">
+            <a href="/documentation/swiftui/scrollview"><h3 class="result-title">ScrollView</h3></a>
+        </li>
+    </ul></body></html>"#;
+
+    let results =
+        extract_search_results("https://developer.apple.com/search/index.php?q=scrollview", html).unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].title, "ScrollView");
+    assert_eq!(results[0].url, "https://developer.apple.com/documentation/swiftui/scrollview");
+    assert_eq!(results[0].description, "A scrollable view.");
+    assert_eq!(results[1].title, "UIScrollView");
+}
+
+#[test]
+fn test_json_data_url() {
+    use adoc::extract::json_data_url;
+
+    assert_eq!(
+        json_data_url("https://developer.apple.com/documentation/swiftui/view/?language=objc#overview").as_deref(),
+        Some("https://developer.apple.com/tutorials/data/documentation/swiftui/view.json")
+    );
+    assert_eq!(json_data_url("https://developer.apple.com/search/index.php?q=View"), None);
+}
+
+#[test]
+fn test_empty_shell() {
+    let shell = r#"<html><body><noscript>This page requires JavaScript.</noscript><div id="app"></div></body></html>"#;
+    let page = extract_html("https://developer.apple.com/documentation/swiftui/view", shell).unwrap();
+    assert!(page.is_empty_shell());
+}
+
+#[test]
+fn test_link_filter() {
+
+    let apple_only = LinkFilter::default();
+    assert!(apple_only.allows("https://developer.apple.com/documentation/swiftui"));
+    assert!(!apple_only.allows("https://www.swift.org/documentation/"));
+
+    let allowed = LinkFilter {
+        allowed_domains: vec!["Swift.org".to_string(), "github.com/apple".to_string()],
+        allow_external: false,
+    };
+    assert!(allowed.allows("https://www.swift.org/documentation/"));
+    assert!(allowed.allows("https://github.com/apple/swift"));
+    assert!(!allowed.allows("https://github.com/applesauce/swift"));
+    assert!(!allowed.allows("https://notswift.org/"));
+    assert!(!allowed.allows("mailto:swift.org"));
+
+    let external = LinkFilter {
+        allow_external: true,
+        ..Default::default()
+    };
+    assert!(external.allows("https://example.com/"));
+    assert!(!external.allows("javascript:void(0)"));
+
+    let html = r#"<html><body><h1>View</h1>
+        <a href="/documentation/swiftui/text">Text</a>
+        <a href="https://www.swift.org/documentation/">Swift</a>
+    </body></html>"#;
+    let page = extract_html_with("https://developer.apple.com/documentation/swiftui/view", html, &allowed).unwrap();
+    assert_eq!(
+        page.link_urls().collect::<Vec<_>>(),
+        vec!["https://developer.apple.com/documentation/swiftui/text", "https://www.swift.org/documentation/"]
+    );
+}
+
+#[test]
+fn test_content_limit() {
+    let json = r#"{
+        "metadata": {"title": "frame(width:height:alignment:)"},
+        "abstract": [{"type": "text", "text": "Positions this view within an invisible frame with the specified size."}],
+        "primaryContentSections": [
+            {"kind": "declarations", "declarations": [{"tokens": [{"text": "func frame(width: CGFloat? = nil) -> some View"}]}]},
+            {"kind": "content", "content": [
+                {"type": "heading", "level": 2, "text": "Discussion", "anchor": "discussion"},
+                {"type": "paragraph", "inlineContent": [{"type": "text", "text": "Use this method to specify a fixed size."}]}
+            ]}
+        ]
+    }"#;
+    let page = extract_json("https://developer.apple.com/documentation/swiftui/view/frame", json).unwrap();
+    assert_eq!(page.anchors.len(), 1);
+
+    let mut excerpt = page.clone();
+    ContentLimit { excerpt_only: true, max_chars: None }.apply(&mut excerpt);
+    assert_eq!(excerpt.content, "Positions this view within an invisible frame with the specified size.");
+    assert!(excerpt.anchors.is_empty());
+
+    // 截断后不超过最大字符数
+    let mut truncated = page.clone();
+    ContentLimit { excerpt_only: false, max_chars: Some(20) }.apply(&mut truncated);
+    assert_eq!(truncated.content, "Positions this view…");
+    assert_eq!(truncated.content.chars().count(), 20);
+
+    let mut unchanged = page.clone();
+    ContentLimit { excerpt_only: false, max_chars: Some(10_000) }.apply(&mut unchanged);
+    assert_eq!(unchanged.content, page.content);
+    assert_eq!(unchanged.anchors, page.anchors);
+}
@@ -0,0 +1,249 @@
+mod common;
+
+use adoc::crawler::{Crawler, CrawlerConfig};
+use adoc::report::{load_failed_urls, FailureKind};
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_failed_pages_written_with_kind_and_status() {
+    // 链接需包含 developer.apple.com 才会被当作文档链接
+    let base = common::serve_status(&[
+        (
+            "/documentation/swiftui",
+            "200 OK",
+            r#"<html><body><h1>SwiftUI</h1><article>
+                <a href="/developer.apple.com/broken">Broken</a>
+            </article></body></html>"#,
+        ),
+        ("/developer.apple.com/broken", "500 Internal Server Error", ""),
+    ]);
+    let seed = format!("{}/documentation/swiftui", base);
+
+    let mut crawler = Crawler::new(CrawlerConfig {
+        max_retries: 3,
+        concurrency: 2,
+        timeout: Duration::from_secs(10),
+        ..Default::default()
+    });
+    let (pages, report) = crawler.crawl_url(&seed, true).await.unwrap();
+    assert_eq!(pages.len(), 1);
+    assert_eq!(report.pages_failed, 1);
+    let failure = &report.failures[0];
+    assert_eq!(failure.url, format!("{}/developer.apple.com/broken", base));
+    assert_eq!(failure.kind, FailureKind::Status);
+    assert_eq!(failure.status, Some(500));
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("failed.txt");
+    report.save_failures(&path).unwrap();
+    let content = std::fs::read_to_string(path).unwrap();
+    let fields: Vec<&str> = content.trim_end().split('\t').collect();
+    assert_eq!(fields[..3], [failure.url.as_str(), "status", "500"]);
+}
+
+#[test]
+fn test_load_failed_urls() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("failed.txt");
+    std::fs::write(
+        &path,
+        "https://developer.apple.com/a\tstatus\t404\tnot found\n\nhttps://developer.apple.com/b\ttimeout\t-\ttimed out\nhttps://developer.apple.com/a\tstatus\t404\tnot found\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        load_failed_urls(&path).unwrap(),
+        vec!["https://developer.apple.com/a", "https://developer.apple.com/b"]
+    );
+}
+
+#[tokio::test]
+async fn test_oversized_body_is_aborted() {
+    let big = format!("<html><body><h1>Big</h1><article>{}</article></body></html>", "x".repeat(64 << 10));
+    let base = common::serve(&[
+        (
+            "/documentation/swiftui",
+            r#"<html><body><h1>SwiftUI</h1><article>
+                <a href="/developer.apple.com/video">Video</a>
+            </article></body></html>"#,
+        ),
+        ("/developer.apple.com/video", &big),
+    ]);
+
+    let mut crawler = Crawler::new(CrawlerConfig {
+        max_body_size: Some(16 << 10),
+        ..Default::default()
+    });
+    let (pages, report) = crawler.crawl_url(&format!("{}/documentation/swiftui", base), true).await.unwrap();
+    assert_eq!(pages.len(), 1);
+    assert_eq!(report.pages_failed, 1);
+    assert_eq!(report.failures[0].kind, FailureKind::TooLarge);
+    assert_eq!(report.failures[0].status, None);
+}
+
+#[tokio::test]
+async fn test_oversized_body_without_content_length() {
+    use std::io::{Read, Write};
+
+    // 不声明 Content-Length，只能在下载过程中发现超出上限
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { continue };
+            let mut request = [0; 1024];
+            let _ = stream.read(&mut request);
+            let _ = stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nConnection: close\r\n\r\n");
+            for _ in 0..64 {
+                if stream.write_all(&[b'x'; 1024]).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+
+    let mut crawler = Crawler::new(CrawlerConfig {
+        max_body_size: Some(4 << 10),
+        ..Default::default()
+    });
+    let error = crawler.crawl_url(&format!("http://{}/documentation/swiftui", addr), false).await.unwrap_err();
+    assert_eq!(adoc::crawler::failed_page("", &error).kind, FailureKind::TooLarge);
+}
+
+#[tokio::test]
+async fn test_non_document_content_is_skipped() {
+    let base = common::serve_typed(&[
+        (
+            "/documentation/swiftui",
+            "text/html; charset=utf-8",
+            r#"<html><body><h1>SwiftUI</h1><article>
+                <a href="/developer.apple.com/guide.pdf">PDF</a>
+                <a href="/developer.apple.com/view.json">JSON</a>
+            </article></body></html>"#,
+        ),
+        ("/developer.apple.com/guide.pdf", "application/pdf", "%PDF-1.7 <h1>garbage</h1>"),
+        (
+            "/developer.apple.com/view.json",
+            "application/json",
+            r#"{"metadata": {"title": "View"}, "abstract": [{"type": "text", "text": "A view."}]}"#,
+        ),
+    ]);
+
+    let mut crawler = Crawler::new(CrawlerConfig::default());
+    let (pages, report) = crawler.crawl_url(&format!("{}/documentation/swiftui", base), true).await.unwrap();
+    let titles: Vec<&str> = pages.iter().map(|page| page.title.as_str()).collect();
+    assert_eq!(titles, ["SwiftUI", "View"]);
+    assert_eq!(pages[1].content, "A view.");
+    assert_eq!(report.pages_skipped, 1);
+    assert_eq!(report.pages_failed, 0);
+}
+
+#[tokio::test]
+async fn test_max_retries_limits_attempts() {
+    // 绑定后立即释放的端口，连接会被拒绝
+    let closed = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+    let start_page = format!(
+        r#"<html><body><h1>SwiftUI</h1><article>
+            <a href="http://{}/developer.apple.com/unreachable">Unreachable</a>
+        </article></body></html>"#,
+        closed
+    );
+    let base = common::serve(&[("/documentation/swiftui", &start_page)]);
+
+    for max_retries in [0, 2] {
+        let mut crawler = Crawler::new(CrawlerConfig {
+            max_retries,
+            ..Default::default()
+        });
+        let (_, report) = crawler.crawl_url(&format!("{}/documentation/swiftui", base), true).await.unwrap();
+        assert_eq!(report.pages_failed, 1);
+        assert_eq!(report.failures[0].kind, FailureKind::Connect);
+        assert_eq!(report.retries, max_retries);
+    }
+}
+
+#[tokio::test]
+async fn test_gone_pages_are_counted_not_failed() {
+    let base = common::serve_status(&[
+        (
+            "/documentation/swiftui",
+            "200 OK",
+            r#"<html><body><h1>SwiftUI</h1><article>
+                <a href="/developer.apple.com/missing">Missing</a>
+                <a href="/developer.apple.com/removed">Removed</a>
+            </article></body></html>"#,
+        ),
+        ("/developer.apple.com/removed", "410 Gone", "<html><body><h1>Gone</h1></body></html>"),
+    ]);
+    let seed = format!("{}/documentation/swiftui", base);
+
+    let mut crawler = Crawler::new(CrawlerConfig::default());
+    let (pages, report) = crawler.crawl_url(&seed, true).await.unwrap();
+    assert_eq!(pages.len(), 1);
+    assert_eq!(report.pages_gone, 2);
+    assert_eq!(report.pages_failed, 0);
+
+    let mut crawler = Crawler::new(CrawlerConfig::default());
+    crawler.set_tombstones(true);
+    let (pages, _) = crawler.crawl_url(&seed, true).await.unwrap();
+    let mut tombstones: Vec<(&str, Option<u16>)> =
+        pages[1..].iter().map(|page| (page.url.as_str(), page.status)).collect();
+    tombstones.sort();
+    assert_eq!(
+        tombstones,
+        [
+            (format!("{}/developer.apple.com/missing", base).as_str(), Some(404)),
+            (format!("{}/developer.apple.com/removed", base).as_str(), Some(410)),
+        ]
+    );
+    let json = serde_json::to_value(&pages[1]).unwrap();
+    assert_eq!(json["status"], pages[1].status.unwrap());
+    assert!(serde_json::to_value(&pages[0]).unwrap().get("status").is_none());
+
+    // 起始页面失效时不报错，只计数
+    let mut crawler = Crawler::new(CrawlerConfig::default());
+    let (pages, report) = crawler.crawl_url(&format!("{}/documentation/missing", base), true).await.unwrap();
+    assert!(pages.is_empty());
+    assert_eq!(report.pages_gone, 1);
+}
+
+#[tokio::test]
+async fn test_ip_family_restricts_connections() {
+    use adoc::crawler::IpFamily;
+
+    let base = common::serve(&[("/documentation/swiftui", "<html><body><h1>SwiftUI</h1></body></html>")]);
+    let seed = format!("{}/documentation/swiftui", base);
+    let crawler = |ip_family| {
+        Crawler::new(CrawlerConfig {
+            max_retries: 0,
+            ip_family,
+            ..Default::default()
+        })
+    };
+
+    let (pages, _) = crawler(IpFamily::V4).crawl_url(&seed, false).await.unwrap();
+    assert_eq!(pages.len(), 1);
+    // 服务只监听 127.0.0.1，只用 IPv6 时没有可用的地址
+    let error = crawler(IpFamily::V6).crawl_url(&seed, false).await.unwrap_err();
+    assert_eq!(adoc::crawler::failed_page(&seed, &error).kind, FailureKind::Connect);
+}
+
+#[tokio::test]
+async fn test_http_version_is_pinned() {
+    use adoc::crawler::HttpVersion;
+
+    let base = common::serve(&[("/documentation/swiftui", "<html><body><h1>SwiftUI</h1></body></html>")]);
+    let seed = format!("{}/documentation/swiftui", base);
+    let crawler = |http_version| {
+        Crawler::new(CrawlerConfig {
+            max_retries: 0,
+            http_version,
+            ..Default::default()
+        })
+    };
+
+    let (pages, _) = crawler(HttpVersion::Http1).crawl_url(&seed, false).await.unwrap();
+    assert_eq!(pages.len(), 1);
+    // 测试服务只支持 HTTP/1.1，直接使用 HTTP/2 时无法完成请求
+    assert!(crawler(HttpVersion::Http2).crawl_url(&seed, false).await.is_err());
+}
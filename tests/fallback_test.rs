@@ -0,0 +1,36 @@
+mod common;
+
+use adoc::crawler::{Crawler, CrawlerConfig};
+
+const SHELL: &str = r#"<html><body><noscript>This page requires JavaScript.</noscript><div id="app"></div></body></html>"#;
+
+#[tokio::test]
+async fn test_empty_shell_falls_back_to_json() {
+    let base = common::serve_typed(&[
+        ("/documentation/swiftui/view", "text/html", SHELL),
+        (
+            "/tutorials/data/documentation/swiftui/view.json",
+            "application/json",
+            r#"{
+                "metadata": { "title": "View" },
+                "abstract": [ { "type": "text", "text": "A type that represents part of your app's user interface." } ]
+            }"#,
+        ),
+        ("/documentation/swiftui/text", "text/html", SHELL),
+    ]);
+
+    let mut crawler = Crawler::new(CrawlerConfig::default());
+    let url = format!("{}/documentation/swiftui/view", base);
+    let (pages, report) = crawler.crawl_url(&url, false).await.unwrap();
+    assert_eq!(pages[0].url, url);
+    assert_eq!(pages[0].title, "View");
+    assert_eq!(pages[0].content, "A type that represents part of your app's user interface.");
+    assert_eq!(report.json_fallbacks, [url]);
+
+    // 没有 JSON 数据时保留原页面，不算失败
+    let url = format!("{}/documentation/swiftui/text", base);
+    let (pages, report) = crawler.crawl_url(&url, false).await.unwrap();
+    assert!(pages[0].is_empty_shell());
+    assert!(report.json_fallbacks.is_empty());
+    assert_eq!(report.pages_failed, 0);
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use adoc::ffi::{adoc_crawl, adoc_last_error, adoc_string_free};
+use std::ffi::{CStr, CString};
+
+#[test]
+fn test_ffi_crawl() {
+    let base = common::serve(&[(
+        "/documentation/swift",
+        "<html><body><h1>Swift</h1><article>Build apps using a powerful open language.</article></body></html>",
+    )]);
+
+    let url = CString::new(format!("{}/documentation/swift", base)).unwrap();
+    let options = CString::new(r#"{"timeout_secs": 10}"#).unwrap();
+
+    unsafe {
+        let json = adoc_crawl(url.as_ptr(), options.as_ptr());
+        assert!(!json.is_null());
+        let pages: serde_json::Value =
+            serde_json::from_str(CStr::from_ptr(json).to_str().unwrap()).unwrap();
+        adoc_string_free(json);
+
+        assert_eq!(pages[0]["title"], "Swift");
+    }
+}
+
+#[test]
+fn test_ffi_zero_concurrency() {
+    let base = common::serve(&[(
+        "/documentation/swift",
+        "<html><body><h1>Swift</h1><article>Build apps using a powerful open language.</article></body></html>",
+    )]);
+
+    let url = CString::new(format!("{}/documentation/swift", base)).unwrap();
+    let options = CString::new(r#"{"recursive": true, "concurrency": 0, "timeout_secs": 10}"#).unwrap();
+
+    unsafe {
+        let json = adoc_crawl(url.as_ptr(), options.as_ptr());
+        assert!(!json.is_null());
+        adoc_string_free(json);
+    }
+}
+
+#[test]
+fn test_ffi_invalid_options() {
+    let url = CString::new("https://developer.apple.com/documentation/swift").unwrap();
+    let options = CString::new("not json").unwrap();
+
+    unsafe {
+        let json = adoc_crawl(url.as_ptr(), options.as_ptr());
+        assert!(json.is_null());
+
+        let error = adoc_last_error();
+        assert!(!error.is_null());
+        adoc_string_free(error);
+    }
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use adoc::find::fuzzy_find;
+
+#[test]
+fn test_fuzzy_find_titles_and_symbols() {
+    let pages = vec![
+        common::page(&common::doc_url("swiftui/text"), "Text", ""),
+        common::page(&common::doc_url("swiftui/scrollview"), "ScrollView", ""),
+        common::page(&common::doc_url("swiftui/scroll-views"), "Scroll views", ""),
+        common::page(&common::doc_url("swiftui/view/scrolltargetbehavior(_:)"), "Configuring scroll behavior", ""),
+    ];
+
+    let matches = fuzzy_find(pages.clone(), "scrollview", 10);
+    assert_eq!(matches[0].page.title, "ScrollView");
+    assert!(matches.iter().all(|found| found.page.title != "Text"));
+
+    // 符号名来自 URL 的最后一段
+    let matches = fuzzy_find(pages, "scrolltargetbeh", 1);
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].page.title, "Configuring scroll behavior");
+}
+
+#[test]
+fn test_suggest_frameworks() {
+    use adoc::find::suggest_frameworks;
+
+    assert_eq!(suggest_frameworks("SwfitUI", 3)[0], "SwiftUI");
+    assert_eq!(suggest_frameworks("uikt", 3)[0], "UIKit");
+    assert!(suggest_frameworks("coredata", 3).contains(&"CoreData"));
+    assert!(suggest_frameworks("zzzzzzzz", 3).is_empty());
+}
+
+#[test]
+fn test_no_search_results_message() {
+    use adoc::crawler::NoSearchResults;
+
+    adoc::i18n::set_lang(adoc::i18n::Lang::En);
+    let error = NoSearchResults::new("SwfitUI");
+    assert!(error.to_string().starts_with("No documentation found for \"SwfitUI\". Did you mean: SwiftUI"));
+}
+
+#[test]
+fn test_search_page_urls() {
+    use adoc::crawler::Crawler;
+
+    assert_eq!(Crawler::search_url("UIKit"), "https://developer.apple.com/search/index.php?q=UIKit");
+    assert_eq!(Crawler::search_page_url("UIKit", 1), Crawler::search_url("UIKit"));
+    assert_eq!(
+        Crawler::search_page_url("Core Data", 3),
+        "https://developer.apple.com/search/index.php?q=Core%20Data&page=3"
+    );
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use adoc::extract::DocPage;
+use adoc::glossary::{build_glossary, save_glossary, to_csv, write_markdown, GlossaryEntry};
+
+fn pages() -> Vec<DocPage> {
+    vec![
+        common::page(
+            &common::doc_url("swiftui/text"),
+            "Text",
+            "A view that displays one or more lines of read-only text.\n\
+             @frozen struct Text\niOS 13.0+\nOverview\nA text view draws a string.",
+        ),
+        common::page(
+            &common::doc_url("swiftui/view/padding(_:_:)"),
+            "padding(_:_:)",
+            "nonisolated func padding(_ edges: Edge.Set = .all, _ length: CGFloat? = nil) -> some View\n\
+             Deprecated\nAdds an equal padding amount to specific edges, of this view.",
+        ),
+        common::page(&common::doc_url("swiftui"), "SwiftUI", "Declare the user interface and behavior for your app."),
+        // 文章不是符号，不列入术语表
+        common::page(
+            &common::doc_url("swiftui/declaring-a-custom-view"),
+            "Declaring a custom view",
+            "Define views and assemble them.",
+        ),
+    ]
+}
+
+#[test]
+fn test_build_glossary() {
+    let glossary = build_glossary(&pages());
+    let summary: Vec<(&str, &str, &str)> =
+        glossary.iter().map(|entry| (entry.title.as_str(), entry.kind.as_str(), entry.summary.as_str())).collect();
+    assert_eq!(
+        summary,
+        [
+            ("padding(_:_:)", "func", "Adds an equal padding amount to specific edges, of this view."),
+            ("SwiftUI", "framework", "Declare the user interface and behavior for your app."),
+            ("Text", "struct", "A view that displays one or more lines of read-only text."),
+        ]
+    );
+    assert_eq!(glossary[2].url, "https://developer.apple.com/documentation/swiftui/text");
+}
+
+#[test]
+fn test_glossary_artifacts() {
+    let glossary = build_glossary(&pages());
+    let csv = to_csv(&glossary);
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "title,kind,abstract,url");
+    assert_eq!(
+        lines[1],
+        "padding(_:_:),func,\"Adds an equal padding amount to specific edges, of this view.\",\
+         https://developer.apple.com/documentation/swiftui/view/padding(_:_:)"
+    );
+
+    let dir = tempfile::tempdir().unwrap();
+    let json = dir.path().join("glossary.json");
+    save_glossary(&glossary, &json).unwrap();
+    let loaded: Vec<GlossaryEntry> = serde_json::from_str(&std::fs::read_to_string(&json).unwrap()).unwrap();
+    assert_eq!(loaded, glossary);
+    assert!(std::fs::read_to_string(&json).unwrap().contains("\"abstract\""));
+    let csv_path = dir.path().join("glossary.CSV");
+    save_glossary(&glossary, &csv_path).unwrap();
+    assert_eq!(std::fs::read_to_string(&csv_path).unwrap(), csv);
+
+    let mut markdown = Vec::new();
+    write_markdown(&glossary, &mut markdown).unwrap();
+    let markdown = String::from_utf8(markdown).unwrap();
+    assert!(markdown.contains(
+        "| [Text](https://developer.apple.com/documentation/swiftui/text) | struct | \
+         A view that displays one or more lines of read-only text. |"
+    ));
+}
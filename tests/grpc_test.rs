@@ -0,0 +1,47 @@
+#![cfg(feature = "grpc")]
+
+mod common;
+
+use adoc::crawler::CrawlerConfig;
+use adoc::grpc::proto::doc_crawler_client::DocCrawlerClient;
+use adoc::grpc::proto::{CrawlRequest, GetPageRequest};
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_grpc_crawl_and_get_page() {
+    let base = common::serve(&[(
+        "/documentation/swift",
+        "<html><body><h1>Swift</h1><article>Build apps.</article></body></html>",
+    )]);
+
+    let addr = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+    let config = CrawlerConfig {
+        max_retries: 3,
+        concurrency: 2,
+        timeout: Duration::from_secs(10),
+        ..Default::default()
+    };
+    tokio::spawn(adoc::grpc::serve(config, addr));
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let mut client = DocCrawlerClient::connect(format!("http://{}", addr)).await.unwrap();
+    let url = format!("{}/documentation/swift", base);
+
+    let mut stream = client
+        .crawl(CrawlRequest { url: url.clone(), recursive: false })
+        .await
+        .unwrap()
+        .into_inner();
+    let page = stream.message().await.unwrap().unwrap();
+    assert_eq!(page.title, "Swift");
+    assert!(stream.message().await.unwrap().is_none());
+
+    let page = client.get_page(GetPageRequest { url }).await.unwrap().into_inner();
+    assert_eq!(page.content, "Build apps.");
+
+    let status = client
+        .get_page(GetPageRequest { url: "not a url".to_string() })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+}
@@ -0,0 +1,15 @@
+use adoc::hash::{fnv1a, sha256_hex, FNV_OFFSET};
+
+#[test]
+fn test_sha256_hex() {
+    assert_eq!(sha256_hex(b""), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+    assert_eq!(sha256_hex(b"abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+}
+
+#[test]
+fn test_fnv1a() {
+    assert_eq!(fnv1a(b"", FNV_OFFSET), FNV_OFFSET);
+    assert_eq!(fnv1a(b"a", FNV_OFFSET), 0xaf63dc4c8601ec8c);
+    assert_eq!(fnv1a(b"foobar", FNV_OFFSET), 0x85944171f73967e8);
+    assert_ne!(fnv1a(b"foobar", 1), fnv1a(b"foobar", FNV_OFFSET));
+}
@@ -0,0 +1,124 @@
+use adoc::extract::{extract_html, extract_json, json_data_url};
+use adoc::hig::is_hig_url;
+
+const BUTTONS: &str = "https://developer.apple.com/design/human-interface-guidelines/buttons";
+
+#[test]
+fn test_is_hig_url() {
+    assert!(is_hig_url(BUTTONS));
+    assert!(!is_hig_url("https://developer.apple.com/documentation/swiftui/button"));
+    assert!(!is_hig_url("https://example.com/design/human-interface-guidelines/buttons"));
+    assert_eq!(
+        json_data_url(BUTTONS).as_deref(),
+        Some("https://developer.apple.com/tutorials/data/design/human-interface-guidelines/buttons.json")
+    );
+}
+
+#[test]
+fn test_extract_hig_html() {
+    let html = r#"<html><body>
+        <nav><a href="/design/human-interface-guidelines">Human Interface Guidelines</a></nav>
+        <main>
+            <h1>Buttons</h1>
+            <div class="abstract"><p>A button initiates an instantaneous action.</p></div>
+            <figure>
+                <picture><img src="/design/images/buttons-intro.png" alt="A screenshot of several buttons."></picture>
+                <figcaption>Buttons in a toolbar.</figcaption>
+            </figure>
+            <h2>Best practices</h2>
+            <ul>
+                <li><p>Make buttons easy to identify.</p></li>
+                <li>Always include a press state.</li>
+            </ul>
+            <img src="/design/icons/chevron.svg" alt="">
+            <h2>Platform considerations</h2>
+            <p>No additional considerations for tvOS or visionOS.</p>
+            <h3>iOS, iPadOS</h3>
+            <p>Configure a button to display an activity indicator.</p>
+            <h3>macOS</h3>
+            <p>Use a push button for a primary action.</p>
+            <h2>Resources</h2>
+            <p>Related: <a href="/design/human-interface-guidelines/menus">Menus</a>, watchOS apps.</p>
+        </main>
+    </body></html>"#;
+    let page = extract_html(BUTTONS, html).unwrap();
+    assert_eq!(page.title, "Buttons");
+    let lines: Vec<&str> = page.content.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "Platforms: iOS, iPadOS, macOS, tvOS, visionOS",
+            "A button initiates an instantaneous action.",
+            "[Figure: A screenshot of several buttons.] Buttons in a toolbar. \
+             (https://developer.apple.com/design/images/buttons-intro.png)",
+            "## Best practices",
+            "- Make buttons easy to identify.",
+            "- Always include a press state.",
+            "## Platform considerations",
+            "No additional considerations for tvOS or visionOS.",
+            "### iOS, iPadOS",
+            "Configure a button to display an activity indicator.",
+            "### macOS",
+            "Use a push button for a primary action.",
+            "## Resources",
+            "Related: Menus, watchOS apps.",
+        ]
+    );
+    assert_eq!(
+        page.link_urls().collect::<Vec<_>>(),
+        [
+            "https://developer.apple.com/design/human-interface-guidelines",
+            "https://developer.apple.com/design/human-interface-guidelines/menus",
+        ]
+    );
+}
+
+#[test]
+fn test_extract_hig_json() {
+    let json = r#"{
+        "metadata": {"title": "Color"},
+        "abstract": [{"type": "text", "text": "Judicious use of color can enhance communication."}],
+        "primaryContentSections": [{"kind": "content", "content": [
+            {"type": "row", "columns": [
+                {"size": 1, "content": [{"type": "image", "identifier": "color-intro.png",
+                    "metadata": {"abstract": [{"type": "text", "text": "System colors."}]}}]}
+            ]},
+            {"type": "heading", "level": 2, "text": "Best practices"},
+            {"type": "unorderedList", "items": [
+                {"content": [{"type": "paragraph", "inlineContent": [
+                    {"type": "text", "text": "Avoid using the same color to mean different things. See "},
+                    {"type": "reference", "identifier": "doc://hig/dark-mode"}
+                ]}]}
+            ]},
+            {"type": "heading", "level": 2, "text": "Platform considerations"},
+            {"type": "heading", "level": 3, "text": "watchOS"},
+            {"type": "paragraph", "inlineContent": [{"type": "text", "text": "Use background color to convey meaning."}]}
+        ]}],
+        "references": {
+            "color-intro.png": {"type": "image", "alt": "A palette of system colors.",
+                "variants": [{"url": "https://docs-assets.developer.apple.com/color-intro.png"}]},
+            "doc://hig/dark-mode": {"type": "topic", "title": "Dark Mode",
+                "url": "/design/human-interface-guidelines/dark-mode"}
+        }
+    }"#;
+    let page = extract_json("https://developer.apple.com/design/human-interface-guidelines/color", json).unwrap();
+    assert_eq!(page.title, "Color");
+    let lines: Vec<&str> = page.content.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "Platforms: watchOS",
+            "Judicious use of color can enhance communication.",
+            "[Figure: A palette of system colors.] System colors. (https://docs-assets.developer.apple.com/color-intro.png)",
+            "## Best practices",
+            "- Avoid using the same color to mean different things. See Dark Mode",
+            "## Platform considerations",
+            "### watchOS",
+            "Use background color to convey meaning.",
+        ]
+    );
+    assert_eq!(
+        page.link_urls().collect::<Vec<_>>(),
+        ["https://developer.apple.com/design/human-interface-guidelines/dark-mode"]
+    );
+}
@@ -0,0 +1,45 @@
+mod common;
+
+use adoc::cache::ResponseCache;
+use adoc::crawler::{Crawler, CrawlerConfig};
+use std::sync::Arc;
+
+const PAGE: &str = "<html><body><h1>View</h1><article><p>A type that represents part of your app.</p></article></body></html>";
+
+#[tokio::test]
+async fn test_http_meta() {
+    let base = common::serve(&[("/documentation/swiftui/view", PAGE)]);
+    let url = format!("{}/documentation/swiftui/view", base);
+
+    // 默认不记录
+    let mut crawler = Crawler::new(CrawlerConfig::default());
+    let (pages, _) = crawler.crawl_url(&url, false).await.unwrap();
+    assert!(pages[0].http.is_none());
+    assert!(!serde_json::to_string(&pages[0]).unwrap().contains("\"http\""));
+
+    let dir = tempfile::tempdir().unwrap();
+    let config = |offline: bool| CrawlerConfig {
+        http_meta: true,
+        cache: Some(Arc::new(ResponseCache::open(dir.path(), offline).unwrap())),
+        ..Default::default()
+    };
+    let mut crawler = Crawler::new(config(false));
+    let (pages, _) = crawler.crawl_url(&url, false).await.unwrap();
+    let http = pages[0].http.clone().unwrap();
+    assert_eq!(http.status_code, 200);
+    assert_eq!(http.content_length, PAGE.len() as u64);
+    assert_eq!(http.content_type.as_deref(), Some("text/html; charset=utf-8"));
+    // RFC 3339，UTC
+    let parse = |at: &str| time::OffsetDateTime::parse(at, &time::format_description::well_known::Rfc3339).unwrap();
+    let fetched_at = parse(&http.fetched_at);
+    assert!(http.fetched_at.ends_with('Z'));
+
+    // 从缓存读取时保留当初下载的时间（缓存精确到秒）
+    let mut crawler = Crawler::new(config(true));
+    let (pages, _) = crawler.crawl_url(&url, false).await.unwrap();
+    let cached = pages[0].http.clone().unwrap();
+    assert_eq!(cached.status_code, 200);
+    assert_eq!(cached.content_length, http.content_length);
+    assert_eq!(cached.content_type, http.content_type);
+    assert!((parse(&cached.fetched_at) - fetched_at).abs() <= time::Duration::seconds(1));
+}
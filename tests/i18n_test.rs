@@ -0,0 +1,37 @@
+mod common;
+
+use adoc::diff::{diff_pages, DiffFormat};
+use adoc::i18n::{set_lang, Lang};
+use adoc::output::{save_results, OutputFormat};
+
+// 语言是进程级设置，切换语言的断言放在同一个测试中
+#[test]
+fn test_switch_lang() {
+    let old = vec![
+        common::page(&common::doc_url("swiftui/text"), "text", "A view."),
+        common::page(&common::doc_url("swiftui/list"), "list", "A container."),
+    ];
+    let new = vec![
+        common::page(&common::doc_url("swiftui/text"), "text", "A view that displays text."),
+        common::page(&common::doc_url("swiftui/grid"), "grid", "A grid."),
+    ];
+    let diff = diff_pages(&old, &new);
+
+    assert_eq!(diff.summary(), "新增 1 个页面，删除 1 个页面，变更 1 个页面");
+
+    set_lang(Lang::En);
+    assert_eq!(diff.summary(), "1 pages added, 1 removed, 1 changed");
+    let markdown = diff.render(DiffFormat::Markdown).unwrap();
+    assert!(markdown.starts_with("# Documentation changes"));
+    assert!(markdown.contains("## Added pages"));
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("docs.md");
+    save_results(&new, &path, OutputFormat::Markdown).unwrap();
+    let content = std::fs::read_to_string(path).unwrap();
+    assert!(content.starts_with("# Apple Developer Documentation"));
+    assert!(!content.contains("原始链接"));
+
+    set_lang(Lang::Zh);
+    assert!(diff.render(DiffFormat::Markdown).unwrap().starts_with("# 文档变更"));
+}
@@ -0,0 +1,35 @@
+mod common;
+
+use adoc::index::{build_index, search};
+
+#[test]
+fn test_build_and_search_index() {
+    let dir = tempfile::tempdir().unwrap();
+    let pages = vec![
+        common::page(
+            "https://developer.apple.com/documentation/swiftui/view/scrolltargetbehavior(_:)",
+            "scrollTargetBehavior(_:)",
+            "Sets the scroll behavior of views scrollable in the provided axes.",
+        ),
+        common::page(
+            "https://developer.apple.com/documentation/uikit/uiview",
+            "UIView",
+            "An object that manages the content for a rectangular area on the screen.",
+        ),
+    ];
+
+    build_index(&pages, dir.path()).unwrap();
+
+    let hits = search(dir.path(), "scrollTargetBehavior", 10).unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].page.title, "scrollTargetBehavior(_:)");
+
+    // 层级字段来自 URL 路径
+    let hits = search(dir.path(), "hierarchy:uikit", 10).unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].page.url, "https://developer.apple.com/documentation/uikit/uiview");
+
+    // 重新构建会覆盖旧索引
+    build_index(&pages[..1], dir.path()).unwrap();
+    assert!(search(dir.path(), "UIView", 10).unwrap().is_empty());
+}
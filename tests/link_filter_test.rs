@@ -0,0 +1,42 @@
+mod common;
+
+use adoc::crawler::{Crawler, CrawlerConfig};
+use adoc::extract::LinkFilter;
+
+#[tokio::test]
+async fn test_allowed_domains_are_crawled() {
+    // 本地服务器以 127.0.0.1 访问，指向 localhost 的链接视为其他域名
+    let book = common::serve(&[("/swift/book", "<html><body><h1>The Swift Programming Language</h1></body></html>")]);
+    let start_page = format!(
+        r#"<html><body><h1>SwiftUI</h1><article>
+            <a href="{}/swift/book">The Swift Programming Language</a>
+        </article></body></html>"#,
+        book.replace("127.0.0.1", "localhost")
+    );
+    let base = common::serve(&[("/documentation/swiftui", &start_page)]);
+    let seed = format!("{}/documentation/swiftui", base);
+
+    let titles = |link_filter: LinkFilter| {
+        let seed = seed.clone();
+        async move {
+            let mut crawler = Crawler::new(CrawlerConfig {
+                link_filter,
+                ..Default::default()
+            });
+            let (pages, _) = crawler.crawl_url(&seed, true).await.unwrap();
+            pages.into_iter().map(|page| page.title).collect::<Vec<_>>()
+        }
+    };
+
+    assert_eq!(titles(LinkFilter::default()).await, ["SwiftUI"]);
+    let allowed = LinkFilter {
+        allowed_domains: vec!["localhost".to_string()],
+        ..Default::default()
+    };
+    assert_eq!(titles(allowed).await, ["SwiftUI", "The Swift Programming Language"]);
+    let external = LinkFilter {
+        allow_external: true,
+        ..Default::default()
+    };
+    assert_eq!(titles(external).await, ["SwiftUI", "The Swift Programming Language"]);
+}
@@ -0,0 +1,140 @@
+mod common;
+
+use adoc::crawler::{Crawler, CrawlerConfig};
+use adoc::extract::{extract_html, extract_json};
+use adoc::media::{format_duration, media_kind, parse_duration, Media, MediaKind};
+use adoc::output::{write_pages, OutputFormat};
+use adoc::sink::MediaDownloadSink;
+use std::time::Duration;
+
+#[test]
+fn test_parse_duration() {
+    assert_eq!(parse_duration("754"), Some(754));
+    assert_eq!(parse_duration("12:34"), Some(754));
+    assert_eq!(parse_duration("1:02:03"), Some(3723));
+    assert_eq!(parse_duration("PT12M34S"), Some(754));
+    assert_eq!(parse_duration("PT1H"), Some(3600));
+    assert_eq!(parse_duration("soon"), None);
+    assert_eq!(format_duration(754), "12:34");
+    assert_eq!(format_duration(3723), "1:02:03");
+    assert_eq!(media_kind("https://example.com/a/intro.MP4?dl=1"), Some(MediaKind::Video));
+    assert_eq!(media_kind("https://example.com/podcast.m4a"), Some(MediaKind::Audio));
+    assert_eq!(media_kind("https://example.com/documentation/swiftui"), None);
+}
+
+#[test]
+fn test_extract_session_media() {
+    let url = "https://developer.apple.com/videos/play/wwdc2023/10187/";
+    let html = r#"<html><head><meta itemprop="duration" content="PT26M9S"></head><body><main>
+        <h1>Meet SwiftData</h1>
+        <video src="https://devstreaming-cdn.apple.com/videos/wwdc/2023/10187/4/hls_vod_mvp.m3u8"></video>
+        <ul class="supplements">
+            <li><a href="https://devstreaming-cdn.apple.com/videos/wwdc/2023/10187/4/10187_hd.mp4?dl=1">HD Video</a></li>
+            <li><a href="https://devstreaming-cdn.apple.com/videos/wwdc/2023/10187/4/10187_sd.mp4?dl=1">SD Video</a></li>
+            <li><a href="/documentation/swiftdata">SwiftData</a></li>
+        </ul>
+    </main></body></html>"#;
+    let page = extract_html(url, html).unwrap();
+    let base = "https://devstreaming-cdn.apple.com/videos/wwdc/2023/10187/4";
+    assert_eq!(
+        page.media,
+        [
+            Media {
+                kind: MediaKind::Video,
+                title: "Meet SwiftData".to_string(),
+                duration: Some(1569),
+                url: format!("{}/hls_vod_mvp.m3u8", base),
+            },
+            Media {
+                kind: MediaKind::Video,
+                title: "Meet SwiftData (HD Video)".to_string(),
+                duration: Some(1569),
+                url: format!("{}/10187_hd.mp4?dl=1", base),
+            },
+            Media {
+                kind: MediaKind::Video,
+                title: "Meet SwiftData (SD Video)".to_string(),
+                duration: Some(1569),
+                url: format!("{}/10187_sd.mp4?dl=1", base),
+            },
+        ]
+    );
+
+    // 没有媒体的页面不记录，也不输出 media 字段
+    let page = extract_html(
+        "https://developer.apple.com/documentation/swiftui",
+        "<html><body><h1>SwiftUI</h1><article>Declare the user interface.</article></body></html>",
+    )
+    .unwrap();
+    assert!(page.media.is_empty());
+    assert!(!serde_json::to_string(&page).unwrap().contains("media"));
+}
+
+#[test]
+fn test_extract_docc_video_reference() {
+    let json = r#"{
+        "metadata": {"title": "Adding a video"},
+        "primaryContentSections": [{"kind": "content", "content": [
+            {"type": "video", "identifier": "intro.mp4", "poster": "intro-poster.png"}
+        ]}],
+        "references": {
+            "intro.mp4": {
+                "type": "video", "identifier": "intro.mp4", "alt": "A tour of the sample app.",
+                "variants": [{"traits": ["1x", "light"], "url": "/videos/intro~light.mp4"}],
+                "poster": "intro-poster.png"
+            },
+            "intro-poster.png": {
+                "type": "image", "identifier": "intro-poster.png",
+                "variants": [{"traits": ["1x", "light"], "url": "/images/intro-poster.png"}]
+            }
+        }
+    }"#;
+    let page = extract_json("https://developer.apple.com/documentation/sample/adding-a-video", json).unwrap();
+    assert_eq!(
+        page.media,
+        [Media {
+            kind: MediaKind::Video,
+            title: "A tour of the sample app.".to_string(),
+            duration: None,
+            url: "https://developer.apple.com/videos/intro~light.mp4".to_string(),
+        }]
+    );
+
+    let mut out = Vec::new();
+    write_pages([page].as_slice(), OutputFormat::Markdown, &mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains("- [A tour of the sample app.](https://developer.apple.com/videos/intro~light.mp4) (video)\n"));
+}
+
+#[tokio::test]
+async fn test_download_media() {
+    let base = common::serve_typed(&[
+        (
+            "/documentation/intro",
+            "text/html; charset=utf-8",
+            r#"<html><body><h1>Intro</h1><article>
+                <video title="Intro" data-duration="1:05"><source src="/media/intro.mp4"></video>
+                <a href="/media/intro.mp4">Download</a>
+                <video src="/media/stream.m3u8"></video>
+            </article></body></html>"#,
+        ),
+        ("/media/intro.mp4", "video/mp4", "not really a video"),
+    ]);
+    let dir = tempfile::tempdir().unwrap();
+    let mut crawler = Crawler::new(CrawlerConfig {
+        max_retries: 0,
+        timeout: Duration::from_secs(10),
+        ..Default::default()
+    });
+    crawler.add_sink(Box::new(MediaDownloadSink::create(dir.path(), Duration::from_secs(10)).unwrap()));
+
+    let (pages, _) = crawler.crawl_url(&format!("{}/documentation/intro", base), false).await.unwrap();
+    assert_eq!(pages[0].media.len(), 2);
+    assert_eq!(pages[0].media[0].title, "Intro");
+    assert_eq!(pages[0].media[0].duration, Some(65));
+    assert_eq!(std::fs::read_to_string(dir.path().join("intro.mp4")).unwrap(), "not really a video");
+    // HLS 播放列表不下载，也不留下未完成的临时文件
+    let mut files: Vec<_> = std::fs::read_dir(dir.path()).unwrap().map(|entry| entry.unwrap().file_name()).collect();
+    files.sort();
+    assert_eq!(files, ["intro.mp4"]);
+}
@@ -0,0 +1,29 @@
+mod common;
+
+use adoc::merge::merge_results;
+
+#[test]
+fn test_merge_keeps_newest_and_reports_conflicts() {
+    let text = "https://developer.apple.com/documentation/swiftui/text";
+    let list = "https://developer.apple.com/documentation/swiftui/list";
+    let label = "https://developer.apple.com/documentation/swiftui/label";
+
+    let merged = merge_results(vec![
+        ("old.json".to_string(), vec![common::page(text, "Text", "old"), common::page(list, "Text", "same")]),
+        (
+            "new.json".to_string(),
+            vec![common::page(&format!("{}/", text), "Text", "new"), common::page(list, "Text", "same")],
+        ),
+        ("other.json".to_string(), vec![common::page(label, "Text", "label")]),
+    ]);
+
+    assert_eq!(merged.pages.len(), 3);
+    assert_eq!(merged.duplicates, 2);
+    let text_page = merged.pages.iter().find(|p| p.url.starts_with(text)).unwrap();
+    assert_eq!(text_page.content, "new");
+
+    assert_eq!(merged.conflicts.len(), 1);
+    assert_eq!(merged.conflicts[0].url, text);
+    assert_eq!(merged.conflicts[0].kept, "new.json");
+    assert_eq!(merged.conflicts[0].replaced, vec!["old.json"]);
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use adoc::crawler::{Crawler, CrawlerConfig};
+use adoc::metrics::{serve_metrics, Metrics};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_crawl_records_metrics() {
+    // 链接需包含 developer.apple.com 才会被当作文档链接
+    let base = common::serve_status(&[
+        (
+            "/documentation/swiftui",
+            "200 OK",
+            r#"<html><body><h1>SwiftUI</h1><article>
+                <a href="/developer.apple.com/view">View</a>
+                <a href="/developer.apple.com/view">View</a>
+                <a href="/developer.apple.com/broken">Broken</a>
+                <a href="/developer.apple.com/removed">Removed</a>
+            </article></body></html>"#,
+        ),
+        ("/developer.apple.com/view", "200 OK", "<html><body><h1>View</h1><article>A view.</article></body></html>"),
+        ("/developer.apple.com/broken", "500 Internal Server Error", ""),
+        ("/developer.apple.com/removed", "404 Not Found", ""),
+    ]);
+
+    let metrics = Arc::new(Metrics::new());
+    let mut crawler = Crawler::new(CrawlerConfig {
+        max_retries: 2,
+        concurrency: 2,
+        timeout: Duration::from_secs(10),
+        ..Default::default()
+    });
+    crawler.set_metrics(metrics.clone());
+    crawler.crawl_url(&format!("{}/documentation/swiftui", base), true).await.unwrap();
+
+    let text = metrics.render();
+    for line in [
+        "adoc_pages_fetched_total 2",
+        "adoc_pages_skipped_total 1",
+        "adoc_pages_gone_total 1",
+        "adoc_pages_failed_total{kind=\"status\"} 1",
+        "adoc_fetch_duration_seconds_count 2",
+        "adoc_queue_depth 0",
+    ] {
+        assert!(text.lines().any(|l| l == line), "missing {:?} in:\n{}", line, text);
+    }
+    // 状态码错误不重试：2 个成功 + 1 个失效 + 1 个失败
+    assert!(text.lines().any(|l| l == "adoc_requests_total 4"), "{}", text);
+}
+
+#[tokio::test]
+async fn test_metrics_endpoint() {
+    let metrics = Arc::new(Metrics::new());
+    metrics.record_fetched(2048, Duration::from_millis(30), 0);
+    metrics.record_failed("timeout", 2);
+    let addr = serve_metrics(metrics, "127.0.0.1:0".parse().unwrap()).await.unwrap();
+
+    let response = reqwest::get(format!("http://{}/metrics", addr)).await.unwrap();
+    assert_eq!(response.status(), 200);
+    let text = response.text().await.unwrap();
+    assert!(text.contains("# TYPE adoc_fetch_duration_seconds histogram"));
+    assert!(text.contains("adoc_fetch_duration_seconds_bucket{le=\"0.05\"} 1"));
+    assert!(text.contains("adoc_fetch_duration_seconds_bucket{le=\"0.025\"} 0"));
+    assert!(text.contains("adoc_bytes_total 2048"));
+    assert!(text.contains("adoc_pages_failed_total{kind=\"timeout\"} 1"));
+    assert!(text.contains("adoc_requests_total 4"));
+
+    let missing = reqwest::get(format!("http://{}/other", addr)).await.unwrap();
+    assert_eq!(missing.status(), 404);
+}
@@ -0,0 +1,30 @@
+mod common;
+
+use adoc::diff::diff_pages;
+use adoc::notify::{notify, WebhookFormat};
+
+#[tokio::test]
+async fn test_webhook_posts_slack_summary_only_on_change() {
+    let (webhook, requests) = common::serve_recording(&[]);
+    let url = format!("{}/hooks/docs", webhook);
+
+    let old = vec![common::page(&common::doc_url("swiftui/text"), "text", "A view.")];
+    notify(&url, &diff_pages(&old, &old), WebhookFormat::Slack).await.unwrap();
+    assert!(requests.lock().unwrap().is_empty());
+
+    let new = vec![
+        common::page(&common::doc_url("swiftui/text"), "text", "A view.\nDeprecated"),
+        common::page(&common::doc_url("swiftui/grid"), "grid", "A grid."),
+    ];
+    notify(&url, &diff_pages(&old, &new), WebhookFormat::Slack).await.unwrap();
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/hooks/docs");
+    let body: serde_json::Value = serde_json::from_str(&requests[0].body).unwrap();
+    assert!(body["text"].as_str().unwrap().contains("新增 1 个页面"));
+    let blocks = body["blocks"].as_array().unwrap();
+    assert_eq!(blocks.len(), 3);
+    assert!(blocks[1]["text"]["text"].as_str().unwrap().contains("|grid>"));
+}
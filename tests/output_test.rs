@@ -0,0 +1,132 @@
+use adoc::extract::DocPage;
+use adoc::output::{load_results, save_results, write_pages, OutputFormat};
+
+#[test]
+fn test_convert_saved_results() {
+    let dir = tempfile::tempdir().unwrap();
+    let json = dir.path().join("docs.json");
+    let pages = vec![DocPage {
+        title: "Text".to_string(),
+        content: "A view that displays one or more lines of read-only text.".to_string(),
+        url: "https://developer.apple.com/documentation/swiftui/text".to_string(),
+        ..Default::default()
+    }];
+    save_results(&pages, &json, OutputFormat::Json).unwrap();
+
+    let loaded = load_results(&json).unwrap();
+    let markdown = dir.path().join("docs.md");
+    save_results(&loaded, &markdown, OutputFormat::Markdown).unwrap();
+    let content = std::fs::read_to_string(markdown).unwrap();
+    assert!(content.contains("Text"));
+    assert!(content.contains("read-only text"));
+
+    let sqlite = dir.path().join("docs.sqlite");
+    save_results(&loaded, &sqlite, OutputFormat::Sqlite).unwrap();
+    assert_eq!(load_results(&sqlite).unwrap()[0].url, pages[0].url);
+}
+
+#[test]
+fn test_alfred_script_filter() {
+    let mut pages = vec![
+        DocPage {
+            title: "Text".to_string(),
+            content: format!("A view that displays\none or more lines of read-only text. {}\n\nOverview", "x".repeat(200)),
+            url: "https://developer.apple.com/documentation/swiftui/text".to_string(),
+            ..Default::default()
+        },
+        DocPage {
+            title: "View".to_string(),
+            content: String::new(),
+            url: "https://developer.apple.com/documentation/swiftui/view".to_string(),
+            ..Default::default()
+        },
+    ];
+    let mut out = Vec::new();
+    write_pages(pages.as_slice(), OutputFormat::Alfred, &mut out).unwrap();
+    let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    let items = value["items"].as_array().unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0]["title"], "Text");
+    assert_eq!(items[0]["arg"], pages[0].url);
+    assert_eq!(items[0]["quicklookurl"], pages[0].url);
+    assert_eq!(items[0]["uid"], pages[0].id());
+    let subtitle = items[0]["subtitle"].as_str().unwrap();
+    assert!(subtitle.starts_with("A view that displays one or more lines"));
+    assert!(subtitle.ends_with('…'));
+    assert_eq!(items[1]["subtitle"], "swiftui › view");
+
+    pages.clear();
+    let mut out = Vec::new();
+    write_pages(pages.as_slice(), OutputFormat::Alfred, &mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), r#"{"items":[]}"#);
+}
+
+#[test]
+fn test_raycast_items() {
+    let page = |url: &str, title: &str, content: &str| DocPage {
+        title: title.to_string(),
+        content: content.to_string(),
+        url: url.to_string(),
+        ..Default::default()
+    };
+    let pages = vec![
+        page("https://developer.apple.com/documentation/swiftui", "SwiftUI", "Declare the user interface."),
+        page(
+            "https://developer.apple.com/documentation/swiftui/view",
+            "View",
+            "A type that represents part of your app’s user interface.\n@MainActor @preconcurrency protocol View",
+        ),
+        page(
+            "https://developer.apple.com/documentation/swiftui/text/init(_:)",
+            "init(_:)",
+            "Creates a text view.\nnonisolated init(_ content: String)",
+        ),
+        page(
+            "https://developer.apple.com/documentation/swiftui/app-organization",
+            "App organization",
+            "Define the entry point.",
+        ),
+    ];
+    let mut out = Vec::new();
+    write_pages(pages.as_slice(), OutputFormat::Raycast, &mut out).unwrap();
+    let items: Vec<serde_json::Value> = serde_json::from_slice(&out).unwrap();
+    assert_eq!(items.len(), 4);
+    let icons: Vec<&str> = items.iter().map(|item| item["icon"].as_str().unwrap()).collect();
+    assert_eq!(icons, vec!["framework.png", "protocol.png", "init.png", "doc.png"]);
+    assert_eq!(items[1]["title"], "View");
+    assert_eq!(items[1]["url"], pages[1].url);
+    assert_eq!(items[1]["id"], pages[1].id());
+    assert!(items[1]["subtitle"].as_str().unwrap().starts_with("A type that represents part"));
+    assert_eq!(items[1]["keywords"], serde_json::json!(["swiftui", "view", "protocol"]));
+    assert!(items[3]["kind"].is_null());
+}
+
+#[test]
+fn test_markdown_links_to_pages_in_output() {
+    let page = |title: &str, url: &str, related_links: Vec<String>| DocPage {
+        title: title.to_string(),
+        content: format!("{} content.", title),
+        url: url.to_string(),
+        related_links: related_links.into_iter().map(Into::into).collect(),
+        ..Default::default()
+    };
+    let pages = vec![
+        page(
+            "View",
+            "https://developer.apple.com/documentation/swiftui/view",
+            vec![
+                "https://developer.apple.com/documentation/swiftui/text/".to_string(),
+                "https://developer.apple.com/documentation/uikit/uiview".to_string(),
+            ],
+        ),
+        page("Text", "https://developer.apple.com/documentation/swiftui/text", vec![]),
+    ];
+    let mut out = Vec::new();
+    write_pages(pages.as_slice(), OutputFormat::Markdown, &mut out).unwrap();
+    let markdown = String::from_utf8(out).unwrap();
+    // 输出中的页面链接到文档内的锚点，其余保留原始链接
+    assert!(markdown.contains("- [Text](#doc-2)\n"));
+    assert!(markdown.contains(
+        "- [https://developer.apple.com/documentation/uikit/uiview](https://developer.apple.com/documentation/uikit/uiview)\n"
+    ));
+}
@@ -0,0 +1,39 @@
+mod common;
+
+use adoc::crawler::{Crawler, CrawlerConfig};
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_plan_lists_urls_without_fetching_them() {
+    let (base, requests) = common::serve_recording(&[(
+        "/documentation/swiftui",
+        r#"<html><body><h1>SwiftUI</h1><article>
+            <a href="https://developer.apple.com/documentation/swiftui/text">Text</a>
+            <a href="https://developer.apple.com/documentation/swiftui/list">List</a>
+            <a href="https://developer.apple.com/documentation/swiftui/text">Text</a>
+        </article></body></html>"#,
+    )]);
+    let seed = format!("{}/documentation/swiftui", base);
+
+    let mut crawler = Crawler::new(CrawlerConfig {
+        max_retries: 3,
+        concurrency: 2,
+        timeout: Duration::from_secs(10),
+        ..Default::default()
+    });
+
+    assert_eq!(crawler.plan_url(&seed, false).await.unwrap(), vec![seed.clone()]);
+    assert!(requests.lock().unwrap().is_empty());
+
+    let urls = crawler.plan_url(&seed, true).await.unwrap();
+    assert_eq!(
+        urls,
+        vec![
+            seed,
+            "https://developer.apple.com/documentation/swiftui/text".to_string(),
+            "https://developer.apple.com/documentation/swiftui/list".to_string(),
+        ]
+    );
+    // 只请求了起始页面
+    assert_eq!(requests.lock().unwrap().len(), 1);
+}
@@ -0,0 +1,203 @@
+mod common;
+
+use adoc::crawler::{Crawler, CrawlerConfig};
+use adoc::extract::{extract_html_for, extract_json_for, LinkFilter};
+use adoc::profile::SiteProfile;
+use std::sync::Arc;
+use std::time::Duration;
+
+const RUST_PROFILE: &str = r##"
+name = "rust-std"
+domains = ["doc.rust-lang.org/std"]
+search_url = "https://doc.rust-lang.org/std/?search={query}"
+search_page_param = "p"
+
+[selectors]
+title = "h1.main-heading"
+content = "#main-content"
+"##;
+
+#[test]
+fn test_builtin_apple_profile() {
+    let apple = SiteProfile::apple();
+    assert!(apple.is_site_link("https://developer.apple.com/documentation/swiftui"));
+    assert!(!apple.is_site_link("https://swift.org/documentation"));
+    assert_eq!(
+        apple.search_page_url("Core Data", 2).as_deref(),
+        Some("https://developer.apple.com/search/index.php?q=Core%20Data&page=2")
+    );
+    assert_eq!(
+        apple.json_data_url("https://developer.apple.com/documentation/swiftui/view").as_deref(),
+        Some("https://developer.apple.com/tutorials/data/documentation/swiftui/view.json")
+    );
+}
+
+#[test]
+fn test_parse_profile() {
+    let profile = SiteProfile::parse(RUST_PROFILE, "fallback").unwrap();
+    assert_eq!(profile.name, "rust-std");
+    assert_eq!(
+        profile.search_page_url("Vec", 1).as_deref(),
+        Some("https://doc.rust-lang.org/std/?search=Vec")
+    );
+    assert_eq!(
+        profile.search_page_url("Vec", 3).as_deref(),
+        Some("https://doc.rust-lang.org/std/?search=Vec&p=3")
+    );
+    assert_eq!(profile.json_data_url("https://doc.rust-lang.org/std/vec/struct.Vec.html"), None);
+
+    let html = r#"<html><body>
+        <h1>Crate std</h1><h1 class="main-heading">Struct Vec</h1>
+        <section id="main-content">A contiguous growable array type.</section>
+        <a href="/std/vec/fn.from_elem.html">from_elem</a>
+        <a href="https://developer.apple.com/documentation/swift/array">Array</a>
+    </body></html>"#;
+    let page =
+        extract_html_for(&profile, "https://doc.rust-lang.org/std/vec/struct.Vec.html", html, &LinkFilter::default())
+            .unwrap();
+    assert_eq!(page.title, "Struct Vec");
+    assert_eq!(page.content, "A contiguous growable array type.");
+    assert_eq!(page.link_urls().collect::<Vec<_>>(), ["https://doc.rust-lang.org/std/vec/fn.from_elem.html"]);
+}
+
+#[test]
+fn test_json_mapping() {
+    let profile = SiteProfile::parse(
+        r#"
+        domains = ["docs.example.com"]
+
+        [json]
+        title = "/page/title"
+        content = "/page/paragraphs"
+        links = "/page/links"
+        "#,
+        "example",
+    )
+    .unwrap();
+    assert_eq!(profile.name, "example");
+
+    let json = r#"{"page": {
+        "title": "Widgets",
+        "paragraphs": ["Widgets show  content.", "", "They update on a timeline."],
+        "links": ["/guides/timeline", "https://other.example.org/x", "/guides/timeline"]
+    }}"#;
+    let page =
+        extract_json_for(&profile, "https://docs.example.com/guides/widgets", json, &LinkFilter::default()).unwrap();
+    assert_eq!(page.title, "Widgets");
+    assert_eq!(page.content, "Widgets show content.\nThey update on a timeline.");
+    assert_eq!(page.link_urls().collect::<Vec<_>>(), ["https://docs.example.com/guides/timeline"]);
+}
+
+#[test]
+fn test_invalid_profiles() {
+    assert!(SiteProfile::parse("domains = []", "x").is_err());
+    assert!(SiteProfile::parse("domains = [\"a.com\"]\nsearch_url = \"https://a.com/search\"", "x").is_err());
+    assert!(SiteProfile::parse("domains = [\"a.com\"]\n[selectors]\ntitle = \"h1[\"", "x").is_err());
+    assert!(SiteProfile::parse("domains = [\"a.com\"]\nunknown = 1", "x").is_err());
+}
+
+#[tokio::test]
+async fn test_crawl_with_site_profile() {
+    let (base, log) = common::serve_recording(&[
+        (
+            "/guide/index.html",
+            r#"<html><body><div class="title">Guide</div><main>
+                <a href="/guide/install.html">Install</a>
+                <a href="/blog/news.html">News</a>
+            </main></body></html>"#,
+        ),
+        (
+            "/guide/install.html",
+            r#"<html><body><div class="title">Install</div><main>Run the installer.</main></body></html>"#,
+        ),
+        ("/search", r#"<html><body><a href="/guide/install.html">Install</a></body></html>"#),
+    ]);
+    let profile = SiteProfile::parse(
+        &format!(
+            r#"
+            domains = ["{base}/guide/"]
+            search_url = "{base}/search?q={{query}}"
+
+            [selectors]
+            title = ".title"
+            content = "main"
+            "#
+        ),
+        "guide",
+    )
+    .unwrap();
+
+    let profile = Arc::new(profile);
+    let new_crawler = || {
+        Crawler::new(CrawlerConfig {
+            max_retries: 1,
+            timeout: Duration::from_secs(10),
+            profile: profile.clone(),
+            ..Default::default()
+        })
+    };
+    let mut crawler = new_crawler();
+    let (pages, _) = crawler.crawl_url(&format!("{}/guide/index.html", base), true).await.unwrap();
+    let titles: Vec<&str> = pages.iter().map(|page| page.title.as_str()).collect();
+    assert_eq!(titles, ["Guide", "Install"]);
+    assert_eq!(pages[1].content, "Run the installer.");
+
+    let results = crawler.search("install").await.unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].url, format!("{}/guide/install.html", base));
+
+    // 关键字爬取从搜索结果开始，搜索页本身只请求一次，不作为文档返回
+    let requests = log.lock().unwrap().len();
+    let (pages, _) = new_crawler().search_and_crawl("install", false).await.unwrap();
+    let titles: Vec<&str> = pages.iter().map(|page| page.title.as_str()).collect();
+    assert_eq!(titles, ["Install"]);
+    let paths: Vec<String> = log.lock().unwrap()[requests..].iter().map(|request| request.path.clone()).collect();
+    assert_eq!(paths, ["/search?q=install", "/guide/install.html"]);
+}
+
+#[tokio::test]
+async fn test_search_result_failure_does_not_abort() {
+    let base = common::serve_status(&[
+        ("/search", "200 OK", r#"<a href="/guide/broken.html">Broken</a><a href="/guide/install.html">Install</a>"#),
+        ("/guide/broken.html", "500 Internal Server Error", ""),
+        ("/guide/install.html", "200 OK", r#"<div class="title">Install</div><main>Run the installer.</main>"#),
+    ]);
+    let profile = SiteProfile::parse(
+        &format!(
+            r#"
+            domains = ["{base}/guide/"]
+            search_url = "{base}/search?q={{query}}"
+
+            [selectors]
+            title = ".title"
+            content = "main"
+            "#
+        ),
+        "guide",
+    )
+    .unwrap();
+    let mut crawler = Crawler::new(CrawlerConfig {
+        max_retries: 0,
+        timeout: Duration::from_secs(10),
+        profile: Arc::new(profile),
+        ..Default::default()
+    });
+
+    // 失败的结果记录在报告中，其余结果照常爬取
+    let (pages, report) = crawler.search_and_crawl("install", false).await.unwrap();
+    let titles: Vec<&str> = pages.iter().map(|page| page.title.as_str()).collect();
+    assert_eq!(titles, ["Install"]);
+    assert_eq!(report.pages_failed, 1);
+    assert_eq!(report.failures[0].url, format!("{}/guide/broken.html", base));
+}
+
+#[tokio::test]
+async fn test_search_without_search_url_fails() {
+    let profile = SiteProfile::parse("domains = [\"docs.example.com\"]", "example").unwrap();
+    let crawler = Crawler::new(CrawlerConfig {
+        profile: Arc::new(profile),
+        ..Default::default()
+    });
+    let error = crawler.search("widgets").await.unwrap_err();
+    assert!(error.to_string().contains("search_url"), "{}", error);
+}
@@ -0,0 +1,37 @@
+mod common;
+
+use adoc::crawler::{Crawler, CrawlerConfig};
+use adoc::progress::ProgressEvents;
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_progress_events_written_as_ndjson() {
+    let base = common::serve(&[(
+        "/documentation/swiftui",
+        "<html><body><h1>SwiftUI</h1><article>Declare the user interface.</article></body></html>",
+    )]);
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("progress.ndjson");
+
+    let mut crawler = Crawler::new(CrawlerConfig {
+        max_retries: 3,
+        concurrency: 2,
+        timeout: Duration::from_secs(10),
+        ..Default::default()
+    });
+    crawler.set_progress_events(ProgressEvents::file(&path).unwrap());
+    let url = format!("{}/documentation/swiftui", base);
+    crawler.crawl_url(&url, false).await.unwrap();
+
+    let events: Vec<serde_json::Value> = std::fs::read_to_string(&path)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let kinds: Vec<_> = events.iter().map(|event| event["event"].as_str().unwrap()).collect();
+    assert_eq!(kinds, vec!["queued", "fetching", "done"]);
+    assert_eq!(events[2]["url"], url.as_str());
+    assert_eq!(events[2]["completed"], 1);
+    assert_eq!(events[2]["total"], 1);
+    assert_eq!(events[2]["percent"], 100.0);
+}
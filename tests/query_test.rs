@@ -0,0 +1,38 @@
+mod common;
+
+use adoc::extract::DocPage;
+use adoc::query::{search_pages, Matcher};
+
+fn pages() -> Vec<DocPage> {
+    vec![
+        common::page(&common::doc_url("swiftui/list"), "List", "Push a NavigationStack to drill into rows."),
+        common::page(
+            &common::doc_url("swiftui/navigationstack"),
+            "NavigationStack",
+            "A view that displays a root view.",
+        ),
+        common::page(
+            &common::doc_url("swiftui/text"),
+            "Text",
+            "A view that displays one or more lines of read-only text.",
+        ),
+    ]
+}
+
+#[test]
+fn test_substring_search_ranks_titles_first() {
+    let results = search_pages(pages(), &Matcher::substring("navigationstack"), 10);
+
+    let titles: Vec<_> = results.iter().map(|page| page.title.as_str()).collect();
+    assert_eq!(titles, vec!["NavigationStack", "List"]);
+}
+
+#[test]
+fn test_regex_search_with_limit() {
+    let matcher = Matcher::regex(r"displays (a|one)").unwrap();
+    let results = search_pages(pages(), &matcher, 1);
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].title, "NavigationStack");
+    assert!(Matcher::regex("(").is_err());
+}
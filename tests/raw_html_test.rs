@@ -0,0 +1,58 @@
+mod common;
+
+use adoc::cache::ResponseCache;
+use adoc::crawler::{Crawler, CrawlerConfig};
+use adoc::raw::{RawEncoding, RawHtml};
+use std::sync::Arc;
+
+const PAGE: &str = "<html><body><h1>View</h1><article><p>A type that represents part of your app.</p></article></body></html>";
+
+#[test]
+fn test_raw_html_encoding() {
+    let plain = RawHtml::encode(PAGE, RawEncoding::Plain).unwrap();
+    assert_eq!(plain.data, PAGE);
+    let gzip = RawHtml::encode(PAGE, RawEncoding::Gzip).unwrap();
+    assert!(gzip.data.starts_with("H4sI"));
+    assert_eq!(gzip.decode().unwrap(), PAGE);
+    let json = serde_json::to_value(&gzip).unwrap();
+    assert_eq!(json["encoding"], "gzip");
+
+    let broken = RawHtml {
+        encoding: RawEncoding::Gzip,
+        data: "bm90IGd6aXA=".to_string(),
+    };
+    assert!(broken.decode().is_err());
+}
+
+#[tokio::test]
+async fn test_include_raw_html() {
+    let base = common::serve(&[("/documentation/swiftui/view", PAGE)]);
+    let url = format!("{}/documentation/swiftui/view", base);
+
+    // 默认不保存
+    let mut crawler = Crawler::new(CrawlerConfig::default());
+    let (pages, _) = crawler.crawl_url(&url, false).await.unwrap();
+    assert!(pages[0].raw_html.is_none());
+    assert!(!serde_json::to_string(&pages[0]).unwrap().contains("raw_html"));
+
+    let dir = tempfile::tempdir().unwrap();
+    let config = |encoding: RawEncoding, offline: bool| CrawlerConfig {
+        raw_html: Some(encoding),
+        cache: Some(Arc::new(ResponseCache::open(dir.path(), offline).unwrap())),
+        ..Default::default()
+    };
+    let mut crawler = Crawler::new(config(RawEncoding::Plain, false));
+    let (pages, _) = crawler.crawl_url(&url, false).await.unwrap();
+    let raw = pages[0].raw_html.clone().unwrap();
+    assert_eq!(raw.encoding, RawEncoding::Plain);
+    assert_eq!(raw.data, PAGE);
+    // 提取出的正文不受影响
+    assert!(pages[0].content.contains("A type that represents part of your app."));
+
+    // 离线模式下保存缓存中的正文
+    let mut crawler = Crawler::new(config(RawEncoding::Gzip, true));
+    let (pages, _) = crawler.crawl_url(&url, false).await.unwrap();
+    let raw = pages[0].raw_html.clone().unwrap();
+    assert_eq!(raw.encoding, RawEncoding::Gzip);
+    assert_eq!(raw.decode().unwrap(), PAGE);
+}
@@ -0,0 +1,127 @@
+use adoc::extract::{extract_json, DocPage};
+use adoc::output::{write_pages, OutputFormat};
+use serde_json::Value;
+
+const ENDPOINT: &str = r#"{
+    "metadata": {"title": "Read App Information"},
+    "abstract": [{"type": "text", "text": "Get information about a specific app."}],
+    "primaryContentSections": [
+        {"kind": "restEndpoint", "title": "URL", "tokens": [
+            {"kind": "method", "text": "GET"},
+            {"kind": "text", "text": " "},
+            {"kind": "baseURL", "text": "https://api.appstoreconnect.apple.com/"},
+            {"kind": "path", "text": "v1/apps/"},
+            {"kind": "parameter", "text": "{id}"}
+        ]},
+        {"kind": "restParameters", "title": "Path Parameters", "source": "path", "items": [
+            {"name": "id", "type": [{"kind": "text", "text": "string"}], "required": true,
+             "content": [{"type": "paragraph", "inlineContent": [{"type": "text", "text": "An opaque resource ID."}]}]}
+        ]},
+        {"kind": "restParameters", "title": "Query Parameters", "source": "query", "items": [
+            {"name": "fields[apps]", "type": [{"kind": "text", "text": "[string]"}]}
+        ]},
+        {"kind": "restResponses", "title": "Response Codes", "items": [
+            {"status": 200, "reason": "OK", "mimeType": "application/json",
+             "type": [{"kind": "typeIdentifier", "text": "AppResponse", "identifier": "doc://asc/AppResponse"}]},
+            {"status": 404, "reason": "Not Found", "mimeType": "application/json",
+             "type": [{"kind": "typeIdentifier", "text": "ErrorResponse"}],
+             "content": [{"type": "paragraph", "inlineContent": [{"type": "text", "text": "Not found error."}]}]}
+        ]}
+    ],
+    "references": {
+        "doc://asc/AppResponse": {"type": "topic", "title": "AppResponse",
+            "url": "/documentation/appstoreconnectapi/appresponse"}
+    }
+}"#;
+
+const OBJECT: &str = r#"{
+    "metadata": {"title": "AppResponse"},
+    "primaryContentSections": [
+        {"kind": "properties", "title": "Properties", "items": [
+            {"name": "data", "type": [{"kind": "typeIdentifier", "text": "App"}], "required": true},
+            {"name": "included", "type": [{"kind": "text", "text": "["}, {"kind": "typeIdentifier", "text": "Build"},
+                {"kind": "text", "text": "]"}],
+             "content": [{"type": "paragraph", "inlineContent": [{"type": "text", "text": "Related builds."}]}]}
+        ]}
+    ]
+}"#;
+
+fn pages() -> Vec<DocPage> {
+    vec![
+        extract_json("https://developer.apple.com/documentation/appstoreconnectapi/get-v1-apps-_id_", ENDPOINT)
+            .unwrap(),
+        extract_json("https://developer.apple.com/documentation/appstoreconnectapi/appresponse", OBJECT).unwrap(),
+        extract_json(
+            "https://developer.apple.com/documentation/swiftui/view",
+            r#"{"metadata": {"title": "View"}, "primaryContentSections": []}"#,
+        )
+        .unwrap(),
+    ]
+}
+
+#[test]
+fn test_extract_rest_endpoint() {
+    let pages = pages();
+    let rest = pages[0].rest.as_ref().unwrap();
+    assert_eq!(rest.method.as_deref(), Some("GET"));
+    assert_eq!(rest.base_url.as_deref(), Some("https://api.appstoreconnect.apple.com/"));
+    assert_eq!(rest.path.as_deref(), Some("v1/apps/{id}"));
+    assert_eq!(rest.parameters.len(), 2);
+    assert_eq!(rest.parameters[0].location.as_deref(), Some("path"));
+    assert!(rest.parameters[0].required);
+    assert_eq!(rest.parameters[1].type_name, "[string]");
+    let statuses: Vec<u16> = rest.responses.iter().map(|response| response.status).collect();
+    assert_eq!(statuses, [200, 404]);
+    assert_eq!(rest.responses[1].description, "Not found error.");
+
+    let lines: Vec<&str> = pages[0].content.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "Get information about a specific app.",
+            "GET https://api.appstoreconnect.apple.com/v1/apps/{id}",
+            "id (string, required): An opaque resource ID.",
+            "fields[apps] ([string])",
+            "200 OK: AppResponse",
+            "404 Not Found: ErrorResponse",
+        ]
+    );
+
+    let properties = &pages[1].rest.as_ref().unwrap().properties;
+    assert_eq!(properties[1].type_name, "[Build]");
+    assert!(pages[2].rest.is_none());
+
+    // 结构化字段随 JSON 结果保存，没有时不输出
+    let json = serde_json::to_value(&pages).unwrap();
+    assert_eq!(json[0]["rest"]["parameters"][0]["in"], "path");
+    assert!(json[2].get("rest").is_none());
+    let loaded: Vec<DocPage> = serde_json::from_value(json).unwrap();
+    assert_eq!(loaded[0].rest, pages[0].rest);
+}
+
+#[test]
+fn test_openapi_export() {
+    let mut out = Vec::new();
+    write_pages(pages().as_slice(), OutputFormat::OpenApi, &mut out).unwrap();
+    let doc: Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(doc["openapi"], "3.0.3");
+    assert_eq!(doc["info"]["title"], "appstoreconnectapi");
+    assert_eq!(doc["servers"][0]["url"], "https://api.appstoreconnect.apple.com");
+
+    let operation = &doc["paths"]["/v1/apps/{id}"]["get"];
+    assert_eq!(operation["summary"], "Read App Information");
+    assert_eq!(operation["operationId"], "get-v1-apps-_id_");
+    assert_eq!(operation["parameters"][0]["in"], "path");
+    assert_eq!(operation["parameters"][1]["schema"]["items"]["type"], "string");
+    assert_eq!(
+        operation["responses"]["200"]["content"]["application/json"]["schema"]["$ref"],
+        "#/components/schemas/AppResponse"
+    );
+    assert_eq!(operation["responses"]["404"]["description"], "Not found error.");
+
+    let schema = &doc["components"]["schemas"]["AppResponse"];
+    assert_eq!(schema["required"], serde_json::json!(["data"]));
+    assert_eq!(schema["properties"]["included"]["type"], "array");
+    assert_eq!(schema["properties"]["included"]["items"]["$ref"], "#/components/schemas/Build");
+    assert_eq!(schema["properties"]["included"]["description"], "Related builds.");
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use adoc::crawler::{Crawler, CrawlerConfig};
+use adoc::extract::extract_json;
+use adoc::sample::{readme_from_zip, SampleCode};
+use std::io::Write;
+use std::time::Duration;
+
+const PAGE: &str = "https://developer.apple.com/documentation/swiftui/food-truck-building-a-swiftui-multiplatform-app";
+
+fn sample_json(download: &str) -> String {
+    format!(
+        r#"{{
+            "metadata": {{
+                "title": "Food Truck: Building a SwiftUI multiplatform app",
+                "role": "sampleCode",
+                "platforms": [
+                    {{"name": "iOS", "introducedAt": "17.0", "beta": false}},
+                    {{"name": "macOS", "introducedAt": "14.0", "beta": false}},
+                    {{"name": "Xcode", "introducedAt": "15.0", "beta": false}}
+                ],
+                "modules": [{{"name": "SwiftUI"}}, {{"name": "WidgetKit"}}]
+            }},
+            "abstract": [
+                {{"type": "text", "text": "Create a single codebase and app target for "}},
+                {{"type": "codeVoice", "code": "Mac"}},
+                {{"type": "text", "text": ", iPad, and iPhone."}}
+            ],
+            "sampleCodeDownload": {{
+                "kind": "sampleDownload",
+                "action": {{"type": "reference", "identifier": "food-truck.zip", "isActive": true, "overridingTitle": "Download"}}
+            }},
+            "primaryContentSections": [{{"kind": "content", "content": [
+                {{"type": "heading", "level": 2, "text": "Overview"}},
+                {{"type": "paragraph", "inlineContent": [{{"type": "text", "text": "Manage a food truck."}}]}}
+            ]}}],
+            "references": {{
+                "food-truck.zip": {{"type": "download", "identifier": "food-truck.zip", "url": "{}"}}
+            }}
+        }}"#,
+        download
+    )
+}
+
+fn project_zip() -> Vec<u8> {
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = zip::write::SimpleFileOptions::default();
+    for (name, content) in [
+        ("__MACOSX/FoodTruck/._README.md", "resource fork"),
+        ("FoodTruck/Shared/README.md", "# Shared code"),
+        ("FoodTruck/README.md", "# Food Truck\n\nBuild the app with Xcode 15.\n"),
+        ("FoodTruck/App/App.swift", "@main struct FoodTruckApp: App {}"),
+    ] {
+        zip.start_file(name, options).unwrap();
+        zip.write_all(content.as_bytes()).unwrap();
+    }
+    zip.finish().unwrap().into_inner()
+}
+
+#[test]
+fn test_extract_sample_code() {
+    let page = extract_json(PAGE, &sample_json("https://docs-assets.developer.apple.com/published/FoodTruck.zip")).unwrap();
+    assert_eq!(
+        page.sample.as_deref(),
+        Some(&SampleCode {
+            description: "Create a single codebase and app target for Mac, iPad, and iPhone.".to_string(),
+            platforms: vec!["iOS 17.0+".to_string(), "macOS 14.0+".to_string()],
+            xcode: Some("15.0".to_string()),
+            frameworks: vec!["SwiftUI".to_string(), "WidgetKit".to_string()],
+            download_url: Some("https://docs-assets.developer.apple.com/published/FoodTruck.zip".to_string()),
+            readme: None,
+        })
+    );
+    let lines: Vec<&str> = page.content.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "Create a single codebase and app target for Mac, iPad, and iPhone.",
+            "Requires: iOS 17.0+, macOS 14.0+, Xcode 15.0+",
+            "Frameworks: SwiftUI, WidgetKit",
+            "Download: https://docs-assets.developer.apple.com/published/FoodTruck.zip",
+            "Overview",
+            "Manage a food truck.",
+        ]
+    );
+
+    // 其他文档页面没有 sample 字段
+    let page = extract_json(PAGE, r#"{"metadata": {"title": "View", "role": "symbol"}}"#).unwrap();
+    assert!(page.sample.is_none());
+}
+
+#[test]
+fn test_readme_from_zip() {
+    let readme = readme_from_zip(&project_zip()).unwrap();
+    assert_eq!(readme.as_deref(), Some("# Food Truck\n\nBuild the app with Xcode 15."));
+}
+
+#[tokio::test]
+async fn test_crawl_fetches_sample_readme() {
+    let zip = project_zip();
+    let json = sample_json("/downloads/FoodTruck.zip");
+    let base = common::serve_bytes(&[
+        ("/documentation/foodtruck", "application/json", json.as_bytes()),
+        ("/downloads/FoodTruck.zip", "application/zip", &zip),
+    ]);
+    let url = format!("{}/documentation/foodtruck", base);
+    let config = CrawlerConfig {
+        max_retries: 0,
+        timeout: Duration::from_secs(10),
+        ..Default::default()
+    };
+
+    let (pages, _) = Crawler::new(config.clone()).crawl_url(&url, false).await.unwrap();
+    assert_eq!(pages[0].sample.as_ref().unwrap().readme, None);
+
+    let mut crawler = Crawler::new(CrawlerConfig { sample_readme: true, ..config });
+    let (pages, report) = crawler.crawl_url(&url, false).await.unwrap();
+    let sample = pages[0].sample.as_ref().unwrap();
+    assert_eq!(sample.download_url.as_deref(), Some(format!("{}/downloads/FoodTruck.zip", base).as_str()));
+    assert_eq!(sample.readme.as_deref(), Some("# Food Truck\n\nBuild the app with Xcode 15."));
+    assert_eq!(report.bytes_downloaded, (json.len() + zip.len()) as u64);
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use adoc::extract::DocPage;
+use adoc::output::{load_results, save_results, OutputFormat};
+use adoc::schema::{json_schema, Envelope, SCHEMA_VERSION};
+
+fn numbered_page(i: usize) -> DocPage {
+    DocPage {
+        related_links: vec![common::doc_url(&format!("swiftui/page{}", i + 1)).into()],
+        tags: vec!["swiftui".to_string()],
+        ..common::page(
+            &common::doc_url(&format!("swiftui/page{}", i)),
+            &format!("Page {}", i),
+            &format!("Content of page {}.", i),
+        )
+    }
+}
+
+#[test]
+fn test_versioned_output() {
+    let dir = tempfile::tempdir().unwrap();
+    for pages in [vec![], vec![numbered_page(0), numbered_page(1)]] {
+        let json = dir.path().join("pages.json");
+        save_results(&pages, &json, OutputFormat::Json).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&std::fs::read(&json).unwrap()).unwrap();
+        assert_eq!(value["schema_version"], SCHEMA_VERSION);
+        assert_eq!(value["pages"].as_array().unwrap().len(), pages.len());
+        assert_eq!(load_results(&json).unwrap().len(), pages.len());
+
+        // 逐页写出的缩进格式与整体序列化相同
+        let pretty = dir.path().join("pages-pretty.json");
+        save_results(&pages, &pretty, OutputFormat::PrettyJson).unwrap();
+        let expected = serde_json::to_string_pretty(&Envelope::new(pages.clone())).unwrap();
+        assert_eq!(std::fs::read_to_string(&pretty).unwrap(), expected);
+    }
+
+    // 早期版本没有信封的页面数组仍然可以读取
+    let legacy = dir.path().join("legacy.json");
+    std::fs::write(&legacy, serde_json::to_string(&[numbered_page(0)]).unwrap()).unwrap();
+    assert_eq!(load_results(&legacy).unwrap()[0].title, "Page 0");
+
+    // 更新版本的输出不能被悄悄当作当前格式读取
+    let newer = dir.path().join("newer.json");
+    let mut envelope = serde_json::to_value(Envelope::new(vec![numbered_page(0)])).unwrap();
+    envelope["schema_version"] = (SCHEMA_VERSION + 1).into();
+    std::fs::write(&newer, envelope.to_string()).unwrap();
+    let error = format!("{:#}", load_results(&newer).unwrap_err());
+    assert!(error.contains(&(SCHEMA_VERSION + 1).to_string()), "{}", error);
+}
+
+/// 去掉字段说明，只比较结构
+fn without_descriptions(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.remove("description");
+            map.values_mut().for_each(without_descriptions);
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(without_descriptions),
+        _ => {}
+    }
+}
+
+/// 输出格式变化时必须递增 SCHEMA_VERSION，并以 `adoc schema > tests/fixtures/schema-v<版本>.json` 保存新的 Schema
+#[test]
+fn test_schema_matches_version() {
+    let path = format!("{}/tests/fixtures/schema-v{}.json", env!("CARGO_MANIFEST_DIR"), SCHEMA_VERSION);
+    let fixture: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    let (mut expected, mut actual) = (fixture.clone(), json_schema(false));
+    without_descriptions(&mut expected);
+    without_descriptions(&mut actual);
+    assert_eq!(actual, expected, "the output schema changed without bumping SCHEMA_VERSION");
+
+    assert_eq!(fixture["properties"]["schema_version"]["const"], SCHEMA_VERSION);
+    let page = json_schema(true);
+    assert_eq!(page["title"], "DocPage");
+    assert_eq!(page["properties"], fixture["$defs"]["DocPage"]["properties"]);
+    for field in ["title", "content", "url", "related_links"] {
+        assert!(page["required"].as_array().unwrap().contains(&field.into()), "{}", field);
+    }
+}
@@ -0,0 +1,158 @@
+mod common;
+
+use adoc::anchor::Anchor;
+use adoc::snapshot::{page_path, relative_link, GitSnapshot, SnapshotManifest, MANIFEST_FILE, SITEMAP_FILE};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn git(repo: &Path, args: &[&str]) -> String {
+    let output = Command::new("git").arg("-C").arg(repo).args(args).output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn open(repo: &Path) -> GitSnapshot {
+    let snapshot = GitSnapshot::open(repo).unwrap();
+    git(repo, &["config", "user.name", "adoc"]);
+    git(repo, &["config", "user.email", "adoc@example.com"]);
+    snapshot
+}
+
+#[test]
+fn test_page_path() {
+    let path = |url| page_path(&common::page(url, "", ""));
+    assert_eq!(path("https://developer.apple.com/documentation/swiftui"), PathBuf::from("swiftui.md"));
+    assert_eq!(
+        path("https://developer.apple.com/documentation/swiftui/view/frame(width:height:)"),
+        PathBuf::from("swiftui/view/frame(width_height_).md")
+    );
+    assert_eq!(path("https://swift.org/documentation/api-design-guidelines"), PathBuf::from("swift.org/api-design-guidelines.md"));
+    assert_eq!(path("https://swift.org/"), PathBuf::from("swift.org.md"));
+}
+
+#[test]
+fn test_snapshot_commits_changes() {
+    let dir = tempfile::tempdir().unwrap();
+    let repo = dir.path().join("docs");
+    let snapshot = open(&repo);
+    let seed = "https://developer.apple.com/documentation/swiftui".to_string();
+    let first = vec![
+        common::page(&seed, "SwiftUI", "Declare the user interface."),
+        common::page("https://developer.apple.com/documentation/swiftui/view", "View", "A view."),
+        common::page("https://developer.apple.com/documentation/swiftui/text", "Text", "Displays text."),
+        common::page("https://developer.apple.com/documentation/swiftui/label", "Label", "A label."),
+    ];
+
+    let changes = snapshot.write(first.as_slice(), std::slice::from_ref(&seed), &[]).unwrap();
+    assert_eq!(changes.added.len(), 4);
+    assert!(snapshot.commit(&changes).unwrap());
+    let content = std::fs::read_to_string(repo.join("swiftui/view.md")).unwrap();
+    assert!(content.starts_with("# View\n\n<https://developer.apple.com/documentation/swiftui/view>"));
+    assert!(git(&repo, &["log", "-1", "--format=%s"]).contains('4'));
+
+    // 没有变化时不提交
+    let changes = snapshot.write(first.as_slice(), std::slice::from_ref(&seed), &[]).unwrap();
+    assert!(changes.is_empty());
+    assert!(!snapshot.commit(&changes).unwrap());
+
+    // Text 有修改，View 不再出现，Label 爬取失败（保留），新增 Button；其他框架和非快照文件不受影响
+    std::fs::write(repo.join("NOTES.txt"), "local notes").unwrap();
+    std::fs::create_dir_all(repo.join("uikit")).unwrap();
+    std::fs::write(repo.join("uikit/uiview.md"), "# UIView").unwrap();
+    let second = vec![
+        common::page(&seed, "SwiftUI", "Declare the user interface."),
+        common::page("https://developer.apple.com/documentation/swiftui/text", "Text", "Displays read-only text."),
+        common::page("https://developer.apple.com/documentation/swiftui/button", "Button", "A button."),
+    ];
+    let keep = ["https://developer.apple.com/documentation/swiftui/label".to_string()];
+    let changes = snapshot.write(second.as_slice(), std::slice::from_ref(&seed), &keep).unwrap();
+    assert_eq!(changes.added, vec![PathBuf::from("swiftui/button.md")]);
+    assert_eq!(changes.changed, vec![PathBuf::from("swiftui/text.md")]);
+    assert_eq!(changes.removed, vec![PathBuf::from("swiftui/view.md")]);
+    assert!(snapshot.commit(&changes).unwrap());
+
+    let message = git(&repo, &["log", "-1", "--format=%B"]);
+    assert!(message.contains("swiftui/button.md"));
+    assert!(message.contains("swiftui/text.md"));
+    assert!(message.contains("swiftui/view.md"));
+    assert!(repo.join("swiftui/label.md").exists());
+    let tracked = git(&repo, &["ls-files"]);
+    assert!(!tracked.contains("NOTES.txt"));
+    assert!(!tracked.contains("uikit"));
+    assert!(repo.join("uikit/uiview.md").exists());
+}
+
+#[test]
+fn test_snapshot_manifest_and_sitemap() {
+    let dir = tempfile::tempdir().unwrap();
+    let repo = dir.path().join("docs");
+    let snapshot = open(&repo);
+    let seed = "https://developer.apple.com/documentation/swiftui".to_string();
+    let first = vec![
+        common::page(&seed, "SwiftUI", "Declare the user interface."),
+        common::page("https://developer.apple.com/documentation/swiftui/view", "View", "A view."),
+        common::page("https://developer.apple.com/documentation/swiftui/text", "Text", "Displays text & more."),
+    ];
+    let changes = snapshot.write(first.as_slice(), std::slice::from_ref(&seed), &[]).unwrap();
+    assert_eq!(changes.added.len(), 3);
+    assert_eq!(changes.listings, [PathBuf::from(MANIFEST_FILE), PathBuf::from(SITEMAP_FILE)]);
+    assert!(snapshot.commit(&changes).unwrap());
+    let tracked = git(&repo, &["ls-files"]);
+    assert!(tracked.contains(MANIFEST_FILE) && tracked.contains(SITEMAP_FILE));
+
+    let manifest = SnapshotManifest::load(&repo.join(MANIFEST_FILE)).unwrap();
+    assert_eq!(manifest.count, 3);
+    let entry = &manifest.entries[1];
+    assert_eq!(entry.url, "https://developer.apple.com/documentation/swiftui/text");
+    assert_eq!(entry.title, "Text");
+    assert_eq!(entry.path, "swiftui/text.md");
+    assert_eq!(entry.bytes, std::fs::metadata(repo.join(&entry.path)).unwrap().len());
+    assert_eq!(entry.sha256.len(), 64);
+    let sitemap = std::fs::read_to_string(repo.join(SITEMAP_FILE)).unwrap();
+    assert!(sitemap.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset"));
+    assert!(sitemap.contains("<url><loc>https://developer.apple.com/documentation/swiftui/view</loc></url>"));
+    assert_eq!(sitemap.matches("<url>").count(), 3);
+
+    // 没有变化时清单也不变；删除的页面从清单中去掉，爬取失败而保留的页面仍在清单中
+    let changes = snapshot.write(first.as_slice(), std::slice::from_ref(&seed), &[]).unwrap();
+    assert!(changes.is_empty());
+    let keep = ["https://developer.apple.com/documentation/swiftui/text".to_string()];
+    let changes = snapshot.write(&first[..1], std::slice::from_ref(&seed), &keep).unwrap();
+    assert_eq!(changes.removed, [PathBuf::from("swiftui/view.md")]);
+    assert_eq!(changes.listings.len(), 2);
+    let manifest = SnapshotManifest::load(&repo.join(MANIFEST_FILE)).unwrap();
+    let paths: Vec<&str> = manifest.entries.iter().map(|entry| entry.path.as_str()).collect();
+    assert_eq!(paths, ["swiftui.md", "swiftui/text.md"]);
+    assert!(!std::fs::read_to_string(repo.join(SITEMAP_FILE)).unwrap().contains("swiftui/view"));
+}
+
+#[test]
+fn test_snapshot_links_to_local_pages() {
+    assert_eq!(relative_link(Path::new("swiftui/view.md"), Path::new("swiftui/text.md")), "text.md");
+    assert_eq!(relative_link(Path::new("swiftui.md"), Path::new("swiftui/view/frame.md")), "swiftui/view/frame.md");
+    assert_eq!(relative_link(Path::new("swiftui/view/frame.md"), Path::new("uikit/uiview.md")), "../../uikit/uiview.md");
+
+    let dir = tempfile::tempdir().unwrap();
+    let repo = dir.path().join("docs");
+    let snapshot = open(&repo);
+    let mut view = common::page("https://developer.apple.com/documentation/swiftui/view", "View", "A view.");
+    view.related_links = vec![
+        "https://developer.apple.com/documentation/swiftui/view/frame(width:height:)#discussion".to_string().into(),
+        "https://developer.apple.com/documentation/uikit/uiview".to_string().into(),
+    ];
+    let mut frame = common::page(
+        "https://developer.apple.com/documentation/swiftui/view/frame(width:height:)",
+        "frame(width:height:)",
+        "Positions this view.\nDiscussion\nUse this method to specify a fixed size.",
+    );
+    frame.anchors = vec![Anchor { id: "discussion".to_string(), title: "Discussion".to_string() }];
+    let pages = vec![view, frame];
+    snapshot.write(pages.as_slice(), &[], &[]).unwrap();
+
+    // 本次快照中的页面改为相对路径并保留小节锚点，其余保留原始链接
+    let content = std::fs::read_to_string(repo.join("swiftui/view.md")).unwrap();
+    assert!(content.contains("- [frame(width:height:)](<view/frame(width_height_).md#discussion>)\n"));
+    assert!(content.contains("- <https://developer.apple.com/documentation/uikit/uiview>\n"));
+    let content = std::fs::read_to_string(repo.join("swiftui/view/frame(width_height_).md")).unwrap();
+    assert!(content.contains("Positions this view.\n\n## <a id=\"discussion\">Discussion</a>\n\nUse this method"));
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use adoc::extract::DocPage;
+use adoc::output::{load_results, save_pages, save_results, OutputFormat};
+use adoc::sink::PageSink;
+use adoc::spill::SpillBuffer;
+
+fn numbered_page(i: usize) -> DocPage {
+    DocPage {
+        related_links: vec![common::doc_url(&format!("swiftui/page{}", i + 1)).into()],
+        ..common::page(
+            &common::doc_url(&format!("swiftui/page{}", i)),
+            &format!("Page {}", i),
+            &format!("Content of page {}.\n\nSecond paragraph.", i),
+        )
+    }
+}
+
+#[tokio::test]
+async fn test_spilled_pages_are_saved_in_order() {
+    let pages: Vec<DocPage> = (0..10).map(numbered_page).collect();
+    // 预算只够放下一个页面，其余页面都会溢出到临时文件
+    let buffer = SpillBuffer::new(300);
+    for page in &pages {
+        buffer.send(page).await.unwrap();
+    }
+    buffer.flush().await.unwrap();
+    let spilled = buffer.finish().unwrap();
+    assert_eq!(spilled.len(), 10);
+    assert!(spilled.spilled() >= 9);
+    assert!(spilled.has_content());
+
+    let dir = tempfile::tempdir().unwrap();
+    for (name, format) in [
+        ("pages.json", OutputFormat::Json),
+        ("pages-pretty.json", OutputFormat::PrettyJson),
+        ("pages.md", OutputFormat::Markdown),
+        ("pages.ndjson", OutputFormat::EsBulk),
+    ] {
+        let expected = dir.path().join(format!("expected-{}", name));
+        let actual = dir.path().join(name);
+        save_results(&pages, &expected, format).unwrap();
+        save_pages(&spilled, &actual, format).unwrap();
+        assert_eq!(std::fs::read(&actual).unwrap(), std::fs::read(&expected).unwrap(), "{}", name);
+    }
+
+    let sqlite = dir.path().join("pages.sqlite");
+    save_pages(&spilled, &sqlite, OutputFormat::Sqlite).unwrap();
+    let loaded = load_results(&sqlite).unwrap();
+    assert_eq!(loaded.len(), 10);
+    assert_eq!(loaded[9].url, pages[9].url);
+}
+
+#[tokio::test]
+async fn test_pages_within_budget_stay_in_memory() {
+    let buffer = SpillBuffer::new(1 << 20);
+    for i in 0..3 {
+        buffer.send(&numbered_page(i)).await.unwrap();
+    }
+    let spilled = buffer.finish().unwrap();
+    assert_eq!(spilled.len(), 3);
+    assert_eq!(spilled.spilled(), 0);
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use adoc::extract::DocPage;
+use adoc::output::{load_results, save_results, OutputFormat};
+use adoc::sqlite::search;
+
+#[test]
+fn test_sqlite_output_is_searchable() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("docs.sqlite");
+    let pages = vec![
+        DocPage {
+            related_links: vec![common::doc_url("swiftui").into()],
+            ..common::page(&common::doc_url("swiftui/list"), "List", "Push a NavigationStack onto rows.")
+        },
+        common::page(
+            &common::doc_url("swiftui/navigationstack"),
+            "NavigationStack",
+            "A view that displays a root view.",
+        ),
+        common::page(&common::doc_url("uikit/uiview"), "UIView", "Manages content on screen."),
+    ];
+
+    save_results(&pages, &path, OutputFormat::Sqlite).unwrap();
+
+    // 标题命中排在正文命中之前
+    let hits = search(&path, "NavigationStack", 10).unwrap();
+    let titles: Vec<_> = hits.iter().map(|page| page.title.as_str()).collect();
+    assert_eq!(titles, vec!["NavigationStack", "List"]);
+
+    // FTS 语法字符按字面处理
+    assert!(search(&path, "frame(width:)", 10).unwrap().is_empty());
+
+    let loaded = load_results(&path).unwrap();
+    assert_eq!(loaded.len(), 3);
+    assert_eq!(loaded[2].url, "https://developer.apple.com/documentation/uikit/uiview");
+    assert_eq!(loaded[0].related_links, pages[0].related_links);
+}
+
+#[test]
+fn test_save_chunks_with_embeddings() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("chunks.sqlite");
+    let mut chunks = adoc::chunk::chunk_pages(&[common::page(&common::doc_url("swiftui/text"), "Text", "one two three")], 2, 0).unwrap();
+    chunks[0].embedding = Some(vec![1.0, -0.5]);
+    adoc::sqlite::save_chunks(&chunks, &path).unwrap();
+
+    let conn = rusqlite::Connection::open(&path).unwrap();
+    let rows: Vec<(String, String, i64, Option<Vec<u8>>)> = conn
+        .prepare("SELECT content, hierarchy, position, embedding FROM chunks ORDER BY id")
+        .unwrap()
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].0, "one two");
+    assert_eq!(rows[0].1, "swiftui / text");
+    assert_eq!(rows[1].2, 1);
+    let vector: Vec<f32> = rows[0]
+        .3
+        .as_ref()
+        .unwrap()
+        .chunks_exact(4)
+        .map(|bytes| f32::from_le_bytes(bytes.try_into().unwrap()))
+        .collect();
+    assert_eq!(vector, vec![1.0, -0.5]);
+    assert_eq!(rows[1].3, None);
+}
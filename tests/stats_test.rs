@@ -0,0 +1,54 @@
+mod common;
+
+use adoc::extract::DocPage;
+use adoc::stats::compute_stats;
+
+const BASE: &str = "https://developer.apple.com/documentation";
+
+fn with_links(page: DocPage, links: &[&str]) -> DocPage {
+    DocPage {
+        related_links: links.iter().map(|link| format!("{}/{}", BASE, link).into()).collect(),
+        ..page
+    }
+}
+
+#[test]
+fn test_compute_stats() {
+    let pages = vec![
+        with_links(
+            common::page(&common::doc_url("swiftui"), "SwiftUI", "Declare the user interface."),
+            &["swiftui/text", "swiftui/list"],
+        ),
+        with_links(
+            common::page(&common::doc_url("swiftui/text"), "Text", "Beta\nA view that displays text."),
+            &["swiftui/missing"],
+        ),
+        with_links(
+            common::page(&common::doc_url("swiftui/view/padding(_:)"), "padding(_:)", &"x".repeat(3_000)),
+            &["swiftui/text"],
+        ),
+        with_links(
+            common::page(&common::doc_url("uikit/uilabel"), "UILabel", "Deprecated\nA label."),
+            &["swiftui/missing#overview"],
+        ),
+    ];
+
+    let stats = compute_stats(&pages);
+    assert_eq!(stats.pages, 4);
+    assert_eq!(stats.by_framework["swiftui"], 3);
+    assert_eq!(stats.by_framework["uikit"], 1);
+    assert_eq!(stats.by_kind["framework"], 1);
+    assert_eq!(stats.by_kind["method"], 1);
+    assert_eq!(stats.by_kind["symbol"], 2);
+    assert_eq!(stats.deprecated, 1);
+    assert_eq!(stats.beta, 1);
+    assert_eq!(stats.content_length_max, 3_000);
+    assert_eq!(stats.content_length_distribution[2], ("2k - 10k".to_string(), 1));
+    // list 和 missing 都不在结果中，missing 的两种写法只计一次
+    assert_eq!(stats.broken_links, 2);
+    assert_eq!(stats.most_linked[0].url, format!("{}/swiftui/text", BASE));
+    assert_eq!(stats.most_linked[0].inbound_links, 2);
+
+    let table = stats.to_string();
+    assert!(table.contains("| 页面总数"));
+}
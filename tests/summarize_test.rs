@@ -0,0 +1,67 @@
+#![cfg(feature = "summarize")]
+
+mod common;
+
+use adoc::output::{write_pages, OutputFormat};
+use adoc::summarize::{Summarizer, SummarizerConfig, DEFAULT_OLLAMA_URL};
+
+#[test]
+fn test_parse_summarizer_config() {
+    let config = SummarizerConfig::parse("ollama://llama3", 0).unwrap();
+    assert_eq!(config.url, DEFAULT_OLLAMA_URL);
+    assert_eq!(config.model, "llama3");
+    assert_eq!(config.workers, 1);
+
+    let config = SummarizerConfig::parse("ollama://gpu-box:11434/qwen2.5:7b", 8).unwrap();
+    assert_eq!(config.url, "http://gpu-box:11434");
+    assert_eq!(config.model, "qwen2.5:7b");
+    assert_eq!(config.workers, 8);
+
+    assert!(SummarizerConfig::parse("openai://gpt-4o", 4).is_err());
+    assert!(SummarizerConfig::parse("ollama://", 4).is_err());
+}
+
+#[tokio::test]
+async fn test_summarize_pages() {
+    let response = r#"{"model": "llama3", "response": " A view that\n displays text. ", "done": true}"#;
+    let (base, log) = common::serve_typed_recording(&[("POST /api/generate", "application/json", response)]);
+    let target = format!("ollama://{}/llama3", base.trim_start_matches("http://"));
+    let summarizer = Summarizer::new(SummarizerConfig::parse(&target, 2).unwrap());
+
+    let mut existing = common::page(&common::doc_url("swiftui/list"), "List", "A container.");
+    existing.summary = Some("Already summarized.".to_string());
+    let mut pages = vec![
+        common::page(&common::doc_url("swiftui/text"), "Text", "Displays one or more lines of read-only text."),
+        common::page(&common::doc_url("swiftui/empty"), "Empty", "  "),
+        existing,
+    ];
+    assert_eq!(summarizer.summarize_pages(&mut pages).await, 1);
+    assert_eq!(pages[0].summary.as_deref(), Some("A view that displays text."));
+    assert_eq!(pages[1].summary, None);
+    assert_eq!(pages[2].summary.as_deref(), Some("Already summarized."));
+
+    // 只为需要摘要的页面发送请求，请求中带有模型名和页面正文
+    let requests = log.lock().unwrap().clone();
+    assert_eq!(requests.len(), 1);
+    let body: serde_json::Value = serde_json::from_str(&requests[0].body).unwrap();
+    assert_eq!(body["model"], "llama3");
+    assert_eq!(body["stream"], false);
+    assert!(body["prompt"].as_str().unwrap().contains("Displays one or more lines of read-only text."));
+
+    // 摘要放在正文之前
+    let mut out = Vec::new();
+    write_pages(&pages[..1], OutputFormat::Markdown, &mut out).unwrap();
+    let markdown = String::from_utf8(out).unwrap();
+    let summary = markdown.find("A view that displays text.").unwrap();
+    assert!(summary < markdown.find("Displays one or more lines").unwrap());
+}
+
+#[tokio::test]
+async fn test_summarize_failure_keeps_page() {
+    let (base, _) = common::serve_status_recording(&[("POST /api/generate", "500 Internal Server Error", "")]);
+    let target = format!("ollama://{}/llama3", base.trim_start_matches("http://"));
+    let summarizer = Summarizer::new(SummarizerConfig::parse(&target, 1).unwrap());
+    let mut pages = vec![common::page(&common::doc_url("swiftui/text"), "Text", "Displays text.")];
+    assert_eq!(summarizer.summarize_pages(&mut pages).await, 0);
+    assert_eq!(pages[0].summary, None);
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use adoc::extract::DocPage;
+use adoc::index::{build_index, search_tagged};
+use adoc::output::{write_pages, OutputFormat};
+use adoc::query::{search_pages, Matcher};
+use adoc::tags::{has_tags, hashtag, tag_pages};
+
+fn pages() -> Vec<DocPage> {
+    vec![
+        common::page(
+            &common::doc_url("swiftui/navigationstack"),
+            "NavigationStack",
+            "struct NavigationStack<Data, Root> where Root : View\niOS 16.0+\nmacOS 13.0+\nXcode 14.0+\n\
+             A view that displays a root view and enables you to present additional views over the root view. \
+             Use a NavigationLink to push views onto the stack; the stack keeps the navigation path.",
+        ),
+        common::page(
+            &common::doc_url("swiftui/navigationlink"),
+            "NavigationLink",
+            "struct NavigationLink<Label, Destination> where Label : View, Destination : View\niOS 13.0+\n\
+             A view that controls a navigation presentation inside a NavigationStack.",
+        ),
+        common::page(
+            &common::doc_url("uikit/uibutton"),
+            "UIButton",
+            "class UIButton : UIControl\niOS 2.0+\nA control that executes your custom code in response to user taps.",
+        ),
+    ]
+}
+
+#[test]
+fn test_tag_pages() {
+    let mut pages = pages();
+    pages.push(DocPage { status: Some(404), ..common::page(&common::doc_url("swiftui/removed"), "", "") });
+    tag_pages(&mut pages);
+
+    // 框架、平台（不含 Xcode）、提到的其他符号在前，关键词在后；不包含页面自己的标题
+    assert_eq!(pages[0].tags[..4], ["swiftui", "iOS", "macOS", "NavigationLink"]);
+    assert!(pages[0].tags.iter().any(|tag| tag == "stack"));
+    assert!(!pages[0].tags.iter().any(|tag| tag.eq_ignore_ascii_case("NavigationStack") || tag == "Xcode"));
+    assert_eq!(pages[1].tags[..3], ["swiftui", "iOS", "NavigationStack"]);
+    // 关键词不包含停用词和过短的单词
+    assert!(pages[2].tags.starts_with(&["uikit".to_string(), "iOS".to_string()]));
+    assert!(!pages[2].tags.iter().any(|tag| tag == "that" || tag == "user"));
+    assert!(pages[2].tags.len() <= 2 + 5);
+    // 失效页面不生成标签
+    assert!(pages[3].tags.is_empty());
+
+    assert!(has_tags(&pages[0], &["SwiftUI".to_string(), "ios".to_string()]));
+    assert!(!has_tags(&pages[2], &["swiftui".to_string()]));
+    assert_eq!(hashtag("Mac Catalyst"), "#Mac-Catalyst");
+}
+
+#[test]
+fn test_tags_in_query_and_output() {
+    let mut pages = pages();
+    tag_pages(&mut pages);
+
+    // 标题和正文中都没有搜索词，按标签命中
+    let results = search_pages(pages.clone(), &Matcher::substring("uikit"), 10);
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].title, "UIButton");
+
+    let dir = tempfile::tempdir().unwrap();
+    build_index(&pages, dir.path()).unwrap();
+    let hits = search_tagged(dir.path(), "view", &["NavigationLink".to_string()], 10).unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].page.title, "NavigationStack");
+    assert_eq!(hits[0].page.tags, pages[0].tags);
+
+    let mut out = Vec::new();
+    write_pages(&pages[..1], OutputFormat::Markdown, &mut out).unwrap();
+    let markdown = String::from_utf8(out).unwrap();
+    assert!(markdown.contains("#swiftui #iOS #macOS #NavigationLink"));
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use adoc::extract::DocPage;
+use adoc::report::CrawlReport;
+use adoc::tokens::{measure_pages, PageSize, TokenEncoding};
+
+#[test]
+fn test_measure_pages_by_words() {
+    let mut pages = vec![
+        common::page(&common::doc_url("swiftui/text"), "Text", "A view that displays\none or more lines of text."),
+        common::page(&common::doc_url("swiftui/list"), "List", "A container — 列表。"),
+        DocPage { status: Some(404), ..common::page(&common::doc_url("swiftui/removed"), "Removed", "") },
+    ];
+    let total = measure_pages(&mut pages, TokenEncoding::Words).unwrap();
+    assert_eq!(pages[0].size, Some(PageSize { words: 10, chars: 47, tokens: 10 }));
+    assert_eq!(pages[1].size, Some(PageSize { words: 4, chars: 17, tokens: 4 }));
+    // 失效页面不统计
+    assert_eq!(pages[2].size, None);
+    assert_eq!((total.pages, total.words, total.chars, total.tokens), (2, 14, 64, 14));
+    assert_eq!(total.encoding, "words");
+    assert_eq!(total.largest_page.as_deref(), Some(pages[0].url.as_str()));
+    assert_eq!(total.largest_page_tokens, 10);
+
+    // 合计写入爬取报告
+    let report = CrawlReport { content: Some(total), ..Default::default() };
+    let table = report.to_string();
+    assert!(table.contains("Tokens counted with words") || table.contains("Token 数按 words 编码计算"));
+    let json: serde_json::Value = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["content"]["tokens"], 14);
+    assert!(serde_json::to_value(CrawlReport::default()).unwrap().get("content").is_none());
+}
+
+#[cfg(feature = "tokens")]
+#[test]
+fn test_measure_pages_with_bpe() {
+    let mut pages = vec![common::page(&common::doc_url("swiftui/text"), "Text", "Hello world")];
+    let total = measure_pages(&mut pages, TokenEncoding::Cl100k).unwrap();
+    assert_eq!(total.tokens, 2);
+    assert_eq!(total.encoding, "cl100k_base");
+    assert_eq!(TokenEncoding::O200k.count("Hello world").unwrap(), 2);
+}
+
+#[cfg(not(feature = "tokens"))]
+#[test]
+fn test_bpe_requires_feature() {
+    assert!(TokenEncoding::Words.check().is_ok());
+    assert!(TokenEncoding::Cl100k.check().is_err());
+    let text = common::page(&common::doc_url("swiftui/text"), "Text", "Hello world");
+    assert!(measure_pages(&mut [text], TokenEncoding::Cl100k).is_err());
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use adoc::output::{write_pages, OutputFormat};
+use adoc::translate::{PageTranslator, Translator};
+
+#[test]
+fn test_parse_translator() {
+    assert_eq!(Translator::parse("cmd:./translate.sh").unwrap(), Translator::Command("./translate.sh".to_string()));
+    assert_eq!(
+        Translator::parse("http://localhost:5000/translate").unwrap(),
+        Translator::Http("http://localhost:5000/translate".to_string())
+    );
+    assert!(Translator::parse("cmd: ").is_err());
+    assert!(Translator::parse("deepl").is_err());
+}
+
+#[tokio::test]
+async fn test_translate_with_command() {
+    let dir = tempfile::tempdir().unwrap();
+    let script = dir.path().join("translate.sh");
+    // 把目标语言和大写后的正文作为译文
+    std::fs::write(&script, "#!/bin/sh\nprintf '[%s] ' \"$1\"\ntr a-z A-Z\n").unwrap();
+    let command = format!("cmd:sh {}", script.display());
+    let translator = PageTranslator::new(Translator::parse(&command).unwrap(), "zh-CN");
+
+    let mut done = common::page(&common::doc_url("swiftui/list"), "List", "Old content.");
+    done.content_translated = Some("已有译文".to_string());
+    let mut pages = vec![
+        common::page(&common::doc_url("swiftui/text"), "Text", "A view that displays text.\n\nSecond paragraph."),
+        common::page(&common::doc_url("swiftui/empty"), "Empty", ""),
+        done,
+    ];
+    assert_eq!(translator.translate_pages(&mut pages).await, 1);
+    assert_eq!(
+        pages[0].content_translated.as_deref(),
+        Some("[zh-CN] A VIEW THAT DISPLAYS TEXT.\n\nSECOND PARAGRAPH.")
+    );
+    assert_eq!(pages[0].content, "A view that displays text.\n\nSecond paragraph.");
+    assert_eq!(pages[1].content_translated, None);
+    assert_eq!(pages[2].content_translated.as_deref(), Some("已有译文"));
+
+    // 译文跟在原文之后
+    let mut out = Vec::new();
+    write_pages(&pages[..1], OutputFormat::Markdown, &mut out).unwrap();
+    let markdown = String::from_utf8(out).unwrap();
+    assert!(markdown.find("Second paragraph.").unwrap() < markdown.find("SECOND PARAGRAPH.").unwrap());
+
+    // 命令失败时不写入译文
+    let failing = PageTranslator::new(Translator::parse("cmd:exit 3").unwrap(), "zh-CN");
+    let mut pages = vec![common::page(&common::doc_url("swiftui/text"), "Text", "A view.")];
+    assert_eq!(failing.translate_pages(&mut pages).await, 0);
+    assert_eq!(pages[0].content_translated, None);
+}
+
+#[tokio::test]
+async fn test_translate_with_http() {
+    let response = r#"{"translatedText": "显示文本的视图。"}"#;
+    let (base, log) = common::serve_typed_recording(&[("POST /translate", "application/json", response)]);
+    let translator = PageTranslator::new(Translator::parse(&format!("{}/translate", base)).unwrap(), "zh-CN");
+    let mut pages = vec![common::page(&common::doc_url("swiftui/text"), "Text", "A view that displays text.")];
+    assert_eq!(translator.translate_pages(&mut pages).await, 1);
+    assert_eq!(pages[0].content_translated.as_deref(), Some("显示文本的视图。"));
+
+    let requests = log.lock().unwrap().clone();
+    let body: serde_json::Value = serde_json::from_str(&requests[0].body).unwrap();
+    assert_eq!(body["q"], "A view that displays text.");
+    assert_eq!(body["target"], "zh-CN");
+    assert_eq!(body["source"], "auto");
+}
@@ -0,0 +1,121 @@
+use adoc::extract::{extract_json, json_data_url};
+use adoc::output::{write_pages, OutputFormat};
+use adoc::tutorial::render_markdown;
+
+const PAGE: &str = "https://developer.apple.com/tutorials/swiftui/creating-and-combining-views";
+
+const JSON: &str = r#"{
+    "kind": "project",
+    "metadata": {"title": "Creating and combining views", "role": "project"},
+    "sections": [
+        {
+            "kind": "hero",
+            "title": "Creating and combining views",
+            "estimatedTimeInMinutes": 40,
+            "content": [{"type": "paragraph", "inlineContent": [{"type": "text", "text": "This tutorial guides you through building Landmarks."}]}]
+        },
+        {
+            "kind": "tasks",
+            "tasks": [
+                {
+                    "title": "Customize the text view",
+                    "contentSection": [{"kind": "contentAndMedia", "content": [
+                        {"type": "paragraph", "inlineContent": [{"type": "text", "text": "Change the text of the view."}]}
+                    ]}],
+                    "stepsSection": [
+                        {
+                            "type": "step",
+                            "content": [{"type": "paragraph", "inlineContent": [{"type": "text", "text": "Open ContentView.swift."}]}],
+                            "caption": [],
+                            "code": "ContentView-1.swift"
+                        },
+                        {
+                            "type": "step",
+                            "content": [{"type": "paragraph", "inlineContent": [{"type": "text", "text": "Change the text to Turtle Rock."}]}],
+                            "caption": [],
+                            "code": "ContentView-2.swift"
+                        },
+                        {
+                            "type": "step",
+                            "content": [{"type": "paragraph", "inlineContent": [{"type": "text", "text": "Look at the preview."}]}],
+                            "caption": []
+                        }
+                    ]
+                }
+            ]
+        }
+    ],
+    "references": {
+        "ContentView-1.swift": {
+            "type": "file", "identifier": "ContentView-1.swift", "fileName": "ContentView.swift", "syntax": "swift",
+            "content": ["struct ContentView: View {", "    var body: some View {", "        Text(\"Hello, world!\")", "    }", "}"]
+        },
+        "ContentView-2.swift": {
+            "type": "file", "identifier": "ContentView-2.swift", "fileName": "ContentView.swift", "syntax": "swift",
+            "content": ["struct ContentView: View {", "    var body: some View {", "        Text(\"Turtle Rock\")", "    }", "}"]
+        },
+        "doc://com.apple.SwiftUI/documentation/SwiftUI/Text": {
+            "type": "topic", "title": "Text", "url": "/documentation/swiftui/text"
+        }
+    }
+}"#;
+
+#[test]
+fn test_tutorial_json_data_url() {
+    assert_eq!(
+        json_data_url(PAGE).as_deref(),
+        Some("https://developer.apple.com/tutorials/data/tutorials/swiftui/creating-and-combining-views.json")
+    );
+}
+
+#[test]
+fn test_extract_tutorial_steps() {
+    let page = extract_json(PAGE, JSON).unwrap();
+    assert_eq!(page.title, "Creating and combining views");
+    let lines: Vec<&str> = page.content.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "This tutorial guides you through building Landmarks.",
+            "Estimated time: 40 min",
+            "## Customize the text view",
+            "Change the text of the view.",
+            "1. Open ContentView.swift.",
+            "2. Change the text to Turtle Rock.",
+            "3. Look at the preview.",
+        ]
+    );
+    assert_eq!(page.link_urls().collect::<Vec<_>>(), ["https://developer.apple.com/documentation/swiftui/text"]);
+
+    assert_eq!(page.steps.len(), 3);
+    assert!(page.steps.iter().all(|step| step.section == "Customize the text view"));
+    assert_eq!(page.steps[0].file.as_deref(), Some("ContentView.swift"));
+    assert!(page.steps[0].code.as_deref().unwrap().contains("Hello, world!"));
+    // 文件第一次出现时没有 diff，之后记录相对上一步的变化
+    assert_eq!(page.steps[0].diff, None);
+    let diff = page.steps[1].diff.as_deref().unwrap();
+    assert!(diff.contains("-        Text(\"Hello, world!\")\n"));
+    assert!(diff.contains("+        Text(\"Turtle Rock\")\n"));
+    assert!(!diff.contains("No newline"));
+    assert_eq!(page.steps[2].file, None);
+}
+
+#[test]
+fn test_render_tutorial_markdown() {
+    let page = extract_json(PAGE, JSON).unwrap();
+    let steps = render_markdown(&page.steps);
+    assert!(steps.starts_with("#### Customize the text view\n\n1. Open ContentView.swift.\n\n```swift\n"));
+    assert!(steps.contains("2. Change the text to Turtle Rock.\n\n```diff\n"));
+    assert!(steps.ends_with("3. Look at the preview.\n\n"));
+
+    let mut out = Vec::new();
+    write_pages([page].as_slice(), OutputFormat::Markdown, &mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains(&steps));
+
+    // JSON 输出保留结构化的步骤
+    let mut out = Vec::new();
+    write_pages([extract_json(PAGE, JSON).unwrap()].as_slice(), OutputFormat::Json, &mut out).unwrap();
+    let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(value["pages"][0]["steps"][1]["file"], "ContentView.swift");
+}
@@ -0,0 +1,14 @@
+use adoc::hash::sha256_hex;
+use adoc::update::{is_newer, parse_checksum};
+
+#[test]
+fn test_compare_versions_and_parse_checksum() {
+    assert!(is_newer("0.1.0", "0.0.2"));
+    assert!(is_newer("0.0.10", "0.0.9"));
+    assert!(!is_newer("0.0.2", "0.0.2"));
+    assert!(!is_newer("0.0.1", "0.0.2"));
+
+    let checksum = "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855  adoc-x86_64-unknown-linux-gnu\n";
+    assert_eq!(parse_checksum(checksum).unwrap(), sha256_hex(b""));
+    assert!(parse_checksum("not-a-checksum").is_err());
+}
@@ -0,0 +1,123 @@
+mod common;
+
+use adoc::cache::{Manifest, ResponseCache};
+use adoc::crawler::{Crawler, CrawlerConfig};
+use adoc::verify::verify_mirror;
+use std::sync::Arc;
+
+#[test]
+fn test_cache_manifest() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = ResponseCache::open(dir.path(), false).unwrap();
+    cache.put("https://developer.apple.com/documentation/swiftui", 200, Some("text/html"), "<h1>SwiftUI</h1>").unwrap();
+    cache.put("https://developer.apple.com/documentation/removed", 404, None, "").unwrap();
+    assert_eq!(cache.save_manifest().unwrap(), 2);
+
+    // 再次打开时合并到已有的清单
+    let cache = ResponseCache::open(dir.path(), false).unwrap();
+    cache.put("https://developer.apple.com/documentation/uikit", 200, Some("text/html"), "<h1>UIKit</h1>").unwrap();
+    assert_eq!(cache.save_manifest().unwrap(), 3);
+
+    let manifest = Manifest::load(&cache.manifest_path()).unwrap();
+    assert_eq!(manifest.count, 3);
+    let urls: Vec<&str> = manifest.entries.iter().map(|entry| entry.url.as_str()).collect();
+    assert_eq!(
+        urls,
+        [
+            "https://developer.apple.com/documentation/removed",
+            "https://developer.apple.com/documentation/swiftui",
+            "https://developer.apple.com/documentation/uikit",
+        ]
+    );
+    let entry = &manifest.entries[1];
+    assert!(entry.file.starts_with("developer.apple.com/"));
+    assert_eq!(entry.bytes, std::fs::metadata(dir.path().join(&entry.file)).unwrap().len());
+    assert_eq!(entry.sha256.len(), 64);
+    assert_eq!(manifest.entries[0].status, 404);
+}
+
+#[tokio::test]
+async fn test_verify_mirror() {
+    let (base, log) = common::serve_recording(&[
+        (
+            "/documentation/swiftui",
+            r#"<html><body><h1>SwiftUI</h1><article>
+                <p>Declare the user interface.</p>
+                <a href="/developer.apple.com/view">View</a>
+                <a href="/developer.apple.com/text">Text</a>
+            </article></body></html>"#,
+        ),
+        (
+            "/developer.apple.com/view",
+            "<html><body><h1>View</h1><article><p>A type that represents part of your app.</p></article></body></html>",
+        ),
+        (
+            "/developer.apple.com/text",
+            "<html><body><h1>Text</h1><article><p>A view that displays read-only text.</p></article></body></html>",
+        ),
+        (
+            "/developer.apple.com/image",
+            "<html><body><h1>Image</h1><article><p>A view that displays an image.</p></article></body></html>",
+        ),
+    ]);
+    let seed = format!("{}/documentation/swiftui", base);
+    let view = format!("{}/developer.apple.com/view", base);
+    let text = format!("{}/developer.apple.com/text", base);
+    let image = format!("{}/developer.apple.com/image", base);
+    let dir = tempfile::tempdir().unwrap();
+    let crawler = |cache: &Arc<ResponseCache>| {
+        Crawler::new(CrawlerConfig {
+            cache: Some(cache.clone()),
+            ..Default::default()
+        })
+    };
+
+    let cache = Arc::new(ResponseCache::open(dir.path(), false).unwrap());
+    crawler(&cache).crawl_url(&seed, true).await.unwrap();
+    cache.save_manifest().unwrap();
+    let report = verify_mirror(dir.path(), &[]).unwrap();
+    assert!(report.is_ok(), "{}", report);
+    assert_eq!((report.listed, report.verified), (3, 3));
+    // 页面的条目记录提取出的标题
+    let manifest = Manifest::load(&cache.manifest_path()).unwrap();
+    let titles: Vec<_> = manifest.entries.iter().map(|entry| entry.title.as_deref()).collect();
+    assert_eq!(titles, [Some("Text"), Some("View"), Some("SwiftUI")]);
+
+    // 删除一个文件、改动一个文件、放入清单之外的文件
+    std::fs::remove_file(cache.path(&view)).unwrap();
+    std::fs::write(cache.path(&text), std::fs::read_to_string(cache.path(&text)).unwrap().replace("Text", "Tex")).unwrap();
+    std::fs::copy(cache.path(&seed), dir.path().join("stray.json")).unwrap();
+    let report = verify_mirror(dir.path(), &[seed.clone(), image.clone()]).unwrap();
+    assert_eq!(report.verified, 1);
+    assert_eq!(report.missing, [view.as_str()]);
+    assert_eq!(report.corrupted.len(), 1);
+    assert_eq!(report.corrupted[0].url, text);
+    assert_eq!(report.not_listed, [image.as_str()]);
+    assert_eq!(report.unlisted, ["stray.json"]);
+    assert_eq!(report.problems(), 3);
+    assert_eq!(report.broken_urls(), [view.clone(), text.clone(), image.clone()]);
+    let printed = report.to_string();
+    assert!(printed.contains(&view) && printed.contains(&image));
+
+    // 只重新下载有问题的条目
+    let requests = log.lock().unwrap().len();
+    let cache = Arc::new(ResponseCache::open(dir.path(), false).unwrap());
+    let mut refetch = crawler(&cache);
+    for url in report.broken_urls() {
+        refetch.crawl_url(&url, false).await.unwrap();
+    }
+    cache.save_manifest().unwrap();
+    assert_eq!(log.lock().unwrap().len(), requests + 3);
+    let report = verify_mirror(dir.path(), &[seed, image]).unwrap();
+    assert!(report.is_ok(), "{}", report);
+    assert_eq!(report.verified, 4);
+
+    // 清单被改动过
+    let mut manifest = Manifest::load(&cache.manifest_path()).unwrap();
+    manifest.entries[0].file = "../outside.json".to_string();
+    manifest.entries.pop();
+    manifest.save(&cache.manifest_path()).unwrap();
+    let report = verify_mirror(dir.path(), &[]).unwrap();
+    assert_eq!(report.count_mismatch, Some((4, 3)));
+    assert_eq!(report.corrupted.len(), 1);
+}
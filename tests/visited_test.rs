@@ -0,0 +1,167 @@
+mod common;
+
+use adoc::crawler::{Crawler, CrawlerConfig, DistributedVisited};
+use adoc::visited::{parse_count, BloomFilter, SharedVisited, VisitedKind};
+use async_trait::async_trait;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[test]
+fn test_bloom_filter_has_no_false_negatives() {
+    let mut filter = BloomFilter::new(10_000, 0.001);
+    for i in 0..10_000 {
+        assert!(filter.insert(&format!("https://developer.apple.com/documentation/page{}", i)));
+    }
+    for i in 0..10_000 {
+        assert!(filter.contains(&format!("https://developer.apple.com/documentation/page{}", i)));
+    }
+    assert!(!filter.insert("https://developer.apple.com/documentation/page0"));
+
+    let false_positives = (0..10_000)
+        .filter(|i| filter.contains(&format!("https://developer.apple.com/documentation/other{}", i)))
+        .count();
+    assert!(false_positives < 50, "false positives: {}", false_positives);
+}
+
+#[test]
+fn test_parse_count() {
+    assert_eq!(parse_count("1200").unwrap(), 1200);
+    assert_eq!(parse_count("200k").unwrap(), 200_000);
+    assert_eq!(parse_count("5M").unwrap(), 5_000_000);
+    assert!(parse_count("M").is_err());
+    assert!(parse_count("5x").is_err());
+}
+
+#[tokio::test]
+async fn test_crawl_with_bloom_visited_set() {
+    // 链接需包含 developer.apple.com 才会被当作文档链接
+    let base = common::serve(&[
+        (
+            "/documentation/swiftui",
+            r#"<html><body><h1>SwiftUI</h1><article>
+                <a href="/developer.apple.com/view">View</a>
+                <a href="/developer.apple.com/view">View</a>
+                <a href="/documentation/swiftui">SwiftUI</a>
+            </article></body></html>"#,
+        ),
+        ("/developer.apple.com/view", "<html><body><h1>View</h1><article>A view.</article></body></html>"),
+    ]);
+
+    let mut crawler = Crawler::new(CrawlerConfig {
+        max_retries: 3,
+        concurrency: 2,
+        timeout: Duration::from_secs(10),
+        ..Default::default()
+    });
+    crawler.set_visited(SharedVisited::new(VisitedKind::Bloom, 100));
+    let (pages, report) = crawler
+        .crawl_url(&format!("{}/documentation/swiftui", base), true)
+        .await
+        .unwrap();
+    assert_eq!(pages.len(), 2);
+    assert_eq!(report.pages_skipped, 1);
+}
+
+#[test]
+fn test_shared_visited_inserts_each_url_once() {
+    for kind in [VisitedKind::Exact, VisitedKind::Bloom] {
+        let visited = SharedVisited::new(kind, 10_000);
+        // 多个线程同时插入同一批 URL，每个 URL 只有一次插入成功
+        let inserted: usize = std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..4)
+                .map(|_| {
+                    scope.spawn(|| {
+                        (0..2_000)
+                            .filter(|i| visited.insert(&format!("https://developer.apple.com/documentation/page{}", i)))
+                            .count()
+                    })
+                })
+                .collect();
+            workers.into_iter().map(|worker| worker.join().unwrap()).sum()
+        });
+        match kind {
+            VisitedKind::Exact => assert_eq!(inserted, 2_000),
+            // 布隆过滤器可能把少量新 URL 误判为已访问，但不会重复插入
+            VisitedKind::Bloom => assert!((1_990..=2_000).contains(&inserted), "{}", inserted),
+        }
+        assert!(visited.contains("https://developer.apple.com/documentation/page42"));
+    }
+}
+
+/// 内存中的共享集合，代替 Redis 模拟多个进程
+#[derive(Default)]
+struct MemoryClaims {
+    claimed: Mutex<HashSet<String>>,
+    released: Mutex<Vec<String>>,
+}
+
+#[async_trait]
+impl DistributedVisited for MemoryClaims {
+    async fn claim(&self, url: &str) -> anyhow::Result<bool> {
+        Ok(self.claimed.lock().unwrap().insert(url.to_string()))
+    }
+
+    async fn release(&self, url: &str) -> anyhow::Result<()> {
+        self.claimed.lock().unwrap().remove(url);
+        self.released.lock().unwrap().push(url.to_string());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_crawlers_split_links_through_distributed_visited() {
+    let links: String = (0..6)
+        .map(|i| format!(r#"<a href="/developer.apple.com/page{}">Page {}</a>"#, i, i))
+        .collect();
+    let seed_page = format!(
+        r#"<html><body><h1>SwiftUI</h1><article>{}<a href="/developer.apple.com/broken">Broken</a></article>"#,
+        links
+    );
+    let pages: Vec<(String, String, String)> = (0..6)
+        .map(|i| {
+            let page = format!("<html><body><h1>Page {}</h1><article>Page {}.</article></body></html>", i, i);
+            (format!("/developer.apple.com/page{}", i), "200 OK".to_string(), page)
+        })
+        .chain([
+            ("/documentation/swiftui".to_string(), "200 OK".to_string(), seed_page),
+            ("/developer.apple.com/broken".to_string(), "500 Internal Server Error".to_string(), String::new()),
+        ])
+        .collect();
+    let pages: Vec<(&str, &str, &str)> = pages
+        .iter()
+        .map(|(path, status, body)| (path.as_str(), status.as_str(), body.as_str()))
+        .collect();
+    let base = common::serve_status(&pages);
+    let seed = format!("{}/documentation/swiftui", base);
+
+    let claims = Arc::new(MemoryClaims::default());
+    let crawler = || {
+        let mut crawler = Crawler::new(CrawlerConfig {
+            max_retries: 0,
+            concurrency: 2,
+            timeout: Duration::from_secs(10),
+            ..Default::default()
+        });
+        crawler.set_distributed_visited(claims.clone());
+        crawler
+    };
+    let (mut first, mut second) = (crawler(), crawler());
+    let ((first, _), (second, _)) = tokio::join!(
+        async { first.crawl_url(&seed, true).await.unwrap() },
+        async { second.crawl_url(&seed, true).await.unwrap() }
+    );
+
+    // 两个进程都爬取起始页面，其余页面各爬一次
+    let mut urls: Vec<String> = first.iter().chain(&second).map(|page| page.url.clone()).collect();
+    assert_eq!(urls.iter().filter(|url| **url == seed).count(), 2);
+    urls.retain(|url| *url != seed);
+    urls.sort();
+    let expected: Vec<String> = (0..6).map(|i| format!("{}/developer.apple.com/page{}", base, i)).collect();
+    assert_eq!(urls, expected);
+
+    // 失败的页面释放认领，之后可以重试
+    let broken = format!("{}/developer.apple.com/broken", base);
+    assert!(claims.released.lock().unwrap().contains(&broken));
+    assert!(!claims.claimed.lock().unwrap().contains(&broken));
+}
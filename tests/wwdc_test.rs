@@ -0,0 +1,82 @@
+use adoc::extract::{extract_html, extract_links};
+use adoc::profile::DocSource;
+use adoc::wwdc::{event_name, is_session_url, is_video_url, ALL_VIDEOS_URL};
+
+const SESSION: &str = "https://developer.apple.com/videos/play/wwdc2023/10187/";
+
+#[test]
+fn test_video_urls() {
+    assert!(is_video_url(ALL_VIDEOS_URL));
+    assert!(!is_session_url(ALL_VIDEOS_URL));
+    assert!(is_session_url(SESSION));
+    assert!(!is_video_url("https://developer.apple.com/documentation/swiftdata"));
+    assert_eq!(event_name(SESSION).as_deref(), Some("WWDC 2023"));
+    assert_eq!(
+        event_name("https://developer.apple.com/videos/play/tech-talks/110337/").as_deref(),
+        Some("Tech Talks")
+    );
+    assert_eq!(DocSource::Wwdc.seed(), Some(ALL_VIDEOS_URL));
+}
+
+#[test]
+fn test_extract_session() {
+    let html = r#"<html><head><meta name="description" content="Fallback description."></head><body>
+        <nav><a href="/videos/">Videos</a></nav>
+        <main>
+            <section id="details">
+                <h1>Meet SwiftData</h1>
+                <p>SwiftData is a powerful and expressive persistence framework.</p>
+                <ul class="video-tags"><li><a href="/videos/topics/?q=swift">Swift</a></li>
+                    <li><a href="/videos/topics/?q=data">App Services</a></li></ul>
+            </section>
+            <li class="supplement resources">
+                <h2>Resources</h2>
+                <a href="/documentation/swiftdata">SwiftData</a>
+                <a href="/documentation/swiftdata">SwiftData</a>
+                <h2>Related Videos</h2>
+                <a href="/videos/play/wwdc2023/10195/">Model your schema with SwiftData</a>
+            </li>
+            <li class="supplement transcript">
+                <section id="transcript-content">
+                    <p><span class="sentence"><span data-start="0">♪ ♪</span></span></p>
+                    <p><span data-start="10">Hi, I'm Ben.</span> <span data-start="12">Welcome to SwiftData.</span></p>
+                </section>
+            </li>
+        </main>
+    </body></html>"#;
+    let page = extract_html(SESSION, html).unwrap();
+    assert_eq!(page.title, "Meet SwiftData");
+    let lines: Vec<&str> = page.content.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "Event: WWDC 2023",
+            "Topics: Swift, App Services",
+            "SwiftData is a powerful and expressive persistence framework.",
+            "## Resources",
+            "- SwiftData (https://developer.apple.com/documentation/swiftdata)",
+            "## Transcript",
+            "♪ ♪",
+            "Hi, I'm Ben. Welcome to SwiftData.",
+        ]
+    );
+    assert_eq!(page.link_urls().collect::<Vec<_>>(), ["https://developer.apple.com/documentation/swiftdata"]);
+}
+
+#[test]
+fn test_extract_video_list() {
+    let html = r#"<html><body>
+        <nav><a href="/documentation/">Documentation</a><a href="/videos/">Videos</a></nav>
+        <h1>All Videos</h1>
+        <a href="/videos/play/wwdc2023/10187/">Meet SwiftData</a>
+        <a href="/videos/play/wwdc2023/10187/"><img alt=""></a>
+        <a href="/videos/play/tech-talks/110337/">Explore Swift</a>
+    </body></html>"#;
+    let page = extract_html(ALL_VIDEOS_URL, html).unwrap();
+    assert_eq!(page.title, "All Videos");
+    assert_eq!(page.content, "Meet SwiftData\nExplore Swift");
+    let expected = [SESSION, "https://developer.apple.com/videos/play/tech-talks/110337/"];
+    assert_eq!(page.link_urls().collect::<Vec<_>>(), expected);
+    // --dry-run 列出的链接与爬取时相同
+    assert_eq!(extract_links(ALL_VIDEOS_URL, html).unwrap(), expected);
+}